@@ -0,0 +1,651 @@
+//! The verification of the individual proof steps.
+//!
+//! Inferences are checked against the constraint which is given by their constraint tag, or, for
+//! untagged inferences, against the constraints and nogoods which share a variable with the
+//! inference. Nogoods are checked with reverse unit propagation: the negation of the nogood is
+//! assumed and the hinted steps are propagated, after which the checker falls back to propagating
+//! all constraints, nogoods and pending inferences until a conflict is found.
+//!
+//! Solutions should assign every variable of the model, and are checked by evaluating every
+//! constraint on that assignment. Since a constraint which is not supported cannot be evaluated,
+//! solutions and optimality conclusions are rejected for models which contain one. A solution may
+//! be used as a hint in the derivation of a nogood, in which case it contributes the clause which
+//! requires the objective to improve on the solution. The bound in an optimality conclusion should
+//! be attained by a solution.
+
+use std::collections::HashMap;
+use std::num::NonZero;
+use std::rc::Rc;
+
+use drcp_format::reader::ReadStep;
+use drcp_format::steps::Conclusion;
+use drcp_format::steps::Definition;
+use drcp_format::steps::Inference;
+use drcp_format::steps::Nogood;
+use drcp_format::steps::Solution;
+use drcp_format::steps::Step;
+use drcp_format::steps::StepId;
+use drcp_format::AtomicConstraint;
+use drcp_format::Comparison;
+use thiserror::Error;
+
+use crate::constraints::has_no_solution;
+use crate::constraints::Clause;
+use crate::constraints::Constraint;
+use crate::constraints::MAX_PROPAGATION_ROUNDS;
+use crate::domains::Atomic;
+use crate::domains::Domain;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+use crate::model::Model;
+use crate::model::OptimisationDirection;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum CheckError {
+    #[error("Step {0} is invalid: {1}")]
+    InvalidStep(StepId, String),
+
+    #[error("The conclusion is invalid: {0}")]
+    InvalidConclusion(String),
+
+    #[error("The proof continues after the conclusion")]
+    StepAfterConclusion,
+
+    #[error("The proof does not contain a conclusion")]
+    MissingConclusion,
+}
+
+/// Checks the steps of a proof against a [`Model`].
+#[derive(Debug)]
+pub(crate) struct ProofChecker {
+    model: Model,
+
+    /// The nogoods which have been derived and are not deleted, stored as clauses.
+    nogoods: HashMap<StepId, Clause>,
+    /// For every variable the nogoods in which it occurs. May contain deleted nogoods.
+    nogoods_by_variable: HashMap<VariableId, Vec<StepId>>,
+    /// The definitions, stored as the clauses which encode them.
+    definitions: HashMap<StepId, Vec<Clause>>,
+    /// The inferences since the last nogood, stored as clauses.
+    inferences: HashMap<StepId, Clause>,
+    /// The solutions, stored as the clauses which require the objective to improve on them.
+    solutions: HashMap<StepId, Clause>,
+    /// The best objective value of the solutions.
+    best_objective_value: Option<i64>,
+
+    derived_empty_nogood: bool,
+    is_concluded: bool,
+}
+
+impl ProofChecker {
+    pub(crate) fn new(model: Model) -> ProofChecker {
+        ProofChecker {
+            model,
+            nogoods: HashMap::new(),
+            nogoods_by_variable: HashMap::new(),
+            definitions: HashMap::new(),
+            inferences: HashMap::new(),
+            solutions: HashMap::new(),
+            best_objective_value: None,
+            derived_empty_nogood: false,
+            is_concluded: false,
+        }
+    }
+
+    /// Check the next step in the proof.
+    pub(crate) fn check_step(
+        &mut self,
+        step: ReadStep<'_, AtomicConstraint<Rc<str>>>,
+    ) -> Result<(), CheckError> {
+        if self.is_concluded {
+            return Err(CheckError::StepAfterConclusion);
+        }
+
+        match step {
+            Step::Inference(inference) => self.check_inference(inference),
+            Step::Nogood(nogood) => self.check_nogood(nogood),
+            Step::Definition(definition) => self.check_definition(definition),
+            Step::Delete(deletion) => match self.nogoods.remove(&deletion.id) {
+                Some(_) => Ok(()),
+                None => Err(CheckError::InvalidStep(
+                    deletion.id,
+                    "the deleted step is not an active nogood".to_owned(),
+                )),
+            },
+            Step::Solution(solution) => self.check_solution(solution),
+            Step::Conclusion(conclusion) => {
+                self.is_concluded = true;
+                self.check_conclusion(conclusion)
+            }
+        }
+    }
+
+    /// Check that the proof has been concluded. Should be called after the last step.
+    pub(crate) fn finish(&self) -> Result<(), CheckError> {
+        if self.is_concluded {
+            Ok(())
+        } else {
+            Err(CheckError::MissingConclusion)
+        }
+    }
+
+    fn check_inference(
+        &mut self,
+        inference: Inference<'_, Vec<AtomicConstraint<Rc<str>>>, AtomicConstraint<Rc<str>>>,
+    ) -> Result<(), CheckError> {
+        let id = inference.id;
+        self.ensure_unused(id)?;
+
+        let premises: Vec<Atomic> = inference
+            .premises
+            .iter()
+            .map(|premise| self.resolve_atomic(premise))
+            .collect();
+        let propagated = inference
+            .propagated
+            .as_ref()
+            .map(|propagated| self.resolve_atomic(propagated));
+
+        let is_implied = match inference.hint_constraint_id {
+            Some(tag) => self.is_implied_by_tagged_constraint(id, tag, &premises, propagated)?,
+            None => self.is_implied_by_any_constraint(&premises, propagated),
+        };
+
+        if !is_implied {
+            let reason = match inference.hint_constraint_id {
+                Some(tag) => format!("the inference is not implied by constraint {tag}"),
+                None => "the inference is not implied by a constraint or nogood".to_owned(),
+            };
+            return Err(CheckError::InvalidStep(id, reason));
+        }
+
+        let clause = premises
+            .iter()
+            .map(|premise| premise.negate())
+            .chain(propagated)
+            .collect();
+        let _ = self.inferences.insert(id, Clause::new(clause));
+
+        Ok(())
+    }
+
+    /// The domains in which the premises hold and the propagated atomic constraint does not, or
+    /// `None` if that is impossible.
+    fn inference_domains(
+        &self,
+        premises: &[Atomic],
+        propagated: Option<Atomic>,
+    ) -> Option<Domains<'_>> {
+        let mut domains = Domains::new(self.model.initial_domains());
+
+        premises
+            .iter()
+            .copied()
+            .chain(propagated.map(Atomic::negate))
+            .try_for_each(|atomic| domains.apply(atomic))
+            .ok()?;
+
+        Some(domains)
+    }
+
+    fn is_implied_by_tagged_constraint(
+        &self,
+        id: StepId,
+        tag: NonZero<u32>,
+        premises: &[Atomic],
+        propagated: Option<Atomic>,
+    ) -> Result<bool, CheckError> {
+        let item = self.model.constraint(tag.get() as usize).ok_or_else(|| {
+            CheckError::InvalidStep(id, format!("constraint {tag} does not exist"))
+        })?;
+        let constraint = item.constraint.as_deref().ok_or_else(|| {
+            CheckError::InvalidStep(
+                id,
+                format!("constraint {tag} ({}) is not supported", item.name),
+            )
+        })?;
+
+        Ok(self
+            .inference_domains(premises, propagated)
+            .is_none_or(|domains| has_no_solution(constraint, &domains)))
+    }
+
+    fn is_implied_by_any_constraint(
+        &self,
+        premises: &[Atomic],
+        propagated: Option<Atomic>,
+    ) -> bool {
+        let Some(domains) = self.inference_domains(premises, propagated) else {
+            return true;
+        };
+
+        let mut variables: Vec<VariableId> = premises
+            .iter()
+            .chain(&propagated)
+            .map(|atomic| atomic.variable)
+            .collect();
+        variables.sort();
+        variables.dedup();
+
+        if variables.is_empty() {
+            return self
+                .nogoods
+                .values()
+                .any(|nogood| nogood.is_falsified(&domains))
+                || self
+                    .model
+                    .supported_constraints()
+                    .any(|constraint| has_no_solution(constraint, &domains));
+        }
+
+        variables.iter().any(|&variable| {
+            self.nogoods_by_variable
+                .get(&variable)
+                .into_iter()
+                .flatten()
+                .filter_map(|id| self.nogoods.get(id))
+                .any(|nogood| nogood.is_falsified(&domains))
+        }) || variables.iter().any(|&variable| {
+            self.model
+                .constraints_on(variable)
+                .any(|constraint| has_no_solution(constraint, &domains))
+        })
+    }
+
+    fn check_nogood(
+        &mut self,
+        nogood: Nogood<Vec<AtomicConstraint<Rc<str>>>, Vec<StepId>>,
+    ) -> Result<(), CheckError> {
+        let id = nogood.id;
+        self.ensure_unused(id)?;
+
+        let literals: Vec<Atomic> = nogood
+            .literals
+            .iter()
+            .map(|literal| self.resolve_atomic(literal))
+            .collect();
+
+        self.check_reverse_unit_propagation(id, &literals, nogood.hints.as_deref())?;
+
+        for literal in &literals {
+            let ids = self
+                .nogoods_by_variable
+                .entry(literal.variable)
+                .or_default();
+            if ids.last() != Some(&id) {
+                ids.push(id);
+            }
+        }
+
+        if literals.is_empty() {
+            self.derived_empty_nogood = true;
+        }
+        let _ = self.nogoods.insert(id, Clause::new(literals));
+        self.inferences.clear();
+
+        Ok(())
+    }
+
+    /// Check that the clause of the `literals` follows by reverse unit propagation.
+    fn check_reverse_unit_propagation(
+        &self,
+        id: StepId,
+        literals: &[Atomic],
+        hints: Option<&[StepId]>,
+    ) -> Result<(), CheckError> {
+        let mut domains = Domains::new(self.model.initial_domains());
+
+        if literals
+            .iter()
+            .try_for_each(|literal| domains.apply(literal.negate()))
+            .is_err()
+        {
+            // The clause is a tautology.
+            return Ok(());
+        }
+
+        let mut hinted_clauses: Vec<&dyn Constraint> = vec![];
+        for &hint in hints.unwrap_or_default() {
+            let clauses = self.hinted_clauses(hint).ok_or_else(|| {
+                CheckError::InvalidStep(id, format!("the hint {hint} is not an active step"))
+            })?;
+            hinted_clauses.extend(clauses.iter().map(|clause| -> &dyn Constraint { clause }));
+        }
+
+        // The hinted steps are not necessarily given in the order in which they apply.
+        if propagate_until_conflict(&hinted_clauses, &mut domains) {
+            return Ok(());
+        }
+
+        if self.propagate_to_conflict(&mut domains) {
+            Ok(())
+        } else {
+            Err(CheckError::InvalidStep(
+                id,
+                "the nogood does not follow by propagation".to_owned(),
+            ))
+        }
+    }
+
+    fn hinted_clauses(&self, hint: StepId) -> Option<&[Clause]> {
+        self.inferences
+            .get(&hint)
+            .or_else(|| self.nogoods.get(&hint))
+            .or_else(|| self.solutions.get(&hint))
+            .map(std::slice::from_ref)
+            .or_else(|| self.definitions.get(&hint).map(Vec::as_slice))
+    }
+
+    /// Propagate all constraints, nogoods, definitions and pending inferences. Returns whether a
+    /// conflict was found.
+    fn propagate_to_conflict(&self, domains: &mut Domains) -> bool {
+        let clauses = self
+            .nogoods
+            .values()
+            .chain(self.definitions.values().flatten())
+            .chain(self.inferences.values())
+            .map(|clause| -> &dyn Constraint { clause });
+        let constraints: Vec<&dyn Constraint> =
+            clauses.chain(self.model.supported_constraints()).collect();
+
+        propagate_until_conflict(&constraints, domains)
+    }
+
+    fn check_definition(
+        &mut self,
+        definition: Definition<Vec<AtomicConstraint<Rc<str>>>, AtomicConstraint<Rc<str>>>,
+    ) -> Result<(), CheckError> {
+        let id = definition.id;
+        self.ensure_unused(id)?;
+
+        if self
+            .model
+            .contains_variable(atomic_name(&definition.literal))
+        {
+            return Err(CheckError::InvalidStep(
+                id,
+                "the defined literal is not fresh".to_owned(),
+            ));
+        }
+
+        // The defined literal is over a fresh 0-1 variable, such that it can take either truth
+        // value.
+        let literal = self.resolve_atomic(&definition.literal);
+        self.model
+            .set_initial_domain(literal.variable, Domain::new(0, 1));
+        let domain = &self.model.initial_domains()[literal.variable.0];
+        if domain.entails(literal.comparison, literal.value)
+            || domain.entails(literal.negate().comparison, literal.negate().value)
+        {
+            return Err(CheckError::InvalidStep(
+                id,
+                "the defined literal is not a Boolean atomic constraint".to_owned(),
+            ));
+        }
+
+        let conjunction: Vec<Atomic> = definition
+            .conjunction
+            .iter()
+            .map(|atomic| self.resolve_atomic(atomic))
+            .collect();
+        if conjunction
+            .iter()
+            .any(|atomic| atomic.variable == literal.variable)
+        {
+            return Err(CheckError::InvalidStep(
+                id,
+                "the defined literal occurs in its definition".to_owned(),
+            ));
+        }
+
+        // Encode `literal <-> /\ conjunction` as the clauses `literal \/ ~conjunction` and
+        // `~literal \/ c` for every `c` in the conjunction.
+        let mut clauses = vec![Clause::new(
+            std::iter::once(literal)
+                .chain(conjunction.iter().map(|atomic| atomic.negate()))
+                .collect(),
+        )];
+        clauses.extend(
+            conjunction
+                .iter()
+                .map(|&atomic| Clause::new(vec![literal.negate(), atomic])),
+        );
+        let _ = self.definitions.insert(id, clauses);
+
+        Ok(())
+    }
+
+    fn check_solution(
+        &mut self,
+        solution: Solution<Vec<AtomicConstraint<Rc<str>>>>,
+    ) -> Result<(), CheckError> {
+        let id = solution.id;
+        self.ensure_unused(id)?;
+
+        let objective = self.model.objective().ok_or_else(|| {
+            CheckError::InvalidStep(id, "the model does not have an objective".to_owned())
+        })?;
+
+        let assignment: Vec<Atomic> = solution
+            .assignment
+            .iter()
+            .map(|atomic| self.resolve_atomic(atomic))
+            .collect();
+        if assignment
+            .iter()
+            .any(|atomic| atomic.comparison != Comparison::Equal)
+        {
+            return Err(CheckError::InvalidStep(
+                id,
+                "the solution is not an assignment".to_owned(),
+            ));
+        }
+
+        // A constraint which is not supported cannot be checked, so the solution may violate it.
+        if let Some((tag, item)) = self.model.unsupported_constraint() {
+            return Err(CheckError::InvalidStep(
+                id,
+                format!(
+                    "the solution cannot be checked against constraint {tag} ({}), which is not supported",
+                    item.name
+                ),
+            ));
+        }
+
+        let mut domains = Domains::new(self.model.initial_domains());
+        if assignment
+            .iter()
+            .try_for_each(|&atomic| domains.apply(atomic))
+            .is_err()
+        {
+            return Err(CheckError::InvalidStep(
+                id,
+                "the solution violates a constraint".to_owned(),
+            ));
+        }
+
+        if let Some(variable) = self
+            .model
+            .model_variables()
+            .find(|&variable| !domains.get(variable).is_fixed())
+        {
+            return Err(CheckError::InvalidStep(
+                id,
+                format!(
+                    "the solution does not assign the variable {}",
+                    self.model.variable_name(variable)
+                ),
+            ));
+        }
+
+        let value_of = |variable: VariableId| domains.lower_bound(variable);
+        if !self
+            .model
+            .supported_constraints()
+            .all(|constraint| constraint.is_satisfied(&value_of))
+        {
+            return Err(CheckError::InvalidStep(
+                id,
+                "the solution violates a constraint".to_owned(),
+            ));
+        }
+
+        // Only solutions which are better than this solution remain of interest.
+        let value = domains.lower_bound(objective.variable);
+        let (improvement, best_objective_value) = match objective.direction {
+            OptimisationDirection::Minimise => (
+                Atomic::new(objective.variable, Comparison::LessThanEqual, value - 1),
+                self.best_objective_value
+                    .map_or(value, |best| best.min(value)),
+            ),
+            OptimisationDirection::Maximise => (
+                Atomic::new(objective.variable, Comparison::GreaterThanEqual, value + 1),
+                self.best_objective_value
+                    .map_or(value, |best| best.max(value)),
+            ),
+        };
+        let _ = self.solutions.insert(id, Clause::new(vec![improvement]));
+        self.best_objective_value = Some(best_objective_value);
+
+        Ok(())
+    }
+
+    fn check_conclusion(
+        &mut self,
+        conclusion: Conclusion<AtomicConstraint<Rc<str>>>,
+    ) -> Result<(), CheckError> {
+        let bound = match conclusion {
+            // A nogood which is derived with the improvement clause of a solution only holds for
+            // the solutions which improve on it, so it cannot establish that there is no solution.
+            Conclusion::Unsatisfiable if !self.solutions.is_empty() => {
+                return Err(CheckError::InvalidConclusion(
+                    "the model has a solution".to_owned(),
+                ))
+            }
+            // The empty nogood does not have to be logged explicitly if it follows by propagation.
+            Conclusion::Unsatisfiable
+                if self.derived_empty_nogood
+                    || self
+                        .propagate_to_conflict(&mut Domains::new(self.model.initial_domains())) =>
+            {
+                return Ok(())
+            }
+            Conclusion::Unsatisfiable => {
+                return Err(CheckError::InvalidConclusion(
+                    "the empty nogood does not follow by propagation".to_owned(),
+                ))
+            }
+            Conclusion::Optimal(bound) => self.resolve_atomic(&bound),
+        };
+
+        // The solutions cannot be checked against a constraint which is not supported, so the
+        // bound may not be attained.
+        if let Some((tag, item)) = self.model.unsupported_constraint() {
+            return Err(CheckError::InvalidConclusion(format!(
+                "the optimality cannot be checked since constraint {tag} ({}) is not supported",
+                item.name
+            )));
+        }
+
+        let objective = self.model.objective().ok_or_else(|| {
+            CheckError::InvalidConclusion("the model does not have an objective".to_owned())
+        })?;
+        if bound.variable != objective.variable {
+            return Err(CheckError::InvalidConclusion(format!(
+                "the bound is not on the objective variable {}",
+                self.model.variable_name(objective.variable)
+            )));
+        }
+
+        // The bound is `[objective <= v]` when minimising, or `[objective >= v]` when maximising,
+        // for the best objective value `v`. No solution can have a better objective value.
+        let dual_bound = match (objective.direction, bound.comparison) {
+            (OptimisationDirection::Minimise, Comparison::LessThanEqual) => {
+                Atomic::new(bound.variable, Comparison::GreaterThanEqual, bound.value)
+            }
+            (OptimisationDirection::Maximise, Comparison::GreaterThanEqual) => {
+                Atomic::new(bound.variable, Comparison::LessThanEqual, bound.value)
+            }
+            _ => {
+                return Err(CheckError::InvalidConclusion(
+                    "the bound does not match the optimisation direction".to_owned(),
+                ))
+            }
+        };
+
+        if self.best_objective_value != Some(bound.value) {
+            return Err(CheckError::InvalidConclusion(format!(
+                "the bound is not attained by a solution with objective value {}",
+                bound.value
+            )));
+        }
+
+        let mut domains = Domains::new(self.model.initial_domains());
+        if domains.apply(dual_bound.negate()).is_err() || self.propagate_to_conflict(&mut domains) {
+            Ok(())
+        } else {
+            Err(CheckError::InvalidConclusion(
+                "the bound does not follow by propagation".to_owned(),
+            ))
+        }
+    }
+
+    fn ensure_unused(&self, id: StepId) -> Result<(), CheckError> {
+        if self.inferences.contains_key(&id)
+            || self.nogoods.contains_key(&id)
+            || self.definitions.contains_key(&id)
+            || self.solutions.contains_key(&id)
+        {
+            Err(CheckError::InvalidStep(
+                id,
+                "the step id is already used".to_owned(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn resolve_atomic(&mut self, atomic: &AtomicConstraint<Rc<str>>) -> Atomic {
+        match atomic {
+            AtomicConstraint::Int(atomic) => Atomic::new(
+                self.model.resolve_variable(&atomic.name),
+                atomic.comparison,
+                atomic.value,
+            ),
+            AtomicConstraint::Bool(atomic) => Atomic::new(
+                self.model.resolve_variable(&atomic.name),
+                Comparison::Equal,
+                i64::from(atomic.value),
+            ),
+        }
+    }
+}
+
+/// Propagate the constraints until a conflict or a fixpoint is reached. Returns whether a conflict
+/// was found.
+fn propagate_until_conflict(constraints: &[&dyn Constraint], domains: &mut Domains) -> bool {
+    for _ in 0..MAX_PROPAGATION_ROUNDS {
+        let num_changes = domains.num_changes();
+
+        if constraints
+            .iter()
+            .try_for_each(|constraint| constraint.propagate(domains))
+            .is_err()
+        {
+            return true;
+        }
+
+        if num_changes == domains.num_changes() {
+            break;
+        }
+    }
+
+    false
+}
+
+fn atomic_name(atomic: &AtomicConstraint<Rc<str>>) -> &str {
+    match atomic {
+        AtomicConstraint::Int(atomic) => &atomic.name,
+        AtomicConstraint::Bool(atomic) => &atomic.name,
+    }
+}
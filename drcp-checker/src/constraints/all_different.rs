@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::Constraint;
+use crate::domains::Conflict;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+
+/// The maximum sum of the domain sizes for which a matching is computed.
+const MAX_MATCHING_SIZE: u64 = 100_000;
+
+/// The constraint which enforces that all variables take a different value.
+#[derive(Clone, Debug)]
+pub(crate) struct AllDifferent {
+    variables: Vec<VariableId>,
+}
+
+impl AllDifferent {
+    pub(crate) fn new(variables: Vec<VariableId>) -> AllDifferent {
+        AllDifferent { variables }
+    }
+}
+
+impl Constraint for AllDifferent {
+    fn scope(&self) -> Vec<VariableId> {
+        self.variables.clone()
+    }
+
+    fn propagate(&self, domains: &mut Domains) -> Result<(), Conflict> {
+        // Remove the values of the fixed variables from the other variables.
+        for (index, &variable) in self.variables.iter().enumerate() {
+            if !domains.get(variable).is_fixed() {
+                continue;
+            }
+
+            let value = domains.lower_bound(variable);
+            for (other_index, &other) in self.variables.iter().enumerate() {
+                if other_index != index {
+                    domains.remove(other, value)?;
+                }
+            }
+        }
+
+        // Every variable should be matched to a distinct value.
+        let total_size: u64 = self
+            .variables
+            .iter()
+            .map(|&variable| domains.get(variable).size())
+            .sum();
+        if total_size <= MAX_MATCHING_SIZE
+            && maximum_matching_size(&self.variables, domains) < self.variables.len()
+        {
+            return Err(Conflict);
+        }
+
+        Ok(())
+    }
+
+    fn is_satisfied(&self, value: &dyn Fn(VariableId) -> i64) -> bool {
+        let mut values = HashSet::new();
+        self.variables
+            .iter()
+            .all(|&variable| values.insert(value(variable)))
+    }
+}
+
+/// Computes the size of a maximum matching between the variables and the values in their domains
+/// using augmenting paths.
+fn maximum_matching_size(variables: &[VariableId], domains: &Domains) -> usize {
+    let mut matched_variable_of_value: HashMap<i64, usize> = HashMap::new();
+    let mut matching_size = 0;
+
+    for index in 0..variables.len() {
+        let mut visited_values = HashSet::new();
+        if find_augmenting_path(
+            index,
+            variables,
+            domains,
+            &mut matched_variable_of_value,
+            &mut visited_values,
+        ) {
+            matching_size += 1;
+        }
+    }
+
+    matching_size
+}
+
+fn find_augmenting_path(
+    index: usize,
+    variables: &[VariableId],
+    domains: &Domains,
+    matched_variable_of_value: &mut HashMap<i64, usize>,
+    visited_values: &mut HashSet<i64>,
+) -> bool {
+    for value in domains.get(variables[index]).values() {
+        if !visited_values.insert(value) {
+            continue;
+        }
+
+        let can_match = match matched_variable_of_value.get(&value) {
+            None => true,
+            Some(&other_index) => find_augmenting_path(
+                other_index,
+                variables,
+                domains,
+                matched_variable_of_value,
+                visited_values,
+            ),
+        };
+
+        if can_match {
+            let _ = matched_variable_of_value.insert(value, index);
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::Domain;
+
+    #[test]
+    fn too_few_values_is_a_conflict() {
+        let initial_domains = [
+            Domain::new(1, 2),
+            Domain::new(1, 2),
+            Domain::from_values([1, 2]),
+        ];
+        let mut domains = Domains::new(&initial_domains);
+
+        let constraint = AllDifferent::new(vec![VariableId(0), VariableId(1), VariableId(2)]);
+        assert_eq!(Err(Conflict), constraint.propagate(&mut domains));
+    }
+
+    #[test]
+    fn fixed_values_are_removed_from_the_other_variables() {
+        let initial_domains = [Domain::new(1, 1), Domain::new(1, 3)];
+        let mut domains = Domains::new(&initial_domains);
+
+        let constraint = AllDifferent::new(vec![VariableId(0), VariableId(1)]);
+        constraint.propagate(&mut domains).expect("no conflict");
+        assert_eq!(2, domains.lower_bound(VariableId(1)));
+    }
+}
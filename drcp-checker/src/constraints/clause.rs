@@ -0,0 +1,56 @@
+use super::Constraint;
+use crate::domains::Atomic;
+use crate::domains::Conflict;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+
+/// The disjunction of the given atomic constraints.
+///
+/// Clauses are used for the Boolean constraints in the model, as well as for the nogoods which are
+/// derived in the proof (a nogood is stored as the clause of its negated atomic constraints).
+#[derive(Clone, Debug)]
+pub(crate) struct Clause {
+    literals: Vec<Atomic>,
+}
+
+impl Clause {
+    pub(crate) fn new(literals: Vec<Atomic>) -> Clause {
+        Clause { literals }
+    }
+
+    /// Returns whether every literal of the clause is falsified, in which case the clause has no
+    /// solution within the domains.
+    pub(crate) fn is_falsified(&self, domains: &Domains) -> bool {
+        self.literals
+            .iter()
+            .all(|&literal| domains.is_falsified(literal))
+    }
+}
+
+impl Constraint for Clause {
+    fn scope(&self) -> Vec<VariableId> {
+        self.literals
+            .iter()
+            .map(|literal| literal.variable)
+            .collect()
+    }
+
+    fn propagate(&self, domains: &mut Domains) -> Result<(), Conflict> {
+        let mut unfalsified_literals = self
+            .literals
+            .iter()
+            .filter(|&&literal| !domains.is_falsified(literal));
+
+        match (unfalsified_literals.next(), unfalsified_literals.next()) {
+            (None, _) => Err(Conflict),
+            (Some(&literal), None) => domains.apply(literal),
+            (Some(_), Some(_)) => Ok(()),
+        }
+    }
+
+    fn is_satisfied(&self, value: &dyn Fn(VariableId) -> i64) -> bool {
+        self.literals
+            .iter()
+            .any(|literal| literal.is_satisfied_by(value(literal.variable)))
+    }
+}
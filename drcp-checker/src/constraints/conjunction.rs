@@ -0,0 +1,38 @@
+use super::Constraint;
+use crate::domains::Conflict;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+
+/// The conjunction of several constraints, which is used for FlatZinc constraints that are
+/// decomposed into multiple constraints (e.g. `array_bool_and` into clauses).
+#[derive(Debug)]
+pub(crate) struct Conjunction {
+    constraints: Vec<Box<dyn Constraint>>,
+}
+
+impl Conjunction {
+    pub(crate) fn new(constraints: Vec<Box<dyn Constraint>>) -> Conjunction {
+        Conjunction { constraints }
+    }
+}
+
+impl Constraint for Conjunction {
+    fn scope(&self) -> Vec<VariableId> {
+        self.constraints
+            .iter()
+            .flat_map(|constraint| constraint.scope())
+            .collect()
+    }
+
+    fn propagate(&self, domains: &mut Domains) -> Result<(), Conflict> {
+        self.constraints
+            .iter()
+            .try_for_each(|constraint| constraint.propagate(domains))
+    }
+
+    fn is_satisfied(&self, value: &dyn Fn(VariableId) -> i64) -> bool {
+        self.constraints
+            .iter()
+            .all(|constraint| constraint.is_satisfied(value))
+    }
+}
@@ -0,0 +1,193 @@
+use super::Constraint;
+use crate::domains::Conflict;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+
+/// The cumulative constraint with fixed durations, resource usages and capacity.
+///
+/// The constraint is propagated with time-table reasoning: the compulsory parts of the tasks form
+/// a resource profile, which should not exceed the capacity, and a task cannot be scheduled such
+/// that it overlaps with a part of the profile which leaves too little capacity for it.
+#[derive(Clone, Debug)]
+pub(crate) struct Cumulative {
+    start_times: Vec<VariableId>,
+    durations: Vec<i64>,
+    resource_usages: Vec<i64>,
+    capacity: i64,
+}
+
+/// A part of the resource profile: the interval `[start, end)` in which `height` of the resource
+/// is used.
+#[derive(Clone, Copy, Debug)]
+struct ProfileSegment {
+    start: i64,
+    end: i64,
+    height: i64,
+}
+
+impl Cumulative {
+    pub(crate) fn new(
+        start_times: Vec<VariableId>,
+        durations: Vec<i64>,
+        resource_usages: Vec<i64>,
+        capacity: i64,
+    ) -> Cumulative {
+        Cumulative {
+            start_times,
+            durations,
+            resource_usages,
+            capacity,
+        }
+    }
+
+    /// The tasks which use the resource, as tuples of the index, duration and resource usage.
+    fn tasks(&self) -> impl Iterator<Item = (usize, i64, i64)> + '_ {
+        self.durations
+            .iter()
+            .zip(&self.resource_usages)
+            .enumerate()
+            .filter(|(_, (&duration, &usage))| duration > 0 && usage > 0)
+            .map(|(index, (&duration, &usage))| (index, duration, usage))
+    }
+
+    /// Computes the resource profile of the compulsory parts of the tasks, except for the task at
+    /// `excluded_index`.
+    fn profile(&self, domains: &Domains, excluded_index: Option<usize>) -> Vec<ProfileSegment> {
+        let mut events = vec![];
+
+        for (index, duration, usage) in self.tasks() {
+            if Some(index) == excluded_index {
+                continue;
+            }
+
+            let start_time = self.start_times[index];
+            let compulsory_start = domains.upper_bound(start_time);
+            let compulsory_end = domains.lower_bound(start_time) + duration;
+
+            if compulsory_start < compulsory_end {
+                events.push((compulsory_start, usage));
+                events.push((compulsory_end, -usage));
+            }
+        }
+
+        events.sort_unstable();
+
+        let mut segments = vec![];
+        let mut height = 0;
+        for (event_index, &(time, change)) in events.iter().enumerate() {
+            height += change;
+
+            if let Some(&(next_time, _)) = events.get(event_index + 1) {
+                if height > 0 && next_time > time {
+                    segments.push(ProfileSegment {
+                        start: time,
+                        end: next_time,
+                        height,
+                    });
+                }
+            }
+        }
+
+        segments
+    }
+}
+
+impl Constraint for Cumulative {
+    fn scope(&self) -> Vec<VariableId> {
+        self.start_times.clone()
+    }
+
+    fn propagate(&self, domains: &mut Domains) -> Result<(), Conflict> {
+        if self
+            .profile(domains, None)
+            .iter()
+            .any(|segment| segment.height > self.capacity)
+        {
+            return Err(Conflict);
+        }
+
+        for (index, duration, usage) in self.tasks() {
+            let start_time = self.start_times[index];
+            let overloaded_segments: Vec<ProfileSegment> = self
+                .profile(domains, Some(index))
+                .into_iter()
+                .filter(|segment| segment.height + usage > self.capacity)
+                .collect();
+
+            // Move the earliest start time past the segments with which the task would overlap.
+            let mut lower_bound = domains.lower_bound(start_time);
+            for segment in &overloaded_segments {
+                if segment.start < lower_bound + duration && lower_bound < segment.end {
+                    lower_bound = segment.end;
+                }
+            }
+            domains.set_lower_bound(start_time, lower_bound)?;
+
+            // Move the latest start time before the segments with which the task would overlap.
+            let mut upper_bound = domains.upper_bound(start_time);
+            for segment in overloaded_segments.iter().rev() {
+                if segment.start < upper_bound + duration && upper_bound < segment.end {
+                    upper_bound = segment.start - duration;
+                }
+            }
+            domains.set_upper_bound(start_time, upper_bound)?;
+        }
+
+        Ok(())
+    }
+
+    fn is_satisfied(&self, value: &dyn Fn(VariableId) -> i64) -> bool {
+        // The resource usage can only exceed the capacity at the start of a task.
+        self.tasks().all(|(index, _, _)| {
+            let time = value(self.start_times[index]);
+
+            let usage: i64 = self
+                .tasks()
+                .filter(|&(other, duration, _)| {
+                    let start = value(self.start_times[other]);
+                    start <= time && time < start + duration
+                })
+                .map(|(_, _, usage)| usage)
+                .sum();
+
+            usage <= self.capacity
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::Domain;
+
+    #[test]
+    fn overlapping_compulsory_parts_are_a_conflict() {
+        let initial_domains = [Domain::new(0, 2), Domain::new(1, 3)];
+        let mut domains = Domains::new(&initial_domains);
+
+        let constraint = Cumulative::new(
+            vec![VariableId(0), VariableId(1)],
+            vec![4, 4],
+            vec![1, 1],
+            1,
+        );
+        assert_eq!(Err(Conflict), constraint.propagate(&mut domains));
+    }
+
+    #[test]
+    fn task_is_pushed_past_the_profile() {
+        let initial_domains = [Domain::new(0, 0), Domain::new(0, 10)];
+        let mut domains = Domains::new(&initial_domains);
+
+        let constraint = Cumulative::new(
+            vec![VariableId(0), VariableId(1)],
+            vec![3, 2],
+            vec![2, 1],
+            2,
+        );
+        constraint.propagate(&mut domains).expect("no conflict");
+
+        assert_eq!(3, domains.lower_bound(VariableId(1)));
+        assert_eq!(10, domains.upper_bound(VariableId(1)));
+    }
+}
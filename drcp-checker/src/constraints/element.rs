@@ -0,0 +1,125 @@
+use super::Constraint;
+use crate::domains::Conflict;
+use crate::domains::Domain;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+
+/// The maximum domain size for which the intersection of two domains is computed explicitly.
+const MAX_INTERSECTION_SIZE: u64 = 10_000;
+
+/// The constraint `array[index] = rhs`, where the array is indexed starting from 1 (as in
+/// FlatZinc).
+#[derive(Clone, Debug)]
+pub(crate) struct Element {
+    index: VariableId,
+    array: Vec<VariableId>,
+    rhs: VariableId,
+}
+
+impl Element {
+    pub(crate) fn new(index: VariableId, array: Vec<VariableId>, rhs: VariableId) -> Element {
+        Element { index, array, rhs }
+    }
+}
+
+impl Constraint for Element {
+    fn scope(&self) -> Vec<VariableId> {
+        let mut scope = self.array.clone();
+        scope.push(self.index);
+        scope.push(self.rhs);
+        scope
+    }
+
+    fn propagate(&self, domains: &mut Domains) -> Result<(), Conflict> {
+        domains.set_lower_bound(self.index, 1)?;
+        domains.set_upper_bound(self.index, self.array.len() as i64)?;
+
+        // Remove the indices at which the element cannot be equal to the right-hand side.
+        let indices: Vec<i64> = domains.get(self.index).values().collect();
+        for &index in &indices {
+            let element = self.array[index as usize - 1];
+            if !may_intersect(domains.get(element), domains.get(self.rhs)) {
+                domains.remove(self.index, index)?;
+            }
+        }
+
+        // The right-hand side is bounded by the elements at the remaining indices.
+        let indices: Vec<i64> = domains.get(self.index).values().collect();
+        let elements = indices.iter().map(|&index| self.array[index as usize - 1]);
+        let lower_bound = elements
+            .clone()
+            .map(|element| domains.lower_bound(element))
+            .min()
+            .expect("the index domain is not empty");
+        let upper_bound = elements
+            .map(|element| domains.upper_bound(element))
+            .max()
+            .expect("the index domain is not empty");
+        domains.set_lower_bound(self.rhs, lower_bound)?;
+        domains.set_upper_bound(self.rhs, upper_bound)?;
+
+        // If the index is fixed, then the element is equal to the right-hand side.
+        if let [index] = indices[..] {
+            let element = self.array[index as usize - 1];
+
+            domains.set_lower_bound(element, domains.lower_bound(self.rhs))?;
+            domains.set_upper_bound(element, domains.upper_bound(self.rhs))?;
+        }
+
+        Ok(())
+    }
+
+    fn is_satisfied(&self, value: &dyn Fn(VariableId) -> i64) -> bool {
+        let index = value(self.index);
+
+        index >= 1
+            && index <= self.array.len() as i64
+            && value(self.array[index as usize - 1]) == value(self.rhs)
+    }
+}
+
+/// Returns whether the domains may have a value in common. If the domains are large, only their
+/// bounds are considered.
+fn may_intersect(first: &Domain, second: &Domain) -> bool {
+    if first.upper_bound() < second.lower_bound() || second.upper_bound() < first.lower_bound() {
+        return false;
+    }
+
+    let (smallest, largest) = if first.size() <= second.size() {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    smallest.size() > MAX_INTERSECTION_SIZE
+        || smallest.values().any(|value| largest.contains(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indices_of_incompatible_elements_are_removed() {
+        let initial_domains = [
+            Domain::new(1, 3),
+            Domain::new(1, 2),
+            Domain::from_values([3, 5]),
+            Domain::new(6, 7),
+            Domain::new(3, 4),
+        ];
+        let mut domains = Domains::new(&initial_domains);
+
+        let constraint = Element::new(
+            VariableId(0),
+            vec![VariableId(1), VariableId(2), VariableId(3)],
+            VariableId(4),
+        );
+        constraint.propagate(&mut domains).expect("no conflict");
+
+        assert!(domains.get(VariableId(0)).is_fixed());
+        assert_eq!(2, domains.lower_bound(VariableId(0)));
+        assert!(domains.get(VariableId(2)).is_fixed());
+        assert_eq!(4, domains.upper_bound(VariableId(4)));
+    }
+}
@@ -0,0 +1,134 @@
+use super::Constraint;
+use crate::domains::Conflict;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+
+/// The (non-closed) global cardinality constraint: every `values[i]` is taken by at least
+/// `lower_bounds[i]` and at most `upper_bounds[i]` of the variables. The variables may take other
+/// values as well.
+#[derive(Clone, Debug)]
+pub(crate) struct GlobalCardinality {
+    variables: Vec<VariableId>,
+    values: Vec<i64>,
+    lower_bounds: Vec<i64>,
+    upper_bounds: Vec<i64>,
+}
+
+impl GlobalCardinality {
+    pub(crate) fn new(
+        variables: Vec<VariableId>,
+        values: Vec<i64>,
+        lower_bounds: Vec<i64>,
+        upper_bounds: Vec<i64>,
+    ) -> GlobalCardinality {
+        GlobalCardinality {
+            variables,
+            values,
+            lower_bounds,
+            upper_bounds,
+        }
+    }
+}
+
+impl Constraint for GlobalCardinality {
+    fn scope(&self) -> Vec<VariableId> {
+        self.variables.clone()
+    }
+
+    fn propagate(&self, domains: &mut Domains) -> Result<(), Conflict> {
+        for ((&value, &lower_bound), &upper_bound) in self
+            .values
+            .iter()
+            .zip(&self.lower_bounds)
+            .zip(&self.upper_bounds)
+        {
+            let num_assigned = self
+                .variables
+                .iter()
+                .filter(|&&variable| {
+                    let domain = domains.get(variable);
+                    domain.is_fixed() && domain.contains(value)
+                })
+                .count() as i64;
+            let candidates: Vec<VariableId> = self
+                .variables
+                .iter()
+                .copied()
+                .filter(|&variable| domains.get(variable).contains(value))
+                .collect();
+
+            if num_assigned > upper_bound || (candidates.len() as i64) < lower_bound {
+                return Err(Conflict);
+            }
+
+            if num_assigned == upper_bound {
+                // No other variable can take the value.
+                for &variable in &candidates {
+                    if !domains.get(variable).is_fixed() {
+                        domains.remove(variable, value)?;
+                    }
+                }
+            } else if candidates.len() as i64 == lower_bound {
+                // Every variable which can take the value has to take it.
+                for &variable in &candidates {
+                    domains.assign(variable, value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_satisfied(&self, value: &dyn Fn(VariableId) -> i64) -> bool {
+        self.values
+            .iter()
+            .zip(&self.lower_bounds)
+            .zip(&self.upper_bounds)
+            .all(|((&counted_value, &lower_bound), &upper_bound)| {
+                let count = self
+                    .variables
+                    .iter()
+                    .filter(|&&variable| value(variable) == counted_value)
+                    .count() as i64;
+
+                lower_bound <= count && count <= upper_bound
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::Domain;
+
+    #[test]
+    fn value_is_removed_when_the_upper_bound_is_reached() {
+        let initial_domains = [Domain::new(1, 1), Domain::new(1, 3), Domain::new(1, 3)];
+        let mut domains = Domains::new(&initial_domains);
+
+        let constraint = GlobalCardinality::new(
+            vec![VariableId(0), VariableId(1), VariableId(2)],
+            vec![1],
+            vec![0],
+            vec![1],
+        );
+        constraint.propagate(&mut domains).expect("no conflict");
+
+        assert_eq!(2, domains.lower_bound(VariableId(1)));
+        assert_eq!(2, domains.lower_bound(VariableId(2)));
+    }
+
+    #[test]
+    fn too_few_candidates_is_a_conflict() {
+        let initial_domains = [Domain::new(1, 2), Domain::new(2, 3)];
+        let mut domains = Domains::new(&initial_domains);
+
+        let constraint = GlobalCardinality::new(
+            vec![VariableId(0), VariableId(1)],
+            vec![1],
+            vec![2],
+            vec![2],
+        );
+        assert_eq!(Err(Conflict), constraint.propagate(&mut domains));
+    }
+}
@@ -0,0 +1,193 @@
+use super::Constraint;
+use crate::domains::Conflict;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LinearComparison {
+    LessThanOrEqual,
+    Equal,
+    NotEqual,
+}
+
+/// The linear constraint `sum(coefficient * variable) <comparison> right_hand_side`.
+#[derive(Clone, Debug)]
+pub(crate) struct Linear {
+    terms: Vec<(i64, VariableId)>,
+    comparison: LinearComparison,
+    right_hand_side: i64,
+}
+
+impl Linear {
+    pub(crate) fn new(
+        terms: Vec<(i64, VariableId)>,
+        comparison: LinearComparison,
+        right_hand_side: i64,
+    ) -> Linear {
+        Linear {
+            terms,
+            comparison,
+            right_hand_side,
+        }
+    }
+}
+
+impl Constraint for Linear {
+    fn scope(&self) -> Vec<VariableId> {
+        self.terms.iter().map(|&(_, variable)| variable).collect()
+    }
+
+    fn propagate(&self, domains: &mut Domains) -> Result<(), Conflict> {
+        let right_hand_side = i128::from(self.right_hand_side);
+
+        match self.comparison {
+            LinearComparison::LessThanOrEqual => {
+                propagate_less_than_or_equal(self.terms.iter().copied(), right_hand_side, domains)
+            }
+            LinearComparison::Equal => {
+                propagate_less_than_or_equal(self.terms.iter().copied(), right_hand_side, domains)?;
+                propagate_less_than_or_equal(
+                    self.terms
+                        .iter()
+                        .map(|&(coefficient, variable)| (-coefficient, variable)),
+                    -right_hand_side,
+                    domains,
+                )
+            }
+            LinearComparison::NotEqual => {
+                let mut unfixed_terms = self
+                    .terms
+                    .iter()
+                    .filter(|&&(_, variable)| !domains.get(variable).is_fixed());
+                let unfixed_term = unfixed_terms.next();
+                if unfixed_terms.next().is_some() {
+                    return Ok(());
+                }
+
+                let fixed_sum: i128 = self
+                    .terms
+                    .iter()
+                    .filter(|&&(_, variable)| domains.get(variable).is_fixed())
+                    .map(|&(coefficient, variable)| {
+                        i128::from(coefficient) * i128::from(domains.lower_bound(variable))
+                    })
+                    .sum();
+
+                match unfixed_term {
+                    None if fixed_sum == right_hand_side => Err(Conflict),
+                    None => Ok(()),
+                    Some(&(coefficient, variable)) => {
+                        let remainder = right_hand_side - fixed_sum;
+                        let coefficient = i128::from(coefficient);
+
+                        if coefficient != 0 && remainder % coefficient == 0 {
+                            if let Ok(value) = i64::try_from(remainder / coefficient) {
+                                domains.remove(variable, value)?;
+                            }
+                        }
+
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_satisfied(&self, value: &dyn Fn(VariableId) -> i64) -> bool {
+        let sum: i128 = self
+            .terms
+            .iter()
+            .map(|&(coefficient, variable)| i128::from(coefficient) * i128::from(value(variable)))
+            .sum();
+        let right_hand_side = i128::from(self.right_hand_side);
+
+        match self.comparison {
+            LinearComparison::LessThanOrEqual => sum <= right_hand_side,
+            LinearComparison::Equal => sum == right_hand_side,
+            LinearComparison::NotEqual => sum != right_hand_side,
+        }
+    }
+}
+
+/// Propagates `sum(terms) <= right_hand_side` on the bounds of the variables.
+fn propagate_less_than_or_equal(
+    terms: impl Iterator<Item = (i64, VariableId)> + Clone,
+    right_hand_side: i128,
+    domains: &mut Domains,
+) -> Result<(), Conflict> {
+    let minimum_term = |coefficient: i64, variable: VariableId, domains: &Domains| {
+        let bound = if coefficient >= 0 {
+            domains.lower_bound(variable)
+        } else {
+            domains.upper_bound(variable)
+        };
+
+        i128::from(coefficient) * i128::from(bound)
+    };
+
+    let minimum_sum: i128 = terms
+        .clone()
+        .map(|(coefficient, variable)| minimum_term(coefficient, variable, domains))
+        .sum();
+
+    if minimum_sum > right_hand_side {
+        return Err(Conflict);
+    }
+
+    for (coefficient, variable) in terms {
+        // The bounds are computed with respect to the domains at the start of the propagation,
+        // which remains correct as the domains only shrink.
+        let slack = right_hand_side - minimum_sum + minimum_term(coefficient, variable, domains);
+        let coefficient = i128::from(coefficient);
+
+        if coefficient > 0 {
+            let bound = slack.div_euclid(coefficient);
+            if bound < i128::from(domains.upper_bound(variable)) {
+                domains.set_upper_bound(variable, bound as i64)?;
+            }
+        } else if coefficient < 0 {
+            let bound = -(slack.div_euclid(-coefficient));
+            if bound > i128::from(domains.lower_bound(variable)) {
+                domains.set_lower_bound(variable, bound as i64)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::Domain;
+
+    #[test]
+    fn less_than_or_equal_tightens_upper_bounds() {
+        let initial_domains = [Domain::new(0, 10), Domain::new(2, 10)];
+        let mut domains = Domains::new(&initial_domains);
+        let (x, y) = (VariableId(0), VariableId(1));
+
+        let constraint = Linear::new(vec![(2, x), (-1, y)], LinearComparison::LessThanOrEqual, 3);
+        // `2x - y <= 3` implies `x <= 6` and `y >= -3`.
+        constraint.propagate(&mut domains).expect("no conflict");
+        assert_eq!(6, domains.upper_bound(x));
+        assert_eq!(2, domains.lower_bound(y));
+
+        domains.set_lower_bound(x, 5).expect("non-empty domain");
+        constraint.propagate(&mut domains).expect("no conflict");
+        assert_eq!(7, domains.lower_bound(y));
+    }
+
+    #[test]
+    fn not_equal_with_fixed_terms_is_conflicting() {
+        let initial_domains = [Domain::new(1, 1), Domain::new(2, 2)];
+        let mut domains = Domains::new(&initial_domains);
+
+        let constraint = Linear::new(
+            vec![(1, VariableId(0)), (1, VariableId(1))],
+            LinearComparison::NotEqual,
+            3,
+        );
+        assert_eq!(Err(Conflict), constraint.propagate(&mut domains));
+    }
+}
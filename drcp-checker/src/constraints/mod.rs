@@ -0,0 +1,132 @@
+//! The constraints for which the checker can verify inferences.
+//!
+//! An inference `premises -> propagated` is implied by a constraint if the constraint has no
+//! solution in which the premises hold and the propagated atomic constraint does not. The
+//! checker establishes this by propagating the constraint to a fixpoint (see
+//! [`Constraint::propagate`]); if that does not lead to a conflict and the remaining domains are
+//! small, all assignments to the scope of the constraint are enumerated instead.
+
+mod all_different;
+mod clause;
+mod conjunction;
+mod cumulative;
+mod element;
+mod global_cardinality;
+mod linear;
+
+use std::fmt::Debug;
+
+pub(crate) use all_different::AllDifferent;
+pub(crate) use clause::Clause;
+pub(crate) use conjunction::Conjunction;
+pub(crate) use cumulative::Cumulative;
+pub(crate) use element::Element;
+pub(crate) use global_cardinality::GlobalCardinality;
+pub(crate) use linear::Linear;
+pub(crate) use linear::LinearComparison;
+
+use crate::domains::Conflict;
+use crate::domains::Domains;
+use crate::domains::VariableId;
+
+/// The maximum number of propagation rounds before giving up on reaching a fixpoint.
+pub(crate) const MAX_PROPAGATION_ROUNDS: usize = 1000;
+
+/// The maximum number of assignments which are enumerated when propagation does not lead to a
+/// conflict.
+const MAX_ENUMERATED_ASSIGNMENTS: u64 = 100_000;
+
+pub(crate) trait Constraint: Debug {
+    /// The variables in the scope of the constraint.
+    fn scope(&self) -> Vec<VariableId>;
+
+    /// Removes values from the domains which cannot be part of a solution to the constraint, or
+    /// returns [`Conflict`] if the constraint has no solution within the domains.
+    ///
+    /// The propagation does not need to reach a fixpoint, nor does it need to remove all values
+    /// which are not part of a solution. It should never remove a value which is part of a
+    /// solution.
+    fn propagate(&self, domains: &mut Domains) -> Result<(), Conflict>;
+
+    /// Returns whether the constraint is satisfied by the assignment which is given by `value`.
+    fn is_satisfied(&self, value: &dyn Fn(VariableId) -> i64) -> bool;
+}
+
+/// Returns whether `constraint` has no solution within the given domains.
+///
+/// If `false` is returned, the constraint may still have no solution; the checker is only able to
+/// detect the absence of a solution through propagation and enumeration of small domains.
+pub(crate) fn has_no_solution(constraint: &dyn Constraint, domains: &Domains) -> bool {
+    let mut domains = domains.clone();
+
+    if propagate_to_fixpoint(constraint, &mut domains).is_err() {
+        return true;
+    }
+
+    let mut scope = constraint.scope();
+    scope.sort();
+    scope.dedup();
+
+    let num_assignments = scope.iter().try_fold(1_u64, |num_assignments, &variable| {
+        num_assignments
+            .checked_mul(domains.get(variable).size())
+            .filter(|&num_assignments| num_assignments <= MAX_ENUMERATED_ASSIGNMENTS)
+    });
+    if num_assignments.is_none() {
+        return false;
+    }
+
+    let candidates: Vec<Vec<i64>> = scope
+        .iter()
+        .map(|&variable| domains.get(variable).values().collect())
+        .collect();
+
+    !has_satisfying_assignment(constraint, &scope, &candidates, &mut vec![])
+}
+
+fn propagate_to_fixpoint(
+    constraint: &dyn Constraint,
+    domains: &mut Domains,
+) -> Result<(), Conflict> {
+    for _ in 0..MAX_PROPAGATION_ROUNDS {
+        let num_changes = domains.num_changes();
+        constraint.propagate(domains)?;
+
+        if num_changes == domains.num_changes() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Enumerates the assignments to `scope` which extend `assigned`, where the variable
+/// `scope[index]` can take the values in `candidates[index]`.
+fn has_satisfying_assignment(
+    constraint: &dyn Constraint,
+    scope: &[VariableId],
+    candidates: &[Vec<i64>],
+    assigned: &mut Vec<i64>,
+) -> bool {
+    if assigned.len() == scope.len() {
+        let value = |variable: VariableId| {
+            let index = scope
+                .binary_search(&variable)
+                .expect("the constraint only evaluates variables in its scope");
+            assigned[index]
+        };
+        return constraint.is_satisfied(&value);
+    }
+
+    for &value in &candidates[assigned.len()] {
+        assigned.push(value);
+        let is_satisfiable = has_satisfying_assignment(constraint, scope, candidates, assigned);
+        let _ = assigned.pop();
+
+        if is_satisfiable {
+            return true;
+        }
+    }
+
+    false
+}
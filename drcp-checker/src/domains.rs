@@ -0,0 +1,337 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use drcp_format::Comparison;
+
+/// The index of a variable in the [`Model`](crate::model::Model).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct VariableId(pub(crate) usize);
+
+/// An atomic constraint over a variable of the model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Atomic {
+    pub(crate) variable: VariableId,
+    pub(crate) comparison: Comparison,
+    pub(crate) value: i64,
+}
+
+impl Atomic {
+    pub(crate) fn new(variable: VariableId, comparison: Comparison, value: i64) -> Atomic {
+        Atomic {
+            variable,
+            comparison,
+            value,
+        }
+    }
+
+    /// Returns whether the atomic constraint holds when its variable is assigned `value`.
+    pub(crate) fn is_satisfied_by(self, value: i64) -> bool {
+        match self.comparison {
+            Comparison::GreaterThanEqual => value >= self.value,
+            Comparison::LessThanEqual => value <= self.value,
+            Comparison::Equal => value == self.value,
+            Comparison::NotEqual => value != self.value,
+        }
+    }
+
+    pub(crate) fn negate(self) -> Atomic {
+        let (comparison, value) = match self.comparison {
+            Comparison::GreaterThanEqual => (Comparison::LessThanEqual, self.value - 1),
+            Comparison::LessThanEqual => (Comparison::GreaterThanEqual, self.value + 1),
+            Comparison::Equal => (Comparison::NotEqual, self.value),
+            Comparison::NotEqual => (Comparison::Equal, self.value),
+        };
+
+        Atomic::new(self.variable, comparison, value)
+    }
+}
+
+/// The domain of a single variable, which is an interval with holes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Domain {
+    lower_bound: i64,
+    upper_bound: i64,
+    /// The values between the bounds which are not in the domain.
+    holes: BTreeSet<i64>,
+}
+
+impl Domain {
+    pub(crate) fn new(lower_bound: i64, upper_bound: i64) -> Domain {
+        Domain {
+            lower_bound,
+            upper_bound,
+            holes: BTreeSet::new(),
+        }
+    }
+
+    /// The domain which contains every value, used for the variables which are not part of the
+    /// model (e.g. literals which are introduced by the proof).
+    pub(crate) fn unbounded() -> Domain {
+        // The bounds are far from the extremes of `i64` such that arithmetic on them cannot
+        // overflow.
+        Domain::new(i64::from(i32::MIN) * 2, i64::from(i32::MAX) * 2)
+    }
+
+    pub(crate) fn from_values(values: impl IntoIterator<Item = i64>) -> Domain {
+        let values: BTreeSet<i64> = values.into_iter().collect();
+
+        let (Some(&lower_bound), Some(&upper_bound)) = (values.first(), values.last()) else {
+            return Domain::new(1, 0);
+        };
+
+        Domain {
+            lower_bound,
+            upper_bound,
+            holes: (lower_bound..=upper_bound)
+                .filter(|value| !values.contains(value))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn lower_bound(&self) -> i64 {
+        self.lower_bound
+    }
+
+    pub(crate) fn upper_bound(&self) -> i64 {
+        self.upper_bound
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lower_bound > self.upper_bound
+    }
+
+    pub(crate) fn is_fixed(&self) -> bool {
+        self.lower_bound == self.upper_bound
+    }
+
+    pub(crate) fn contains(&self, value: i64) -> bool {
+        self.lower_bound <= value && value <= self.upper_bound && !self.holes.contains(&value)
+    }
+
+    /// The number of values in the domain.
+    pub(crate) fn size(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            (self.upper_bound - self.lower_bound + 1) as u64 - self.holes.len() as u64
+        }
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = i64> + '_ {
+        (self.lower_bound..=self.upper_bound).filter(|value| !self.holes.contains(value))
+    }
+
+    /// Returns whether the atomic constraint holds for every value in the domain.
+    pub(crate) fn entails(&self, comparison: Comparison, value: i64) -> bool {
+        match comparison {
+            Comparison::GreaterThanEqual => self.lower_bound >= value,
+            Comparison::LessThanEqual => self.upper_bound <= value,
+            Comparison::Equal => self.lower_bound == value && self.upper_bound == value,
+            Comparison::NotEqual => !self.contains(value),
+        }
+    }
+
+    /// Removes the values from the domain which do not satisfy the atomic constraint. Returns
+    /// whether the domain changed.
+    pub(crate) fn restrict(&mut self, comparison: Comparison, value: i64) -> bool {
+        if self.entails(comparison, value) {
+            return false;
+        }
+
+        match comparison {
+            Comparison::GreaterThanEqual => self.lower_bound = value,
+            Comparison::LessThanEqual => self.upper_bound = value,
+            Comparison::Equal => {
+                if self.contains(value) {
+                    self.lower_bound = value;
+                    self.upper_bound = value;
+                } else {
+                    self.lower_bound = 1;
+                    self.upper_bound = 0;
+                }
+            }
+            Comparison::NotEqual => {
+                let _ = self.holes.insert(value);
+            }
+        }
+
+        self.normalise();
+        true
+    }
+
+    /// Removes the values from the domain which are not in `other`.
+    pub(crate) fn intersect(&mut self, other: &Domain) {
+        let _ = self.restrict(Comparison::GreaterThanEqual, other.lower_bound);
+        let _ = self.restrict(Comparison::LessThanEqual, other.upper_bound);
+
+        for &hole in &other.holes {
+            let _ = self.restrict(Comparison::NotEqual, hole);
+        }
+    }
+
+    /// Ensures the bounds are values in the domain, and that the holes are within the bounds.
+    fn normalise(&mut self) {
+        while self.lower_bound <= self.upper_bound && self.holes.contains(&self.lower_bound) {
+            self.lower_bound += 1;
+        }
+        while self.lower_bound <= self.upper_bound && self.holes.contains(&self.upper_bound) {
+            self.upper_bound -= 1;
+        }
+
+        if self.is_empty() {
+            self.holes.clear();
+        } else {
+            self.holes = self
+                .holes
+                .range(self.lower_bound..=self.upper_bound)
+                .copied()
+                .collect();
+        }
+    }
+}
+
+/// The domains of the variables during the checking of a single step.
+///
+/// Only the domains which differ from the initial domains of the model are stored, such that
+/// creating the domains for a step is cheap.
+#[derive(Clone, Debug)]
+pub(crate) struct Domains<'model> {
+    initial_domains: &'model [Domain],
+    changed_domains: HashMap<VariableId, Domain>,
+    num_changes: usize,
+}
+
+impl<'model> Domains<'model> {
+    pub(crate) fn new(initial_domains: &'model [Domain]) -> Domains<'model> {
+        Domains {
+            initial_domains,
+            changed_domains: HashMap::new(),
+            num_changes: 0,
+        }
+    }
+
+    pub(crate) fn get(&self, variable: VariableId) -> &Domain {
+        self.changed_domains
+            .get(&variable)
+            .unwrap_or(&self.initial_domains[variable.0])
+    }
+
+    /// The number of times a domain has been changed, which is used to detect a fixpoint.
+    pub(crate) fn num_changes(&self) -> usize {
+        self.num_changes
+    }
+
+    pub(crate) fn lower_bound(&self, variable: VariableId) -> i64 {
+        self.get(variable).lower_bound()
+    }
+
+    pub(crate) fn upper_bound(&self, variable: VariableId) -> i64 {
+        self.get(variable).upper_bound()
+    }
+
+    pub(crate) fn is_entailed(&self, atomic: Atomic) -> bool {
+        self.get(atomic.variable)
+            .entails(atomic.comparison, atomic.value)
+    }
+
+    pub(crate) fn is_falsified(&self, atomic: Atomic) -> bool {
+        self.is_entailed(atomic.negate())
+    }
+
+    /// Restricts the domain of the variable to the values which satisfy the atomic constraint.
+    ///
+    /// Returns [`Conflict`] if the domain becomes empty.
+    pub(crate) fn apply(&mut self, atomic: Atomic) -> Result<(), Conflict> {
+        if self.is_entailed(atomic) {
+            return Ok(());
+        }
+
+        let initial_domains = self.initial_domains;
+        let domain = self
+            .changed_domains
+            .entry(atomic.variable)
+            .or_insert_with(|| initial_domains[atomic.variable.0].clone());
+
+        if domain.restrict(atomic.comparison, atomic.value) {
+            self.num_changes += 1;
+        }
+
+        if domain.is_empty() {
+            Err(Conflict)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn set_lower_bound(
+        &mut self,
+        variable: VariableId,
+        bound: i64,
+    ) -> Result<(), Conflict> {
+        self.apply(Atomic::new(variable, Comparison::GreaterThanEqual, bound))
+    }
+
+    pub(crate) fn set_upper_bound(
+        &mut self,
+        variable: VariableId,
+        bound: i64,
+    ) -> Result<(), Conflict> {
+        self.apply(Atomic::new(variable, Comparison::LessThanEqual, bound))
+    }
+
+    pub(crate) fn remove(&mut self, variable: VariableId, value: i64) -> Result<(), Conflict> {
+        self.apply(Atomic::new(variable, Comparison::NotEqual, value))
+    }
+
+    pub(crate) fn assign(&mut self, variable: VariableId, value: i64) -> Result<(), Conflict> {
+        self.apply(Atomic::new(variable, Comparison::Equal, value))
+    }
+}
+
+/// Indicates that the domains cannot be extended to an assignment which satisfies the
+/// constraints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Conflict;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removing_a_bound_moves_it_past_the_holes() {
+        let mut domain = Domain::from_values([1, 3, 4, 7]);
+        assert_eq!(4, domain.size());
+
+        assert!(domain.restrict(Comparison::NotEqual, 1));
+        assert_eq!(3, domain.lower_bound());
+
+        assert!(domain.restrict(Comparison::LessThanEqual, 6));
+        assert_eq!(4, domain.upper_bound());
+        assert!(domain.entails(Comparison::NotEqual, 2));
+        assert!(!domain.entails(Comparison::NotEqual, 4));
+    }
+
+    #[test]
+    fn assigning_a_removed_value_empties_the_domain() {
+        let mut domain = Domain::from_values([1, 3]);
+
+        assert!(domain.restrict(Comparison::Equal, 2));
+        assert!(domain.is_empty());
+    }
+
+    #[test]
+    fn changed_domains_do_not_affect_the_initial_domains() {
+        let initial_domains = [Domain::new(0, 10)];
+        let variable = VariableId(0);
+
+        let mut domains = Domains::new(&initial_domains);
+        domains
+            .set_lower_bound(variable, 5)
+            .expect("non-empty domain");
+        assert_eq!(5, domains.lower_bound(variable));
+        assert_eq!(1, domains.num_changes());
+
+        assert_eq!(Err(Conflict), domains.set_upper_bound(variable, 4));
+        assert_eq!(0, Domains::new(&initial_domains).lower_bound(variable));
+    }
+}
@@ -0,0 +1,85 @@
+//! A checker for DRCP proofs of FlatZinc models.
+//!
+//! The checker verifies every step of the proof against the model. It supports a core set of
+//! constraints (see [`model::Model::parse`]); inferences which are attributed to other constraints
+//! are rejected.
+
+mod checker;
+mod constraints;
+mod domains;
+mod model;
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::rc::Rc;
+
+use anyhow::Context;
+use clap::Parser;
+use drcp_format::compression;
+use drcp_format::reader::ProofReader;
+use drcp_format::Format;
+use drcp_format::LiteralDefinitions;
+
+use crate::checker::ProofChecker;
+use crate::model::Model;
+
+#[derive(Parser)]
+struct Cli {
+    /// The FlatZinc model.
+    model: PathBuf,
+    /// The proof of the model, which may be compressed with gzip (`.gz`) or zstd (`.zst`).
+    proof: PathBuf,
+    /// The literals of the proof. Defaults to the proof path with the `.lits` extension, which is
+    /// compressed in the same way as the proof.
+    #[arg(long)]
+    literals: Option<PathBuf>,
+    /// Whether the proof is in the binary format.
+    #[arg(long)]
+    binary: bool,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Cli::parse();
+
+    let model_file = File::open(&args.model)
+        .with_context(|| format!("Failed to open {}", args.model.display()))?;
+    let model = Model::parse(model_file)
+        .with_context(|| format!("Failed to parse the model {}", args.model.display()))?;
+
+    let literals_path = args
+        .literals
+        .unwrap_or_else(|| compression::literals_path(&args.proof));
+    let literals_file = compression::open(&literals_path)
+        .with_context(|| format!("Failed to open {}", literals_path.display()))?;
+    let literals = LiteralDefinitions::<Rc<str>>::parse(literals_file).with_context(|| {
+        format!(
+            "Failed to parse literal definitions from {}.",
+            literals_path.display()
+        )
+    })?;
+
+    let format = if args.binary {
+        Format::Binary
+    } else {
+        Format::Text
+    };
+    let mut reader = ProofReader::open(&args.proof, format, literals)
+        .with_context(|| format!("Failed to open {}", args.proof.display()))?;
+
+    let mut checker = ProofChecker::new(model);
+    while let Some(step) = reader.next_step()? {
+        if let Err(error) = checker.check_step(step) {
+            println!("{error}");
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    if let Err(error) = checker.finish() {
+        println!("{error}");
+        return Ok(ExitCode::FAILURE);
+    }
+
+    println!("The proof is valid");
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,774 @@
+//! The FlatZinc model against which a proof is checked.
+//!
+//! Every constraint item in the model is identified by its constraint tag, which is the 1-based
+//! index of the item among the constraint items in the file. Only a core set of constraints is
+//! supported (see [`Model::parse`]); the other constraints are kept by name, such that inferences
+//! which are attributed to them are reported as unsupported rather than invalid.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use drcp_format::Comparison;
+use flatzinc::ArrayOfBoolExpr;
+use flatzinc::ArrayOfIntExpr;
+use flatzinc::BoolExpr;
+use flatzinc::Expr;
+use flatzinc::IntExpr;
+use thiserror::Error;
+
+use crate::constraints::AllDifferent;
+use crate::constraints::Clause;
+use crate::constraints::Conjunction;
+use crate::constraints::Constraint;
+use crate::constraints::Cumulative;
+use crate::constraints::Element;
+use crate::constraints::GlobalCardinality;
+use crate::constraints::Linear;
+use crate::constraints::LinearComparison;
+use crate::domains::Atomic;
+use crate::domains::Domain;
+use crate::domains::VariableId;
+
+#[derive(Debug, Error)]
+pub(crate) enum ModelError {
+    #[error("Failed to read the model: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Syntax error: {0}")]
+    Syntax(String),
+
+    #[error("Unknown identifier '{0}'")]
+    UnknownIdentifier(String),
+
+    #[error("Invalid arguments for constraint '{0}'")]
+    InvalidArguments(String),
+
+    #[error("The value {0} is out of range")]
+    ValueOutOfRange(i128),
+}
+
+/// A constraint item in the model.
+#[derive(Debug)]
+pub(crate) struct ModelConstraint {
+    /// The name of the FlatZinc constraint.
+    pub(crate) name: String,
+    /// The constraint, or `None` if the checker does not support the constraint.
+    pub(crate) constraint: Option<Box<dyn Constraint>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum OptimisationDirection {
+    Minimise,
+    Maximise,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Objective {
+    pub(crate) variable: VariableId,
+    pub(crate) direction: OptimisationDirection,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Model {
+    /// The name of every variable, indexed by [`VariableId`].
+    variable_names: Vec<Rc<str>>,
+    /// The initial domain of every variable, indexed by [`VariableId`].
+    initial_domains: Vec<Domain>,
+    /// The number of variables which are declared by the model. These are the variables with an id
+    /// below this number; the variables after them are introduced by the proof.
+    num_model_variables: usize,
+    /// Maps the identifiers of the variables to the variables. Aliased identifiers map to the same
+    /// variable.
+    variables: HashMap<Rc<str>, VariableId>,
+    /// The variables which are introduced for the constants in the model.
+    constants: HashMap<i64, VariableId>,
+
+    /// The integer and Boolean parameters, where Booleans are stored as 0 or 1.
+    parameters: HashMap<String, i64>,
+    /// The integer and Boolean array parameters, where Booleans are stored as 0 or 1.
+    array_parameters: HashMap<String, Vec<i64>>,
+    /// The arrays of variables.
+    variable_arrays: HashMap<String, Vec<VariableId>>,
+
+    /// The constraint items, where the constraint with tag `t` is at index `t - 1`.
+    constraints: Vec<ModelConstraint>,
+    /// For every variable the indices of the constraints in which it occurs.
+    constraints_by_variable: HashMap<VariableId, Vec<usize>>,
+    objective: Option<Objective>,
+}
+
+impl Model {
+    /// Parse a FlatZinc model.
+    ///
+    /// The supported constraints are the linear integer constraints (`int_lin_*`, `int_le`,
+    /// `int_lt`, `int_eq`, `int_ne`, `int_plus`), the Boolean constraints (`bool_clause`,
+    /// `array_bool_or`, `array_bool_and`, `bool_not`, `bool_eq`, `bool_le`, `bool_lt`, `bool2int`),
+    /// the element constraints, and the Pumpkin globals `pumpkin_all_different`,
+    /// `pumpkin_cumulative` and `pumpkin_gcc`.
+    pub(crate) fn parse(source: impl Read) -> Result<Model, ModelError> {
+        let mut model = Model::default();
+
+        for line in BufReader::new(source).lines() {
+            let line = line?;
+
+            match flatzinc::Stmt::from_str(&line).map_err(ModelError::Syntax)? {
+                flatzinc::Stmt::Comment(_) | flatzinc::Stmt::Predicate(_) => {}
+                flatzinc::Stmt::Parameter(decl) => model.add_parameter(decl)?,
+                flatzinc::Stmt::Variable(decl) => model.add_variable(decl)?,
+                flatzinc::Stmt::Constraint(item) => model.add_constraint(item)?,
+                flatzinc::Stmt::SolveItem(item) => model.set_objective(item)?,
+            }
+        }
+
+        model.num_model_variables = model.variable_names.len();
+
+        Ok(model)
+    }
+
+    pub(crate) fn initial_domains(&self) -> &[Domain] {
+        &self.initial_domains
+    }
+
+    pub(crate) fn variable_name(&self, variable: VariableId) -> &str {
+        &self.variable_names[variable.0]
+    }
+
+    pub(crate) fn objective(&self) -> Option<Objective> {
+        self.objective
+    }
+
+    /// The variables which are declared by the model, including the constants in the model.
+    pub(crate) fn model_variables(&self) -> impl Iterator<Item = VariableId> {
+        (0..self.num_model_variables).map(VariableId)
+    }
+
+    /// Returns whether the model contains a variable with the given name.
+    pub(crate) fn contains_variable(&self, name: &str) -> bool {
+        self.variables.contains_key(name)
+    }
+
+    /// Get the variable with the given name.
+    ///
+    /// Names which do not occur in the model are variables which are introduced by the proof, and
+    /// are given an unbounded domain. The exception are names which are integers, which are the
+    /// names of constants.
+    pub(crate) fn resolve_variable(&mut self, name: &Rc<str>) -> VariableId {
+        if let Some(&variable) = self.variables.get(name) {
+            return variable;
+        }
+
+        match name.parse::<i64>() {
+            Ok(value) => self.constant(value),
+            Err(_) => self.new_variable(Rc::clone(name), Domain::unbounded()),
+        }
+    }
+
+    /// Replace the initial domain of a variable which was introduced by the proof.
+    pub(crate) fn set_initial_domain(&mut self, variable: VariableId, domain: Domain) {
+        self.initial_domains[variable.0] = domain;
+    }
+
+    /// Get the constraint with the given tag.
+    pub(crate) fn constraint(&self, tag: usize) -> Option<&ModelConstraint> {
+        tag.checked_sub(1)
+            .and_then(|index| self.constraints.get(index))
+    }
+
+    /// The first constraint in the model which is not supported, together with its tag.
+    pub(crate) fn unsupported_constraint(&self) -> Option<(usize, &ModelConstraint)> {
+        self.constraints
+            .iter()
+            .enumerate()
+            .find(|(_, item)| item.constraint.is_none())
+            .map(|(index, item)| (index + 1, item))
+    }
+
+    /// The supported constraints in the model.
+    pub(crate) fn supported_constraints(&self) -> impl Iterator<Item = &dyn Constraint> + '_ {
+        self.constraints
+            .iter()
+            .filter_map(|item| item.constraint.as_deref())
+    }
+
+    /// The supported constraints in the model in which the variable occurs.
+    pub(crate) fn constraints_on(
+        &self,
+        variable: VariableId,
+    ) -> impl Iterator<Item = &dyn Constraint> + '_ {
+        self.constraints_by_variable
+            .get(&variable)
+            .into_iter()
+            .flatten()
+            .filter_map(|&index| self.constraints[index].constraint.as_deref())
+    }
+
+    fn new_variable(&mut self, name: Rc<str>, domain: Domain) -> VariableId {
+        let variable = VariableId(self.variable_names.len());
+
+        self.variable_names.push(Rc::clone(&name));
+        self.initial_domains.push(domain);
+        let _ = self.variables.insert(name, variable);
+
+        variable
+    }
+
+    fn constant(&mut self, value: i64) -> VariableId {
+        if let Some(&variable) = self.constants.get(&value) {
+            return variable;
+        }
+
+        let variable = self.new_variable(value.to_string().into(), Domain::new(value, value));
+        let _ = self.constants.insert(value, variable);
+        variable
+    }
+
+    fn add_parameter(&mut self, decl: flatzinc::ParDeclItem) -> Result<(), ModelError> {
+        match decl {
+            flatzinc::ParDeclItem::Int { id, int } => {
+                let _ = self.parameters.insert(id, to_i64(int)?);
+            }
+            flatzinc::ParDeclItem::Bool { id, bool } => {
+                let _ = self.parameters.insert(id, i64::from(bool));
+            }
+            flatzinc::ParDeclItem::ArrayOfInt { id, v, .. } => {
+                let values = v.into_iter().map(to_i64).collect::<Result<_, _>>()?;
+                let _ = self.array_parameters.insert(id, values);
+            }
+            flatzinc::ParDeclItem::ArrayOfBool { id, v, .. } => {
+                let values = v.into_iter().map(i64::from).collect();
+                let _ = self.array_parameters.insert(id, values);
+            }
+            // The other parameters cannot be used by the supported constraints.
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn add_variable(&mut self, decl: flatzinc::VarDeclItem) -> Result<(), ModelError> {
+        match decl {
+            flatzinc::VarDeclItem::Bool { id, expr, .. } => {
+                let expr = expr.map(|expr| match expr {
+                    BoolExpr::Bool(value) => IntExpr::Int(i128::from(value)),
+                    BoolExpr::VarParIdentifier(identifier) => IntExpr::VarParIdentifier(identifier),
+                });
+                self.add_single_variable(id, Domain::new(0, 1), expr)
+            }
+            flatzinc::VarDeclItem::Int { id, expr, .. } => {
+                self.add_single_variable(id, Domain::unbounded(), expr)
+            }
+            flatzinc::VarDeclItem::IntInRange {
+                id, lb, ub, expr, ..
+            } => self.add_single_variable(id, Domain::new(to_i64(lb)?, to_i64(ub)?), expr),
+            flatzinc::VarDeclItem::IntInSet { id, set, expr, .. } => {
+                let values = set.into_iter().map(to_i64).collect::<Result<Vec<_>, _>>()?;
+                self.add_single_variable(id, Domain::from_values(values), expr)
+            }
+
+            flatzinc::VarDeclItem::ArrayOfBool {
+                id,
+                array_expr: Some(array_expr),
+                ..
+            } => {
+                let variables = match array_expr {
+                    ArrayOfBoolExpr::Array(exprs) => exprs
+                        .into_iter()
+                        .map(|expr| self.resolve_bool_expr(&expr))
+                        .collect::<Result<_, _>>()?,
+                    ArrayOfBoolExpr::VarParIdentifier(identifier) => {
+                        self.resolve_array_identifier(&identifier)?
+                    }
+                };
+                let _ = self.variable_arrays.insert(id, variables);
+                Ok(())
+            }
+            flatzinc::VarDeclItem::ArrayOfInt {
+                id,
+                array_expr: Some(array_expr),
+                ..
+            }
+            | flatzinc::VarDeclItem::ArrayOfIntInRange {
+                id,
+                array_expr: Some(array_expr),
+                ..
+            }
+            | flatzinc::VarDeclItem::ArrayOfIntInSet {
+                id,
+                array_expr: Some(array_expr),
+                ..
+            } => {
+                let variables = match array_expr {
+                    ArrayOfIntExpr::Array(exprs) => exprs
+                        .into_iter()
+                        .map(|expr| self.resolve_int_expr(&expr))
+                        .collect::<Result<_, _>>()?,
+                    ArrayOfIntExpr::VarParIdentifier(identifier) => {
+                        self.resolve_array_identifier(&identifier)?
+                    }
+                };
+                let _ = self.variable_arrays.insert(id, variables);
+                Ok(())
+            }
+
+            // Float and set variables, as well as arrays without elements, cannot be used by the
+            // supported constraints.
+            _ => Ok(()),
+        }
+    }
+
+    /// Add a variable with the given domain. If the variable is defined by an expression, it is
+    /// either fixed to a value or an alias of another variable.
+    fn add_single_variable(
+        &mut self,
+        id: String,
+        domain: Domain,
+        expr: Option<IntExpr>,
+    ) -> Result<(), ModelError> {
+        let aliased_variable = match &expr {
+            Some(IntExpr::VarParIdentifier(identifier)) => {
+                self.variables.get(identifier.as_str()).copied()
+            }
+            _ => None,
+        };
+
+        if let Some(variable) = aliased_variable {
+            // The domain of the alias is the intersection of both domains.
+            self.initial_domains[variable.0].intersect(&domain);
+            let _ = self.variables.insert(id.into(), variable);
+
+            return Ok(());
+        }
+
+        let domain = match expr {
+            Some(expr) => {
+                let value = self.resolve_int_value(&expr)?;
+                if domain.contains(value) {
+                    Domain::new(value, value)
+                } else {
+                    Domain::from_values([])
+                }
+            }
+            None => domain,
+        };
+        let _ = self.new_variable(id.into(), domain);
+
+        Ok(())
+    }
+
+    fn add_constraint(&mut self, item: flatzinc::ConstraintItem) -> Result<(), ModelError> {
+        let constraint = self.compile_constraint(&item.id, &item.exprs)?;
+
+        let index = self.constraints.len();
+        if let Some(constraint) = constraint.as_ref() {
+            let mut scope = constraint.scope();
+            scope.sort();
+            scope.dedup();
+
+            for variable in scope {
+                self.constraints_by_variable
+                    .entry(variable)
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        self.constraints.push(ModelConstraint {
+            name: item.id,
+            constraint,
+        });
+
+        Ok(())
+    }
+
+    fn compile_constraint(
+        &mut self,
+        name: &str,
+        exprs: &[Expr],
+    ) -> Result<Option<Box<dyn Constraint>>, ModelError> {
+        let expect_arguments = |num_arguments: usize| {
+            if exprs.len() == num_arguments {
+                Ok(())
+            } else {
+                Err(ModelError::InvalidArguments(name.to_owned()))
+            }
+        };
+
+        let constraint: Box<dyn Constraint> = match name {
+            "int_lin_le" | "int_lin_eq" | "int_lin_ne" | "bool_lin_le" | "bool_lin_eq" => {
+                expect_arguments(3)?;
+
+                let coefficients = self.resolve_values(&exprs[0])?;
+                let variables = self.resolve_variables(&exprs[1])?;
+                let right_hand_side = self.resolve_value(&exprs[2])?;
+                if coefficients.len() != variables.len() {
+                    return Err(ModelError::InvalidArguments(name.to_owned()));
+                }
+
+                let comparison = match name {
+                    "int_lin_le" | "bool_lin_le" => LinearComparison::LessThanOrEqual,
+                    "int_lin_eq" | "bool_lin_eq" => LinearComparison::Equal,
+                    _ => LinearComparison::NotEqual,
+                };
+
+                let terms = coefficients.into_iter().zip(variables).collect();
+                Box::new(Linear::new(terms, comparison, right_hand_side))
+            }
+
+            "int_le" | "int_lt" | "int_eq" | "int_ne" | "bool_le" | "bool_lt" | "bool_eq"
+            | "bool_not" | "bool2int" => {
+                expect_arguments(2)?;
+
+                let a = self.resolve_variable_expr(&exprs[0])?;
+                let b = self.resolve_variable_expr(&exprs[1])?;
+
+                let (terms, comparison, right_hand_side) = match name {
+                    "int_le" | "bool_le" => {
+                        (vec![(1, a), (-1, b)], LinearComparison::LessThanOrEqual, 0)
+                    }
+                    "int_lt" | "bool_lt" => {
+                        (vec![(1, a), (-1, b)], LinearComparison::LessThanOrEqual, -1)
+                    }
+                    "int_eq" | "bool_eq" | "bool2int" => {
+                        (vec![(1, a), (-1, b)], LinearComparison::Equal, 0)
+                    }
+                    "int_ne" => (vec![(1, a), (-1, b)], LinearComparison::NotEqual, 0),
+                    _ => (vec![(1, a), (1, b)], LinearComparison::Equal, 1),
+                };
+
+                Box::new(Linear::new(terms, comparison, right_hand_side))
+            }
+
+            "int_plus" => {
+                expect_arguments(3)?;
+
+                let a = self.resolve_variable_expr(&exprs[0])?;
+                let b = self.resolve_variable_expr(&exprs[1])?;
+                let c = self.resolve_variable_expr(&exprs[2])?;
+
+                Box::new(Linear::new(
+                    vec![(1, a), (1, b), (-1, c)],
+                    LinearComparison::Equal,
+                    0,
+                ))
+            }
+
+            "bool_clause" => {
+                expect_arguments(2)?;
+
+                let positive = self.resolve_variables(&exprs[0])?;
+                let negative = self.resolve_variables(&exprs[1])?;
+
+                let literals = positive
+                    .into_iter()
+                    .map(is_true)
+                    .chain(
+                        negative
+                            .into_iter()
+                            .map(|variable| is_true(variable).negate()),
+                    )
+                    .collect();
+                Box::new(Clause::new(literals))
+            }
+
+            "array_bool_or" | "array_bool_and" => {
+                expect_arguments(2)?;
+
+                let variables = self.resolve_variables(&exprs[0])?;
+                let reification = self.resolve_variable_expr(&exprs[1])?;
+
+                // Encode `r <-> \/ x` as the clauses `~r \/ (\/ x)` and `r \/ ~x_i`. The
+                // conjunction is encoded in the same way through `~r <-> \/ ~x`.
+                let (reification, literals): (Atomic, Vec<Atomic>) = if name == "array_bool_or" {
+                    (
+                        is_true(reification),
+                        variables.into_iter().map(is_true).collect(),
+                    )
+                } else {
+                    (
+                        is_true(reification).negate(),
+                        variables
+                            .into_iter()
+                            .map(|variable| is_true(variable).negate())
+                            .collect(),
+                    )
+                };
+
+                let mut clauses: Vec<Box<dyn Constraint>> = vec![Box::new(Clause::new(
+                    std::iter::once(reification.negate())
+                        .chain(literals.iter().copied())
+                        .collect(),
+                ))];
+                clauses.extend(literals.into_iter().map(|literal| -> Box<dyn Constraint> {
+                    Box::new(Clause::new(vec![reification, literal.negate()]))
+                }));
+
+                Box::new(Conjunction::new(clauses))
+            }
+
+            "array_int_element"
+            | "array_var_int_element"
+            | "array_bool_element"
+            | "array_var_bool_element" => {
+                expect_arguments(3)?;
+
+                let index = self.resolve_variable_expr(&exprs[0])?;
+                let array = self.resolve_variables(&exprs[1])?;
+                let rhs = self.resolve_variable_expr(&exprs[2])?;
+
+                Box::new(Element::new(index, array, rhs))
+            }
+
+            "pumpkin_all_different" => {
+                expect_arguments(1)?;
+
+                Box::new(AllDifferent::new(self.resolve_variables(&exprs[0])?))
+            }
+
+            "pumpkin_cumulative" => {
+                expect_arguments(4)?;
+
+                let start_times = self.resolve_variables(&exprs[0])?;
+                let durations = self.resolve_values(&exprs[1])?;
+                let resource_usages = self.resolve_values(&exprs[2])?;
+                let capacity = self.resolve_value(&exprs[3])?;
+                if start_times.len() != durations.len()
+                    || start_times.len() != resource_usages.len()
+                {
+                    return Err(ModelError::InvalidArguments(name.to_owned()));
+                }
+
+                Box::new(Cumulative::new(
+                    start_times,
+                    durations,
+                    resource_usages,
+                    capacity,
+                ))
+            }
+
+            "pumpkin_gcc" => {
+                expect_arguments(4)?;
+
+                let variables = self.resolve_variables(&exprs[0])?;
+                let values = self.resolve_values(&exprs[1])?;
+                let lower_bounds = self.resolve_values(&exprs[2])?;
+                let upper_bounds = self.resolve_values(&exprs[3])?;
+                if values.len() != lower_bounds.len() || values.len() != upper_bounds.len() {
+                    return Err(ModelError::InvalidArguments(name.to_owned()));
+                }
+
+                Box::new(GlobalCardinality::new(
+                    variables,
+                    values,
+                    lower_bounds,
+                    upper_bounds,
+                ))
+            }
+
+            _ => return Ok(None),
+        };
+
+        Ok(Some(constraint))
+    }
+
+    fn set_objective(&mut self, item: flatzinc::SolveItem) -> Result<(), ModelError> {
+        // An objective variable is parsed as a Boolean, as that is the first type which matches an
+        // identifier.
+        let (optimization_type, expr) = match item.goal {
+            flatzinc::Goal::OptimizeInt(optimization_type, expr) => (optimization_type, expr),
+            flatzinc::Goal::OptimizeBool(
+                optimization_type,
+                BoolExpr::VarParIdentifier(identifier),
+            ) => (optimization_type, IntExpr::VarParIdentifier(identifier)),
+            _ => return Ok(()),
+        };
+        let direction = match optimization_type {
+            flatzinc::OptimizationType::Minimize => OptimisationDirection::Minimise,
+            flatzinc::OptimizationType::Maximize => OptimisationDirection::Maximise,
+        };
+
+        self.objective = Some(Objective {
+            variable: self.resolve_int_expr(&expr)?,
+            direction,
+        });
+
+        Ok(())
+    }
+
+    fn resolve_identifier(&mut self, identifier: &str) -> Result<VariableId, ModelError> {
+        if let Some(&variable) = self.variables.get(identifier) {
+            Ok(variable)
+        } else if let Some(&value) = self.parameters.get(identifier) {
+            // The solver can refer to the parameter by its identifier in the proof (e.g. when it is
+            // the objective), so the identifier is an alias of the constant.
+            let variable = self.constant(value);
+            let _ = self.variables.insert(identifier.into(), variable);
+            Ok(variable)
+        } else {
+            Err(ModelError::UnknownIdentifier(identifier.to_owned()))
+        }
+    }
+
+    fn resolve_array_identifier(
+        &mut self,
+        identifier: &str,
+    ) -> Result<Vec<VariableId>, ModelError> {
+        if let Some(variables) = self.variable_arrays.get(identifier) {
+            Ok(variables.clone())
+        } else if let Some(values) = self.array_parameters.get(identifier).cloned() {
+            Ok(values
+                .into_iter()
+                .map(|value| self.constant(value))
+                .collect())
+        } else {
+            Err(ModelError::UnknownIdentifier(identifier.to_owned()))
+        }
+    }
+
+    fn resolve_int_expr(&mut self, expr: &IntExpr) -> Result<VariableId, ModelError> {
+        match expr {
+            IntExpr::Int(value) => Ok(self.constant(to_i64(*value)?)),
+            IntExpr::VarParIdentifier(identifier) => self.resolve_identifier(identifier),
+        }
+    }
+
+    fn resolve_bool_expr(&mut self, expr: &BoolExpr) -> Result<VariableId, ModelError> {
+        match expr {
+            BoolExpr::Bool(value) => Ok(self.constant(i64::from(*value))),
+            BoolExpr::VarParIdentifier(identifier) => self.resolve_identifier(identifier),
+        }
+    }
+
+    fn resolve_int_value(&self, expr: &IntExpr) -> Result<i64, ModelError> {
+        match expr {
+            IntExpr::Int(value) => to_i64(*value),
+            IntExpr::VarParIdentifier(identifier) => self
+                .parameters
+                .get(identifier)
+                .copied()
+                .ok_or_else(|| ModelError::UnknownIdentifier(identifier.clone())),
+        }
+    }
+
+    /// Resolve an argument of a constraint which is a single variable.
+    fn resolve_variable_expr(&mut self, expr: &Expr) -> Result<VariableId, ModelError> {
+        match expr {
+            Expr::VarParIdentifier(identifier) => self.resolve_identifier(identifier),
+            Expr::Int(value) => Ok(self.constant(to_i64(*value)?)),
+            Expr::Bool(value) => Ok(self.constant(i64::from(*value))),
+            _ => Err(ModelError::InvalidArguments(format!("{expr:?}"))),
+        }
+    }
+
+    /// Resolve an argument of a constraint which is an array of variables.
+    fn resolve_variables(&mut self, expr: &Expr) -> Result<Vec<VariableId>, ModelError> {
+        match expr {
+            Expr::VarParIdentifier(identifier) => self.resolve_array_identifier(identifier),
+            Expr::ArrayOfInt(exprs) => exprs
+                .iter()
+                .map(|expr| self.resolve_int_expr(expr))
+                .collect(),
+            Expr::ArrayOfBool(exprs) => exprs
+                .iter()
+                .map(|expr| self.resolve_bool_expr(expr))
+                .collect(),
+            _ => Err(ModelError::InvalidArguments(format!("{expr:?}"))),
+        }
+    }
+
+    /// Resolve an argument of a constraint which is a single value.
+    fn resolve_value(&self, expr: &Expr) -> Result<i64, ModelError> {
+        match expr {
+            Expr::VarParIdentifier(identifier) => {
+                self.resolve_int_value(&IntExpr::VarParIdentifier(identifier.clone()))
+            }
+            Expr::Int(value) => to_i64(*value),
+            Expr::Bool(value) => Ok(i64::from(*value)),
+            _ => Err(ModelError::InvalidArguments(format!("{expr:?}"))),
+        }
+    }
+
+    /// Resolve an argument of a constraint which is an array of values.
+    fn resolve_values(&self, expr: &Expr) -> Result<Vec<i64>, ModelError> {
+        match expr {
+            Expr::VarParIdentifier(identifier) => self
+                .array_parameters
+                .get(identifier)
+                .cloned()
+                .ok_or_else(|| ModelError::UnknownIdentifier(identifier.clone())),
+            Expr::ArrayOfInt(exprs) => exprs
+                .iter()
+                .map(|expr| self.resolve_int_value(expr))
+                .collect(),
+            Expr::ArrayOfBool(exprs) => exprs
+                .iter()
+                .map(|expr| match expr {
+                    BoolExpr::Bool(value) => Ok(i64::from(*value)),
+                    BoolExpr::VarParIdentifier(identifier) => {
+                        self.resolve_int_value(&IntExpr::VarParIdentifier(identifier.clone()))
+                    }
+                })
+                .collect(),
+            _ => Err(ModelError::InvalidArguments(format!("{expr:?}"))),
+        }
+    }
+}
+
+/// The atomic constraint which holds when the Boolean variable is true.
+fn is_true(variable: VariableId) -> Atomic {
+    Atomic::new(variable, Comparison::Equal, 1)
+}
+
+fn to_i64(value: i128) -> Result<i64, ModelError> {
+    i64::try_from(value).map_err(|_| ModelError::ValueOutOfRange(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_and_constants_are_resolved() {
+        let source = r#"
+array [1..2] of int: coefficients = [1, -1];
+var 1..5: x;
+var 3..8: y = x;
+var bool: b;
+array [1..2] of var int: xs = [x, 4];
+constraint int_lin_le(coefficients, xs, 0);
+constraint int_times(x, x, 4);
+constraint bool_clause([b], []);
+solve minimize y;
+"#;
+        let mut model = Model::parse(source.as_bytes()).expect("valid model");
+
+        let x = model.resolve_variable(&"x".into());
+        assert_eq!(x, model.resolve_variable(&"y".into()));
+        assert_eq!(&Domain::new(3, 5), &model.initial_domains()[x.0]);
+
+        let four = model.resolve_variable(&"4".into());
+        assert_eq!(&Domain::new(4, 4), &model.initial_domains()[four.0]);
+
+        assert!(model
+            .constraint(1)
+            .and_then(|c| c.constraint.as_ref())
+            .is_some());
+        assert!(model
+            .constraint(2)
+            .and_then(|c| c.constraint.as_ref())
+            .is_none());
+        assert_eq!(
+            "int_times",
+            model.constraint(2).expect("two constraints").name
+        );
+        assert!(model.constraint(4).is_none());
+        assert_eq!(1, model.constraints_on(x).count());
+
+        let objective = model.objective().expect("optimisation problem");
+        assert_eq!(x, objective.variable);
+        assert_eq!(OptimisationDirection::Minimise, objective.direction);
+    }
+}
@@ -0,0 +1,427 @@
+#![cfg(test)] // workaround for https://github.com/rust-lang/rust-clippy/issues/11024
+
+use std::num::NonZero;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+use drcp_format::Format;
+use pumpkin_solver::constraints;
+use pumpkin_solver::optimisation::linear_sat_unsat::LinearSatUnsat;
+use pumpkin_solver::optimisation::OptimisationDirection;
+use pumpkin_solver::options::ExtendedResolutionOptions;
+use pumpkin_solver::options::RestartOptions;
+use pumpkin_solver::options::SolverOptions;
+use pumpkin_solver::proof::ProofLog;
+use pumpkin_solver::results::OptimisationResult;
+use pumpkin_solver::results::ProblemSolution;
+use pumpkin_solver::results::SatisfactionResult;
+use pumpkin_solver::results::SolutionReference;
+use pumpkin_solver::termination::Indefinite;
+use pumpkin_solver::variables::TransformableVariable;
+use pumpkin_solver::DefaultBrancher;
+use pumpkin_solver::Solver;
+
+const CUMULATIVE: &str = r#"
+var 0..4: s0;
+var 0..4: s1;
+var 0..4: s2;
+constraint pumpkin_cumulative([s0,s1,s2],[3,3,2],[2,2,2],3);
+solve satisfy;
+"#;
+
+const SUM_OF_DISTINCT: &str = r#"
+var 1..4: x;
+var 1..4: y;
+var 2..8: sum;
+constraint int_lin_eq([1,1,-1],[x,y,sum],0);
+constraint int_ne(x,y);
+solve minimize sum;
+"#;
+
+/// Writes the model, proof and literals to temporary files named after `name`, and runs the
+/// checker on them.
+fn run_checker(
+    name: &str,
+    model: &str,
+    proof: Option<&str>,
+    literals: Option<&str>,
+    format: Format,
+) -> Output {
+    let directory = std::env::temp_dir();
+    let model_path = directory.join(format!("drcp_checker_{name}.fzn"));
+    let proof_path = directory.join(format!("drcp_checker_{name}.drcp"));
+
+    std::fs::write(&model_path, model).expect("can write model");
+    if let Some(proof) = proof {
+        std::fs::write(&proof_path, proof).expect("can write proof");
+    }
+    if let Some(literals) = literals {
+        std::fs::write(proof_path.with_extension("lits"), literals).expect("can write literals");
+    }
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_drcp-checker"));
+    let _ = command.arg(&model_path).arg(&proof_path);
+    if format == Format::Binary {
+        let _ = command.arg("--binary");
+    }
+
+    command.output().expect("checker can be run")
+}
+
+fn proof_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("drcp_checker_{name}.drcp"))
+}
+
+fn assert_valid(output: &Output) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert_eq!("The proof is valid", stdout.trim());
+}
+
+fn assert_invalid(output: &Output, message: &str) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success());
+    assert_eq!(message, stdout.trim());
+}
+
+fn solve_unsatisfiable(mut solver: Solver) {
+    let mut brancher = solver.default_brancher();
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+    solver.conclude_proof_unsat();
+}
+
+/// The FlatZinc model of the pigeonhole problem, with the variables `x0, ..., x{num_pigeons - 1} ∈
+/// [1, num_holes]` and an all-different constraint.
+fn pigeon_hole_model(num_pigeons: i32, num_holes: i32) -> String {
+    let names = (0..num_pigeons)
+        .map(|index| format!("x{index}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    (0..num_pigeons)
+        .map(|index| format!("var 1..{num_holes}: x{index};\n"))
+        .chain([
+            format!("constraint pumpkin_all_different([{names}]);\n"),
+            "solve satisfy;\n".to_owned(),
+        ])
+        .collect()
+}
+
+/// Adds the variables and constraint of [`pigeon_hole_model`] to the solver, where the constraint
+/// is tagged with `1`, and returns the model.
+fn create_pigeon_hole_instance(solver: &mut Solver, num_pigeons: i32, num_holes: i32) -> String {
+    let pigeons = (0..num_pigeons)
+        .map(|index| solver.new_named_bounded_integer(1, num_holes, format!("x{index}")))
+        .collect::<Vec<_>>();
+    solver
+        .add_constraint(constraints::all_different(pigeons))
+        .with_tag(NonZero::new(1).unwrap())
+        .post()
+        .expect("no conflict");
+
+    pigeon_hole_model(num_pigeons, num_holes)
+}
+
+fn solver_with_proof(name: &str, format: Format) -> Solver {
+    Solver::with_options(SolverOptions {
+        proof_log: ProofLog::cp(&proof_path(name), format, true, true).expect("created proof"),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn pigeon_hole_proof_is_valid() {
+    let mut solver = solver_with_proof("pigeon_hole", Format::Text);
+    let model = create_pigeon_hole_instance(&mut solver, 5, 4);
+    solve_unsatisfiable(solver);
+
+    assert_valid(&run_checker(
+        "pigeon_hole",
+        &model,
+        None,
+        None,
+        Format::Text,
+    ));
+}
+
+#[test]
+fn proof_with_extension_literal_definitions_is_valid() {
+    let mut solver = Solver::with_options(SolverOptions {
+        proof_log: ProofLog::cp(&proof_path("extended_resolution"), Format::Text, true, true)
+            .expect("created proof"),
+        restart_options: RestartOptions {
+            base_interval: 10,
+            min_num_conflicts_before_first_restart: 0,
+            ..Default::default()
+        },
+        extended_resolution_options: ExtendedResolutionOptions {
+            enabled: true,
+            min_num_occurrences: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    let model = create_pigeon_hole_instance(&mut solver, 7, 6);
+    solve_unsatisfiable(solver);
+
+    let proof = std::fs::read_to_string(proof_path("extended_resolution")).expect("proof exists");
+    assert!(proof.lines().any(|line| line.starts_with("e ")));
+
+    assert_valid(&run_checker(
+        "extended_resolution",
+        &model,
+        None,
+        None,
+        Format::Text,
+    ));
+}
+
+#[test]
+fn binary_cumulative_proof_is_valid() {
+    let mut solver = solver_with_proof("cumulative", Format::Binary);
+    let start_times = (0..3)
+        .map(|index| solver.new_named_bounded_integer(0, 4, format!("s{index}")))
+        .collect::<Vec<_>>();
+    solver
+        .add_constraint(constraints::cumulative(
+            start_times,
+            [3, 3, 2],
+            [2, 2, 2],
+            3,
+        ))
+        .with_tag(NonZero::new(1).unwrap())
+        .post()
+        .expect("no conflict");
+    solve_unsatisfiable(solver);
+
+    assert_valid(&run_checker(
+        "cumulative",
+        CUMULATIVE,
+        None,
+        None,
+        Format::Binary,
+    ));
+}
+
+#[test]
+fn nogood_which_does_not_follow_is_reported() {
+    let proof = "n 1 1\nn 2 0 1\nc UNSAT\n";
+    let literals = "1 [x0 <= 3]\n";
+
+    let output = run_checker(
+        "invalid_nogood",
+        &pigeon_hole_model(5, 5),
+        Some(proof),
+        Some(literals),
+        Format::Text,
+    );
+    assert_invalid(
+        &output,
+        "Step 1 is invalid: the nogood does not follow by propagation",
+    );
+}
+
+#[test]
+fn inference_is_checked_against_its_constraint_tag() {
+    let model = r#"
+var 1..4: x;
+var 1..4: y;
+constraint int_le(x,y);
+constraint int_ne(x,y);
+solve satisfy;
+"#;
+    let literals = "1 [y <= 2]\n2 [x <= 2]\n";
+
+    let output = run_checker(
+        "tagged_inference",
+        model,
+        Some("i 1 1 0 2 c:1\ni 2 1 0 2 c:2\n"),
+        Some(literals),
+        Format::Text,
+    );
+    assert_invalid(
+        &output,
+        "Step 2 is invalid: the inference is not implied by constraint 2",
+    );
+}
+
+#[test]
+fn unsatisfiable_conclusion_requires_a_refutation() {
+    let output = run_checker(
+        "missing_refutation",
+        &pigeon_hole_model(5, 5),
+        Some("c UNSAT\n"),
+        Some(""),
+        Format::Text,
+    );
+    assert_invalid(
+        &output,
+        "The conclusion is invalid: the empty nogood does not follow by propagation",
+    );
+}
+
+#[test]
+fn optimisation_proof_with_solutions_is_valid() {
+    let mut solver = solver_with_proof("sum_of_distinct", Format::Text);
+    let x = solver.new_named_bounded_integer(1, 4, "x");
+    let y = solver.new_named_bounded_integer(1, 4, "y");
+    let sum = solver.new_named_bounded_integer(2, 8, "sum");
+    solver
+        .add_constraint(constraints::equals(
+            vec![x.scaled(1), y.scaled(1), sum.scaled(-1)],
+            0,
+        ))
+        .with_tag(NonZero::new(1).unwrap())
+        .post()
+        .expect("no conflict");
+    solver
+        .add_constraint(constraints::binary_not_equals(x, y))
+        .with_tag(NonZero::new(2).unwrap())
+        .post()
+        .expect("no conflict");
+
+    let mut brancher = solver.default_brancher();
+    let callback: fn(&Solver, SolutionReference, &DefaultBrancher) = |_, _, _| {};
+    let result = solver.optimise(
+        &mut brancher,
+        &mut Indefinite,
+        LinearSatUnsat::new(OptimisationDirection::Minimise, sum, callback),
+    );
+    let OptimisationResult::Optimal(solution) = result else {
+        panic!("expected an optimal solution");
+    };
+    assert_eq!(3, solution.get_integer_value(sum));
+
+    let proof = std::fs::read_to_string(proof_path("sum_of_distinct")).expect("proof exists");
+    assert!(proof.lines().any(|line| line.starts_with("s ")));
+
+    assert_valid(&run_checker(
+        "sum_of_distinct",
+        SUM_OF_DISTINCT,
+        None,
+        None,
+        Format::Text,
+    ));
+}
+
+#[test]
+fn solution_which_violates_a_constraint_is_reported() {
+    let literals = "1 [x == 2]\n2 [y == 2]\n3 [sum == 4]\n";
+
+    let output = run_checker(
+        "violating_solution",
+        SUM_OF_DISTINCT,
+        Some("s 1 1 2 3\n"),
+        Some(literals),
+        Format::Text,
+    );
+    assert_invalid(
+        &output,
+        "Step 1 is invalid: the solution violates a constraint",
+    );
+}
+
+#[test]
+fn solution_which_does_not_assign_every_variable_is_reported() {
+    let literals = "1 [x == 1]\n2 [sum == 4]\n";
+
+    let output = run_checker(
+        "partial_solution",
+        SUM_OF_DISTINCT,
+        Some("s 1 1 2\n"),
+        Some(literals),
+        Format::Text,
+    );
+    assert_invalid(
+        &output,
+        "Step 1 is invalid: the solution does not assign the variable y",
+    );
+}
+
+#[test]
+fn solution_of_a_model_with_an_unsupported_constraint_is_reported() {
+    // The checker does not support `int_times`, so it cannot establish that the solution violates
+    // it; the true optimum is 1.
+    let model = r#"
+var 1..5: x;
+var 0..25: y;
+constraint int_times(x,x,y);
+solve minimize y;
+"#;
+    let literals = "1 [x == 1]\n2 [y == 0]\n3 [y <= 0]\n";
+
+    let output = run_checker(
+        "unsupported_solution",
+        model,
+        Some("s 1 1 2\nc 3\n"),
+        Some(literals),
+        Format::Text,
+    );
+    assert_invalid(
+        &output,
+        "Step 1 is invalid: the solution cannot be checked against constraint 1 (int_times), which is not supported",
+    );
+}
+
+#[test]
+fn unsatisfiable_conclusion_after_a_solution_is_reported() {
+    // The empty nogood follows from the improvement clause of the solution, which only states
+    // that there is no better solution.
+    let literals = "1 [x == 1]\n2 [y == 2]\n3 [sum == 3]\n";
+
+    let output = run_checker(
+        "unsatisfiable_after_solution",
+        SUM_OF_DISTINCT,
+        Some("s 1 1 2 3\nn 2 0 1\nc UNSAT\n"),
+        Some(literals),
+        Format::Text,
+    );
+    assert_invalid(
+        &output,
+        "The conclusion is invalid: the model has a solution",
+    );
+}
+
+#[test]
+fn optimality_conclusion_requires_a_solution_which_attains_the_bound() {
+    // The bound is optimal, but the only solution has objective value 4.
+    let literals = "1 [x == 1]\n2 [y == 3]\n3 [sum == 4]\n4 [sum <= 3]\n";
+
+    let output = run_checker(
+        "unattained_bound",
+        SUM_OF_DISTINCT,
+        Some("s 1 1 2 3\nc 4\n"),
+        Some(literals),
+        Format::Text,
+    );
+    assert_invalid(
+        &output,
+        "The conclusion is invalid: the bound is not attained by a solution with objective value 3",
+    );
+}
+
+#[test]
+fn proof_with_a_parameter_as_objective_is_valid() {
+    let mut solver = solver_with_proof("constant_objective", Format::Text);
+    let objective = solver.new_named_bounded_integer(10, 10, "objective");
+
+    let mut brancher = solver.default_brancher();
+    let callback: fn(&Solver, SolutionReference, &DefaultBrancher) = |_, _, _| {};
+    let result = solver.optimise(
+        &mut brancher,
+        &mut Indefinite,
+        LinearSatUnsat::new(OptimisationDirection::Maximise, objective, callback),
+    );
+    assert!(matches!(result, OptimisationResult::Optimal(_)));
+
+    // The model declares the objective as a parameter, which the proof refers to by name.
+    assert_valid(&run_checker(
+        "constant_objective",
+        include_str!("../../pumpkin-solver/tests/mzn_optimization/constant_objective.fzn"),
+        None,
+        None,
+        Format::Text,
+    ));
+}
@@ -0,0 +1,380 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::num::NonZero;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+use drcp_format::compression;
+use serde::Deserialize;
+
+use crate::model;
+
+#[derive(Args)]
+pub(crate) struct ExploreArgs {
+    /// The conflict trace, as written by the solver with `--conflict-trace-path`.
+    input_trace: PathBuf,
+    /// The FlatZinc model which was solved. If given, the constraint item of every constraint tag
+    /// is named.
+    #[arg(long)]
+    model: Option<PathBuf>,
+    /// Show the conflict with this number in detail, instead of listing the conflicts.
+    #[arg(long)]
+    conflict: Option<u64>,
+    /// Only list the conflicts in which a propagator with this name or inference label reported
+    /// the conflict or explained a propagation.
+    #[arg(long)]
+    propagator: Option<String>,
+    /// Browse the conflicts with commands read from the standard input.
+    #[arg(long)]
+    interactive: bool,
+}
+
+/// A conflict as it is recorded in the trace.
+#[derive(Clone, Debug, Deserialize)]
+struct Conflict {
+    #[serde(rename = "conflict")]
+    index: u64,
+    decision_level: i64,
+    /// The propagator which reported the conflict, or [`None`] for an empty domain.
+    propagator: Option<Propagator>,
+    conflict_nogood: Vec<String>,
+    #[serde(rename = "resolution")]
+    resolution_steps: Vec<ResolutionStep>,
+    #[serde(rename = "minimisation")]
+    minimisation_steps: Vec<MinimisationStep>,
+    learned_nogood: Vec<String>,
+    backjump_level: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ResolutionStep {
+    predicate: String,
+    /// The propagator which propagated the predicate, or [`None`] if the predicate is implied by
+    /// a stronger predicate on the trail.
+    propagator: Option<Propagator>,
+    reason: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MinimisationStep {
+    minimiser: String,
+    nogood: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Propagator {
+    id: i64,
+    name: String,
+    label: String,
+    tag: Option<NonZero<u32>>,
+}
+
+pub(crate) fn run(args: ExploreArgs) -> anyhow::Result<()> {
+    let trace = compression::open(&args.input_trace)
+        .with_context(|| format!("Failed to open {}", args.input_trace.display()))?;
+
+    let mut conflicts = vec![];
+    for (line_number, line) in BufReader::new(trace).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let conflict = serde_json::from_str::<Conflict>(&line).with_context(|| {
+            format!(
+                "Failed to parse line {} of {}",
+                line_number + 1,
+                args.input_trace.display()
+            )
+        })?;
+        conflicts.push(conflict);
+    }
+
+    let constraint_names = match &args.model {
+        Some(model) => model::read_constraint_names(model)?,
+        None => vec![],
+    };
+    let explorer = Explorer {
+        conflicts,
+        constraint_names,
+    };
+
+    let mut output = std::io::stdout().lock();
+    if args.interactive {
+        explorer.interact(std::io::stdin().lock(), &mut output)
+    } else if let Some(index) = args.conflict {
+        explorer.show(&mut output, index)
+    } else {
+        explorer.list(&mut output, args.propagator.as_deref())
+    }
+}
+
+struct Explorer {
+    conflicts: Vec<Conflict>,
+    constraint_names: Vec<String>,
+}
+
+impl Explorer {
+    /// Print one line per conflict, optionally only for the conflicts which involve the given
+    /// propagator.
+    fn list(&self, output: &mut impl Write, propagator: Option<&str>) -> anyhow::Result<()> {
+        for conflict in self
+            .conflicts
+            .iter()
+            .filter(|conflict| propagator.is_none_or(|name| involves(conflict, name)))
+        {
+            writeln!(
+                output,
+                "#{}: level {}, {}, {} resolution steps, learned {} predicates, backjump to {}",
+                conflict.index,
+                conflict.decision_level,
+                self.describe_conflict_source(conflict),
+                conflict.resolution_steps.len(),
+                conflict.learned_nogood.len(),
+                conflict.backjump_level,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Print the conflict with the given number in detail.
+    fn show(&self, output: &mut impl Write, index: u64) -> anyhow::Result<()> {
+        let conflict = self
+            .conflicts
+            .iter()
+            .find(|conflict| conflict.index == index)
+            .with_context(|| format!("There is no conflict #{index} in the trace"))?;
+
+        writeln!(
+            output,
+            "Conflict #{} at decision level {}",
+            conflict.index, conflict.decision_level
+        )?;
+        writeln!(
+            output,
+            "Reported by {}",
+            self.describe_conflict_source(conflict)
+        )?;
+        writeln!(
+            output,
+            "Conflict nogood: {}",
+            conjunction(&conflict.conflict_nogood)
+        )?;
+
+        writeln!(output, "Resolution:")?;
+        for (index, step) in conflict.resolution_steps.iter().enumerate() {
+            let explanation = match &step.propagator {
+                Some(propagator) => self.describe_propagator(propagator),
+                None => "implied by the domain".to_owned(),
+            };
+            writeln!(
+                output,
+                "  {}. {} <- {}  ({explanation})",
+                index + 1,
+                step.predicate,
+                conjunction(&step.reason),
+            )?;
+        }
+
+        writeln!(output, "Minimisation:")?;
+        let mut previous: Option<&Vec<String>> = None;
+        for MinimisationStep { minimiser, nogood } in &conflict.minimisation_steps {
+            write!(output, "  {minimiser}: {}", conjunction(nogood))?;
+
+            if let Some(previous) = previous {
+                let removed = previous
+                    .iter()
+                    .filter(|predicate| !nogood.contains(predicate))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !removed.is_empty() {
+                    write!(output, "  (removed {})", removed.join(", "))?;
+                }
+            }
+            writeln!(output)?;
+
+            previous = Some(nogood);
+        }
+
+        writeln!(
+            output,
+            "Learned nogood: {}  (backjump to level {})",
+            conjunction(&conflict.learned_nogood),
+            conflict.backjump_level
+        )?;
+
+        Ok(())
+    }
+
+    /// Execute the commands which are read from `input`, until the input ends or `quit` is read.
+    fn interact(&self, input: impl BufRead, output: &mut impl Write) -> anyhow::Result<()> {
+        const HELP: &str = "Commands:
+  list [propagator]  list the conflicts, optionally only those involving the propagator
+  show <n>           show conflict #n
+  next               show the conflict after the last shown one
+  previous           show the conflict before the last shown one
+  help               show this message
+  quit               stop exploring";
+
+        writeln!(output, "{} conflicts in the trace.", self.conflicts.len())?;
+        writeln!(output, "{HELP}")?;
+
+        let mut current: Option<u64> = None;
+        let mut lines = input.lines();
+        loop {
+            write!(output, "> ")?;
+            output.flush()?;
+
+            let Some(line) = lines.next() else {
+                break;
+            };
+            let line = line?;
+            let mut words = line.split_whitespace();
+
+            let result = match (words.next(), words.next()) {
+                (None, _) => Ok(()),
+                (Some("list"), propagator) => self.list(output, propagator),
+                (Some("show"), Some(index)) => match index.parse() {
+                    Ok(index) => {
+                        current = Some(index);
+                        self.show(output, index)
+                    }
+                    Err(_) => Err(anyhow::anyhow!("Invalid conflict number '{index}'")),
+                },
+                (Some("next"), None) => {
+                    let index = current.map_or(1, |index| index + 1);
+                    current = Some(index);
+                    self.show(output, index)
+                }
+                (Some("previous"), None) => {
+                    let index = current.map_or(1, |index| index.saturating_sub(1).max(1));
+                    current = Some(index);
+                    self.show(output, index)
+                }
+                (Some("help"), None) => writeln!(output, "{HELP}").map_err(Into::into),
+                (Some("quit"), None) => break,
+                _ => Err(anyhow::anyhow!(
+                    "Unknown command '{line}', type 'help' for help"
+                )),
+            };
+
+            if let Err(error) = result {
+                writeln!(output, "{error}")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn describe_conflict_source(&self, conflict: &Conflict) -> String {
+        match &conflict.propagator {
+            Some(propagator) => self.describe_propagator(propagator),
+            None => "an empty domain".to_owned(),
+        }
+    }
+
+    fn describe_propagator(&self, propagator: &Propagator) -> String {
+        let mut description = format!(
+            "{} #{} ({}",
+            propagator.name, propagator.id, propagator.label
+        );
+
+        if let Some(tag) = propagator.tag {
+            match self.constraint_names.get(tag.get() as usize - 1) {
+                Some(name) => description.push_str(&format!(", constraint {tag}: {name}")),
+                None => description.push_str(&format!(", constraint {tag}")),
+            }
+        }
+
+        description.push(')');
+        description
+    }
+}
+
+/// Whether a propagator with the given name or inference label reported the conflict or
+/// explained one of the propagations in it.
+fn involves(conflict: &Conflict, name: &str) -> bool {
+    let matches = |propagator: &Option<Propagator>| {
+        propagator
+            .as_ref()
+            .is_some_and(|propagator| propagator.name == name || propagator.label == name)
+    };
+
+    matches(&conflict.propagator)
+        || conflict
+            .resolution_steps
+            .iter()
+            .any(|step| matches(&step.propagator))
+}
+
+fn conjunction(predicates: &[String]) -> String {
+    if predicates.is_empty() {
+        "true".to_owned()
+    } else {
+        predicates.join(" /\\ ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACE_LINE: &str = r#"{"conflict":1,"decision_level":2,"propagator":{"id":3,"name":"LinearNe","label":"linear_not_equal","tag":4},"conflict_nogood":["[x1 == 1]","[x2 == 1]"],"resolution":[{"predicate":"[x2 == 1]","propagator":{"id":5,"name":"Element","label":"element","tag":null},"reason":["[x1 == 1]"]}],"minimisation":[{"minimiser":"resolution","nogood":["[x1 == 1]","[x1 >= 1]"]},{"minimiser":"semantic","nogood":["[x1 == 1]"]}],"learned_nogood":["[x1 == 1]"],"backjump_level":0}"#;
+
+    fn explorer() -> Explorer {
+        Explorer {
+            conflicts: vec![serde_json::from_str(TRACE_LINE).expect("valid conflict")],
+            constraint_names: vec![
+                "int_lin_le".to_owned(),
+                "int_lin_le".to_owned(),
+                "int_lin_le".to_owned(),
+                "int_ne".to_owned(),
+            ],
+        }
+    }
+
+    #[test]
+    fn conflicts_are_filtered_by_the_propagators_in_their_resolution() {
+        let explorer = explorer();
+
+        let mut output = Vec::new();
+        explorer.list(&mut output, Some("element")).unwrap();
+        assert_eq!(
+            "#1: level 2, LinearNe #3 (linear_not_equal, constraint 4: int_ne), 1 resolution steps, learned 1 predicates, backjump to 0\n",
+            String::from_utf8(output).unwrap()
+        );
+
+        let mut output = Vec::new();
+        explorer.list(&mut output, Some("LinearLeq")).unwrap();
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn minimisation_shows_the_removed_predicates() {
+        let explorer = explorer();
+
+        let mut output = Vec::new();
+        explorer.show(&mut output, 1).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("  1. [x2 == 1] <- [x1 == 1]  (Element #5 (element))\n"));
+        assert!(output.contains("  semantic: [x1 == 1]  (removed [x1 >= 1])\n"));
+    }
+
+    #[test]
+    fn interactive_commands_are_executed_until_quit() {
+        let explorer = explorer();
+
+        let mut output = Vec::new();
+        explorer
+            .interact("next\nshow x\nquit\nnext\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(1, output.matches("Conflict #1 at decision level 2").count());
+        assert!(output.contains("Invalid conflict number 'x'"));
+    }
+}
@@ -1,89 +1,62 @@
-use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
-
-use anyhow::Context;
+//! Tools for inspecting DRCP proofs.
+//!
+//! - `translate` replaces the literals in a proof with the atomic constraints they represent. This
+//!   is the default when no subcommand is given, as in earlier versions of the debugger.
+//! - `trim` removes the steps which are not needed to derive the conclusion of a proof, and reports
+//!   which propagators contributed to the proof.
+//! - `print` renders a proof with the names of the variables, optionally restricted to some
+//!   constraint tag or hint label, or to the derivation of a single nogood.
+//! - `explore` browses a conflict trace of the solver, which records the resolution and
+//!   minimisation steps of every conflict together with the propagators which explained them.
+//!
+//! Input and output files which end in `.gz` or `.zst` are compressed with gzip or zstd.
+
+mod explore;
+mod model;
+mod print;
+mod translate;
+mod trim;
+
+use clap::CommandFactory;
 use clap::Parser;
-use drcp_format::reader::ProofReader;
-use drcp_format::steps::Conclusion;
-use drcp_format::steps::Step;
-use drcp_format::LiteralDefinitions;
+use clap::Subcommand;
 
 #[derive(Parser)]
+#[command(after_help = "Without a subcommand, the arguments are passed to `translate`.")]
 struct Cli {
-    /// The input proof.
-    input_proof: PathBuf,
-    /// The input literals.
-    input_lits: PathBuf,
-    /// The output proof where literals are replaced with atomics.
-    output: PathBuf,
+    #[command(subcommand)]
+    command: Command,
 }
 
-fn main() -> anyhow::Result<()> {
-    let args = Cli::parse();
-
-    let input_proof = File::open(&args.input_proof)
-        .with_context(|| format!("Failed to open {}", args.input_proof.display()))?;
-
-    let input_lits = File::open(&args.input_lits)
-        .with_context(|| format!("Failed to open {}", args.input_proof.display()))?;
-
-    let literals = LiteralDefinitions::<String>::parse(input_lits).with_context(|| {
-        format!(
-            "Failed to parse literal definitions from {}.",
-            args.input_lits.display()
-        )
-    })?;
-
-    let mut reader = ProofReader::new(input_proof, literals);
-    let mut output = File::create(&args.output)
-        .with_context(|| format!("Failed to create {}", args.output.display()))?;
-
-    while let Some(step) = reader.next_step()? {
-        match step {
-            Step::Inference(inference) => {
-                write!(output, "i {}", inference.id)?;
-
-                for premise in inference.premises {
-                    write!(output, " {}", premise)?;
-                }
-
-                if let Some(propagated) = inference.propagated {
-                    write!(output, " 0 {}", propagated)?;
-                }
-
-                if let Some(label) = inference.hint_label {
-                    write!(output, " l:{label}")?;
-                }
-
-                if let Some(constraint_id) = inference.hint_constraint_id {
-                    write!(output, " c:{constraint_id}")?;
-                }
-
-                writeln!(output)?;
-            }
-            Step::Nogood(nogood) => {
-                write!(output, "n {}", nogood.id)?;
-
-                for literal in nogood.literals {
-                    write!(output, " {}", literal)?;
-                }
-
-                write!(output, " 0")?;
-
-                for hint in nogood.hints.iter().flatten() {
-                    write!(output, " {}", hint)?;
-                }
+#[derive(Subcommand)]
+enum Command {
+    /// Write the proof with the literals replaced by atomic constraints.
+    Translate(translate::TranslateArgs),
+    /// Write the proof without the steps which are not used to derive the conclusion, and print
+    /// usage statistics per hint label and constraint tag.
+    Trim(trim::TrimArgs),
+    /// Print the proof in a human-readable form, as text or HTML.
+    Print(print::PrintArgs),
+    /// Browse a conflict trace which was written by the solver.
+    Explore(explore::ExploreArgs),
+}
 
-                writeln!(output)?;
-            }
-            Step::Delete(step) => writeln!(output, "d {}", step.id)?,
-            Step::Conclusion(conclusion) => match conclusion {
-                Conclusion::Unsatisfiable => writeln!(output, "c UNSAT")?,
-                Conclusion::Optimal(bound) => writeln!(output, "c {bound}")?,
-            },
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args_os().collect::<Vec<_>>();
+    // Without a subcommand, the proof is translated; i.e. `drcp-debugger <INPUT_PROOF>
+    // <INPUT_LITS> <OUTPUT>` is the same as `drcp-debugger translate <INPUT_PROOF> <INPUT_LITS>
+    // <OUTPUT>`.
+    if let Some(first) = args.get(1).and_then(|arg| arg.to_str()) {
+        let is_subcommand = first == "help" || Cli::command().find_subcommand(first).is_some();
+        if !is_subcommand && !first.starts_with('-') {
+            args.insert(1, "translate".into());
         }
     }
 
-    Ok(())
+    match Cli::parse_from(args).command {
+        Command::Translate(args) => translate::run(args),
+        Command::Trim(args) => trim::run(args),
+        Command::Print(args) => print::run(args),
+        Command::Explore(args) => explore::run(args),
+    }
 }
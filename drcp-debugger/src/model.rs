@@ -0,0 +1,28 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use drcp_format::compression;
+
+/// Reads the names of the constraint items in the FlatZinc model, in the order in which they
+/// occur. The item with constraint tag `t` is at index `t - 1`.
+pub(crate) fn read_constraint_names(path: &Path) -> anyhow::Result<Vec<String>> {
+    let model =
+        compression::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let mut names = vec![];
+    for line in BufReader::new(model).lines() {
+        let line = line?;
+        let statement = flatzinc::Stmt::from_str(&line)
+            .map_err(|error| anyhow::anyhow!("{error}"))
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        if let flatzinc::Stmt::Constraint(item) = statement {
+            names.push(item.id);
+        }
+    }
+
+    Ok(names)
+}
@@ -0,0 +1,485 @@
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::num::NonZero;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+use clap::ValueEnum;
+use drcp_format::compression;
+use drcp_format::reader::ProofReader;
+use drcp_format::steps::Conclusion;
+use drcp_format::steps::Step;
+use drcp_format::steps::StepId;
+use drcp_format::AtomicConstraint;
+use drcp_format::Format;
+use drcp_format::LiteralDefinitions;
+use drcp_format::VariableNameMap;
+
+use crate::model;
+
+#[derive(Args)]
+pub(crate) struct PrintArgs {
+    /// The input proof.
+    input_proof: PathBuf,
+    /// The input literals.
+    input_lits: PathBuf,
+    /// The names of the variables in the literal definitions. By default, the names map which is
+    /// written next to the proof is used, if it exists.
+    #[arg(long)]
+    names: Option<PathBuf>,
+    /// The FlatZinc model which the proof is for. If given, the constraint item of every
+    /// constraint tag is named.
+    #[arg(long)]
+    model: Option<PathBuf>,
+    /// Whether the input proof is in the binary format.
+    #[arg(long)]
+    binary: bool,
+    /// The format in which the proof is printed.
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+    /// Only print the inferences with this constraint tag.
+    #[arg(long)]
+    tag: Option<NonZero<u32>>,
+    /// Only print the inferences with this label.
+    #[arg(long)]
+    label: Option<String>,
+    /// Only print the derivation of the nogood with this step id, i.e. the nogood and the steps
+    /// which are given as hints for it, recursively.
+    #[arg(long)]
+    derivation: Option<StepId>,
+    /// The file to write to. By default, the proof is printed to the standard output.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+enum OutputFormat {
+    /// One line of text per step.
+    #[default]
+    Text,
+    /// A standalone HTML page, in which the hints link to the steps they refer to.
+    Html,
+}
+
+/// A proof step in a human-readable form.
+#[derive(Clone, Debug)]
+struct PrintedStep {
+    /// The letter which identifies the type of step in the DRCP format.
+    kind: char,
+    /// The id of the step, which is absent for conclusions.
+    id: Option<StepId>,
+    /// What the step states, e.g. the implication for an inference.
+    statement: String,
+    /// The hint label of an inference.
+    label: Option<String>,
+    /// The constraint tag of an inference.
+    tag: Option<NonZero<u32>>,
+    /// The steps which are given as hints for a nogood.
+    hints: Vec<StepId>,
+}
+
+pub(crate) fn run(args: PrintArgs) -> anyhow::Result<()> {
+    let format = if args.binary {
+        Format::Binary
+    } else {
+        Format::Text
+    };
+
+    let input_lits = compression::open(&args.input_lits)
+        .with_context(|| format!("Failed to open {}", args.input_lits.display()))?;
+    let literals = LiteralDefinitions::<String>::parse(input_lits).with_context(|| {
+        format!(
+            "Failed to parse literal definitions from {}.",
+            args.input_lits.display()
+        )
+    })?;
+
+    let names_path = args
+        .names
+        .clone()
+        .unwrap_or_else(|| compression::names_path(&args.input_proof));
+    let names = if args.names.is_some() || names_path.is_file() {
+        let names_file = compression::open(&names_path)
+            .with_context(|| format!("Failed to open {}", names_path.display()))?;
+        VariableNameMap::parse(names_file)
+            .with_context(|| format!("Failed to parse names from {}.", names_path.display()))?
+    } else {
+        VariableNameMap::default()
+    };
+
+    let constraint_names = match &args.model {
+        Some(model) => model::read_constraint_names(model)?,
+        None => vec![],
+    };
+
+    let mut reader = ProofReader::open(&args.input_proof, format, literals)
+        .with_context(|| format!("Failed to open {}", args.input_proof.display()))?;
+    let mut steps = vec![];
+    while let Some(step) = reader.next_step()? {
+        steps.push(printed_step(step, &names));
+    }
+
+    let derivation = match args.derivation {
+        Some(nogood) => Some(derivation(&steps, nogood)?),
+        None => None,
+    };
+    let steps: Vec<&PrintedStep> = steps
+        .iter()
+        .filter(|step| {
+            derivation
+                .as_ref()
+                .is_none_or(|derivation| step.id.is_some_and(|id| derivation.contains(&id)))
+        })
+        .filter(|step| args.tag.is_none_or(|tag| step.tag == Some(tag)))
+        .filter(|step| {
+            args.label
+                .as_ref()
+                .is_none_or(|label| step.label.as_ref() == Some(label))
+        })
+        .collect();
+
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(
+            compression::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?,
+        ),
+        None => Box::new(std::io::stdout().lock()),
+    };
+
+    match args.format {
+        OutputFormat::Text => write_text(&mut output, &steps, &constraint_names)?,
+        OutputFormat::Html => write_html(&mut output, &steps, &constraint_names)?,
+    }
+    output.flush()?;
+
+    Ok(())
+}
+
+fn printed_step(
+    step: Step<'_, Vec<AtomicConstraint<String>>, AtomicConstraint<String>, Vec<StepId>>,
+    names: &VariableNameMap,
+) -> PrintedStep {
+    let conjunction = |atomics: &[AtomicConstraint<String>]| {
+        if atomics.is_empty() {
+            "true".to_owned()
+        } else {
+            atomics
+                .iter()
+                .map(|atomic| display_atomic(atomic, names))
+                .collect::<Vec<_>>()
+                .join(" /\\ ")
+        }
+    };
+    // Nogoods are logged in their clausal form.
+    let disjunction = |atomics: &[AtomicConstraint<String>]| {
+        if atomics.is_empty() {
+            "false".to_owned()
+        } else {
+            atomics
+                .iter()
+                .map(|atomic| display_atomic(atomic, names))
+                .collect::<Vec<_>>()
+                .join(" \\/ ")
+        }
+    };
+
+    let (kind, id, statement) = match &step {
+        Step::Inference(inference) => {
+            let conclusion = inference.propagated.as_ref().map_or_else(
+                || "false".to_owned(),
+                |atomic| display_atomic(atomic, names),
+            );
+            let statement = format!("{} -> {conclusion}", conjunction(&inference.premises));
+            ('i', Some(inference.id), statement)
+        }
+        Step::Nogood(nogood) => {
+            let statement = disjunction(&nogood.literals);
+            ('n', Some(nogood.id), statement)
+        }
+        Step::Definition(definition) => {
+            let statement = format!(
+                "{} <-> {}",
+                display_atomic(&definition.literal, names),
+                conjunction(&definition.conjunction)
+            );
+            ('e', Some(definition.id), statement)
+        }
+        Step::Solution(solution) => {
+            let statement = format!("solution {}", conjunction(&solution.assignment));
+            ('s', Some(solution.id), statement)
+        }
+        Step::Delete(deletion) => ('d', Some(deletion.id), "deleted".to_owned()),
+        Step::Conclusion(conclusion) => {
+            let statement = match conclusion {
+                Conclusion::Unsatisfiable => "unsatisfiable".to_owned(),
+                Conclusion::Optimal(bound) => format!("optimal {}", display_atomic(bound, names)),
+            };
+            ('c', None, statement)
+        }
+    };
+
+    let (label, tag) = match &step {
+        Step::Inference(inference) => (
+            inference.hint_label.map(str::to_owned),
+            inference.hint_constraint_id,
+        ),
+        _ => (None, None),
+    };
+    let hints = match step {
+        Step::Nogood(nogood) => nogood.hints.unwrap_or_default(),
+        _ => vec![],
+    };
+
+    PrintedStep {
+        kind,
+        id,
+        statement,
+        label,
+        tag,
+        hints,
+    }
+}
+
+/// Displays the atomic constraint with the names of its variable. A variable which has multiple
+/// names is displayed as `name1=name2`.
+fn display_atomic(atomic: &AtomicConstraint<String>, names: &VariableNameMap) -> String {
+    let display_name = |identifier: &String| {
+        let names = names.names(identifier);
+        if names.is_empty() {
+            identifier.clone()
+        } else {
+            names.join("=")
+        }
+    };
+
+    match atomic.clone() {
+        AtomicConstraint::Bool(mut atomic) => {
+            atomic.name = display_name(&atomic.name);
+            atomic.to_string()
+        }
+        AtomicConstraint::Int(mut atomic) => {
+            atomic.name = display_name(&atomic.name);
+            atomic.to_string()
+        }
+    }
+}
+
+/// The ids of the steps in the derivation of the given nogood. These are the nogood itself and
+/// the steps which are given as hints, where the hints of the nogoods are followed recursively.
+fn derivation(steps: &[PrintedStep], nogood: StepId) -> anyhow::Result<BTreeSet<StepId>> {
+    // Step ids can be reused after a deletion, so the hints refer to the last step with that id
+    // which occurs before the step that uses them.
+    let position = steps
+        .iter()
+        .position(|step| step.kind == 'n' && step.id == Some(nogood))
+        .with_context(|| format!("Step {nogood} is not a nogood in the proof"))?;
+
+    let mut derivation = BTreeSet::new();
+    let mut to_visit = vec![position];
+    while let Some(position) = to_visit.pop() {
+        let step = &steps[position];
+        if !derivation.insert(step.id.expect("only steps with an id are visited")) {
+            continue;
+        }
+
+        for &hint in &step.hints {
+            let hinted = steps[..position]
+                .iter()
+                .rposition(|step| step.kind != 'd' && step.id == Some(hint));
+            if let Some(hinted) = hinted {
+                to_visit.push(hinted);
+            }
+        }
+    }
+
+    Ok(derivation)
+}
+
+/// The hint label and the constraint tag of an inference, with the name of the constraint item
+/// if it is known.
+fn annotation(step: &PrintedStep, constraint_names: &[String]) -> String {
+    let mut annotation = vec![];
+
+    if let Some(label) = &step.label {
+        annotation.push(label.clone());
+    }
+
+    if let Some(tag) = step.tag {
+        match constraint_names.get(tag.get() as usize - 1) {
+            Some(name) => annotation.push(format!("constraint {tag}: {name}")),
+            None => annotation.push(format!("constraint {tag}")),
+        }
+    }
+
+    annotation.join(", ")
+}
+
+fn write_text(
+    output: &mut impl Write,
+    steps: &[&PrintedStep],
+    constraint_names: &[String],
+) -> std::io::Result<()> {
+    for step in steps {
+        match step.id {
+            Some(id) => write!(output, "{}{id}: {}", step.kind, step.statement)?,
+            None => write!(output, "{}: {}", step.kind, step.statement)?,
+        }
+
+        let annotation = annotation(step, constraint_names);
+        if !annotation.is_empty() {
+            write!(output, "  ({annotation})")?;
+        }
+
+        if !step.hints.is_empty() {
+            let hints = step
+                .hints
+                .iter()
+                .map(|hint| hint.to_string())
+                .collect::<Vec<_>>();
+            write!(output, "  uses {}", hints.join(", "))?;
+        }
+
+        writeln!(output)?;
+    }
+
+    Ok(())
+}
+
+fn write_html(
+    output: &mut impl Write,
+    steps: &[&PrintedStep],
+    constraint_names: &[String],
+) -> std::io::Result<()> {
+    writeln!(output, "<!DOCTYPE html>")?;
+    writeln!(output, "<html>")?;
+    writeln!(output, "<head>")?;
+    writeln!(output, "<meta charset=\"utf-8\">")?;
+    writeln!(output, "<title>DRCP proof</title>")?;
+    writeln!(
+        output,
+        "<style>body {{ font-family: monospace; }} td {{ padding: 0 1em; }} \
+         tr:target {{ background: #ffe9a8; }}</style>"
+    )?;
+    writeln!(output, "</head>")?;
+    writeln!(output, "<body>")?;
+    writeln!(output, "<table>")?;
+    writeln!(
+        output,
+        "<tr><th>Step</th><th>Statement</th><th>Hints</th><th>Uses</th></tr>"
+    )?;
+
+    for step in steps {
+        let anchor = match step.id {
+            // Deletions share their id with the deleted step, so they do not get an anchor.
+            Some(id) if step.kind != 'd' => format!(" id=\"step-{id}\""),
+            _ => String::new(),
+        };
+        let step_name = match step.id {
+            Some(id) => format!("{}{id}", step.kind),
+            None => step.kind.to_string(),
+        };
+        let hints = step
+            .hints
+            .iter()
+            .map(|hint| format!("<a href=\"#step-{hint}\">{hint}</a>"))
+            .collect::<Vec<_>>();
+
+        writeln!(
+            output,
+            "<tr{anchor}><td>{step_name}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&step.statement),
+            escape_html(&annotation(step, constraint_names)),
+            hints.join(", "),
+        )?;
+    }
+
+    writeln!(output, "</table>")?;
+    writeln!(output, "</body>")?;
+    writeln!(output, "</html>")?;
+
+    Ok(())
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use drcp_format::IntAtomicConstraint;
+
+    use super::*;
+
+    fn nogood(id: u64, hints: &[u64]) -> PrintedStep {
+        PrintedStep {
+            kind: 'n',
+            id: StepId::new(id),
+            statement: String::new(),
+            label: None,
+            tag: None,
+            hints: hints
+                .iter()
+                .map(|&hint| StepId::new(hint).unwrap())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn derivation_follows_the_hints_of_nogoods() {
+        let steps = [
+            nogood(1, &[]),
+            nogood(2, &[1]),
+            nogood(3, &[]),
+            nogood(4, &[2]),
+        ];
+
+        let derivation = derivation(&steps, StepId::new(4).unwrap()).unwrap();
+        assert_eq!(
+            vec![1, 2, 4],
+            derivation.iter().map(|id| id.get()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn variables_are_displayed_with_all_their_names() {
+        let mut names = VariableNameMap::default();
+        names.add("x", "x");
+        names.add("x", "y");
+
+        let atomic = AtomicConstraint::Int(IntAtomicConstraint {
+            name: "x".to_owned(),
+            comparison: drcp_format::Comparison::LessThanEqual,
+            value: 3,
+        });
+        assert_eq!("[x=y <= 3]", display_atomic(&atomic, &names));
+    }
+
+    #[test]
+    fn nogoods_are_printed_as_clauses() {
+        let literals = LiteralDefinitions::<String>::parse("1 [a >= 1]\n4 [b <= 2]\n".as_bytes())
+            .expect("valid literals");
+        let mut names = VariableNameMap::default();
+        names.add("a", "a");
+        names.add("b", "b");
+
+        let mut reader = ProofReader::new("n 1 4\nn 2 -1 4 0 1\nn 3 0 1 2\n".as_bytes(), literals);
+        let mut statements = vec![];
+        while let Some(step) = reader.next_step().expect("valid proof") {
+            statements.push(printed_step(step, &names).statement);
+        }
+
+        assert_eq!(
+            vec!["[b <= 2]", "[a <= 0] \\/ [b <= 2]", "false"],
+            statements
+        );
+    }
+
+    #[test]
+    fn html_is_escaped() {
+        assert_eq!("[x &lt;= 3] -&gt; false", escape_html("[x <= 3] -> false"));
+    }
+}
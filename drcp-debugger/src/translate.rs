@@ -0,0 +1,114 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+use drcp_format::compression;
+use drcp_format::reader::ProofReader;
+use drcp_format::steps::Conclusion;
+use drcp_format::steps::Step;
+use drcp_format::Format;
+use drcp_format::LiteralDefinitions;
+
+#[derive(Args)]
+pub(crate) struct TranslateArgs {
+    /// The input proof.
+    input_proof: PathBuf,
+    /// The input literals.
+    input_lits: PathBuf,
+    /// The output proof where literals are replaced with atomics.
+    output: PathBuf,
+    /// Whether the input proof is in the binary format. The output proof is always textual.
+    #[arg(long)]
+    binary: bool,
+}
+
+pub(crate) fn run(args: TranslateArgs) -> anyhow::Result<()> {
+    let input_lits = compression::open(&args.input_lits)
+        .with_context(|| format!("Failed to open {}", args.input_proof.display()))?;
+
+    let literals = LiteralDefinitions::<String>::parse(input_lits).with_context(|| {
+        format!(
+            "Failed to parse literal definitions from {}.",
+            args.input_lits.display()
+        )
+    })?;
+
+    let format = if args.binary {
+        Format::Binary
+    } else {
+        Format::Text
+    };
+    let mut reader = ProofReader::open(&args.input_proof, format, literals)
+        .with_context(|| format!("Failed to open {}", args.input_proof.display()))?;
+    let mut output = compression::create(&args.output)
+        .with_context(|| format!("Failed to create {}", args.output.display()))?;
+
+    while let Some(step) = reader.next_step()? {
+        match step {
+            Step::Inference(inference) => {
+                write!(output, "i {}", inference.id)?;
+
+                for premise in inference.premises {
+                    write!(output, " {}", premise)?;
+                }
+
+                if let Some(propagated) = inference.propagated {
+                    write!(output, " 0 {}", propagated)?;
+                }
+
+                if let Some(label) = inference.hint_label {
+                    write!(output, " l:{label}")?;
+                }
+
+                if let Some(constraint_id) = inference.hint_constraint_id {
+                    write!(output, " c:{constraint_id}")?;
+                }
+
+                writeln!(output)?;
+            }
+            Step::Nogood(nogood) => {
+                write!(output, "n {}", nogood.id)?;
+
+                for literal in nogood.literals {
+                    write!(output, " {}", literal)?;
+                }
+
+                write!(output, " 0")?;
+
+                for hint in nogood.hints.iter().flatten() {
+                    write!(output, " {}", hint)?;
+                }
+
+                writeln!(output)?;
+            }
+            Step::Definition(definition) => {
+                write!(output, "e {}", definition.id)?;
+
+                for literal in definition.conjunction {
+                    write!(output, " {}", literal)?;
+                }
+
+                writeln!(output, " 0 {}", definition.literal)?;
+            }
+            Step::Solution(solution) => {
+                write!(output, "s {}", solution.id)?;
+
+                for literal in solution.assignment {
+                    write!(output, " {}", literal)?;
+                }
+
+                writeln!(output)?;
+            }
+            Step::Delete(step) => writeln!(output, "d {}", step.id)?,
+            Step::Conclusion(conclusion) => match conclusion {
+                Conclusion::Unsatisfiable => writeln!(output, "c UNSAT")?,
+                Conclusion::Optimal(bound) => writeln!(output, "c {bound}")?,
+            },
+        }
+    }
+
+    let _ = output.finish()?;
+
+    Ok(())
+}
@@ -0,0 +1,648 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fs::File;
+use std::num::NonZero;
+use std::num::NonZeroI32;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+use drcp_format::compression;
+use drcp_format::compression::CompressedReader;
+use drcp_format::reader::LiteralAtomicMap;
+use drcp_format::reader::ProofReader;
+use drcp_format::steps::Conclusion;
+use drcp_format::steps::Step;
+use drcp_format::steps::StepId;
+use drcp_format::writer::ProofWriter;
+use drcp_format::AtomicConstraint;
+use drcp_format::Comparison;
+use drcp_format::Format;
+use drcp_format::IntAtomicConstraint;
+use drcp_format::LiteralDefinitions;
+
+use crate::model;
+
+#[derive(Args)]
+pub(crate) struct TrimArgs {
+    /// The input proof.
+    input_proof: PathBuf,
+    /// The input literals.
+    input_lits: PathBuf,
+    /// The output proof, which only contains the steps that are used to derive the conclusion.
+    ///
+    /// The literal codes are not changed, so the literal definitions of the input proof also
+    /// apply to the output proof.
+    output: PathBuf,
+    /// Whether the input proof is in the binary format. The output proof is written in the same
+    /// format as the input proof.
+    #[arg(long)]
+    binary: bool,
+    /// The FlatZinc model which the proof is for. If given, the usage statistics name the
+    /// constraint item of every constraint tag, which is the 1-based index of the item among the
+    /// constraint items in the model.
+    #[arg(long)]
+    model: Option<PathBuf>,
+}
+
+/// The information about a step which is needed to determine whether it is used in the proof.
+#[derive(Clone, Debug)]
+struct StepSummary {
+    kind: StepKind,
+    /// The steps which may be used to derive this step.
+    uses: Uses,
+    /// The number of steps which were logged before this step was deleted, if it is deleted.
+    deleted_at: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+enum StepKind {
+    Inference {
+        hint_label: Option<String>,
+        hint_constraint_id: Option<NonZero<u32>>,
+    },
+    Nogood {
+        /// The sorted literals of the nogood clause.
+        literals: Vec<NonZeroI32>,
+    },
+    Definition,
+    Solution,
+}
+
+/// The steps which may be used to derive a step or the conclusion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Uses {
+    /// The steps at the given indices.
+    Steps(Vec<usize>),
+    /// Any step which is active at the point where the step is logged.
+    ActiveSteps,
+}
+
+pub(crate) fn run(args: TrimArgs) -> anyhow::Result<()> {
+    let format = if args.binary {
+        Format::Binary
+    } else {
+        Format::Text
+    };
+
+    let input_lits = compression::open(&args.input_lits)
+        .with_context(|| format!("Failed to open {}", args.input_lits.display()))?;
+    let literals = LiteralDefinitions::<String>::parse(input_lits).with_context(|| {
+        format!(
+            "Failed to parse literal definitions from {}.",
+            args.input_lits.display()
+        )
+    })?;
+
+    let (steps, conclusion_uses) = summarise(&args.input_proof, format, &literals)?;
+    let needed = needed_steps(&steps, conclusion_uses);
+
+    let constraint_names = match &args.model {
+        Some(model) => model::read_constraint_names(model)?,
+        None => vec![],
+    };
+
+    write_trimmed_proof(&args.input_proof, &args.output, format, &needed)?;
+    print_statistics(&steps, &needed, &constraint_names);
+
+    Ok(())
+}
+
+/// Maps the literal codes to themselves, as they are written unchanged to the output.
+type LiteralCodes = fn(NonZeroI32) -> NonZeroI32;
+
+fn open_reader(
+    path: &Path,
+    format: Format,
+) -> anyhow::Result<ProofReader<CompressedReader<File>, LiteralCodes>> {
+    let identity: LiteralCodes = std::convert::identity;
+    ProofReader::open(path, format, identity)
+        .with_context(|| format!("Failed to open {}", path.display()))
+}
+
+/// Sorts the literals and removes the duplicates, such that clauses can be compared.
+fn clause_key(literals: impl IntoIterator<Item = NonZeroI32>) -> Vec<NonZeroI32> {
+    let mut literals: Vec<NonZeroI32> = literals.into_iter().collect();
+    literals.sort_unstable();
+    literals.dedup();
+    literals
+}
+
+/// Converts the atomic constraint to an integer atomic constraint, where Boolean variables are
+/// treated as 0-1 integer variables.
+fn to_int_atomic(atomic: AtomicConstraint<String>) -> IntAtomicConstraint<String> {
+    match atomic {
+        AtomicConstraint::Bool(atomic) => IntAtomicConstraint {
+            name: atomic.name,
+            comparison: Comparison::Equal,
+            value: i64::from(atomic.value),
+        },
+        AtomicConstraint::Int(atomic) => atomic,
+    }
+}
+
+/// Returns whether the conjunction of the `assumptions` implies that `atomic` is false.
+fn is_falsified(
+    atomic: &IntAtomicConstraint<String>,
+    assumptions: &[IntAtomicConstraint<String>],
+) -> bool {
+    let mut lower_bound = i64::MIN;
+    let mut upper_bound = i64::MAX;
+    let mut holes = vec![];
+
+    for assumption in assumptions
+        .iter()
+        .filter(|assumption| assumption.name == atomic.name)
+    {
+        match assumption.comparison {
+            Comparison::GreaterThanEqual => lower_bound = lower_bound.max(assumption.value),
+            Comparison::LessThanEqual => upper_bound = upper_bound.min(assumption.value),
+            Comparison::Equal => {
+                lower_bound = lower_bound.max(assumption.value);
+                upper_bound = upper_bound.min(assumption.value);
+            }
+            Comparison::NotEqual => holes.push(assumption.value),
+        }
+    }
+
+    let value = atomic.value;
+    match atomic.comparison {
+        Comparison::GreaterThanEqual => upper_bound < value,
+        Comparison::LessThanEqual => lower_bound > value,
+        Comparison::Equal => value < lower_bound || value > upper_bound || holes.contains(&value),
+        Comparison::NotEqual => lower_bound == value && upper_bound == value,
+    }
+}
+
+/// The active nogoods, such that inferences and conclusions can be traced to the nogood which
+/// implies them.
+#[derive(Debug, Default)]
+struct ActiveNogoods {
+    /// The index of the nogood with the given sorted literals.
+    by_literals: HashMap<Vec<NonZeroI32>, usize>,
+    /// The atomic constraints of the nogood with the given index.
+    atomics: HashMap<usize, Vec<IntAtomicConstraint<String>>>,
+    /// The indices of the nogoods by the variable of their first atomic constraint. This may
+    /// contain deleted nogoods.
+    by_variable: HashMap<String, Vec<usize>>,
+}
+
+impl ActiveNogoods {
+    fn add(
+        &mut self,
+        index: usize,
+        literals: Vec<NonZeroI32>,
+        atomics: Vec<IntAtomicConstraint<String>>,
+    ) {
+        if let Some(first) = atomics.first() {
+            self.by_variable
+                .entry(first.name.clone())
+                .or_default()
+                .push(index);
+        }
+
+        let _ = self.by_literals.insert(literals, index);
+        let _ = self.atomics.insert(index, atomics);
+    }
+
+    fn remove(&mut self, index: usize, literals: &[NonZeroI32]) {
+        if self.by_literals.get(literals) == Some(&index) {
+            let _ = self.by_literals.remove(literals);
+        }
+        let _ = self.atomics.remove(&index);
+    }
+
+    /// Finds the nogood which implies the inference from the `premises` to `propagated`, i.e. the
+    /// nogood of which every literal is falsified by the premises and the negation of the
+    /// propagated literal.
+    fn find_implying(
+        &self,
+        premises: &[NonZeroI32],
+        propagated: Option<NonZeroI32>,
+        literals: &LiteralDefinitions<String>,
+    ) -> Option<usize> {
+        let key = clause_key(premises.iter().map(|&premise| -premise).chain(propagated));
+        if let Some(&index) = self.by_literals.get(&key) {
+            return Some(index);
+        }
+
+        let assumptions: Vec<IntAtomicConstraint<String>> = premises
+            .iter()
+            .copied()
+            .chain(propagated.map(|propagated| -propagated))
+            .map(|literal| to_int_atomic(literals.to_atomic(literal)))
+            .collect();
+
+        // Every variable in an implying nogood is a variable of the assumptions.
+        let mut variables: Vec<&str> = assumptions
+            .iter()
+            .map(|atomic| atomic.name.as_str())
+            .collect();
+        variables.sort_unstable();
+        variables.dedup();
+
+        variables
+            .into_iter()
+            .filter_map(|variable| self.by_variable.get(variable))
+            .flat_map(|indices| indices.iter().rev())
+            .copied()
+            .find(|index| {
+                self.atomics.get(index).is_some_and(|atomics| {
+                    atomics
+                        .iter()
+                        .all(|atomic| is_falsified(atomic, &assumptions))
+                })
+            })
+    }
+}
+
+/// Reads the proof and summarises the steps in the order in which they are logged, together with
+/// the steps which may be used to derive the conclusion.
+fn summarise(
+    path: &Path,
+    format: Format,
+    literals: &LiteralDefinitions<String>,
+) -> anyhow::Result<(Vec<StepSummary>, Uses)> {
+    let mut reader = open_reader(path, format)?;
+
+    let mut steps: Vec<StepSummary> = vec![];
+    let mut indices: HashMap<StepId, usize> = HashMap::new();
+    let mut nogoods = ActiveNogoods::default();
+
+    while let Some(step) = reader.next_step()? {
+        let (id, kind, uses) = match step {
+            Step::Inference(inference) => {
+                // An inference without a constraint tag may be the propagation of a nogood. If no
+                // nogood implies the inference, it is assumed to be implied by a constraint.
+                let uses = Uses::Steps(
+                    inference
+                        .hint_constraint_id
+                        .is_none()
+                        .then(|| {
+                            nogoods.find_implying(
+                                &inference.premises,
+                                inference.propagated,
+                                literals,
+                            )
+                        })
+                        .flatten()
+                        .into_iter()
+                        .collect(),
+                );
+
+                let kind = StepKind::Inference {
+                    hint_label: inference.hint_label.map(str::to_owned),
+                    hint_constraint_id: inference.hint_constraint_id,
+                };
+                (inference.id, kind, uses)
+            }
+            Step::Nogood(nogood) => {
+                let uses = match nogood.hints {
+                    Some(hints) => Uses::Steps(
+                        hints
+                            .iter()
+                            .map(|hint| {
+                                indices.get(hint).copied().with_context(|| {
+                                    format!(
+                                        "Step {} has hint {hint} which is not logged",
+                                        nogood.id
+                                    )
+                                })
+                            })
+                            .collect::<anyhow::Result<_>>()?,
+                    ),
+                    None => Uses::ActiveSteps,
+                };
+
+                let atomics = nogood
+                    .literals
+                    .iter()
+                    .map(|&literal| to_int_atomic(literals.to_atomic(literal)))
+                    .collect();
+                let clause = clause_key(nogood.literals);
+                nogoods.add(steps.len(), clause.clone(), atomics);
+
+                (nogood.id, StepKind::Nogood { literals: clause }, uses)
+            }
+            Step::Definition(definition) => {
+                (definition.id, StepKind::Definition, Uses::Steps(vec![]))
+            }
+            Step::Solution(solution) => (solution.id, StepKind::Solution, Uses::Steps(vec![])),
+            Step::Delete(deletion) => {
+                let index = *indices
+                    .get(&deletion.id)
+                    .with_context(|| format!("Step {} is deleted but not logged", deletion.id))?;
+                steps[index].deleted_at = Some(steps.len());
+
+                if let StepKind::Nogood { literals } = &steps[index].kind {
+                    nogoods.remove(index, literals);
+                }
+                continue;
+            }
+            Step::Conclusion(conclusion) => {
+                // The conclusion is derived by the empty clause or the unit clause of the bound.
+                let key = match conclusion {
+                    Conclusion::Unsatisfiable => vec![],
+                    Conclusion::Optimal(bound) => vec![bound],
+                };
+                let uses = nogoods
+                    .by_literals
+                    .get(&key)
+                    .map_or(Uses::ActiveSteps, |&index| Uses::Steps(vec![index]));
+
+                return Ok((steps, uses));
+            }
+        };
+
+        let _ = indices.insert(id, steps.len());
+        steps.push(StepSummary {
+            kind,
+            uses,
+            deleted_at: None,
+        });
+    }
+
+    anyhow::bail!("The proof {} does not contain a conclusion", path.display())
+}
+
+/// Traces the proof backwards from the conclusion, and returns for every step whether it is used
+/// to derive the conclusion.
+///
+/// Definitions are always kept, as the defined literals may occur in the kept steps. Solutions are
+/// always kept as well, as the bound of an optimality conclusion should be attained by a solution.
+fn needed_steps(steps: &[StepSummary], conclusion_uses: Uses) -> Vec<bool> {
+    let mut needed: Vec<bool> = steps
+        .iter()
+        .map(|step| matches!(step.kind, StepKind::Definition | StepKind::Solution))
+        .collect();
+
+    // The positions of the needed steps which may use every step that is active at that position.
+    let mut unhinted_positions = BTreeSet::new();
+
+    mark_uses(
+        &mut needed,
+        &mut unhinted_positions,
+        steps.len(),
+        &conclusion_uses,
+    );
+
+    // Steps can only use earlier steps, so a single backward pass suffices.
+    for (index, step) in steps.iter().enumerate().rev() {
+        // The step is active from the next position until it is deleted.
+        let active_until = step.deleted_at.unwrap_or(usize::MAX);
+        if unhinted_positions
+            .range(index + 1..active_until)
+            .next()
+            .is_some()
+        {
+            needed[index] = true;
+        }
+
+        if needed[index] {
+            mark_uses(&mut needed, &mut unhinted_positions, index, &step.uses);
+        }
+    }
+
+    needed
+}
+
+/// Marks the steps which are used by the step at `position`.
+fn mark_uses(
+    needed: &mut [bool],
+    unhinted_positions: &mut BTreeSet<usize>,
+    position: usize,
+    uses: &Uses,
+) {
+    match uses {
+        Uses::Steps(indices) => indices.iter().for_each(|&index| needed[index] = true),
+        Uses::ActiveSteps => {
+            let _ = unhinted_positions.insert(position);
+        }
+    }
+}
+
+fn write_trimmed_proof(
+    input: &Path,
+    output: &Path,
+    format: Format,
+    needed: &[bool],
+) -> anyhow::Result<()> {
+    let mut reader = open_reader(input, format)?;
+    let output_file = compression::create(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let mut writer = ProofWriter::new(format, output_file, std::convert::identity);
+
+    // The writer numbers the steps consecutively, so the references to steps are renumbered.
+    let mut new_ids: HashMap<StepId, StepId> = HashMap::new();
+    let mut index = 0;
+    let mut conclusion = None;
+
+    while let Some(step) = reader.next_step()? {
+        let is_needed = match step {
+            Step::Inference(_) | Step::Nogood(_) | Step::Definition(_) | Step::Solution(_) => {
+                index += 1;
+                needed[index - 1]
+            }
+            Step::Delete(_) | Step::Conclusion(_) => false,
+        };
+
+        match step {
+            Step::Inference(inference) if is_needed => {
+                let new_id = writer.log_inference(
+                    inference.hint_constraint_id,
+                    inference.hint_label,
+                    inference.premises,
+                    inference.propagated,
+                )?;
+                let _ = new_ids.insert(inference.id, new_id);
+            }
+            Step::Nogood(nogood) if is_needed => {
+                let hints = nogood
+                    .hints
+                    .map(|hints| hints.iter().map(|hint| new_ids[hint]).collect::<Vec<_>>());
+                let new_id = writer.log_nogood_clause(nogood.literals, hints)?;
+                let _ = new_ids.insert(nogood.id, new_id);
+            }
+            Step::Definition(definition) if is_needed => {
+                let new_id = writer.log_definition(definition.literal, definition.conjunction)?;
+                let _ = new_ids.insert(definition.id, new_id);
+            }
+            Step::Solution(solution) if is_needed => {
+                let new_id = writer.log_solution(solution.assignment)?;
+                let _ = new_ids.insert(solution.id, new_id);
+            }
+            Step::Delete(deletion) => {
+                if let Some(&new_id) = new_ids.get(&deletion.id) {
+                    writer.log_deletion(new_id)?;
+                }
+            }
+            Step::Conclusion(step) => {
+                conclusion = Some(step);
+                break;
+            }
+            Step::Inference(_) | Step::Nogood(_) | Step::Definition(_) | Step::Solution(_) => {}
+        }
+    }
+
+    let _ = match conclusion {
+        Some(Conclusion::Unsatisfiable) => writer.unsat()?,
+        Some(Conclusion::Optimal(bound)) => writer.optimal(bound)?,
+        None => unreachable!("the conclusion is found when summarising the proof"),
+    };
+
+    Ok(())
+}
+
+/// The number of steps of a kind, and how many of them are used to derive the conclusion.
+#[derive(Clone, Copy, Debug, Default)]
+struct Usage {
+    used: usize,
+    total: usize,
+}
+
+impl Usage {
+    fn add(&mut self, is_used: bool) {
+        self.total += 1;
+        if is_used {
+            self.used += 1;
+        }
+    }
+}
+
+fn print_statistics(steps: &[StepSummary], needed: &[bool], constraint_names: &[String]) {
+    let mut inferences = Usage::default();
+    let mut nogoods = Usage::default();
+    let mut by_label: BTreeMap<&str, Usage> = BTreeMap::new();
+    let mut by_tag: BTreeMap<Option<NonZero<u32>>, Usage> = BTreeMap::new();
+
+    for (step, &is_needed) in steps.iter().zip(needed) {
+        match &step.kind {
+            StepKind::Inference {
+                hint_label,
+                hint_constraint_id,
+            } => {
+                inferences.add(is_needed);
+                by_label
+                    .entry(hint_label.as_deref().unwrap_or("<none>"))
+                    .or_default()
+                    .add(is_needed);
+                by_tag
+                    .entry(*hint_constraint_id)
+                    .or_default()
+                    .add(is_needed);
+            }
+            StepKind::Nogood { .. } => nogoods.add(is_needed),
+            StepKind::Definition | StepKind::Solution => {}
+        }
+    }
+
+    println!(
+        "Kept {} of {} inferences and {} of {} nogoods",
+        inferences.used, inferences.total, nogoods.used, nogoods.total
+    );
+
+    println!("\nInferences per hint label (used / total):");
+    for (label, usage) in by_label {
+        println!("  {label}: {} / {}", usage.used, usage.total);
+    }
+
+    println!("\nInferences per constraint tag (used / total):");
+    for (tag, usage) in by_tag {
+        let tag = match tag {
+            Some(tag) => match constraint_names.get(tag.get() as usize - 1) {
+                Some(name) => format!("{tag} ({name})"),
+                None => tag.to_string(),
+            },
+            None => "<none>".to_owned(),
+        };
+        println!("  {tag}: {} / {}", usage.used, usage.total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inference() -> StepSummary {
+        StepSummary {
+            kind: StepKind::Inference {
+                hint_label: None,
+                hint_constraint_id: NonZero::new(1),
+            },
+            uses: Uses::Steps(vec![]),
+            deleted_at: None,
+        }
+    }
+
+    fn nogood(uses: Uses) -> StepSummary {
+        StepSummary {
+            kind: StepKind::Nogood { literals: vec![] },
+            uses,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn only_hinted_steps_are_needed() {
+        let steps = vec![
+            inference(),
+            inference(),
+            nogood(Uses::Steps(vec![0])),
+            nogood(Uses::Steps(vec![2])),
+        ];
+
+        let needed = needed_steps(&steps, Uses::Steps(vec![3]));
+        assert_eq!(vec![true, false, true, true], needed);
+    }
+
+    #[test]
+    fn nogood_without_hints_needs_the_active_steps() {
+        let mut deleted = inference();
+        deleted.deleted_at = Some(2);
+
+        let steps = vec![
+            inference(),
+            deleted,
+            nogood(Uses::ActiveSteps),
+            inference(),
+            nogood(Uses::Steps(vec![2])),
+        ];
+
+        let needed = needed_steps(&steps, Uses::Steps(vec![4]));
+        assert_eq!(vec![true, false, true, false, true], needed);
+    }
+
+    #[test]
+    fn trimmed_proof_only_contains_the_used_steps() {
+        let literals = LiteralDefinitions::<String>::parse("1 [x >= 2]\n2 [y >= 2]\n".as_bytes())
+            .expect("valid literals");
+        let proof = "i 1 1 c:1 l:linear_bound\n\
+                     i 2 2 c:2 l:linear_bound\n\
+                     n 3 -1 0 1\n\
+                     n 4 -2 0 2\n\
+                     d 4\n\
+                     n 5 0 3\n\
+                     c UNSAT\n";
+
+        let directory = std::env::temp_dir();
+        let input = directory.join("drcp_debugger_untrimmed.drcp");
+        let output = directory.join("drcp_debugger_trimmed.drcp");
+        std::fs::write(&input, proof).expect("can write proof");
+
+        let (steps, conclusion_uses) =
+            summarise(&input, Format::Text, &literals).expect("valid proof");
+        let needed = needed_steps(&steps, conclusion_uses);
+        write_trimmed_proof(&input, &output, Format::Text, &needed).expect("can trim proof");
+
+        // The steps are renumbered, and the hints refer to the new step ids.
+        assert_eq!(
+            "i 1 1 c:1 l:linear_bound\n\
+             n 2 -1 0 1\n\
+             n 3 0 2\n\
+             c UNSAT\n",
+            std::fs::read_to_string(&output).expect("trimmed proof exists")
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! The primitives of the binary DRCP encoding.
+//!
+//! Every step starts with a single byte which identifies the type of the step; this is the same
+//! character as the one which starts the step in the textual format (e.g. `b'i'` for an inference).
+//! The remainder of the step consists of the following values:
+//!  - Unsigned integers (step ids, constraint tags and lengths) are encoded as unsigned LEB128
+//!    variable-length integers.
+//!  - Literals are zig-zag encoded (i.e. `1, -1, 2, -2, ...` become `2, 1, 4, 3, ...`), after which
+//!    they are encoded as unsigned LEB128 variable-length integers. As literals are non-zero, an
+//!    encoded literal is never zero.
+//!  - Lists of literals and step ids are terminated with a zero.
+//!
+//! See the README of this crate for the layout of every step.
+
+use std::io::BufRead;
+use std::io::Write;
+use std::num::NonZero;
+
+use crate::reader::DrcpError;
+
+pub(crate) const INFERENCE_TAG: u8 = b'i';
+pub(crate) const NOGOOD_TAG: u8 = b'n';
+pub(crate) const DEFINITION_TAG: u8 = b'e';
+pub(crate) const DELETION_TAG: u8 = b'd';
+pub(crate) const SOLUTION_TAG: u8 = b's';
+pub(crate) const CONCLUSION_TAG: u8 = b'c';
+
+/// The maximum number of bytes in the LEB128 encoding of a `u64`.
+const MAX_UNSIGNED_LENGTH: usize = 10;
+
+/// Write an unsigned integer as an LEB128 variable-length integer.
+pub(crate) fn write_unsigned(sink: &mut impl Write, mut value: u64) -> std::io::Result<()> {
+    let mut buffer = [0; MAX_UNSIGNED_LENGTH];
+    let mut length = 0;
+
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            buffer[length] = byte;
+            length += 1;
+            break;
+        }
+
+        buffer[length] = byte | 0x80;
+        length += 1;
+    }
+
+    sink.write_all(&buffer[..length])
+}
+
+/// Write a literal, or zero if the literal is absent.
+pub(crate) fn write_literal(
+    sink: &mut impl Write,
+    literal: Option<NonZero<i32>>,
+) -> std::io::Result<()> {
+    write_unsigned(sink, literal.map_or(0, zig_zag_encode))
+}
+
+/// Write a list of literals, which is terminated with a zero.
+pub(crate) fn write_literal_list(
+    sink: &mut impl Write,
+    literals: impl IntoIterator<Item = NonZero<i32>>,
+) -> std::io::Result<()> {
+    for literal in literals {
+        write_literal(sink, Some(literal))?;
+    }
+
+    write_unsigned(sink, 0)
+}
+
+/// Write a list of step ids, which is terminated with a zero.
+pub(crate) fn write_step_id_list(
+    sink: &mut impl Write,
+    step_ids: impl IntoIterator<Item = NonZero<u64>>,
+) -> std::io::Result<()> {
+    for step_id in step_ids {
+        write_unsigned(sink, step_id.get())?;
+    }
+
+    write_unsigned(sink, 0)
+}
+
+/// Read a single byte, or [`None`] if the end of the source has been reached.
+pub(crate) fn read_byte(source: &mut impl BufRead) -> Result<Option<u8>, DrcpError> {
+    let byte = source.fill_buf()?.first().copied();
+
+    if byte.is_some() {
+        source.consume(1);
+    }
+
+    Ok(byte)
+}
+
+/// Read an LEB128 variable-length unsigned integer.
+pub(crate) fn read_unsigned(source: &mut impl BufRead) -> Result<u64, DrcpError> {
+    let mut value = 0;
+
+    for index in 0..MAX_UNSIGNED_LENGTH {
+        let byte = read_byte(source)?.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "the proof ends in the middle of a step",
+            )
+        })?;
+
+        let bits = u64::from(byte & 0x7f);
+        let shift = 7 * index as u32;
+        if shift == 63 && bits > 1 {
+            break;
+        }
+
+        value |= bits << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(DrcpError::Syntax(
+        "variable-length integer does not fit in 64 bits".to_owned(),
+    ))
+}
+
+/// Read a step id, or [`None`] if the encoded value is zero.
+pub(crate) fn read_step_id(source: &mut impl BufRead) -> Result<Option<NonZero<u64>>, DrcpError> {
+    Ok(NonZero::new(read_unsigned(source)?))
+}
+
+/// Read a step id which is not allowed to be zero.
+pub(crate) fn read_required_step_id(source: &mut impl BufRead) -> Result<NonZero<u64>, DrcpError> {
+    read_step_id(source)?.ok_or_else(|| DrcpError::Syntax("step id is zero".to_owned()))
+}
+
+/// Read a list of step ids which is terminated with a zero.
+pub(crate) fn read_step_id_list(source: &mut impl BufRead) -> Result<Vec<NonZero<u64>>, DrcpError> {
+    let mut step_ids = vec![];
+
+    while let Some(step_id) = read_step_id(source)? {
+        step_ids.push(step_id);
+    }
+
+    Ok(step_ids)
+}
+
+/// Read a constraint tag, or [`None`] if the encoded value is zero.
+pub(crate) fn read_constraint_id(
+    source: &mut impl BufRead,
+) -> Result<Option<NonZero<u32>>, DrcpError> {
+    let value = read_unsigned(source)?;
+    let value = u32::try_from(value)
+        .map_err(|_| DrcpError::Syntax(format!("constraint tag {value} is out of range")))?;
+
+    Ok(NonZero::new(value))
+}
+
+/// Read a literal, or [`None`] if the encoded value is zero.
+pub(crate) fn read_literal(source: &mut impl BufRead) -> Result<Option<NonZero<i32>>, DrcpError> {
+    let value = read_unsigned(source)?;
+    let value = u32::try_from(value)
+        .map_err(|_| DrcpError::Syntax(format!("literal code {value} is out of range")))?;
+
+    Ok(zig_zag_decode(value))
+}
+
+/// Read a literal which is not allowed to be zero.
+pub(crate) fn read_required_literal(source: &mut impl BufRead) -> Result<NonZero<i32>, DrcpError> {
+    read_literal(source)?.ok_or_else(|| DrcpError::Syntax("literal is zero".to_owned()))
+}
+
+/// Read a list of literals which is terminated with a zero.
+pub(crate) fn read_literal_list(source: &mut impl BufRead) -> Result<Vec<NonZero<i32>>, DrcpError> {
+    let mut literals = vec![];
+
+    while let Some(literal) = read_literal(source)? {
+        literals.push(literal);
+    }
+
+    Ok(literals)
+}
+
+fn zig_zag_encode(literal: NonZero<i32>) -> u64 {
+    let value = literal.get();
+    u64::from(((value << 1) ^ (value >> 31)) as u32)
+}
+
+fn zig_zag_decode(value: u32) -> Option<NonZero<i32>> {
+    NonZero::new((value >> 1) as i32 ^ -((value & 1) as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsigned_integers_are_encoded_with_seven_bits_per_byte() {
+        let mut buffer = Vec::new();
+        write_unsigned(&mut buffer, 1).unwrap();
+        write_unsigned(&mut buffer, 300).unwrap();
+        write_unsigned(&mut buffer, u64::MAX).unwrap();
+
+        assert_eq!(&[1, 0xac, 0x02], &buffer[..3]);
+        assert_eq!(3 + MAX_UNSIGNED_LENGTH, buffer.len());
+
+        let mut source = buffer.as_slice();
+        assert_eq!(1, read_unsigned(&mut source).unwrap());
+        assert_eq!(300, read_unsigned(&mut source).unwrap());
+        assert_eq!(u64::MAX, read_unsigned(&mut source).unwrap());
+    }
+
+    #[test]
+    fn literals_are_zig_zag_encoded() {
+        let lit = |num: i32| NonZero::new(num).unwrap();
+
+        assert_eq!(2, zig_zag_encode(lit(1)));
+        assert_eq!(1, zig_zag_encode(lit(-1)));
+        assert_eq!(4, zig_zag_encode(lit(2)));
+
+        for literal in [1, -1, 64, -65, i32::MAX, i32::MIN] {
+            let mut buffer = Vec::new();
+            write_literal(&mut buffer, Some(lit(literal))).unwrap();
+
+            let mut source = buffer.as_slice();
+            assert_eq!(Some(lit(literal)), read_literal(&mut source).unwrap());
+        }
+    }
+
+    #[test]
+    fn overlong_unsigned_integer_is_rejected() {
+        let buffer = [0xff; MAX_UNSIGNED_LENGTH + 1];
+        assert!(read_unsigned(&mut buffer.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,408 @@
+//! Transparent compression of proof and literal definition files.
+//!
+//! The compression is selected by the extension of the file: files ending in `.gz` are compressed
+//! with gzip, and files ending in `.zst` are compressed with zstd. All other files are not
+//! compressed. Use [`create`] and [`open`] to obtain a writer or reader for such a file, which can
+//! be given to a [`ProofWriter`](crate::writer::ProofWriter) and
+//! [`ProofReader`](crate::reader::ProofReader) respectively.
+//!
+//! # Example
+//! ```
+//! # use std::io::Read;
+//! # use std::io::Write;
+//! # use drcp_format::compression;
+//! let path = std::env::temp_dir().join("drcp_format_compression_example.drcp.gz");
+//!
+//! let mut writer = compression::create(&path).unwrap();
+//! writer.write_all(b"c UNSAT\n").unwrap();
+//! writer.finish().unwrap();
+//!
+//! let mut contents = String::new();
+//! compression::open(&path)
+//!     .unwrap()
+//!     .read_to_string(&mut contents)
+//!     .unwrap();
+//! assert_eq!("c UNSAT\n", contents);
+//! ```
+
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use ruzstd::decoding::FrameDecoder;
+use ruzstd::decoding::StreamingDecoder;
+use ruzstd::encoding::CompressionLevel;
+
+/// The number of uncompressed bytes which are compressed into a single zstd frame.
+const ZSTD_FRAME_SIZE: usize = 1 << 20;
+
+/// The compression of a proof or literal definitions file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Determine the compression from the extension of the path.
+    pub fn from_path(path: &Path) -> Compression {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// The file extension which selects the compression, if any.
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gz"),
+            Compression::Zstd => Some("zst"),
+        }
+    }
+
+    /// Compress everything which is written to `sink`.
+    pub fn writer<W: Write>(self, sink: W) -> CompressedWriter<W> {
+        let inner = match self {
+            Compression::None => WriterImpl::Plain(sink),
+            Compression::Gzip => WriterImpl::Gzip(Box::new(GzEncoder::new(
+                sink,
+                flate2::Compression::default(),
+            ))),
+            Compression::Zstd => WriterImpl::Zstd {
+                sink,
+                buffer: Vec::with_capacity(ZSTD_FRAME_SIZE),
+            },
+        };
+
+        CompressedWriter { inner: Some(inner) }
+    }
+
+    /// Decompress everything which is read from `source`.
+    pub fn reader<R: Read>(self, source: R) -> CompressedReader<R> {
+        let inner = match self {
+            Compression::None => ReaderImpl::Plain(source),
+            Compression::Gzip => ReaderImpl::Gzip(MultiGzDecoder::new(source)),
+            Compression::Zstd => ReaderImpl::Zstd {
+                source: Some(BufReader::new(source)),
+                frame: None,
+            },
+        };
+
+        CompressedReader { inner }
+    }
+}
+
+/// Create the file at `path`, which is compressed according to its extension.
+pub fn create(path: impl AsRef<Path>) -> std::io::Result<CompressedWriter<File>> {
+    let path = path.as_ref();
+    let file = File::create(path)?;
+    Ok(Compression::from_path(path).writer(file))
+}
+
+/// Open the file at `path`, which is decompressed according to its extension.
+pub fn open(path: impl AsRef<Path>) -> std::io::Result<CompressedReader<File>> {
+    let path = path.as_ref();
+    let file = File::open(path)?;
+    Ok(Compression::from_path(path).reader(file))
+}
+
+/// The path of the literal definitions which belong to the proof at `proof_path`. The literal
+/// definitions are compressed in the same way as the proof.
+///
+/// # Example
+/// ```
+/// # use std::path::Path;
+/// # use drcp_format::compression::literals_path;
+/// assert_eq!(
+///     Path::new("proof.lits"),
+///     literals_path(Path::new("proof.drcp"))
+/// );
+/// assert_eq!(
+///     Path::new("proof.lits.gz"),
+///     literals_path(Path::new("proof.drcp.gz"))
+/// );
+/// ```
+pub fn literals_path(proof_path: &Path) -> PathBuf {
+    sibling_path(proof_path, "lits")
+}
+
+/// The path of the variable name map (see [`VariableNameMap`](crate::VariableNameMap)) which
+/// belongs to the proof at `proof_path`. The name map is compressed in the same way as the proof.
+///
+/// # Example
+/// ```
+/// # use std::path::Path;
+/// # use drcp_format::compression::names_path;
+/// assert_eq!(
+///     Path::new("proof.names"),
+///     names_path(Path::new("proof.drcp"))
+/// );
+/// assert_eq!(
+///     Path::new("proof.names.zst"),
+///     names_path(Path::new("proof.drcp.zst"))
+/// );
+/// ```
+pub fn names_path(proof_path: &Path) -> PathBuf {
+    sibling_path(proof_path, "names")
+}
+
+/// The path next to `proof_path` with the given extension, followed by the compression extension
+/// of the proof (if any).
+fn sibling_path(proof_path: &Path, extension: &str) -> PathBuf {
+    let compression = Compression::from_path(proof_path);
+
+    match compression.extension() {
+        Some(compression_extension) => proof_path
+            .with_extension("")
+            .with_extension(format!("{extension}.{compression_extension}")),
+        None => proof_path.with_extension(extension),
+    }
+}
+
+/// A writer which compresses the data before it is written to the underlying sink.
+///
+/// The compressed stream is completed by [`CompressedWriter::finish`], or otherwise when the
+/// writer is dropped, in which case errors are ignored.
+pub struct CompressedWriter<W: Write> {
+    /// This is only `None` after the writer is finished.
+    inner: Option<WriterImpl<W>>,
+}
+
+enum WriterImpl<W: Write> {
+    Plain(W),
+    Gzip(Box<GzEncoder<W>>),
+    /// The data is buffered, and compressed into a frame whenever the buffer is full. A zstd file
+    /// may consist of multiple frames.
+    Zstd {
+        sink: W,
+        buffer: Vec<u8>,
+    },
+}
+
+impl<W: Write> CompressedWriter<W> {
+    /// Complete the compressed stream, and return the underlying sink.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        let inner = self.inner.take().expect("only taken when finishing");
+
+        match inner {
+            WriterImpl::Plain(mut sink) => {
+                sink.flush()?;
+                Ok(sink)
+            }
+            WriterImpl::Gzip(encoder) => {
+                let mut sink = encoder.finish()?;
+                sink.flush()?;
+                Ok(sink)
+            }
+            WriterImpl::Zstd {
+                mut sink,
+                mut buffer,
+            } => {
+                write_zstd_frame(&mut sink, &mut buffer)?;
+                sink.flush()?;
+                Ok(sink)
+            }
+        }
+    }
+}
+
+/// Compress the buffer into a single zstd frame, and clear it.
+fn write_zstd_frame(sink: &mut impl Write, buffer: &mut Vec<u8>) -> std::io::Result<()> {
+    if buffer.is_empty() {
+        return Ok(());
+    }
+
+    let frame = ruzstd::encoding::compress_to_vec(buffer.as_slice(), CompressionLevel::Fastest);
+    buffer.clear();
+
+    sink.write_all(&frame)
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.inner.as_mut().expect("not finished") {
+            WriterImpl::Plain(sink) => sink.write(buf),
+            WriterImpl::Gzip(encoder) => encoder.write(buf),
+            WriterImpl::Zstd { sink, buffer } => {
+                let num_bytes = buf.len().min(ZSTD_FRAME_SIZE - buffer.len());
+                buffer.extend_from_slice(&buf[..num_bytes]);
+
+                if buffer.len() == ZSTD_FRAME_SIZE {
+                    write_zstd_frame(sink, buffer)?;
+                }
+
+                Ok(num_bytes)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self.inner.as_mut().expect("not finished") {
+            WriterImpl::Plain(sink) => sink.flush(),
+            WriterImpl::Gzip(encoder) => encoder.flush(),
+            WriterImpl::Zstd { sink, buffer } => {
+                write_zstd_frame(sink, buffer)?;
+                sink.flush()
+            }
+        }
+    }
+}
+
+impl<W: Write> Drop for CompressedWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = CompressedWriter {
+                inner: self.inner.take(),
+            }
+            .finish();
+        }
+    }
+}
+
+impl<W: Write> Debug for CompressedWriter<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let compression = match self.inner {
+            Some(WriterImpl::Plain(_)) => Some(Compression::None),
+            Some(WriterImpl::Gzip(_)) => Some(Compression::Gzip),
+            Some(WriterImpl::Zstd { .. }) => Some(Compression::Zstd),
+            None => None,
+        };
+
+        f.debug_struct("CompressedWriter")
+            .field("compression", &compression)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A reader which decompresses the data which is read from the underlying source.
+pub struct CompressedReader<R: Read> {
+    inner: ReaderImpl<R>,
+}
+
+enum ReaderImpl<R: Read> {
+    Plain(R),
+    Gzip(MultiGzDecoder<R>),
+    /// Between frames, the source is stored in `source`. Whilst a frame is decoded, it is owned
+    /// by the decoder of that frame.
+    Zstd {
+        source: Option<BufReader<R>>,
+        frame: Option<Box<StreamingDecoder<BufReader<R>, FrameDecoder>>>,
+    },
+}
+
+impl<R: Read> Read for CompressedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let (source, frame) = match &mut self.inner {
+            ReaderImpl::Plain(source) => return source.read(buf),
+            ReaderImpl::Gzip(decoder) => return decoder.read(buf),
+            ReaderImpl::Zstd { source, frame } => (source, frame),
+        };
+
+        loop {
+            if let Some(decoder) = frame {
+                let num_bytes = decoder.read(buf)?;
+                if num_bytes > 0 || buf.is_empty() {
+                    return Ok(num_bytes);
+                }
+
+                // The frame is completely decoded, so the next frame can be started.
+                *source = frame.take().map(|decoder| decoder.into_inner());
+            }
+
+            let Some(mut next_source) = source.take() else {
+                return Ok(0);
+            };
+
+            if next_source.fill_buf()?.is_empty() {
+                *source = Some(next_source);
+                return Ok(0);
+            }
+
+            let decoder = StreamingDecoder::new(next_source)
+                .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+            *frame = Some(Box::new(decoder));
+        }
+    }
+}
+
+impl<R: Read> Debug for CompressedReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let compression = match self.inner {
+            ReaderImpl::Plain(_) => Compression::None,
+            ReaderImpl::Gzip(_) => Compression::Gzip,
+            ReaderImpl::Zstd { .. } => Compression::Zstd,
+        };
+
+        f.debug_struct("CompressedReader")
+            .field("compression", &compression)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(compression: Compression, data: &[u8]) {
+        let mut writer = compression.writer(Vec::new());
+        writer.write_all(data).expect("writing to a vec");
+        let compressed = writer.finish().expect("writing to a vec");
+
+        let mut decompressed = vec![];
+        let _ = compression
+            .reader(compressed.as_slice())
+            .read_to_end(&mut decompressed)
+            .expect("valid compressed data");
+
+        assert_eq!(data, decompressed);
+    }
+
+    fn proof_like_data(num_bytes: usize) -> Vec<u8> {
+        (1..)
+            .map(|id| format!("n {id} {} -{} 0 {}\n", id % 97, id % 13 + 1, id / 2 + 1))
+            .flat_map(String::into_bytes)
+            .take(num_bytes)
+            .collect()
+    }
+
+    #[test]
+    fn gzip_round_trip() {
+        round_trip(Compression::Gzip, &proof_like_data(10_000));
+    }
+
+    #[test]
+    fn zstd_round_trip_with_multiple_frames() {
+        round_trip(
+            Compression::Zstd,
+            &proof_like_data(ZSTD_FRAME_SIZE * 2 + 100),
+        );
+    }
+
+    #[test]
+    fn empty_stream_round_trip() {
+        round_trip(Compression::Gzip, &[]);
+        round_trip(Compression::Zstd, &[]);
+    }
+
+    #[test]
+    fn compression_is_selected_by_extension() {
+        assert_eq!(
+            Compression::Zstd,
+            Compression::from_path(Path::new("proof.drcp.zst"))
+        );
+        assert_eq!(
+            Compression::None,
+            Compression::from_path(Path::new("proof.drcp"))
+        );
+    }
+}
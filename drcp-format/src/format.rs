@@ -2,11 +2,12 @@
 ///
 /// The textual format takes more disk space, but may be easier to read when looking at the files
 /// directly. On the other hand, the binary format is more compact and marginally faster to write.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum Format {
     /// A textual UTF-8 encoded proof.
     #[default]
     Text,
-    /// A binary encoded proof.
+    /// A binary encoded proof, in which integers are encoded as variable-length integers. See the
+    /// README of this crate for the specification of the encoding.
     Binary,
 }
@@ -4,12 +4,16 @@
 //!
 //! To read DRCP files see [`reader::ProofReader`], and to write DRCP files see
 //! [`writer::ProofWriter`]. Literal definitions (`.lits`) files can be read and parsed with
-//! [`LiteralDefinitions`].
+//! [`LiteralDefinitions`], and the names of the variables in those definitions with
+//! [`VariableNameMap`]. The files can be compressed, see [`compression`].
 
 mod atomic;
+mod binary;
 mod format;
 mod literal_definitions;
+mod name_map;
 
+pub mod compression;
 pub mod reader;
 pub mod steps;
 pub mod writer;
@@ -17,3 +21,4 @@ pub mod writer;
 pub use atomic::*;
 pub use format::*;
 pub use literal_definitions::*;
+pub use name_map::*;
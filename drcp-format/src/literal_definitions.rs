@@ -11,8 +11,10 @@ use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::alpha1;
 use nom::character::complete::alphanumeric1;
+use nom::character::complete::digit1;
 use nom::combinator::map;
 use nom::combinator::map_opt;
+use nom::combinator::opt;
 use nom::combinator::recognize;
 use nom::combinator::value;
 use nom::multi::many0_count;
@@ -251,15 +253,19 @@ fn comparator(input: &str) -> IResult<&str, Comparison> {
 
 /// Parses a variable name and turns it into an identifier.
 ///
-/// Variable names be accepted by the following regex: `[A-Za-z_][A-Za-z0-9_]*`
+/// Variable names be accepted by the following regex: `[A-Za-z_][A-Za-z0-9_]*|-?[0-9]+`. The
+/// latter are the names of constants, which are named by their value.
 fn identifier<Identifier>(input: &str) -> IResult<&str, Identifier>
 where
     Identifier: for<'a> From<&'a str>,
 {
     map(
-        recognize(pair(
-            alt((alpha1, tag("_"))),
-            many0_count(alt((alphanumeric1, tag("_")))),
+        alt((
+            recognize(pair(
+                alt((alpha1, tag("_"))),
+                many0_count(alt((alphanumeric1, tag("_")))),
+            )),
+            recognize(pair(opt(tag("-")), digit1)),
         )),
         Identifier::from,
     )(input)
@@ -269,3 +275,32 @@ where
 fn space(input: &str) -> IResult<&str, &str> {
     tag(" ")(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_are_valid_variable_names() {
+        let source = "1 [-3 <= 0]\n2 [x_1 == 4]\n";
+        let definitions = LiteralDefinitions::<String>::parse(source.as_bytes())
+            .expect("valid literal definitions");
+
+        let atomic = |name: &str, comparison, value| {
+            AtomicConstraint::Int(IntAtomicConstraint {
+                name: name.to_owned(),
+                comparison,
+                value,
+            })
+        };
+
+        assert_eq!(
+            Some([atomic("-3", Comparison::LessThanEqual, 0)].as_slice()),
+            definitions.get(NonZeroU32::new(1).unwrap())
+        );
+        assert_eq!(
+            Some([atomic("x_1", Comparison::Equal, 4)].as_slice()),
+            definitions.get(NonZeroU32::new(2).unwrap())
+        );
+    }
+}
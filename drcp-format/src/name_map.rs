@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+
+use crate::reader::DrcpError;
+
+/// Associates the identifiers of the variables in the literal definitions with the names under
+/// which the variables are known to the user, e.g. the identifiers in a FlatZinc model or the
+/// names given to the variables when they were created in the solver.
+///
+/// A variable can have multiple names, for example when several model variables are known to be
+/// equal and are represented by a single variable in the proof. The names are kept in the order in
+/// which they were added.
+///
+/// The serialized form has one line per name, which consists of the identifier followed by a space
+/// and the name. As the name extends until the end of the line, it may contain spaces.
+///
+/// # Example
+/// ```
+/// use drcp_format::VariableNameMap;
+///
+/// let mut names = VariableNameMap::default();
+/// names.add("X_INTRODUCED_1_", "X_INTRODUCED_1_");
+/// names.add("X_INTRODUCED_1_", "start of task 1");
+///
+/// let mut serialized = Vec::new();
+/// names.write(&mut serialized).expect("no io errors");
+/// assert_eq!(
+///     "X_INTRODUCED_1_ X_INTRODUCED_1_\nX_INTRODUCED_1_ start of task 1\n",
+///     String::from_utf8(serialized.clone()).unwrap()
+/// );
+///
+/// let parsed = VariableNameMap::parse(serialized.as_slice()).expect("valid names map");
+/// assert_eq!(
+///     ["X_INTRODUCED_1_", "start of task 1"].as_slice(),
+///     parsed.names("X_INTRODUCED_1_")
+/// );
+/// assert!(parsed.names("y").is_empty());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VariableNameMap {
+    names: BTreeMap<String, Vec<String>>,
+}
+
+impl VariableNameMap {
+    /// Parse from a source as written to by [`VariableNameMap::write`]. Empty lines are ignored.
+    pub fn parse(source: impl Read) -> Result<Self, DrcpError> {
+        let mut names = VariableNameMap::default();
+
+        for line in BufReader::new(source).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (identifier, name) = line
+                .split_once(' ')
+                .filter(|(identifier, name)| !identifier.is_empty() && !name.is_empty())
+                .ok_or_else(|| DrcpError::Syntax(format!("invalid name mapping '{line}'")))?;
+            names.add(identifier, name);
+        }
+
+        Ok(names)
+    }
+
+    /// Add a name to the variable with the given identifier. Names which the variable already has
+    /// are ignored.
+    pub fn add(&mut self, identifier: impl Into<String>, name: impl Into<String>) {
+        let name = name.into();
+        let names = self.names.entry(identifier.into()).or_default();
+
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    /// Get the names of the variable with the given identifier.
+    pub fn names(&self, identifier: &str) -> &[String] {
+        self.names
+            .get(identifier)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Write the names of all variables to the given sink, ordered by identifier.
+    pub fn write(&self, mut sink: impl Write) -> std::io::Result<()> {
+        for (identifier, names) in self.names.iter() {
+            for name in names {
+                writeln!(sink, "{identifier} {name}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_not_duplicated() {
+        let mut names = VariableNameMap::default();
+        names.add("x", "x");
+        names.add("x", "y");
+        names.add("x", "x");
+
+        assert_eq!(["x", "y"].as_slice(), names.names("x"));
+    }
+
+    #[test]
+    fn line_without_name_is_an_error() {
+        let result = VariableNameMap::parse("x y\nz\n".as_bytes());
+        assert!(matches!(result, Err(DrcpError::Syntax(_))));
+    }
+}
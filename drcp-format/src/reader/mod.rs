@@ -5,10 +5,12 @@
 mod error;
 mod literal_atomic_map;
 
+use std::fs::File;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Read;
 use std::num::NonZero;
+use std::path::Path;
 
 pub use error::DrcpError;
 pub use literal_atomic_map::LiteralAtomicMap;
@@ -29,10 +31,16 @@ use nom::sequence::preceded;
 use nom::sequence::tuple;
 use nom::IResult;
 
+use crate::binary;
+use crate::compression;
+use crate::compression::CompressedReader;
+use crate::format::Format;
 use crate::steps::Conclusion;
+use crate::steps::Definition;
 use crate::steps::Deletion;
 use crate::steps::Inference;
 use crate::steps::Nogood;
+use crate::steps::Solution;
 use crate::steps::Step;
 use crate::steps::StepId;
 
@@ -42,8 +50,9 @@ pub type ReadStep<'a, AtomicConstraint> =
 
 /// Used to read and parse DRCP proofs.
 ///
-/// The reader will read the proof line-by-line, using the fact that DRCP is a line-based format.
-/// Any leading or trailing whitespace, and empty lines, will be ignored. Literals that are
+/// A textual proof is read line-by-line, using the fact that DRCP is a line-based format. Any
+/// leading or trailing whitespace, and empty lines, will be ignored. A binary proof (see
+/// [`ProofReader::with_format`]) is read step-by-step. Literals that are
 /// encountered are mapped to atomics through an implementation of [`LiteralAtomicMap`]. It is
 /// assumed this is a total mapping, i.e. all literals encountered in the proof can be mapped to an
 /// atomic constraint.
@@ -106,24 +115,53 @@ pub type ReadStep<'a, AtomicConstraint> =
 #[derive(Debug)]
 pub struct ProofReader<R, AtomicConstraints> {
     source: BufReader<R>,
+    format: Format,
     string_buffer: String,
     atomics: AtomicConstraints,
 }
 
 impl<R: Read, AtomicConstraints> ProofReader<R, AtomicConstraints> {
-    /// Construct a new proof reader which reads from `source`.
+    /// Construct a new proof reader which reads a textual proof from `source`.
     ///
     /// The `atomics` are used to map the proof literals to atomic constraints. This is likely
     /// based on a parsed `.lits` file, but that does not have to be the case.
     pub fn new(source: R, atomics: AtomicConstraints) -> ProofReader<R, AtomicConstraints> {
+        ProofReader::with_format(Format::Text, source, atomics)
+    }
+
+    /// Construct a new proof reader which reads a proof in the given [`Format`] from `source`.
+    ///
+    /// See [`ProofReader::new`] for the meaning of `atomics`.
+    pub fn with_format(
+        format: Format,
+        source: R,
+        atomics: AtomicConstraints,
+    ) -> ProofReader<R, AtomicConstraints> {
         ProofReader {
             source: BufReader::new(source),
+            format,
             string_buffer: String::new(),
             atomics,
         }
     }
 }
 
+impl<AtomicConstraints> ProofReader<CompressedReader<File>, AtomicConstraints> {
+    /// Construct a new proof reader which reads a proof in the given [`Format`] from the file at
+    /// `path`. The proof is decompressed while it is read if the extension of the file indicates
+    /// that it is compressed (see [`compression`](crate::compression)).
+    ///
+    /// See [`ProofReader::new`] for the meaning of `atomics`.
+    pub fn open(
+        path: impl AsRef<Path>,
+        format: Format,
+        atomics: AtomicConstraints,
+    ) -> std::io::Result<ProofReader<CompressedReader<File>, AtomicConstraints>> {
+        let source = compression::open(path)?;
+        Ok(ProofReader::with_format(format, source, atomics))
+    }
+}
+
 impl<R, AtomicConstraints> ProofReader<R, AtomicConstraints>
 where
     R: Read,
@@ -137,74 +175,200 @@ where
     pub fn next_step(
         &mut self,
     ) -> Result<Option<ReadStep<'_, AtomicConstraints::Atomic>>, DrcpError> {
-        self.string_buffer.clear();
-
-        // Read lines until we find a non-empty line. The contents of `line` will be trimmed.
-        while self.string_buffer.trim().is_empty() {
-            let read_bytes = self.source.read_line(&mut self.string_buffer)?;
+        let step = match self.format {
+            Format::Text => {
+                self.string_buffer.clear();
+
+                // Read lines until we find a non-empty line. The contents of `line` will be
+                // trimmed.
+                while self.string_buffer.trim().is_empty() {
+                    let read_bytes = self.source.read_line(&mut self.string_buffer)?;
+
+                    if read_bytes == 0 {
+                        // The end of the file has been reached.
+                        return Ok(None);
+                    }
+                }
+
+                let (_, step) = proof_step(self.string_buffer.trim())?;
+                step
+            }
 
-            if read_bytes == 0 {
-                // The end of the file has been reached.
-                return Ok(None);
+            Format::Binary => {
+                match binary_proof_step(&mut self.source, &mut self.string_buffer)? {
+                    Some(step) => step,
+                    // The end of the file has been reached.
+                    None => return Ok(None),
+                }
             }
-        }
+        };
+
+        Ok(Some(map_step(&self.atomics, step)))
+    }
+}
+
+/// Map the literals to the atomic constraints in the proof step.
+fn map_step<'s, AtomicConstraints: LiteralAtomicMap>(
+    atomics: &AtomicConstraints,
+    step: ReadStep<'s, NonZero<i32>>,
+) -> ReadStep<'s, AtomicConstraints::Atomic> {
+    match step {
+        Step::Inference(Inference {
+            id,
+            hint_constraint_id,
+            hint_label,
+            premises,
+            propagated,
+        }) => Step::Inference(Inference {
+            id,
+            hint_constraint_id,
+            hint_label,
+            premises: premises
+                .into_iter()
+                .map(|literal| atomics.to_atomic(literal))
+                .collect(),
+            propagated: propagated.map(|p| atomics.to_atomic(p)),
+        }),
+
+        Step::Nogood(Nogood {
+            id,
+            literals,
+            hints,
+        }) => Step::Nogood(Nogood {
+            id,
+            literals: literals
+                .into_iter()
+                .map(|literal| atomics.to_atomic(literal))
+                .collect(),
+            hints,
+        }),
 
-        let (_, step) = proof_step(self.string_buffer.trim())?;
+        Step::Definition(Definition {
+            id,
+            literal,
+            conjunction,
+        }) => Step::Definition(Definition {
+            id,
+            literal: atomics.to_atomic(literal),
+            conjunction: conjunction
+                .into_iter()
+                .map(|literal| atomics.to_atomic(literal))
+                .collect(),
+        }),
+
+        // Here we cannot just forward the input value, as it has a different type due to the
+        // generics on `Step`.
+        Step::Delete(Deletion { id }) => Step::Delete(Deletion { id }),
+
+        Step::Solution(Solution { id, assignment }) => Step::Solution(Solution {
+            id,
+            assignment: assignment
+                .into_iter()
+                .map(|literal| atomics.to_atomic(literal))
+                .collect(),
+        }),
 
-        let step = self.map_step(step);
+        Step::Conclusion(Conclusion::Unsatisfiable) => Step::Conclusion(Conclusion::Unsatisfiable),
 
-        Ok(Some(step))
+        Step::Conclusion(Conclusion::Optimal(literal)) => {
+            Step::Conclusion(Conclusion::Optimal(atomics.to_atomic(literal)))
+        }
     }
+}
+
+/// Read a binary encoded proof step from `source`, or [`None`] if the end of `source` has been
+/// reached.
+///
+/// The label of an inference is read into `label_buffer`, from which it is borrowed by the step.
+fn binary_proof_step<'buf>(
+    source: &mut impl BufRead,
+    label_buffer: &'buf mut String,
+) -> Result<Option<ReadStep<'buf, NonZero<i32>>>, DrcpError> {
+    let Some(tag) = binary::read_byte(source)? else {
+        return Ok(None);
+    };
+
+    let step = match tag {
+        binary::INFERENCE_TAG => {
+            let id = binary::read_required_step_id(source)?;
+            let premises = binary::read_literal_list(source)?;
+            let propagated = binary::read_literal(source)?;
+            let hint_constraint_id = binary::read_constraint_id(source)?;
+
+            let label_length = binary::read_unsigned(source)?;
+            let mut label_bytes = std::mem::take(label_buffer).into_bytes();
+            label_bytes.clear();
+            let read_bytes = source.take(label_length).read_to_end(&mut label_bytes)?;
+            if read_bytes as u64 != label_length {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "the proof ends in the middle of a step",
+                )
+                .into());
+            }
+            *label_buffer = String::from_utf8(label_bytes)
+                .map_err(|error| DrcpError::Syntax(format!("invalid label: {error}")))?;
 
-    /// Map the literals to the atomic constraints in the proof step.
-    fn map_step<'s>(
-        &self,
-        step: ReadStep<'s, NonZero<i32>>,
-    ) -> ReadStep<'s, AtomicConstraints::Atomic> {
-        match step {
+            let label_buffer: &'buf String = label_buffer;
             Step::Inference(Inference {
                 id,
                 hint_constraint_id,
-                hint_label,
+                hint_label: (label_length > 0).then_some(label_buffer.as_str()),
                 premises,
                 propagated,
-            }) => Step::Inference(Inference {
-                id,
-                hint_constraint_id,
-                hint_label,
-                premises: premises
-                    .into_iter()
-                    .map(|literal| self.atomics.to_atomic(literal))
-                    .collect(),
-                propagated: propagated.map(|p| self.atomics.to_atomic(p)),
-            }),
+            })
+        }
+
+        binary::NOGOOD_TAG => {
+            let id = binary::read_required_step_id(source)?;
+            let literals = binary::read_literal_list(source)?;
+            let hints = match binary::read_unsigned(source)? {
+                0 => None,
+                1 => Some(binary::read_step_id_list(source)?),
+                flag => {
+                    return Err(DrcpError::Syntax(format!(
+                        "invalid propagation hints flag {flag}"
+                    )))
+                }
+            };
 
             Step::Nogood(Nogood {
                 id,
                 literals,
                 hints,
-            }) => Step::Nogood(Nogood {
+            })
+        }
+
+        binary::DEFINITION_TAG => {
+            let id = binary::read_required_step_id(source)?;
+            let conjunction = binary::read_literal_list(source)?;
+            let literal = binary::read_required_literal(source)?;
+
+            Step::Definition(Definition {
                 id,
-                literals: literals
-                    .into_iter()
-                    .map(|literal| self.atomics.to_atomic(literal))
-                    .collect(),
-                hints,
-            }),
+                literal,
+                conjunction,
+            })
+        }
 
-            // Here we cannot just forward the input value, as it has a different type due to the
-            // generics on `Step`.
-            Step::Delete(Deletion { id }) => Step::Delete(Deletion { id }),
+        binary::DELETION_TAG => Step::Delete(Deletion {
+            id: binary::read_required_step_id(source)?,
+        }),
 
-            Step::Conclusion(Conclusion::Unsatisfiable) => {
-                Step::Conclusion(Conclusion::Unsatisfiable)
-            }
+        binary::SOLUTION_TAG => Step::Solution(Solution {
+            id: binary::read_required_step_id(source)?,
+            assignment: binary::read_literal_list(source)?,
+        }),
 
-            Step::Conclusion(Conclusion::Optimal(literal)) => {
-                Step::Conclusion(Conclusion::Optimal(self.atomics.to_atomic(literal)))
-            }
-        }
-    }
+        binary::CONCLUSION_TAG => Step::Conclusion(match binary::read_literal(source)? {
+            Some(literal) => Conclusion::Optimal(literal),
+            None => Conclusion::Unsatisfiable,
+        }),
+
+        tag => return Err(DrcpError::Syntax(format!("unknown step type {tag:#04x}"))),
+    };
+
+    Ok(Some(step))
 }
 
 /// Parse a proof step from a line.
@@ -215,7 +379,9 @@ fn proof_step(input: &str) -> IResult<&str, ReadStep<'_, NonZero<i32>>> {
     all_consuming(alt((
         map(inference_step, Step::Inference),
         map(nogood_step, Step::Nogood),
+        map(definition_step, Step::Definition),
         map(deletion_step, Step::Delete),
+        map(solution_step, Step::Solution),
         map(conclusion_step, Step::Conclusion),
     )))(input)
 }
@@ -226,9 +392,11 @@ fn inference_step(input: &str) -> IResult<&str, Inference<'_, Vec<NonZero<i32>>,
         tuple((
             tag("i "),
             step_id,
-            tag(" "),
+            // The space is absent if the inference has no premises nor propagated literal.
+            opt(tag(" ")),
             literal_list,
-            opt(preceded(tag(" 0 "), literal)),
+            // If `literal_list` is empty, then the space will be parsed already.
+            opt(preceded(alt((tag("0 "), tag(" 0 "))), literal)),
             opt(preceded(tag(" c:"), constraint_id)),
             opt(preceded(tag(" l:"), identifier)),
         )),
@@ -248,7 +416,8 @@ fn nogood_step(input: &str) -> IResult<&str, Nogood<Vec<NonZero<i32>>, Vec<StepI
         tuple((
             tag("n "),
             step_id,
-            tag(" "),
+            // The space is absent if the nogood is empty and has no hints.
+            opt(tag(" ")),
             literal_list,
             opt(preceded(
                 // Hack! If `literal_list` is empty, then the space will be parsed already.
@@ -264,11 +433,45 @@ fn nogood_step(input: &str) -> IResult<&str, Nogood<Vec<NonZero<i32>>, Vec<StepI
     )(input)
 }
 
+/// `e <step_id> <conjunction> 0 <defined literal>`
+fn definition_step(input: &str) -> IResult<&str, Definition<Vec<NonZero<i32>>, NonZero<i32>>> {
+    map(
+        tuple((
+            tag("e "),
+            step_id,
+            tag(" "),
+            literal_list,
+            // If `literal_list` is empty, then the space will be parsed already.
+            alt((tag("0 "), tag(" 0 "))),
+            literal,
+        )),
+        |(_, id, _, conjunction, _, literal)| Definition {
+            id,
+            literal,
+            conjunction,
+        },
+    )(input)
+}
+
 /// `d <step_id>`
 fn deletion_step(input: &str) -> IResult<&str, Deletion> {
     preceded(tag("d "), map(step_id, |id| Deletion { id }))(input)
 }
 
+/// `s <step_id> <assignment>`
+fn solution_step(input: &str) -> IResult<&str, Solution<Vec<NonZero<i32>>>> {
+    map(
+        tuple((
+            tag("s "),
+            step_id,
+            // The space is absent if the assignment is empty.
+            opt(tag(" ")),
+            literal_list,
+        )),
+        |(_, id, _, assignment)| Solution { id, assignment },
+    )(input)
+}
+
 /// `c UNSAT` or `c <objective bound literal>`
 fn conclusion_step(input: &str) -> IResult<&str, Conclusion<NonZero<i32>>> {
     preceded(
@@ -311,6 +514,7 @@ fn identifier(input: &str) -> IResult<&str, &str> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::writer::ProofWriter;
 
     #[test]
     fn inference_nogood_without_hints() {
@@ -344,6 +548,50 @@ mod tests {
         assert_eq!(Some(Step::Inference(expected_inference)), inference_step);
     }
 
+    #[test]
+    fn definition_of_conjunction() {
+        let source = "e 3 4 -5 0 6\n";
+        let mut reader = ProofReader::new(source.as_bytes(), std::convert::identity);
+
+        let definition_step = reader.next_step().expect("valid drcp definition step");
+        let expected_definition = Definition {
+            id: NonZero::new(3).unwrap(),
+            literal: NonZero::new(6).unwrap(),
+            conjunction: vec![NonZero::new(4).unwrap(), NonZero::new(-5).unwrap()],
+        };
+        assert_eq!(Some(Step::Definition(expected_definition)), definition_step);
+    }
+
+    #[test]
+    fn inference_without_premises() {
+        let source = "i 2 0 -3\n";
+        let mut reader = ProofReader::new(source.as_bytes(), std::convert::identity);
+
+        let inference_step = reader.next_step().expect("valid drcp inference step");
+        let expected_inference = Inference {
+            id: NonZero::new(2).unwrap(),
+            hint_constraint_id: None,
+            hint_label: None,
+            premises: vec![],
+            propagated: Some(NonZero::new(-3).unwrap()),
+        };
+        assert_eq!(Some(Step::Inference(expected_inference)), inference_step);
+    }
+
+    #[test]
+    fn empty_nogood_without_hints() {
+        let source = "n 12\n";
+        let mut reader = ProofReader::new(source.as_bytes(), std::convert::identity);
+
+        let nogood_step = reader.next_step().expect("valid drcp nogood step");
+        let expected_nogood = Nogood {
+            id: NonZero::new(12).unwrap(),
+            literals: vec![],
+            hints: None,
+        };
+        assert_eq!(Some(Step::Nogood(expected_nogood)), nogood_step);
+    }
+
     #[test]
     fn empty_nogood_with_hints() {
         let source = "n 100 0 1 4 5\n";
@@ -361,4 +609,135 @@ mod tests {
         };
         assert_eq!(Some(Step::Nogood(expected_nogood)), nogood_step);
     }
+
+    #[test]
+    fn solution_with_assignment() {
+        let source = "s 4 1 -2\n";
+        let mut reader = ProofReader::new(source.as_bytes(), std::convert::identity);
+
+        let solution_step = reader.next_step().expect("valid drcp solution step");
+        let expected_solution = Solution {
+            id: NonZero::new(4).unwrap(),
+            assignment: vec![NonZero::new(1).unwrap(), NonZero::new(-2).unwrap()],
+        };
+        assert_eq!(Some(Step::Solution(expected_solution)), solution_step);
+    }
+
+    #[test]
+    fn binary_proof_is_read_identically_to_textual_proof() {
+        let text_proof = write_test_proof(Format::Text);
+        let binary_proof = write_test_proof(Format::Binary);
+        assert!(binary_proof.len() < text_proof.len());
+
+        let mut text_reader =
+            ProofReader::with_format(Format::Text, text_proof.as_slice(), std::convert::identity);
+        let mut binary_reader = ProofReader::with_format(
+            Format::Binary,
+            binary_proof.as_slice(),
+            std::convert::identity,
+        );
+
+        let mut num_steps = 0;
+        loop {
+            let text_step = text_reader.next_step().expect("valid textual step");
+            let binary_step = binary_reader.next_step().expect("valid binary step");
+            assert_eq!(text_step, binary_step);
+
+            if text_step.is_none() {
+                break;
+            }
+
+            num_steps += 1;
+        }
+
+        assert_eq!(10, num_steps);
+    }
+
+    #[test]
+    fn binary_unsat_conclusion_and_empty_nogood() {
+        let mut proof = Vec::new();
+        let mut writer = ProofWriter::new(Format::Binary, &mut proof, std::convert::identity);
+        let _ = writer
+            .log_nogood_clause([], Some([NonZero::new(1).unwrap()]))
+            .expect("no error");
+        let _ = writer.unsat().expect("no error");
+
+        let mut reader =
+            ProofReader::with_format(Format::Binary, proof.as_slice(), std::convert::identity);
+
+        let expected_nogood = Nogood {
+            id: NonZero::new(1).unwrap(),
+            literals: vec![],
+            hints: Some(vec![NonZero::new(1).unwrap()]),
+        };
+        assert_eq!(
+            Some(Step::Nogood(expected_nogood)),
+            reader.next_step().expect("valid nogood step")
+        );
+        assert_eq!(
+            Some(Step::Conclusion(Conclusion::Unsatisfiable)),
+            reader.next_step().expect("valid conclusion step")
+        );
+        assert_eq!(None, reader.next_step().expect("end of proof"));
+    }
+
+    #[test]
+    fn truncated_binary_proof_is_an_error() {
+        let mut proof = write_test_proof(Format::Binary);
+        let _ = proof.pop();
+
+        let mut reader =
+            ProofReader::with_format(Format::Binary, proof.as_slice(), std::convert::identity);
+
+        let result = loop {
+            match reader.next_step() {
+                Ok(Some(_)) => {}
+                Ok(None) => break Ok(()),
+                Err(error) => break Err(error),
+            }
+        };
+        assert!(result.is_err());
+    }
+
+    /// Writes a proof which contains every type of step in the given format.
+    fn write_test_proof(format: Format) -> Vec<u8> {
+        let lit = |num: i32| NonZero::new(num).unwrap();
+
+        let mut proof = Vec::new();
+        let mut writer = ProofWriter::new(format, &mut proof, std::convert::identity);
+
+        let inference = writer
+            .log_inference(
+                NonZero::new(20),
+                Some("linear_bound"),
+                [lit(4), lit(-500)],
+                Some(lit(-2)),
+            )
+            .expect("no error");
+        let conflict = writer
+            .log_inference(None, None, [lit(1), lit(i32::MIN + 1)], None)
+            .expect("no error");
+        let _ = writer
+            .log_inference(NonZero::new(u32::MAX), None, [lit(8)], Some(lit(i32::MAX)))
+            .expect("no error");
+        let definition = writer
+            .log_definition(lit(100), [lit(3), lit(-4)])
+            .expect("no error");
+        let nogood = writer
+            .log_nogood_clause([lit(1), lit(-3), lit(5)], Some([inference, conflict]))
+            .expect("no error");
+        let _ = writer
+            .log_nogood_clause([lit(-100)], Some([definition]))
+            .expect("no error");
+        writer.log_deletion(nogood).expect("no error");
+        let solution = writer
+            .log_solution([lit(6), lit(-7), lit(300)])
+            .expect("no error");
+        let _ = writer
+            .log_nogood_clause([lit(-7)], Some([solution]))
+            .expect("no error");
+        let _ = writer.optimal(lit(-7)).expect("no error");
+
+        proof
+    }
 }
@@ -22,6 +22,30 @@ impl Deletion {
     }
 }
 
+/// Introduces a fresh literal which is defined to be equivalent to the conjunction of the given
+/// literals, i.e. `literal <-> /\ conjunction`.
+///
+/// The defined literal should not occur in the model nor in any of the previous steps. The step
+/// can be referenced by the [`StepId`] as a hint in the derivation of a nogood.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Definition<Literals, Literal> {
+    pub id: StepId,
+    pub literal: Literal,
+    pub conjunction: Literals,
+}
+
+/// A solution of the model, given as the assignment of the variables, i.e. every literal is of the
+/// form `[x == v]`.
+///
+/// In an optimisation proof, a solution justifies that only solutions which improve on its
+/// objective value are of interest. The step can be referenced by the [`StepId`] as a hint in the
+/// derivation of the nogood which tightens the bound on the objective.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Solution<Literals> {
+    pub id: StepId,
+    pub assignment: Literals,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Conclusion<Literal> {
     Unsatisfiable,
@@ -49,6 +73,8 @@ pub struct Inference<'label, Premises, Propagated> {
 pub enum Step<'a, Literals, Literal, Hints> {
     Inference(Inference<'a, Literals, Literal>),
     Nogood(Nogood<Literals, Hints>),
+    Definition(Definition<Literals, Literal>),
     Delete(Deletion),
+    Solution(Solution<Literals>),
     Conclusion(Conclusion<Literal>),
 }
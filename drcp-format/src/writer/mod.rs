@@ -12,11 +12,14 @@ use std::num::NonZeroU64;
 
 pub use literal_code_provider::*;
 
+use crate::binary;
 use crate::format::Format;
 use crate::steps::Conclusion;
+use crate::steps::Definition;
 use crate::steps::Deletion;
 use crate::steps::Inference;
 use crate::steps::Nogood;
+use crate::steps::Solution;
 use crate::steps::StepId;
 
 /// Abstraction for writing DRCP proofs.
@@ -167,6 +170,59 @@ where
         Ok(id)
     }
 
+    /// Log a definition step, which introduces the fresh `literal` which is equivalent to the
+    /// conjunction of the literals in `conjunction`.
+    ///
+    /// This step can be referenced by the [`StepId`] that is returned, e.g. as a hint in the
+    /// derivation of a nogood which contains the defined literal.
+    ///
+    /// This function wraps an IO operation, which is why it can fail with an IO error.
+    pub fn log_definition(
+        &mut self,
+        literal: Literals::Literal,
+        conjunction: impl IntoIterator<Item = Literals::Literal>,
+    ) -> std::io::Result<StepId> {
+        let literal = self.encountered_literals.to_code(literal);
+        let id = self.next_step_id();
+
+        let definition = Definition {
+            id,
+            literal,
+            conjunction: conjunction
+                .into_iter()
+                .map(|pred| self.encountered_literals.to_code(pred)),
+        };
+
+        definition.write(self.format, &mut self.writer)?;
+
+        Ok(id)
+    }
+
+    /// Log a solution step, which records the assignment of the variables in a solution. Every
+    /// literal in `assignment` should be of the form `[x == v]`.
+    ///
+    /// This step can be referenced by the [`StepId`] that is returned, e.g. as a hint in the
+    /// derivation of the nogood which tightens the bound on the objective.
+    ///
+    /// This function wraps an IO operation, which is why it can fail with an IO error.
+    pub fn log_solution(
+        &mut self,
+        assignment: impl IntoIterator<Item = Literals::Literal>,
+    ) -> std::io::Result<StepId> {
+        let id = self.next_step_id();
+
+        let solution = Solution {
+            id,
+            assignment: assignment
+                .into_iter()
+                .map(|pred| self.encountered_literals.to_code(pred)),
+        };
+
+        solution.write(self.format, &mut self.writer)?;
+
+        Ok(id)
+    }
+
     /// Conclude with the unsatisfiable claim.
     ///
     /// Since the conclusion is the very last step in the proof, this method takes ownership of
@@ -190,6 +246,7 @@ where
 
     fn conclude(mut self, conclusion: Conclusion<NonZeroI32>) -> std::io::Result<Literals> {
         conclusion.write(self.format, &mut self.writer)?;
+        self.writer.flush()?;
         Ok(self.encountered_literals)
     }
 }
@@ -235,8 +292,18 @@ where
         Ok(())
     }
 
-    fn write_binary(self, _sink: &mut impl Write) -> std::io::Result<()> {
-        todo!()
+    fn write_binary(self, sink: &mut impl Write) -> std::io::Result<()> {
+        sink.write_all(&[binary::NOGOOD_TAG])?;
+        binary::write_unsigned(sink, self.id.get())?;
+        binary::write_literal_list(sink, self.literals)?;
+
+        match self.hints {
+            Some(hints) => {
+                binary::write_unsigned(sink, 1)?;
+                binary::write_step_id_list(sink, hints)
+            }
+            None => binary::write_unsigned(sink, 0),
+        }
     }
 }
 
@@ -248,8 +315,13 @@ impl WritableProofStep for Conclusion<NonZeroI32> {
         }
     }
 
-    fn write_binary(self, _sink: &mut impl Write) -> std::io::Result<()> {
-        todo!()
+    fn write_binary(self, sink: &mut impl Write) -> std::io::Result<()> {
+        sink.write_all(&[binary::CONCLUSION_TAG])?;
+
+        match self {
+            Conclusion::Unsatisfiable => binary::write_literal(sink, None),
+            Conclusion::Optimal(literal) => binary::write_literal(sink, Some(literal)),
+        }
     }
 }
 
@@ -281,8 +353,41 @@ where
         Ok(())
     }
 
-    fn write_binary(self, _sink: &mut impl Write) -> std::io::Result<()> {
-        todo!()
+    fn write_binary(self, sink: &mut impl Write) -> std::io::Result<()> {
+        sink.write_all(&[binary::INFERENCE_TAG])?;
+        binary::write_unsigned(sink, self.id.get())?;
+        binary::write_literal_list(sink, self.premises)?;
+        binary::write_literal(sink, self.propagated)?;
+        binary::write_unsigned(
+            sink,
+            self.hint_constraint_id.map_or(0, |id| id.get().into()),
+        )?;
+
+        let label = self.hint_label.unwrap_or_default();
+        binary::write_unsigned(sink, label.len() as u64)?;
+        sink.write_all(label.as_bytes())
+    }
+}
+
+impl<Literals> WritableProofStep for Definition<Literals, NonZeroI32>
+where
+    Literals: IntoIterator<Item = NonZeroI32>,
+{
+    fn write_string(self, sink: &mut impl Write) -> std::io::Result<()> {
+        write!(sink, "e {}", self.id)?;
+
+        for literal in self.conjunction {
+            write!(sink, " {literal}")?;
+        }
+
+        writeln!(sink, " 0 {}", self.literal)
+    }
+
+    fn write_binary(self, sink: &mut impl Write) -> std::io::Result<()> {
+        sink.write_all(&[binary::DEFINITION_TAG])?;
+        binary::write_unsigned(sink, self.id.get())?;
+        binary::write_literal_list(sink, self.conjunction)?;
+        binary::write_literal(sink, Some(self.literal))
     }
 }
 
@@ -291,8 +396,30 @@ impl WritableProofStep for Deletion {
         writeln!(sink, "d {}", self.id)
     }
 
-    fn write_binary(self, _sink: &mut impl Write) -> std::io::Result<()> {
-        todo!()
+    fn write_binary(self, sink: &mut impl Write) -> std::io::Result<()> {
+        sink.write_all(&[binary::DELETION_TAG])?;
+        binary::write_unsigned(sink, self.id.get())
+    }
+}
+
+impl<Literals> WritableProofStep for Solution<Literals>
+where
+    Literals: IntoIterator<Item = NonZeroI32>,
+{
+    fn write_string(self, sink: &mut impl Write) -> std::io::Result<()> {
+        write!(sink, "s {}", self.id)?;
+
+        for literal in self.assignment {
+            write!(sink, " {literal}")?;
+        }
+
+        writeln!(sink)
+    }
+
+    fn write_binary(self, sink: &mut impl Write) -> std::io::Result<()> {
+        sink.write_all(&[binary::SOLUTION_TAG])?;
+        binary::write_unsigned(sink, self.id.get())?;
+        binary::write_literal_list(sink, self.assignment)
     }
 }
 
@@ -386,6 +513,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_definition() {
+        test_step_serialization(
+            Definition {
+                id: TEST_ID,
+                literal: lit(5),
+                conjunction: [lit(2), lit(-3)],
+            },
+            "e 1 2 -3 0 5\n",
+        );
+    }
+
+    #[test]
+    fn write_solution() {
+        test_step_serialization(
+            Solution {
+                id: TEST_ID,
+                assignment: [lit(2), lit(4)],
+            },
+            "s 1 2 4\n",
+        );
+    }
+
+    #[test]
+    fn write_binary_inference() {
+        test_binary_serialization(
+            Inference {
+                id: TEST_ID,
+                hint_constraint_id: Some(NonZero::new(1).unwrap()),
+                hint_label: Some("lbl"),
+                premises: [lit(2), lit(-3)],
+                propagated: Some(lit(1)),
+            },
+            &[b'i', 1, 4, 5, 0, 2, 1, 3, b'l', b'b', b'l'],
+        );
+    }
+
+    #[test]
+    fn write_binary_nogood_without_hints() {
+        test_binary_serialization(
+            Nogood {
+                id: NonZeroU64::new(300).unwrap(),
+                literals: [lit(-1), lit(64)],
+                hints: None::<[StepId; 0]>,
+            },
+            &[b'n', 0xac, 0x02, 1, 0x80, 0x01, 0, 0],
+        );
+    }
+
     fn lit(num: i32) -> NonZero<i32> {
         NonZero::new(num).unwrap()
     }
@@ -397,4 +573,11 @@ mod tests {
         let actual = String::from_utf8(buffer).expect("valid utf8");
         assert_eq!(expected, actual);
     }
+
+    fn test_binary_serialization(step: impl WritableProofStep, expected: &[u8]) {
+        let mut buffer = Vec::new();
+        step.write_binary(&mut buffer).expect("no error writing");
+
+        assert_eq!(expected, buffer);
+    }
 }
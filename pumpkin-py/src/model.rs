@@ -1,10 +1,16 @@
+use std::cell::RefCell;
 use std::num::NonZero;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use pumpkin_solver::containers::KeyedVec;
+use pumpkin_solver::optimisation::bisection::Bisection;
+use pumpkin_solver::optimisation::lexicographic::LexicographicOptimisation;
 use pumpkin_solver::optimisation::linear_sat_unsat::LinearSatUnsat;
 use pumpkin_solver::optimisation::linear_unsat_sat::LinearUnsatSat;
+use pumpkin_solver::optimisation::pareto_front::ParetoFront;
 use pumpkin_solver::optimisation::OptimisationDirection;
+use pumpkin_solver::optimisation::OptimisationStrategy;
 use pumpkin_solver::options::SolverOptions;
 use pumpkin_solver::predicate;
 use pumpkin_solver::proof::Format;
@@ -240,13 +246,20 @@ impl Model {
         result
     }
 
-    #[pyo3(signature = (objective, optimiser=Optimiser::LinearSatUnsat, direction=Direction::Minimise, proof=None))]
+    /// Optimise the objective with the given optimiser.
+    ///
+    /// When the optimiser is `Optimiser.Bisection`, every probe is given a time budget of
+    /// `bisection_probe_budget` milliseconds (or no budget if it is `None`), and the search falls
+    /// back to linear steps after `bisection_max_probe_timeouts` probes have exceeded their budget.
+    #[pyo3(signature = (objective, optimiser=Optimiser::LinearSatUnsat, direction=Direction::Minimise, proof=None, bisection_probe_budget=Some(10000), bisection_max_probe_timeouts=3))]
     fn optimise(
         &self,
         objective: IntExpression,
         optimiser: Optimiser,
         direction: Direction,
         proof: Option<PathBuf>,
+        bisection_probe_budget: Option<u64>,
+        bisection_max_probe_timeouts: usize,
     ) -> OptimisationResult {
         let solver_setup = self.create_solver(proof);
 
@@ -256,10 +269,7 @@ impl Model {
 
         let mut brancher = solver.default_brancher();
 
-        let direction = match direction {
-            Direction::Minimise => OptimisationDirection::Minimise,
-            Direction::Maximise => OptimisationDirection::Maximise,
-        };
+        let direction = direction.into();
 
         let objective = objective.to_affine_view(&variable_map);
 
@@ -276,26 +286,128 @@ impl Model {
                 &mut Indefinite,
                 LinearUnsatSat::new(direction, objective, callback),
             ),
+            Optimiser::Bisection => solver.optimise(
+                &mut brancher,
+                &mut Indefinite,
+                Bisection::new(direction, objective, callback)
+                    .with_probe_budget(bisection_probe_budget.map(Duration::from_millis))
+                    .with_max_probe_timeouts(bisection_max_probe_timeouts),
+            ),
         };
 
-        match result {
-            pumpkin_solver::results::OptimisationResult::Satisfiable(solution) => {
-                OptimisationResult::Satisfiable(Solution {
-                    solver_solution: solution,
-                    variable_map,
-                })
-            }
-            pumpkin_solver::results::OptimisationResult::Optimal(solution) => {
-                OptimisationResult::Optimal(Solution {
-                    solver_solution: solution,
-                    variable_map,
-                })
-            }
-            pumpkin_solver::results::OptimisationResult::Unsatisfiable => {
-                OptimisationResult::Unsatisfiable()
-            }
-            pumpkin_solver::results::OptimisationResult::Unknown => OptimisationResult::Unknown(),
+        convert_optimisation_result(result, variable_map)
+    }
+
+    /// Optimise the objectives lexicographically; the objectives are ordered from most to least
+    /// important.
+    ///
+    /// The bisection options are used as in `optimise` for every objective when the optimiser is
+    /// `Optimiser.Bisection`.
+    ///
+    /// Unlike `optimise`, this does not take a proof path: a proof has a single objective, so the
+    /// bounds on the other objectives cannot be justified in it.
+    #[pyo3(signature = (objectives, optimiser=Optimiser::LinearSatUnsat, bisection_probe_budget=Some(10000), bisection_max_probe_timeouts=3))]
+    fn optimise_lexicographic(
+        &self,
+        objectives: Vec<(IntExpression, Direction)>,
+        optimiser: Optimiser,
+        bisection_probe_budget: Option<u64>,
+        bisection_max_probe_timeouts: usize,
+    ) -> OptimisationResult {
+        let solver_setup = self.create_solver(None);
+
+        let Ok((mut solver, variable_map)) = solver_setup else {
+            return OptimisationResult::Unsatisfiable();
+        };
+
+        let mut brancher = solver.default_brancher();
+
+        let objectives = objectives
+            .into_iter()
+            .map(|(objective, direction)| {
+                (direction.into(), objective.to_affine_view(&variable_map))
+            })
+            .collect::<Vec<(OptimisationDirection, _)>>();
+
+        let strategy = match optimiser {
+            Optimiser::LinearSatUnsat => OptimisationStrategy::LinearSatUnsat,
+            Optimiser::LinearUnsatSat => OptimisationStrategy::LinearUnsatSat,
+            Optimiser::Bisection => OptimisationStrategy::Bisection,
+        };
+
+        let callback: fn(&Solver, SolutionReference, &DefaultBrancher) = |_, _, _| {};
+
+        let result = solver.optimise(
+            &mut brancher,
+            &mut Indefinite,
+            LexicographicOptimisation::new(objectives, strategy, callback)
+                .with_probe_budget(bisection_probe_budget.map(Duration::from_millis))
+                .with_max_probe_timeouts(bisection_max_probe_timeouts),
+        );
+
+        convert_optimisation_result(result, variable_map)
+    }
+
+    /// Enumerate the Pareto front of the given objectives; every solution in the returned list
+    /// is Pareto-optimal.
+    ///
+    /// Unlike `optimise`, this does not take a proof path: a proof has a single objective, so the
+    /// nogoods which exclude the dominated solutions cannot be justified in it.
+    fn pareto_front(&self, objectives: Vec<(IntExpression, Direction)>) -> Vec<Solution> {
+        let solver_setup = self.create_solver(None);
+
+        let Ok((mut solver, variable_map)) = solver_setup else {
+            return vec![];
+        };
+
+        let mut brancher = solver.default_brancher();
+
+        let objectives = objectives
+            .into_iter()
+            .map(|(objective, direction)| {
+                (direction.into(), objective.to_affine_view(&variable_map))
+            })
+            .collect::<Vec<(OptimisationDirection, _)>>();
+
+        let front = RefCell::new(vec![]);
+        let callback = |_: &Solver, solution: SolutionReference<'_>, _: &DefaultBrancher| {
+            front.borrow_mut().push(Solution {
+                solver_solution: solution.into(),
+                variable_map: variable_map.clone(),
+            });
+        };
+
+        let _ = solver.optimise(
+            &mut brancher,
+            &mut Indefinite,
+            ParetoFront::new(objectives, callback),
+        );
+
+        front.into_inner()
+    }
+}
+
+fn convert_optimisation_result(
+    result: pumpkin_solver::results::OptimisationResult,
+    variable_map: VariableMap,
+) -> OptimisationResult {
+    match result {
+        pumpkin_solver::results::OptimisationResult::Satisfiable(solution) => {
+            OptimisationResult::Satisfiable(Solution {
+                solver_solution: solution,
+                variable_map,
+            })
+        }
+        pumpkin_solver::results::OptimisationResult::Optimal(solution) => {
+            OptimisationResult::Optimal(Solution {
+                solver_solution: solution,
+                variable_map,
+            })
+        }
+        pumpkin_solver::results::OptimisationResult::Unsatisfiable => {
+            OptimisationResult::Unsatisfiable()
         }
+        pumpkin_solver::results::OptimisationResult::Unknown => OptimisationResult::Unknown(),
     }
 }
 
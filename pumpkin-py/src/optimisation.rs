@@ -1,3 +1,4 @@
+use pumpkin_solver::optimisation::OptimisationDirection;
 use pyo3::prelude::*;
 
 use crate::result::Solution;
@@ -19,6 +20,7 @@ pub enum OptimisationResult {
 pub enum Optimiser {
     LinearSatUnsat,
     LinearUnsatSat,
+    Bisection,
 }
 
 #[pyclass(eq, eq_int)]
@@ -28,6 +30,15 @@ pub enum Direction {
     Maximise,
 }
 
+impl From<Direction> for OptimisationDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Minimise => OptimisationDirection::Minimise,
+            Direction::Maximise => OptimisationDirection::Maximise,
+        }
+    }
+}
+
 pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Optimiser>()?;
     m.add_class::<Direction>()?;
@@ -1,5 +1,5 @@
-from pumpkin_py import Model
-from pumpkin_py.optimisation import Direction, OptimisationResult
+from pumpkin_py import Model, constraints
+from pumpkin_py.optimisation import Direction, OptimisationResult, Optimiser
 
 
 def test_linear_sat_unsat_minimisation():
@@ -28,3 +28,72 @@ def test_linear_sat_unsat_maximisation():
     assert solution.int_value(objective) == 5
 
 
+def test_bisection_minimisation():
+    model = Model()
+
+    x = model.new_integer_variable(0, 1000, name="x")
+    y = model.new_integer_variable(0, 500, name="y")
+    model.add_constraint(constraints.LessThanOrEquals([x.scaled(-1), y.scaled(-1)], -731))
+
+    result = model.optimise(
+        x,
+        optimiser=Optimiser.Bisection,
+        direction=Direction.Minimise,
+        bisection_probe_budget=None,
+        bisection_max_probe_timeouts=1,
+    )
+
+    assert isinstance(result, OptimisationResult.Optimal)
+
+    solution = result._0
+    assert solution.int_value(x) == 231
+
+
+def test_lexicographic_optimisation():
+    model = Model()
+
+    x = model.new_integer_variable(0, 5, name="x")
+    y = model.new_integer_variable(0, 5, name="y")
+    model.add_constraint(constraints.LessThanOrEquals([x.scaled(-1), y.scaled(-1)], -5))
+
+    result = model.optimise_lexicographic([(x, Direction.Maximise), (y, Direction.Minimise)])
+
+    assert isinstance(result, OptimisationResult.Optimal)
+
+    solution = result._0
+    assert solution.int_value(x) == 5
+    assert solution.int_value(y) == 0
+
+
+def test_lexicographic_bisection():
+    model = Model()
+
+    x = model.new_integer_variable(0, 1000, name="x")
+    y = model.new_integer_variable(0, 500, name="y")
+    model.add_constraint(constraints.LessThanOrEquals([x.scaled(-1), y.scaled(-1)], -731))
+
+    result = model.optimise_lexicographic(
+        [(x, Direction.Minimise), (y, Direction.Minimise)],
+        optimiser=Optimiser.Bisection,
+        bisection_probe_budget=None,
+        bisection_max_probe_timeouts=1,
+    )
+
+    assert isinstance(result, OptimisationResult.Optimal)
+
+    solution = result._0
+    assert solution.int_value(x) == 231
+    assert solution.int_value(y) == 500
+
+
+def test_pareto_front():
+    model = Model()
+
+    x = model.new_integer_variable(0, 3, name="x")
+    y = model.new_integer_variable(0, 3, name="y")
+    model.add_constraint(constraints.LessThanOrEquals([x.scaled(-1), y.scaled(-1)], -3))
+
+    front = model.pareto_front([(x, Direction.Minimise), (y, Direction.Minimise)])
+
+    values = sorted((solution.int_value(x), solution.int_value(y)) for solution in front)
+    assert values == [(0, 3), (1, 2), (2, 1), (3, 0)]
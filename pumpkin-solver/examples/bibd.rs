@@ -131,6 +131,25 @@ fn main() {
         }
     }
 
+    // Break the symmetries between the rows and between the columns by ordering them
+    // lexicographically.
+    for rows in matrix.windows(2) {
+        let _ = solver
+            .add_constraint(constraints::lex_less_or_equal(
+                rows[0].clone(),
+                rows[1].clone(),
+            ))
+            .post();
+    }
+    for columns in transpose(&matrix).windows(2) {
+        let _ = solver
+            .add_constraint(constraints::lex_less_or_equal(
+                columns[0].clone(),
+                columns[1].clone(),
+            ))
+            .post();
+    }
+
     let mut brancher = solver.default_brancher();
     match solver.satisfy(&mut brancher, &mut Indefinite) {
         SatisfactionResult::Satisfiable(solution) => {
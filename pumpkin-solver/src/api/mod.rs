@@ -62,9 +62,15 @@ pub mod options {
     //! - The learned clause database management approach
     //! - The proof logging
     pub use crate::basic_types::sequence_generators::SequenceGeneratorType;
+    pub use crate::engine::propagation::PropagationDelay;
     pub use crate::engine::ConflictResolver;
+    pub use crate::engine::ConflictTrace;
+    pub use crate::engine::ExtendedResolutionOptions;
+    pub use crate::engine::PresolveOptions;
     pub use crate::engine::RestartOptions;
     pub use crate::engine::SatisfactionSolverOptions as SolverOptions;
+    pub use crate::engine::SearchMode;
+    pub use crate::engine::SearchModeStrategy;
     pub use crate::propagators::nogoods::LearnedNogoodSortingStrategy;
     pub use crate::propagators::nogoods::LearningOptions;
     pub use crate::propagators::CumulativeExplanationType;
@@ -95,6 +101,28 @@ pub mod termination {
     use crate::Solver;
 }
 
+/// A harness for testing the propagators of a [`Constraint`] on small random instances, see
+/// [`testing::fuzz_propagators`].
+///
+/// [`Constraint`]: crate::constraints::Constraint
+pub mod testing {
+    pub use crate::engine::fuzz_propagators;
+    pub use crate::engine::PropagatorFuzzerOptions;
+}
+
+pub mod symmetry {
+    //! Contains the symmetries which can be registered with the [`Solver`] to derive symmetric
+    //! nogoods from the nogoods which are learned during search (see [`Symmetry`]).
+    //!
+    //! A [`Symmetry`] can be implemented to describe symmetries which are not covered by the
+    //! provided [`VariablePermutation`] and [`ValuePermutation`].
+    pub use crate::engine::symmetry::Symmetry;
+    pub use crate::engine::symmetry::ValuePermutation;
+    pub use crate::engine::symmetry::VariablePermutation;
+    #[cfg(doc)]
+    use crate::Solver;
+}
+
 pub mod predicates {
     //! Contains structures which represent certain [predicates](https://en.wikipedia.org/wiki/Predicate_(mathematical_logic)).
     //!
@@ -11,6 +11,8 @@ use crate::predicates::Predicate;
 use crate::results::ProblemSolution;
 use crate::results::Solution;
 use crate::termination::TerminationCondition;
+use crate::variables::AffineView;
+use crate::variables::DomainId;
 use crate::Solver;
 
 /// A struct which allows the retrieval of multiple solutions to a satisfaction problem.
@@ -21,6 +23,10 @@ pub struct SolutionIterator<'solver, 'brancher, 'termination, B, T> {
     termination: &'termination mut T,
     next_blocking_clause: Option<Vec<Predicate>>,
     has_solution: bool,
+    /// The variables on which solutions are blocked; if `None` then all variables are blocked.
+    projection: Option<Vec<AffineView<DomainId>>>,
+    /// Whether solutions which assign the same values to the projection are reported only once.
+    deduplicate: bool,
 }
 
 impl<'solver, 'brancher, 'termination, B: Brancher, T: TerminationCondition>
@@ -37,13 +43,74 @@ impl<'solver, 'brancher, 'termination, B: Brancher, T: TerminationCondition>
             termination,
             next_blocking_clause: None,
             has_solution: false,
+            projection: None,
+            deduplicate: true,
         }
     }
 
+    /// Only block solutions on the provided variables; i.e., every solution which is found by the
+    /// iterator has a distinct assignment to these variables, and solutions which only differ in
+    /// the other variables are not enumerated (see also [`SolutionIterator::with_deduplication`]).
+    ///
+    /// Every solution assigns the same (empty) values to an empty projection, so in that case the
+    /// iterator finishes after the first solution.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use pumpkin_solver::Solver;
+    /// # use pumpkin_solver::termination::Indefinite;
+    /// # use pumpkin_solver::results::solution_iterator::IteratedSolution;
+    /// let mut solver = Solver::default();
+    /// let x = solver.new_bounded_integer(0, 2);
+    /// let _y = solver.new_bounded_integer(0, 2);
+    ///
+    /// let mut brancher = solver.default_brancher();
+    /// let mut termination = Indefinite;
+    /// let mut solution_iterator = solver
+    ///     .get_solution_iterator(&mut brancher, &mut termination)
+    ///     .with_projection([x]);
+    ///
+    /// let mut number_of_solutions = 0;
+    /// while let IteratedSolution::Solution(_, _, _) = solution_iterator.next_solution() {
+    ///     number_of_solutions += 1;
+    /// }
+    ///
+    /// // There are 3 distinct values for `x`.
+    /// assert_eq!(number_of_solutions, 3);
+    /// ```
+    pub fn with_projection(
+        mut self,
+        projection: impl IntoIterator<Item = impl Into<AffineView<DomainId>>>,
+    ) -> Self {
+        self.projection = Some(projection.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets whether solutions which assign the same values to the projection (see
+    /// [`SolutionIterator::with_projection`]) are reported only once, which is the default.
+    ///
+    /// If `false`, solutions are blocked on all variables instead, and so every solution is
+    /// enumerated, including solutions which only differ outside of the projection.
+    pub fn with_deduplication(mut self, deduplicate: bool) -> Self {
+        self.deduplicate = deduplicate;
+        self
+    }
+
     /// Find a new solution by blocking the previous solution from being found. Also calls the
     /// [`Brancher::on_solution`] method from the [`Brancher`] used to run the initial solve.
     pub fn next_solution(&mut self) -> IteratedSolution<B> {
         if let Some(blocking_clause) = self.next_blocking_clause.take() {
+            // An empty blocking clause means that no other solution can be distinguished from the
+            // previous one; adding it would make the solver infeasible, so we stop instead.
+            if blocking_clause.is_empty() {
+                self.next_blocking_clause = Some(blocking_clause);
+                return IteratedSolution::Finished;
+            }
+
+            // The blocking clause is not symmetric, so the images of nogoods which are learned from
+            // it could exclude solutions which have not been found yet.
+            self.solver.satisfaction_solver.remove_symmetries();
+
             if self.solver.add_clause(blocking_clause).is_err() {
                 return IteratedSolution::Finished;
             }
@@ -52,7 +119,12 @@ impl<'solver, 'brancher, 'termination, B: Brancher, T: TerminationCondition>
         match self.solver.satisfy(self.brancher, self.termination) {
             Satisfiable(solution) => {
                 self.has_solution = true;
-                self.next_blocking_clause = Some(get_blocking_clause(&solution));
+                self.next_blocking_clause = Some(match &self.projection {
+                    Some(projection) if self.deduplicate => {
+                        get_projected_blocking_clause(&solution, projection)
+                    }
+                    _ => get_blocking_clause(&solution),
+                });
                 IteratedSolution::Solution(solution, self.solver, self.brancher)
             }
             Unsatisfiable => {
@@ -78,6 +150,19 @@ fn get_blocking_clause(solution: &Solution) -> Vec<Predicate> {
         .map(|variable| predicate!(variable != solution.get_integer_value(variable)))
         .collect::<Vec<_>>()
 }
+
+/// Creates a clause which prevents the assignment of the current solution to the `projection`
+/// variables from occurring again.
+fn get_projected_blocking_clause(
+    solution: &Solution,
+    projection: &[AffineView<DomainId>],
+) -> Vec<Predicate> {
+    projection
+        .iter()
+        .map(|variable| predicate!(variable != solution.get_integer_value(*variable)))
+        .collect::<Vec<_>>()
+}
+
 /// Enum which specifies the status of the call to [`SolutionIterator::next_solution`].
 #[allow(
     clippy::large_enum_variant,
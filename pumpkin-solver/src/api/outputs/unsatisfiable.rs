@@ -5,6 +5,8 @@ use crate::engine::constraint_satisfaction_solver::CoreExtractionResult;
 use crate::engine::ConstraintSatisfactionSolver;
 use crate::predicates::Predicate;
 #[cfg(doc)]
+use crate::proof::ProofLog;
+#[cfg(doc)]
 use crate::Solver;
 
 /// A struct which allows the retrieval of an unsatisfiable core consisting of the provided
@@ -44,6 +46,10 @@ impl<'solver, 'brancher, B: Brancher> UnsatisfiableUnderAssumptions<'solver, 'br
     /// core-guided solvers generate multiple cores while to solve the instance \[1\]. This
     /// core-guided search technique has been translated to CP in \[2\].
     ///
+    /// If the solver logs a proof (see [`ProofLog`]), the core is logged to the proof as a nogood,
+    /// together with the inferences from which it is derived. Together with the model, the proof
+    /// thus certifies that the assumptions in the core cannot be satisfied at the same time.
+    ///
     /// # Example
     /// ```rust
     /// # use pumpkin_solver::Solver;
@@ -1,4 +1,5 @@
 use std::num::NonZero;
+use std::num::NonZeroU64;
 
 use super::outputs::SolutionReference;
 use super::results::OptimisationResult;
@@ -34,10 +35,13 @@ use crate::optimisation::OptimisationProcedure;
 use crate::options::SolverOptions;
 #[cfg(doc)]
 use crate::predicates;
+use crate::proof::Reification;
+use crate::propagators::ReifiedPropagator;
 use crate::results::solution_iterator::SolutionIterator;
 use crate::results::unsatisfiable::UnsatisfiableUnderAssumptions;
 use crate::statistics::log_statistic;
 use crate::statistics::log_statistic_postfix;
+use crate::symmetry::Symmetry;
 
 /// The main interaction point which allows the creation of variables, the addition of constraints,
 /// and solving problems.
@@ -293,6 +297,25 @@ impl Solver {
         self.satisfaction_solver
             .create_new_integer_variable_sparse(values.into(), Some(name.into()))
     }
+
+    /// Give an additional name to an integer variable, for example when several model variables
+    /// are represented by the same variable in the solver. The name of the variable does not
+    /// change, but the alias is included in the names map which is written alongside a CP proof
+    /// (see [`ProofLog::cp`](crate::proof::ProofLog::cp)).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use pumpkin_solver::Solver;
+    /// let mut solver = Solver::default();
+    ///
+    /// let x = solver.new_named_bounded_integer(0, 10, "x");
+    /// // The model variable `y` is known to be equal to `x`
+    /// solver.add_variable_alias(x, "y");
+    /// ```
+    pub fn add_variable_alias(&mut self, domain_id: DomainId, alias: impl Into<String>) {
+        self.satisfaction_solver
+            .add_variable_alias(domain_id, alias.into())
+    }
 }
 
 /// Functions for solving with the constraints that have been added to the [`Solver`].
@@ -448,11 +471,69 @@ impl Solver {
     /// If the formula becomes trivially unsatisfiable, a [`ConstraintOperationError`] will be
     /// returned. Subsequent calls to this method will always return an error, and no
     /// modification of the solver will take place.
+    ///
+    /// If a scope is open (see [`Solver::push_scope`]), the clause is only enforced while the
+    /// scope is open.
     pub fn add_clause(
         &mut self,
         clause: impl IntoIterator<Item = Predicate>,
     ) -> Result<(), ConstraintOperationError> {
-        self.satisfaction_solver.add_clause(clause)
+        match self.satisfaction_solver.innermost_scope() {
+            Some(activation_literal) => self.satisfaction_solver.add_clause(
+                clause
+                    .into_iter()
+                    .chain(std::iter::once(!activation_literal.get_true_predicate())),
+            ),
+            None => self.satisfaction_solver.add_clause(clause),
+        }
+    }
+
+    /// Adds a clause with a tag, which is used to identify the inferences made with the clause in
+    /// the proof log. See [`Solver::add_clause`].
+    pub(crate) fn add_tagged_clause(
+        &mut self,
+        clause: impl IntoIterator<Item = Predicate>,
+        tag: Option<NonZero<u32>>,
+    ) -> Result<(), ConstraintOperationError> {
+        match self.satisfaction_solver.innermost_scope() {
+            Some(activation_literal) => self.satisfaction_solver.add_tagged_clause(
+                clause
+                    .into_iter()
+                    .chain(std::iter::once(!activation_literal.get_true_predicate())),
+                tag,
+            ),
+            None => self.satisfaction_solver.add_tagged_clause(clause, tag),
+        }
+    }
+
+    /// Registers a [`Symmetry`] of the problem. Whenever the solver learns a nogood, its image
+    /// under the symmetry is added as a learned nogood as well.
+    ///
+    /// The symmetry should be a symmetry of all the constraints which are added to the solver
+    /// (see [`Symmetry`]). The nogoods which block the found solutions when iterating over all
+    /// solutions (see [`Solver::get_solution_iterator`]) are not symmetric; hence the registered
+    /// symmetries are discarded once the first solution is blocked, so that every solution is
+    /// still enumerated. No symmetric nogoods are derived when proof logging is enabled, since
+    /// these cannot be justified in the proof.
+    ///
+    /// # Example
+    /// ```
+    /// # use pumpkin_solver::constraints;
+    /// # use pumpkin_solver::symmetry::VariablePermutation;
+    /// # use pumpkin_solver::Solver;
+    /// let mut solver = Solver::default();
+    ///
+    /// let a = solver.new_bounded_integer(0, 3);
+    /// let b = solver.new_bounded_integer(0, 3);
+    /// solver
+    ///     .add_constraint(constraints::not_equals([a, b], 0))
+    ///     .post();
+    ///
+    /// // Swapping `a` and `b` maps solutions onto solutions.
+    /// solver.add_symmetry(VariablePermutation::new([(a, b), (b, a)]));
+    /// ```
+    pub fn add_symmetry(&mut self, symmetry: impl Symmetry + 'static) {
+        self.satisfaction_solver.add_symmetry(Box::new(symmetry));
     }
 
     /// Adds a propagator with a tag, which is used to identify inferences made by this propagator
@@ -462,8 +543,15 @@ impl Solver {
         propagator: impl Propagator + 'static,
         tag: NonZero<u32>,
     ) -> Result<(), ConstraintOperationError> {
-        self.satisfaction_solver
-            .add_propagator(propagator, Some(tag))
+        match self.satisfaction_solver.innermost_scope() {
+            Some(activation_literal) => self.satisfaction_solver.add_propagator(
+                ReifiedPropagator::new(propagator, activation_literal),
+                Some(tag),
+            ),
+            None => self
+                .satisfaction_solver
+                .add_propagator(propagator, Some(tag)),
+        }
     }
 
     /// Post a new propagator to the solver. If unsatisfiability can be immediately determined
@@ -476,11 +564,94 @@ impl Solver {
     /// If the solver is already in a conflicting state, i.e. a previous call to this method
     /// already returned `false`, calling this again will not alter the solver in any way, and
     /// `false` will be returned again.
+    ///
+    /// If a scope is open (see [`Solver::push_scope`]), the propagator is only active while the
+    /// scope is open.
     pub(crate) fn add_propagator(
         &mut self,
         propagator: impl Propagator + 'static,
     ) -> Result<(), ConstraintOperationError> {
-        self.satisfaction_solver.add_propagator(propagator, None)
+        match self.satisfaction_solver.innermost_scope() {
+            Some(activation_literal) => self
+                .satisfaction_solver
+                .add_propagator(ReifiedPropagator::new(propagator, activation_literal), None),
+            None => self.satisfaction_solver.add_propagator(propagator, None),
+        }
+    }
+}
+
+/// Functions for incremental solving.
+///
+/// After a call to [`Solver::satisfy`] or [`Solver::optimise`] returns, the solver is back at the
+/// root and new variables and constraints can be added before solving again. Learned nogoods are
+/// kept between calls, and so is the state of the [`Brancher`] (e.g. VSIDS activities) if the same
+/// brancher is reused.
+///
+/// Constraints which should only hold temporarily can be added in a scope. A scope is opened with
+/// [`Solver::push_scope`] and closed with [`Solver::pop_scope`]; every constraint and clause added
+/// while a scope is open is retracted when that scope is closed. Scopes are implemented using an
+/// activation literal which is assumed whenever the solver is solving; since every nogood which is
+/// learned from a scoped constraint contains that activation literal, all learned nogoods remain
+/// valid after the scope is closed.
+///
+/// # Example
+/// ```rust
+/// # use pumpkin_solver::constraints;
+/// # use pumpkin_solver::predicate;
+/// # use pumpkin_solver::results::SatisfactionResult;
+/// # use pumpkin_solver::termination::Indefinite;
+/// # use pumpkin_solver::Solver;
+/// let mut solver = Solver::default();
+/// let x = solver.new_bounded_integer(0, 5);
+/// let y = solver.new_bounded_integer(0, 5);
+/// let _ = solver
+///     .add_constraint(constraints::less_than_or_equals(vec![x, y], 5))
+///     .post();
+///
+/// let mut brancher = solver.default_brancher();
+///
+/// // Temporarily require both variables to be at least 3, which is infeasible.
+/// let _ = solver.push_scope();
+/// let _ = solver.add_clause([predicate!(x >= 3)]);
+/// let _ = solver.add_clause([predicate!(y >= 3)]);
+/// let result = solver.satisfy(&mut brancher, &mut Indefinite);
+/// assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+///
+/// // After closing the scope, the problem is feasible again.
+/// solver
+///     .pop_scope()
+///     .expect("the solver is not in an infeasible state");
+/// let result = solver.satisfy(&mut brancher, &mut Indefinite);
+/// assert!(matches!(result, SatisfactionResult::Satisfiable(_)));
+/// ```
+impl Solver {
+    /// Opens a new scope and returns its activation literal. All constraints and clauses which are
+    /// added until the matching call to [`Solver::pop_scope`] are only enforced whilst the scope is
+    /// open.
+    ///
+    /// The returned literal is assumed to be true whenever the solver is solving; it can be used to
+    /// identify the scope in unsatisfiable cores.
+    ///
+    /// When a proof is logged, it is not concluded while a scope is open, since the conclusion
+    /// would depend on constraints which can still be retracted. Instead, a warning is logged and
+    /// the proof is left without a conclusion; solving after the scopes have been closed can
+    /// still conclude it.
+    pub fn push_scope(&mut self) -> Literal {
+        self.satisfaction_solver.push_scope()
+    }
+
+    /// Closes the innermost open scope, retracting all constraints and clauses which were added
+    /// since the matching call to [`Solver::push_scope`].
+    ///
+    /// # Panics
+    /// If there is no open scope.
+    pub fn pop_scope(&mut self) -> Result<(), ConstraintOperationError> {
+        self.satisfaction_solver.pop_scope()
+    }
+
+    /// Returns the number of scopes which are currently open.
+    pub fn num_open_scopes(&self) -> usize {
+        self.satisfaction_solver.num_open_scopes()
     }
 }
 
@@ -494,6 +665,12 @@ impl Solver {
 
 /// Proof logging methods
 impl Solver {
+    #[doc(hidden)]
+    /// Returns whether the solver is logging a proof.
+    pub fn is_logging_proof(&self) -> bool {
+        self.satisfaction_solver.is_logging_proof()
+    }
+
     #[doc(hidden)]
     /// Conclude the proof with the unsatisfiable claim.
     ///
@@ -511,6 +688,91 @@ impl Solver {
             .satisfaction_solver
             .conclude_proof_optimal(bound.get_true_predicate());
     }
+
+    #[doc(hidden)]
+    /// Log a solution with the given objective value to the proof, after which only better
+    /// solutions are sought. The `assignment` contains the literals which are true in the
+    /// solution, and should at least assign the variables of the instance.
+    ///
+    /// Returns the id of the proof step which derives that the objective value is smaller than
+    /// `objective_value`. This is only supported by VeriPB proofs.
+    pub fn log_improving_solution_to_proof(
+        &mut self,
+        assignment: impl IntoIterator<Item = Literal>,
+        objective_value: u64,
+    ) -> NonZeroU64 {
+        self.satisfaction_solver
+            .log_improving_solution_to_proof(assignment, objective_value)
+    }
+
+    #[doc(hidden)]
+    /// Log the definition of the fresh `literal` as the reification of the pseudo-Boolean
+    /// constraint `\sum w_i * l_i >= degree`, where `terms` contains the pairs `(w_i, l_i)`. This
+    /// is only supported by VeriPB proofs.
+    ///
+    /// The literal should not occur in any constraint which has been added before.
+    pub fn log_reification_to_proof(
+        &mut self,
+        literal: Literal,
+        terms: &[(u64, Literal)],
+        degree: u64,
+    ) -> Reification {
+        self.satisfaction_solver
+            .log_reification_to_proof(literal, terms, degree)
+    }
+
+    #[doc(hidden)]
+    /// Log the constraint `\sum w_i * l_i >= degree` to the proof, where `terms` contains the
+    /// pairs `(w_i, l_i)`. The constraint should be implied by unit propagation on the
+    /// constraints in the proof. This is only supported by VeriPB proofs.
+    ///
+    /// Note that the constraint is not added to the solver.
+    pub fn log_implied_constraint_to_proof(
+        &mut self,
+        terms: &[(u64, Literal)],
+        degree: u64,
+    ) -> NonZeroU64 {
+        self.satisfaction_solver
+            .log_implied_constraint_to_proof(terms, degree)
+    }
+
+    #[doc(hidden)]
+    /// Log the sum of the constraints derived by the proof steps in `terms`, where each constraint
+    /// is multiplied by its factor, to the proof. The sum is divided by the `divisor`, rounding up
+    /// the coefficients and the degree. This is only supported by VeriPB proofs.
+    ///
+    /// Note that the constraint is not added to the solver.
+    pub fn log_linear_combination_to_proof(
+        &mut self,
+        terms: &[(u64, NonZeroU64)],
+        divisor: u64,
+    ) -> NonZeroU64 {
+        self.satisfaction_solver
+            .log_linear_combination_to_proof(terms, divisor)
+    }
+
+    #[doc(hidden)]
+    /// Log the definition of the fresh `literal` as false to the proof. This is only supported by
+    /// VeriPB proofs.
+    ///
+    /// The literal should not occur in any constraint which has been added before.
+    pub fn log_false_literal_to_proof(&mut self, literal: Literal) -> NonZeroU64 {
+        self.satisfaction_solver.log_false_literal_to_proof(literal)
+    }
+
+    #[doc(hidden)]
+    /// Adds the clause to the solver, see [`Solver::add_clause`]. In a VeriPB proof, the clause
+    /// is logged as being implied by unit propagation on the sum of the `antecedents`.
+    pub fn add_implied_clause(
+        &mut self,
+        clause: impl IntoIterator<Item = Predicate>,
+        antecedents: &[NonZeroU64],
+    ) -> Result<(), ConstraintOperationError> {
+        let clause = clause.into_iter().collect::<Vec<_>>();
+        self.satisfaction_solver
+            .log_implied_clause_to_proof(clause.iter().copied(), antecedents);
+        self.add_clause(clause)
+    }
 }
 
 /// A brancher which makes use of VSIDS \[1\] and solution-based phase saving (both adapted for CP).
@@ -21,6 +21,18 @@ where
     }
 }
 
+impl<Term> CumulativeMovingAverage<Term>
+where
+    Term: NumAssign + Copy,
+{
+    /// Adds all the terms of `other` to this average; the result is the same as if all the terms
+    /// of `other` were added one by one.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.sum += other.sum;
+        self.num_terms += other.num_terms;
+    }
+}
+
 impl<Term> MovingAverage<Term> for CumulativeMovingAverage<Term>
 where
     Term: Debug + NumAssign + AsPrimitive<f64>,
@@ -64,6 +76,18 @@ mod tests {
         assert!(empty_sum.value() == 0.0);
     }
 
+    #[test]
+    fn test_merge() {
+        let mut first_average = CumulativeMovingAverage::default();
+        first_average.add_term(10);
+        let mut second_average = CumulativeMovingAverage::default();
+        second_average.add_term(20);
+        second_average.add_term(30);
+
+        first_average.merge(&second_average);
+        assert!(first_average.value() == 20.0);
+    }
+
     #[test]
     fn test_simple1() {
         let mut constant_average = CumulativeMovingAverage::default();
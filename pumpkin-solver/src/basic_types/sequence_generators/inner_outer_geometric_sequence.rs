@@ -0,0 +1,79 @@
+use super::SequenceGenerator;
+
+/// The inner-outer geometric sequence (see \[1\]) consists of an inner geometric sequence which is
+/// restarted from its initial value whenever it exceeds the outer limit; the outer limit is itself
+/// increased geometrically whenever this happens.
+///
+/// Given constants 'a' and 'm', the sequence is computed as follows:
+/// - inner(0) = a, outer(0) = a
+/// - If inner(i - 1) * m <= outer(i - 1), then inner(i) = inner(i - 1) * m, outer(i) = outer(i - 1)
+/// - Otherwise inner(i) = a and outer(i) = outer(i - 1) * m
+///
+/// For example, with a = 1 and m = 2 the sequence is: 1, 1, 2, 1, 2, 4, 1, 2, 4, 8, 1, ...
+///
+/// Note that overflows are not taken into account
+///
+/// # Bibliography
+/// \[1\] A. Biere, ‘PicoSAT essentials’, Journal on Satisfiability, Boolean Modeling and
+/// Computation, vol. 4, no. 2-4, pp. 75–97, 2008.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct InnerOuterGeometricSequence {
+    starting_value: i64,
+    inner_value: i64,
+    outer_value: i64,
+    multiplication_factor: f64,
+}
+
+impl InnerOuterGeometricSequence {
+    pub(crate) fn new(
+        starting_value: i64,
+        multiplication_factor: f64,
+    ) -> InnerOuterGeometricSequence {
+        InnerOuterGeometricSequence {
+            starting_value,
+            inner_value: starting_value,
+            outer_value: starting_value,
+            multiplication_factor,
+        }
+    }
+}
+
+impl SequenceGenerator for InnerOuterGeometricSequence {
+    fn next(&mut self) -> i64 {
+        let next_value = self.inner_value;
+
+        let next_inner_value = (self.inner_value as f64 * self.multiplication_factor) as i64;
+        if next_inner_value > self.outer_value {
+            self.outer_value = (self.outer_value as f64 * self.multiplication_factor) as i64;
+            self.inner_value = self.starting_value;
+        } else {
+            self.inner_value = next_inner_value;
+        }
+
+        next_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InnerOuterGeometricSequence;
+    use crate::basic_types::sequence_generators::SequenceGenerator;
+
+    #[test]
+    fn test_doubling() {
+        let mut inner_outer_sequence = InnerOuterGeometricSequence::new(1, 2.0);
+        let expected = [1, 1, 2, 1, 2, 4, 1, 2, 4, 8, 1, 2, 4, 8, 16, 1];
+        for value in expected {
+            assert_eq!(inner_outer_sequence.next(), value);
+        }
+    }
+
+    #[test]
+    fn test_multiply_50_procent() {
+        let mut inner_outer_sequence = InnerOuterGeometricSequence::new(100, 1.50);
+        let expected = [100, 100, 150, 100, 150, 225, 100, 150, 225, 337, 100];
+        for value in expected {
+            assert_eq!(inner_outer_sequence.next(), value);
+        }
+    }
+}
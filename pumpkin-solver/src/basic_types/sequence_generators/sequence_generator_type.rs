@@ -26,6 +26,17 @@ pub enum SequenceGeneratorType {
     /// \[1\] M. Luby, A. Sinclair, and D. Zuckerman, ‘Optimal speedup of Las Vegas algorithms’,
     /// Information Processing Letters, vol. 47, no. 4, pp. 173–180, 1993.
     Luby,
+    /// Indicates that the restart strategy should use inner-outer geometric restarts \[1\].
+    ///
+    /// Given two constants `base` and `multiplicative_factor`, the restart limit increases
+    /// geometrically (as in [`SequenceGeneratorType::Geometric`]) until it exceeds an outer
+    /// limit; at that point, the restart limit is reset to `base` and the outer limit is
+    /// multiplied by `multiplicative_factor`.
+    ///
+    /// # Bibliography
+    /// \[1\] A. Biere, ‘PicoSAT essentials’, Journal on Satisfiability, Boolean Modeling and
+    /// Computation, vol. 4, no. 2-4, pp. 75–97, 2008.
+    InnerOuterGeometric,
 }
 
 impl std::fmt::Display for SequenceGeneratorType {
@@ -34,6 +45,7 @@ impl std::fmt::Display for SequenceGeneratorType {
             SequenceGeneratorType::Constant => write!(f, "constant"),
             SequenceGeneratorType::Geometric => write!(f, "geometric"),
             SequenceGeneratorType::Luby => write!(f, "luby"),
+            SequenceGeneratorType::InnerOuterGeometric => write!(f, "inner-outer-geometric"),
         }
     }
 }
@@ -177,6 +177,9 @@ pub(crate) struct SearchStrategy {
     pub(crate) value_selection_strategy: ValueSelectionStrategy,
 }
 
+/// The annotations on the solve item which specify the objectives of a multi-objective problem.
+pub(crate) const MULTI_OBJECTIVE_ANNOTATIONS: [&str; 2] = ["goal_hierarchy", "goal_pareto"];
+
 pub(crate) struct FlatZincAst {
     pub(crate) parameter_decls: Vec<flatzinc::ParDeclItem>,
     pub(crate) single_variables: Vec<SingleVarDecl>,
@@ -227,7 +230,13 @@ impl FlatZincAstBuilder {
     }
 
     pub(crate) fn set_solve_item(&mut self, solve_item: flatzinc::SolveItem) {
-        if let Some(annotation) = solve_item.annotations.first() {
+        // The solve item can also contain annotations which do not specify the search (e.g. the
+        // objectives of a multi-objective problem), these are handled during compilation.
+        if let Some(annotation) = solve_item
+            .annotations
+            .iter()
+            .find(|annotation| !MULTI_OBJECTIVE_ANNOTATIONS.contains(&&annotation.id[..]))
+        {
             self.search = Some(FlatZincAstBuilder::find_search(annotation));
         } else {
             self.search = Some(Search::Unspecified)
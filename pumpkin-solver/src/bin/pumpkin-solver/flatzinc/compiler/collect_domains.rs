@@ -23,10 +23,22 @@ pub(crate) fn run(
                 let representative = context.literal_equivalences.representative(&id);
                 let domain = context.literal_equivalences.domain(&id);
 
+                let mut is_new_variable = false;
                 let literal = *context
                     .boolean_variable_map
                     .entry(representative)
-                    .or_insert_with(|| domain.into_boolean(context.solver, id.to_string()));
+                    .or_insert_with(|| {
+                        is_new_variable = true;
+                        domain.into_boolean(context.solver, id.to_string())
+                    });
+
+                // The variable is equal to a variable which was declared before, so it is given
+                // as an alias in the names map of the proof.
+                if !is_new_variable {
+                    context
+                        .solver
+                        .add_variable_alias(literal.get_true_predicate().get_domain(), id.as_ref());
+                }
 
                 if is_output_variable(annos) {
                     context.outputs.push(Output::bool(id, literal));
@@ -39,6 +51,7 @@ pub(crate) fn run(
                 let representative = context.integer_equivalences.representative(&id);
                 let domain = context.integer_equivalences.domain(&id);
 
+                let mut is_new_variable = false;
                 let domain_id = *context
                     .integer_variable_map
                     .entry(representative)
@@ -51,13 +64,19 @@ pub(crate) fn run(
                                     Domain::SparseDomain { values } => values[0],
                                 })
                                 .or_insert_with(|| {
+                                    is_new_variable = true;
                                     domain.into_variable(context.solver, id.to_string())
                                 })
                         } else {
+                            is_new_variable = true;
                             domain.into_variable(context.solver, id.to_string())
                         }
                     });
 
+                if !is_new_variable {
+                    context.solver.add_variable_alias(domain_id, id.as_ref());
+                }
+
                 if is_output_variable(annos) {
                     context.outputs.push(Output::int(id, domain_id));
                 }
@@ -69,11 +88,20 @@ pub(crate) fn run(
                 let domain_id = if set.len() == 1 {
                     let value = i32::try_from(set[0])?;
 
-                    *context.constant_domain_ids.entry(value).or_insert_with(|| {
-                        context
-                            .solver
-                            .new_named_bounded_integer(value, value, id.to_string())
-                    })
+                    let mut is_new_variable = false;
+                    let domain_id =
+                        *context.constant_domain_ids.entry(value).or_insert_with(|| {
+                            is_new_variable = true;
+                            context
+                                .solver
+                                .new_named_bounded_integer(value, value, id.to_string())
+                        });
+
+                    if !is_new_variable {
+                        context.solver.add_variable_alias(domain_id, id.as_ref());
+                    }
+
+                    domain_id
                 } else {
                     let values = set
                         .iter()
@@ -1,4 +1,5 @@
 use std::collections::BTreeSet;
+use std::num::NonZero;
 use std::rc::Rc;
 
 use fnv::FnvBuildHasher;
@@ -62,6 +63,11 @@ pub(crate) struct CompilationContext<'a> {
     // Mapping from pairs of variables (x, y) to E_{x, y}.
     // The following property should hold: x.id <= y.id
     pub(crate) extended_equality_variables: HashMap<(DomainId, DomainId), Literal>,
+
+    /// The tag of the constraint item which is being compiled, which is the 1-based index of the
+    /// item among the constraint items in the model. Every constraint which is posted for the item
+    /// is given this tag, such that its inferences in the proof refer to the item.
+    pub(crate) constraint_tag: Option<NonZero<u32>>,
 }
 
 /// A set parameter.
@@ -100,6 +106,8 @@ impl CompilationContext<'_> {
 
             set_constants: Default::default(),
             extended_equality_variables: Default::default(),
+
+            constraint_tag: None,
         }
     }
 
@@ -1,10 +1,13 @@
 //! Add objective function to solver
 
+use flatzinc::AnnExpr;
 use flatzinc::BoolExpr;
+use flatzinc::Expr;
 use flatzinc::Goal;
 
 use super::context::CompilationContext;
 use crate::flatzinc::ast::FlatZincAst;
+use crate::flatzinc::instance::FlatzincMultiObjective;
 use crate::flatzinc::instance::FlatzincObjective;
 use crate::flatzinc::FlatZincError;
 
@@ -42,3 +45,61 @@ pub(crate) fn run(
         ),
     }
 }
+
+/// Creates the objectives specified by a `goal_hierarchy` or `goal_pareto` annotation on the solve
+/// item, e.g. `solve :: goal_hierarchy([int_min_goal(x), int_max_goal(y)]) satisfy;`.
+pub(crate) fn create_multi_objective(
+    ast: &FlatZincAst,
+    context: &mut CompilationContext,
+) -> Result<Option<FlatzincMultiObjective>, FlatZincError> {
+    for annotation in ast.solve_item.annotations.iter() {
+        let create: fn(Vec<FlatzincObjective>) -> FlatzincMultiObjective = match &annotation.id[..]
+        {
+            "goal_hierarchy" => FlatzincMultiObjective::Lexicographic,
+            "goal_pareto" => FlatzincMultiObjective::Pareto,
+            _ => continue,
+        };
+
+        let [AnnExpr::Annotations(goals)] = &annotation.expressions[..] else {
+            return Err(FlatZincError::UnexpectedExpr);
+        };
+
+        let objectives = goals
+            .iter()
+            .map(|goal| create_goal(goal, context))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        return Ok(Some(create(objectives)));
+    }
+
+    Ok(None)
+}
+
+fn create_goal(
+    goal: &flatzinc::Annotation,
+    context: &mut CompilationContext,
+) -> Result<FlatzincObjective, FlatZincError> {
+    let identifier = match &goal.expressions[..] {
+        [AnnExpr::Expr(Expr::VarParIdentifier(identifier))] => identifier,
+        // An identifier as argument of an annotation can also be parsed as an annotation without
+        // arguments.
+        [AnnExpr::Annotations(annotations)]
+            if annotations.len() == 1 && annotations[0].expressions.is_empty() =>
+        {
+            &annotations[0].id
+        }
+        _ => return Err(FlatZincError::UnexpectedExpr),
+    };
+
+    let domain = if context.is_identifier_parameter(identifier) {
+        context.resolve_integer_constant_from_id(identifier)?
+    } else {
+        context.resolve_integer_variable_from_identifier(identifier)?
+    };
+
+    match &goal.id[..] {
+        "int_min_goal" | "min_goal" => Ok(FlatzincObjective::Minimize(domain)),
+        "int_max_goal" | "max_goal" => Ok(FlatzincObjective::Maximize(domain)),
+        _ => Err(FlatZincError::UnexpectedExpr),
+    }
+}
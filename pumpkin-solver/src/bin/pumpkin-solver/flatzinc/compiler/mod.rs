@@ -32,11 +32,13 @@ pub(crate) fn compile(
     define_variable_arrays::run(&ast, &mut context)?;
     post_constraints::run(&ast, &mut context, options)?;
     let objective_function = create_objective::run(&ast, &mut context)?;
+    let multi_objective = create_objective::create_multi_objective(&ast, &mut context)?;
     let search = create_search_strategy::run(&ast, &mut context)?;
 
     Ok(FlatZincInstance {
         outputs: context.outputs,
         objective_function,
+        multi_objective,
         search: Some(search),
     })
 }
@@ -1,5 +1,6 @@
 //! Compile constraints into CP propagators
 
+use std::num::NonZero;
 use std::rc::Rc;
 
 use pumpkin_solver::constraints;
@@ -10,9 +11,9 @@ use pumpkin_solver::constraints::GccMethod;
 use pumpkin_solver::constraints::NegatableConstraint;
 use pumpkin_solver::constraints::Values;
 use pumpkin_solver::predicate;
-use pumpkin_solver::predicates::Predicate;
 use pumpkin_solver::variables::AffineView;
 use pumpkin_solver::variables::DomainId;
+use pumpkin_solver::variables::Literal;
 use pumpkin_solver::variables::TransformableVariable;
 
 use super::context::CompilationContext;
@@ -27,8 +28,9 @@ pub(crate) fn run(
     context: &mut CompilationContext,
     options: FlatZincOptions,
 ) -> Result<(), FlatZincError> {
-    for constraint_item in &ast.constraint_decls {
+    for (index, constraint_item) in ast.constraint_decls.iter().enumerate() {
         let flatzinc::ConstraintItem { id, exprs, annos } = constraint_item;
+        context.constraint_tag = NonZero::new(index as u32 + 1);
 
         let is_satisfiable: bool = match id.as_str() {
             "array_int_maximum" => compile_array_int_maximum(context, exprs)?,
@@ -208,6 +210,14 @@ pub(crate) fn run(
             "pumpkin_cumulative_var" => todo!("The `cumulative` constraint with variable duration/resource consumption/bound is not implemented yet!"),
             "pumpkin_gcc" => compile_gcc(context, exprs, &options)?,
             "pumpkin_gcc_imp" => compile_gcc_imp(context, exprs, &options)?,
+            "pumpkin_lex_lesseq" => compile_lex(context, exprs, "pumpkin_lex_lesseq", false)?,
+            "pumpkin_lex_less" => compile_lex(context, exprs, "pumpkin_lex_less", true)?,
+            "pumpkin_lex_lesseq_bool" => {
+                compile_lex_bool(context, exprs, "pumpkin_lex_lesseq_bool", false)?
+            }
+            "pumpkin_lex_less_bool" => {
+                compile_lex_bool(context, exprs, "pumpkin_lex_less_bool", true)?
+            }
             unknown => todo!("unsupported constraint {unknown}"),
         };
 
@@ -216,6 +226,8 @@ pub(crate) fn run(
         }
     }
 
+    context.constraint_tag = None;
+
     Ok(())
 }
 
@@ -250,7 +262,7 @@ fn compile_cumulative(
         resource_capacity,
         options.cumulative_options,
     )
-    .post(context.solver, None);
+    .post(context.solver, context.constraint_tag);
     Ok(post_result.is_ok())
 }
 
@@ -280,34 +292,34 @@ fn compile_gcc(
     Ok(match options.gcc_options.propagation_method {
         pumpkin_solver::options::GccPropagatorMethod::Bruteforce => {
             global_cardinality_lower_upper(variables, values, GccMethod::Bruteforce)
-                .post(context.solver, None)
+                .post(context.solver, context.constraint_tag)
                 .is_ok()
         }
         pumpkin_solver::options::GccPropagatorMethod::BasicFilter => {
             global_cardinality_lower_upper(variables, values, GccMethod::BasicFilter)
-                .post(context.solver, None)
+                .post(context.solver, context.constraint_tag)
                 .is_ok()
         }
         pumpkin_solver::options::GccPropagatorMethod::ReginArcConsistent => {
             global_cardinality_lower_upper(variables, values, GccMethod::ReginArcConsistent)
-                .post(context.solver, None)
+                .post(context.solver, context.constraint_tag)
                 .is_ok()
         }
         pumpkin_solver::options::GccPropagatorMethod::ExtendedResolution => {
-            let extended_variables: HashMap<(usize, usize), pumpkin_solver::variables::Literal> =
+            let extended_variables: HashMap<(usize, usize), Literal> =
                 context.init_extended_equality_variables(&variables);
             gcc_extended_resolution(variables, values, extended_variables)
-                .post(context.solver, None)
+                .post(context.solver, context.constraint_tag)
                 .is_ok()
         }
         pumpkin_solver::options::GccPropagatorMethod::ExtendedResolutionWithRegin => {
-            let extended_variables: HashMap<(usize, usize), pumpkin_solver::variables::Literal> =
+            let extended_variables: HashMap<(usize, usize), Literal> =
                 context.init_extended_equality_variables(&variables);
             gcc_extended_resolution(variables.clone(), values.clone(), extended_variables)
-                .post(context.solver, None)
+                .post(context.solver, context.constraint_tag)
                 .is_ok()
                 && global_cardinality_lower_upper(variables, values, GccMethod::ReginArcConsistent)
-                    .post(context.solver, None)
+                    .post(context.solver, context.constraint_tag)
                     .is_ok()
         }
     })
@@ -340,34 +352,34 @@ fn compile_gcc_imp(
     Ok(match options.gcc_options.propagation_method {
         pumpkin_solver::options::GccPropagatorMethod::Bruteforce => {
             global_cardinality_lower_upper(variables, values, GccMethod::Bruteforce)
-                .implied_by(context.solver, reif, None)
+                .implied_by(context.solver, reif, context.constraint_tag)
                 .is_ok()
         }
         pumpkin_solver::options::GccPropagatorMethod::BasicFilter => {
             global_cardinality_lower_upper(variables, values, GccMethod::BasicFilter)
-                .implied_by(context.solver, reif, None)
+                .implied_by(context.solver, reif, context.constraint_tag)
                 .is_ok()
         }
         pumpkin_solver::options::GccPropagatorMethod::ReginArcConsistent => {
             global_cardinality_lower_upper(variables, values, GccMethod::ReginArcConsistent)
-                .implied_by(context.solver, reif, None)
+                .implied_by(context.solver, reif, context.constraint_tag)
                 .is_ok()
         }
         pumpkin_solver::options::GccPropagatorMethod::ExtendedResolution => {
-            let extended_variables: HashMap<(usize, usize), pumpkin_solver::variables::Literal> =
+            let extended_variables: HashMap<(usize, usize), Literal> =
                 context.init_extended_equality_variables(&variables);
             gcc_extended_resolution(variables, values, extended_variables)
-                .implied_by(context.solver, reif, None)
+                .implied_by(context.solver, reif, context.constraint_tag)
                 .is_ok()
         }
         pumpkin_solver::options::GccPropagatorMethod::ExtendedResolutionWithRegin => {
-            let extended_variables: HashMap<(usize, usize), pumpkin_solver::variables::Literal> =
+            let extended_variables: HashMap<(usize, usize), Literal> =
                 context.init_extended_equality_variables(&variables);
             gcc_extended_resolution(variables.clone(), values.clone(), extended_variables)
-                .implied_by(context.solver, reif, None)
+                .implied_by(context.solver, reif, context.constraint_tag)
                 .is_ok()
                 && global_cardinality_lower_upper(variables, values, GccMethod::ReginArcConsistent)
-                    .implied_by(context.solver, reif, None)
+                    .implied_by(context.solver, reif, context.constraint_tag)
                     .is_ok()
         }
     })
@@ -383,7 +395,7 @@ fn compile_array_int_maximum(
     let array = context.resolve_integer_variable_array(&exprs[1])?;
 
     Ok(constraints::maximum(array.as_ref().to_owned(), rhs)
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok())
 }
 
@@ -397,7 +409,7 @@ fn compile_array_int_minimum(
     let array = context.resolve_integer_variable_array(&exprs[1])?;
 
     Ok(constraints::minimum(array.as_ref().to_owned(), rhs)
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok())
 }
 
@@ -416,32 +428,26 @@ fn compile_set_in_reif(
             lower_bound,
             upper_bound,
         } => {
+            let lower_bound = context
+                .solver
+                .new_literal_for_predicate(predicate![variable >= lower_bound]);
+            let upper_bound = context
+                .solver
+                .new_literal_for_predicate(predicate![variable <= upper_bound]);
+
             // `reif -> x \in S`
             // Decomposed to `reif -> x >= lb /\ reif -> x <= ub`
-            let forward = context
-                .solver
-                .add_clause([
-                    !reif.get_true_predicate(),
-                    predicate![variable >= lower_bound],
-                ])
+            let forward = constraints::clause([!reif, lower_bound])
+                .post(context.solver, context.constraint_tag)
                 .is_ok()
-                && context
-                    .solver
-                    .add_clause([
-                        !reif.get_true_predicate(),
-                        !predicate![variable >= upper_bound + 1],
-                    ])
+                && constraints::clause([!reif, upper_bound])
+                    .post(context.solver, context.constraint_tag)
                     .is_ok();
 
             // `!reif -> x \notin S`
             // Decomposed to `!reif -> (x < lb \/ x > ub)`
-            let backward = context
-                .solver
-                .add_clause([
-                    reif.get_true_predicate(),
-                    !predicate![variable >= lower_bound],
-                    predicate![variable >= upper_bound + 1],
-                ])
+            let backward = constraints::clause([reif, !lower_bound, !upper_bound])
+                .post(context.solver, context.constraint_tag)
                 .is_ok();
 
             forward && backward
@@ -458,7 +464,7 @@ fn compile_set_in_reif(
                 .collect::<Vec<_>>();
 
             constraints::clause(clause)
-                .reify(context.solver, reif, None)
+                .reify(context.solver, reif, context.constraint_tag)
                 .is_ok()
         }
     };
@@ -477,7 +483,7 @@ fn compile_array_var_int_element(
     let rhs = context.resolve_integer_variable(&exprs[2])?;
 
     Ok(constraints::element(index, array.as_ref().to_owned(), rhs)
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok())
 }
 
@@ -495,7 +501,7 @@ fn compile_bool_not(
     let b = context.resolve_bool_variable(&exprs[1])?;
 
     Ok(constraints::binary_not_equals(a, b)
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok())
 }
 
@@ -510,7 +516,7 @@ fn compile_bool_eq_reif(
     let r = context.resolve_bool_variable(&exprs[2])?;
 
     Ok(constraints::binary_equals(a, b)
-        .reify(context.solver, r, None)
+        .reify(context.solver, r, context.constraint_tag)
         .is_ok())
 }
 
@@ -526,7 +532,7 @@ fn compile_bool_eq(
     let b = context.resolve_bool_variable(&exprs[1])?;
 
     Ok(constraints::binary_equals(a, b)
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok())
 }
 
@@ -539,14 +545,15 @@ fn compile_bool_clause(
     let clause_1 = context.resolve_bool_variable_array(&exprs[0])?;
     let clause_2 = context.resolve_bool_variable_array(&exprs[1])?;
 
-    let clause: Vec<Predicate> = clause_1
+    let clause: Vec<Literal> = clause_1
         .iter()
         .cloned()
         .chain(clause_2.iter().map(|literal| !*literal))
-        .map(|literal| literal.get_true_predicate())
         .collect();
 
-    Ok(context.solver.add_clause(clause).is_ok())
+    Ok(constraints::clause(clause)
+        .post(context.solver, context.constraint_tag)
+        .is_ok())
 }
 
 fn compile_bool_and(
@@ -560,7 +567,7 @@ fn compile_bool_and(
     let r = context.resolve_bool_variable(&exprs[2])?;
 
     Ok(constraints::conjunction([a, b])
-        .reify(context.solver, r, None)
+        .reify(context.solver, r, context.constraint_tag)
         .is_ok())
 }
 
@@ -579,7 +586,7 @@ fn compile_bool2int(
 
     Ok(
         constraints::binary_equals(a.get_integer_variable(), b.scaled(1))
-            .post(context.solver, None)
+            .post(context.solver, context.constraint_tag)
             .is_ok(),
     )
 }
@@ -594,7 +601,7 @@ fn compile_bool_or(
     let r = context.resolve_bool_variable(&exprs[1])?;
 
     Ok(constraints::clause(clause.as_ref())
-        .reify(context.solver, r, None)
+        .reify(context.solver, r, context.constraint_tag)
         .is_ok())
 }
 
@@ -604,15 +611,15 @@ fn compile_bool_xor(
 ) -> Result<bool, FlatZincError> {
     check_parameters!(exprs, 2, "pumpkin_bool_xor");
 
-    let a = context
-        .resolve_bool_variable(&exprs[0])?
-        .get_true_predicate();
-    let b = context
-        .resolve_bool_variable(&exprs[1])?
-        .get_true_predicate();
+    let a = context.resolve_bool_variable(&exprs[0])?;
+    let b = context.resolve_bool_variable(&exprs[1])?;
 
-    let c1 = context.solver.add_clause([!a, !b]).is_ok();
-    let c2 = context.solver.add_clause([b, a]).is_ok();
+    let c1 = constraints::clause([!a, !b])
+        .post(context.solver, context.constraint_tag)
+        .is_ok();
+    let c2 = constraints::clause([b, a])
+        .post(context.solver, context.constraint_tag)
+        .is_ok();
 
     Ok(c1 && c2)
 }
@@ -628,16 +635,16 @@ fn compile_bool_xor_reif(
     let r = context.resolve_bool_variable(&exprs[2])?;
 
     let c1 = constraints::clause([!a, !b, !r])
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok();
     let c2 = constraints::clause([!a, b, r])
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok();
     let c3 = constraints::clause([a, !b, r])
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok();
     let c4 = constraints::clause([a, b, !r])
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok();
 
     Ok(c1 && c2 && c3 && c4)
@@ -655,7 +662,7 @@ fn compile_array_var_bool_element(
     let rhs = context.resolve_bool_variable(&exprs[2])?;
 
     Ok(constraints::element(index, array.iter().cloned(), rhs)
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok())
 }
 
@@ -669,7 +676,7 @@ fn compile_array_bool_and(
     let r = context.resolve_bool_variable(&exprs[1])?;
 
     Ok(constraints::conjunction(conjunction.as_ref())
-        .reify(context.solver, r, None)
+        .reify(context.solver, r, context.constraint_tag)
         .is_ok())
 }
 
@@ -687,7 +694,9 @@ fn compile_ternary_int_predicate<C: Constraint>(
     let c = context.resolve_integer_variable(&exprs[2])?;
 
     let constraint = create_constraint(a, b, c);
-    Ok(constraint.post(context.solver, None).is_ok())
+    Ok(constraint
+        .post(context.solver, context.constraint_tag)
+        .is_ok())
 }
 
 fn compile_binary_int_predicate<C: Constraint>(
@@ -703,7 +712,9 @@ fn compile_binary_int_predicate<C: Constraint>(
     let b = context.resolve_integer_variable(&exprs[1])?;
 
     let constraint = create_constraint(a, b);
-    Ok(constraint.post(context.solver, None).is_ok())
+    Ok(constraint
+        .post(context.solver, context.constraint_tag)
+        .is_ok())
 }
 
 fn compile_reified_binary_int_predicate<C: NegatableConstraint>(
@@ -720,7 +731,9 @@ fn compile_reified_binary_int_predicate<C: NegatableConstraint>(
     let reif = context.resolve_bool_variable(&exprs[2])?;
 
     let constraint = create_constraint(a, b);
-    Ok(constraint.reify(context.solver, reif, None).is_ok())
+    Ok(constraint
+        .reify(context.solver, reif, context.constraint_tag)
+        .is_ok())
 }
 
 fn weighted_vars(weights: Rc<[i32]>, vars: Rc<[DomainId]>) -> Box<[AffineView<DomainId>]> {
@@ -746,7 +759,9 @@ fn compile_int_lin_predicate<C: Constraint>(
     let terms = weighted_vars(weights, vars);
 
     let constraint = create_constraint(terms, rhs);
-    Ok(constraint.post(context.solver, None).is_ok())
+    Ok(constraint
+        .post(context.solver, context.constraint_tag)
+        .is_ok())
 }
 
 fn compile_reified_int_lin_predicate<C: NegatableConstraint>(
@@ -766,7 +781,9 @@ fn compile_reified_int_lin_predicate<C: NegatableConstraint>(
     let terms = weighted_vars(weights, vars);
 
     let constraint = create_constraint(terms, rhs);
-    Ok(constraint.reify(context.solver, reif, None).is_ok())
+    Ok(constraint
+        .reify(context.solver, reif, context.constraint_tag)
+        .is_ok())
 }
 
 fn compile_bool_lin_eq_predicate(
@@ -781,7 +798,7 @@ fn compile_bool_lin_eq_predicate(
 
     Ok(
         constraints::boolean_equals(weights.as_ref().to_owned(), bools.as_ref().to_owned(), rhs)
-            .post(context.solver, None)
+            .post(context.solver, context.constraint_tag)
             .is_ok(),
     )
 }
@@ -801,10 +818,48 @@ fn compile_bool_lin_le_predicate(
         bools.as_ref().to_owned(),
         rhs,
     )
-    .post(context.solver, None)
+    .post(context.solver, context.constraint_tag)
     .is_ok())
 }
 
+fn compile_lex(
+    context: &mut CompilationContext,
+    exprs: &[flatzinc::Expr],
+    name: &str,
+    is_strict: bool,
+) -> Result<bool, FlatZincError> {
+    check_parameters!(exprs, 2, name);
+
+    let x = context.resolve_integer_variable_array(&exprs[0])?.to_vec();
+    let y = context.resolve_integer_variable_array(&exprs[1])?.to_vec();
+
+    let post_result = if is_strict {
+        constraints::lex_less(x, y).post(context.solver, context.constraint_tag)
+    } else {
+        constraints::lex_less_or_equal(x, y).post(context.solver, context.constraint_tag)
+    };
+    Ok(post_result.is_ok())
+}
+
+fn compile_lex_bool(
+    context: &mut CompilationContext,
+    exprs: &[flatzinc::Expr],
+    name: &str,
+    is_strict: bool,
+) -> Result<bool, FlatZincError> {
+    check_parameters!(exprs, 2, name);
+
+    let x = context.resolve_bool_variable_array(&exprs[0])?.to_vec();
+    let y = context.resolve_bool_variable_array(&exprs[1])?.to_vec();
+
+    let post_result = if is_strict {
+        constraints::lex_less(x, y).post(context.solver, context.constraint_tag)
+    } else {
+        constraints::lex_less_or_equal(x, y).post(context.solver, context.constraint_tag)
+    };
+    Ok(post_result.is_ok())
+}
+
 fn compile_all_different(
     context: &mut CompilationContext,
     exprs: &[flatzinc::Expr],
@@ -814,6 +869,6 @@ fn compile_all_different(
 
     let variables = context.resolve_integer_variable_array(&exprs[0])?.to_vec();
     Ok(constraints::all_different(variables)
-        .post(context.solver, None)
+        .post(context.solver, context.constraint_tag)
         .is_ok())
 }
@@ -34,4 +34,7 @@ pub(crate) enum FlatZincError {
 
     #[error("missing solve item")]
     MissingSolveItem,
+
+    #[error("proof logging is not supported for {0}")]
+    ProofLoggingNotSupported(Box<str>),
 }
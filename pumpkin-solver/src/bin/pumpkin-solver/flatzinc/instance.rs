@@ -4,6 +4,7 @@ use std::rc::Rc;
 
 use pumpkin_solver::branching::branchers::dynamic_brancher::DynamicBrancher;
 use pumpkin_solver::optimisation::OptimisationDirection;
+use pumpkin_solver::variables::AffineView;
 use pumpkin_solver::variables::DomainId;
 use pumpkin_solver::variables::Literal;
 
@@ -25,10 +26,21 @@ impl From<FlatzincObjective> for (OptimisationDirection, DomainId) {
     }
 }
 
+/// The objectives of a multi-objective FlatZinc model, which are specified using the
+/// `goal_hierarchy` or `goal_pareto` annotation on the solve item.
+#[derive(Debug, Clone)]
+pub(crate) enum FlatzincMultiObjective {
+    /// The objectives are optimised lexicographically, ordered from most to least important.
+    Lexicographic(Vec<FlatzincObjective>),
+    /// The Pareto front over the objectives is enumerated.
+    Pareto(Vec<FlatzincObjective>),
+}
+
 #[derive(Default)]
 pub(crate) struct FlatZincInstance {
     pub(super) outputs: Vec<Output>,
     pub(super) objective_function: Option<FlatzincObjective>,
+    pub(super) multi_objective: Option<FlatzincMultiObjective>,
     pub(super) search: Option<DynamicBrancher>,
 }
 
@@ -85,6 +97,24 @@ impl Output {
             contents,
         })
     }
+
+    /// Returns the variables which are printed by this output.
+    pub(crate) fn variables(&self) -> Vec<AffineView<DomainId>> {
+        match self {
+            Output::Bool(output) => vec![output.variable.into()],
+            Output::Int(output) => vec![output.variable.into()],
+            Output::ArrayOfBool(output) => output
+                .contents
+                .iter()
+                .map(|&literal| literal.into())
+                .collect(),
+            Output::ArrayOfInt(output) => output
+                .contents
+                .iter()
+                .map(|&domain_id| domain_id.into())
+                .collect(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
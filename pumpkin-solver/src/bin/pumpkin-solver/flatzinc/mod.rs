@@ -15,8 +15,11 @@ use pumpkin_solver::branching::branchers::dynamic_brancher::DynamicBrancher;
 use pumpkin_solver::branching::Brancher;
 #[cfg(doc)]
 use pumpkin_solver::constraints::cumulative;
+use pumpkin_solver::optimisation::bisection::Bisection;
+use pumpkin_solver::optimisation::lexicographic::LexicographicOptimisation;
 use pumpkin_solver::optimisation::linear_sat_unsat::LinearSatUnsat;
 use pumpkin_solver::optimisation::linear_unsat_sat::LinearUnsatSat;
+use pumpkin_solver::optimisation::pareto_front::ParetoFront;
 use pumpkin_solver::optimisation::OptimisationDirection;
 use pumpkin_solver::optimisation::OptimisationStrategy;
 use pumpkin_solver::options::CumulativeOptions;
@@ -35,6 +38,7 @@ use pumpkin_solver::variables::DomainId;
 use pumpkin_solver::Solver;
 
 use self::instance::FlatZincInstance;
+use self::instance::FlatzincMultiObjective;
 use self::instance::Output;
 use crate::flatzinc::error::FlatZincError;
 
@@ -58,6 +62,13 @@ pub(crate) struct FlatZincOptions {
 
     /// Determines which type of search is performed by the solver
     pub(crate) optimisation_strategy: OptimisationStrategy,
+
+    /// The time budget for a single probe of the bisection optimisation strategy.
+    pub(crate) bisection_probe_budget: Duration,
+
+    /// The number of probes of the bisection optimisation strategy which can exceed their budget
+    /// before falling back to linear steps.
+    pub(crate) bisection_max_probe_timeouts: usize,
 }
 
 fn solution_callback(
@@ -106,6 +117,24 @@ pub(crate) fn solve(
         instance.search.expect("Expected a search to be defined")
     };
 
+    if let Some(multi_objective) = instance.multi_objective {
+        if solver.is_logging_proof() {
+            return Err(FlatZincError::ProofLoggingNotSupported(
+                "multi-objective optimisation".into(),
+            ));
+        }
+
+        optimise_multi_objective(
+            options,
+            &mut solver,
+            brancher,
+            termination,
+            multi_objective,
+            outputs,
+        );
+        return Ok(());
+    }
+
     let (direction, objective): (OptimisationDirection, DomainId) =
         match instance.objective_function {
             Some(objective) => objective.into(),
@@ -138,6 +167,13 @@ pub(crate) fn solve(
             &mut termination,
             LinearUnsatSat::new(direction, objective, callback),
         ),
+        OptimisationStrategy::Bisection => solver.optimise(
+            &mut brancher,
+            &mut termination,
+            Bisection::new(direction, objective, callback)
+                .with_probe_budget(Some(options.bisection_probe_budget))
+                .with_max_probe_timeouts(options.bisection_max_probe_timeouts),
+        ),
     };
 
     match result {
@@ -167,6 +203,96 @@ pub(crate) fn solve(
     Ok(())
 }
 
+fn optimise_multi_objective(
+    options: FlatZincOptions,
+    solver: &mut Solver,
+    mut brancher: DynamicBrancher,
+    mut termination: impl TerminationCondition,
+    multi_objective: FlatzincMultiObjective,
+    outputs: Vec<Output>,
+) {
+    let result = match multi_objective {
+        FlatzincMultiObjective::Lexicographic(objectives) => {
+            let objectives = objectives
+                .into_iter()
+                .map(<(OptimisationDirection, DomainId)>::from)
+                .collect::<Vec<_>>();
+            let most_important_objective = objectives.first().map(|&(_, objective)| objective);
+
+            let callback =
+                |solver: &Solver, solution: SolutionReference<'_>, brancher: &DynamicBrancher| {
+                    if let Some(objective) = most_important_objective {
+                        solution_callback(
+                            brancher,
+                            Some(objective),
+                            options.all_solutions,
+                            &outputs,
+                            solver,
+                            solution,
+                        );
+                    }
+                };
+
+            let result = solver.optimise(
+                &mut brancher,
+                &mut termination,
+                LexicographicOptimisation::new(objectives, options.optimisation_strategy, callback)
+                    .with_probe_budget(Some(options.bisection_probe_budget))
+                    .with_max_probe_timeouts(options.bisection_max_probe_timeouts),
+            );
+
+            // Similar to single-objective optimisation, only the optimal solution is printed if
+            // intermediate solutions are not requested.
+            if let OptimisationResult::Optimal(optimal_solution) = &result {
+                if !options.all_solutions {
+                    brancher.log_statistics(StatisticLogger::default());
+                    solver.log_statistics();
+                    print_solution_from_solver(optimal_solution.as_reference(), &outputs)
+                }
+            }
+
+            result
+        }
+        FlatzincMultiObjective::Pareto(objectives) => {
+            // Every solution on the Pareto front is printed.
+            let callback =
+                |solver: &Solver, solution: SolutionReference<'_>, brancher: &DynamicBrancher| {
+                    solution_callback(brancher, None, true, &outputs, solver, solution);
+                };
+
+            solver.optimise(
+                &mut brancher,
+                &mut termination,
+                ParetoFront::new(
+                    objectives
+                        .into_iter()
+                        .map(<(OptimisationDirection, DomainId)>::from),
+                    callback,
+                ),
+            )
+        }
+    };
+
+    match result {
+        OptimisationResult::Optimal(_) => {
+            println!("==========");
+            solver.log_statistics();
+        }
+        OptimisationResult::Satisfiable(_) => {
+            // Solutions are printed in the callback.
+            solver.log_statistics();
+        }
+        OptimisationResult::Unsatisfiable => {
+            println!("{MSG_UNSATISFIABLE}");
+            solver.log_statistics();
+        }
+        OptimisationResult::Unknown => {
+            println!("{MSG_UNKNOWN}");
+            solver.log_statistics();
+        }
+    }
+}
+
 fn satisfy(
     options: FlatZincOptions,
     solver: &mut Solver,
@@ -175,7 +301,15 @@ fn satisfy(
     outputs: Vec<Output>,
 ) {
     if options.all_solutions {
-        let mut solution_iterator = solver.get_solution_iterator(&mut brancher, &mut termination);
+        // Solutions are only blocked on the output variables, since solutions which only differ
+        // in the other variables are indistinguishable.
+        let projection = outputs
+            .iter()
+            .flat_map(Output::variables)
+            .collect::<Vec<_>>();
+        let mut solution_iterator = solver
+            .get_solution_iterator(&mut brancher, &mut termination)
+            .with_projection(projection);
         loop {
             match solution_iterator.next_solution() {
                 IteratedSolution::Solution(solution, solver, brancher) => {
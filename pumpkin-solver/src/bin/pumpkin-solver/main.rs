@@ -7,6 +7,7 @@ mod result;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fs::File;
+use std::io::BufWriter;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
@@ -74,8 +75,13 @@ struct Args {
 
     /// The output path for the proof file.
     ///
-    /// When solving a DIMACS instance, a DRAT proof is logged. In case of a FlatZinc model, a DRCP
-    /// proof is logged.
+    /// When solving a CNF instance, a DRAT proof is logged, unless the path ends in `.pbp` in
+    /// which case a VeriPB proof is logged. When solving a WCNF instance, a VeriPB proof is
+    /// logged. The VeriPB proof is checked against the pseudo-Boolean translation of the
+    /// instance, which is written next to the proof with the `opb` extension.
+    ///
+    /// In case of a FlatZinc model, a DRCP proof is logged. A DRCP proof and its literal
+    /// definitions are compressed if the path ends in `.gz` (gzip) or `.zst` (zstd).
     #[arg(long, verbatim_doc_comment)]
     proof_path: Option<PathBuf>,
 
@@ -85,6 +91,24 @@ struct Args {
     #[arg(long, value_enum, default_value_t)]
     proof_type: ProofType,
 
+    /// The format in which the DRCP proof is written.
+    ///
+    /// The binary format is considerably more compact than the textual format. The literal
+    /// definitions are always written as text. If the `proof_path` option is not provided, or a
+    /// DIMACS instance is solved, this is ignored.
+    #[arg(long, value_enum, default_value_t)]
+    proof_format: ProofFormat,
+
+    /// The output path for a trace of the analysed conflicts, which is used to debug the
+    /// explanations of propagators.
+    ///
+    /// For every conflict, the trace records the conflicting propagator, the resolution steps
+    /// together with the propagators whose explanations were used, the nogood after each
+    /// minimisation step, and the learned nogood. The trace is written in the JSON Lines format,
+    /// and can be browsed with the `explore` command of `drcp-debugger`.
+    #[arg(long)]
+    conflict_trace_path: Option<PathBuf>,
+
     /// The number of high lbd learned clauses that are kept in the database.
     /// Learned clauses are kept based on the tiered system introduced in "Improving
     /// SAT Solvers by Exploiting Empirical Characteristics of CDCL - Chanseok Oh (2016)".
@@ -125,6 +149,136 @@ struct Args {
     #[arg(long = "no-learning-minimise", verbatim_doc_comment)]
     no_learning_clause_minimisation: bool,
 
+    /// Decides whether learned clause minimisation asks the propagators for generalised reasons
+    /// (e.g. a bound rather than an equality) to remove predicates which are implied by the other
+    /// predicates in the learned clause. Currently, the linear and cumulative propagators provide
+    /// generalised reasons.
+    ///
+    /// If this flag is present then the generalisation of reasons is turned on.
+    ///
+    /// Possible values: bool
+    #[arg(long = "learning-generalise-reasons", verbatim_doc_comment)]
+    learning_generalise_reasons: bool,
+
+    /// Decides whether the learned clauses are inprocessed at the root level during restarts.
+    /// Inprocessing vivifies learned clauses (see "Clause Vivification by Unit Propagation in CDCL
+    /// SAT Solvers - Li et al. (2020)") and removes learned clauses which are (self-)subsumed by
+    /// other clauses.
+    ///
+    /// If this flag is present then inprocessing is turned on.
+    ///
+    /// Possible values: bool
+    #[arg(long = "learning-inprocessing", verbatim_doc_comment)]
+    learning_inprocessing: bool,
+
+    /// The number of restarts between two rounds of inprocessing of the learned clauses.
+    ///
+    /// Possible values: u64
+    #[arg(
+        long = "learning-inprocessing-interval",
+        default_value_t = 5,
+        verbatim_doc_comment
+    )]
+    learning_inprocessing_interval: u64,
+
+    /// The maximum number of learned clauses which are vivified during a single round of
+    /// inprocessing.
+    ///
+    /// Possible values: usize
+    #[arg(
+        long = "learning-max-num-vivified",
+        default_value_t = 500,
+        verbatim_doc_comment
+    )]
+    learning_max_num_vivified: usize,
+
+    /// Decides whether extension literals are introduced during conflict analysis. For a pair of
+    /// predicates `p` and `q` which frequently occur together in learned clauses, the literal `d`
+    /// is defined as `d <-> (p /\ q)`, and `d` replaces both `p` and `q` in future learned clauses
+    /// (see "A Restriction of Extended Resolution for Clause Learning SAT Solvers - Audemard et
+    /// al. (2010)"). The definitions are logged to the proof.
+    ///
+    /// If this flag is present then extended resolution is turned on.
+    ///
+    /// Possible values: bool
+    #[arg(long = "extended-resolution", verbatim_doc_comment)]
+    extended_resolution: bool,
+
+    /// The number of learned clauses in which a pair of predicates should occur before an
+    /// extension literal is defined for the pair.
+    ///
+    /// Possible values: u32
+    #[arg(
+        long = "extended-resolution-min-occurrences",
+        default_value_t = 10,
+        verbatim_doc_comment
+    )]
+    extended_resolution_min_occurrences: u32,
+
+    /// The maximum length of a learned clause for which the pairs of predicates are counted.
+    ///
+    /// Possible values: usize
+    #[arg(
+        long = "extended-resolution-max-clause-length",
+        default_value_t = 10,
+        verbatim_doc_comment
+    )]
+    extended_resolution_max_clause_length: usize,
+
+    /// If provided, the solver backtracks a single decision level after a conflict whenever
+    /// backjumping to the asserting level would undo more than the provided number of decision
+    /// levels (see "Chronological Backtracking - Nadel and Ryvchin (2018)"). This avoids
+    /// re-propagating large parts of the trail after long backjumps.
+    ///
+    /// If this argument is not provided, then the solver always backjumps to the asserting level.
+    ///
+    /// Possible values: usize
+    #[arg(long = "chronological-backtracking-threshold", verbatim_doc_comment)]
+    chronological_backtracking_threshold: Option<usize>,
+
+    /// If provided, the model is presolved at the root level before searching, for at most the
+    /// provided number of milliseconds. Presolving probes the bounds of every variable and shaves
+    /// the bounds which lead to a conflict, and detects implied and equivalent Boolean literals by
+    /// probing both polarities of every Boolean variable; equivalent literals are substituted in
+    /// the clauses. The derived clauses are logged to the proof.
+    ///
+    /// If this argument is not provided, then no presolving takes place.
+    ///
+    /// Possible values: u64
+    #[arg(long = "presolve-time-budget", verbatim_doc_comment)]
+    presolve_time_budget: Option<u64>,
+
+    /// Decides whether equivalent Boolean literals are detected and substituted during presolving.
+    ///
+    /// If this flag is present then the detection of equivalent literals is turned off.
+    ///
+    /// Possible values: bool
+    #[arg(long = "no-presolve-equivalences", verbatim_doc_comment)]
+    no_presolve_equivalences: bool,
+
+    /// Determines until when the propagation of expensive propagators (e.g. the cumulative and
+    /// global cardinality propagators) is delayed once they are enqueued. Expensive propagators
+    /// are always propagated after the cheaper propagators have reached a fixed point.
+    ///
+    /// - The "none" approach applies no further delay
+    /// - The "until-fixed-point" approach propagates them only once all other propagators have
+    ///   reached a fixed point
+    /// - The "until-leaf" approach propagates them only once the search cannot make any further
+    ///   decisions; they then only check the candidate solution
+    #[arg(long, value_enum, default_value_t, verbatim_doc_comment)]
+    expensive_propagator_delay: PropagationDelay,
+
+    /// Measures the time spent in every call to a propagator, which is reported in the
+    /// statistics, and reports the profile of every propagator instance rather than only the
+    /// profiles aggregated per propagator name. Since this adds overhead to every call, it is
+    /// disabled by default.
+    ///
+    /// If this flag is present then the propagators are profiled.
+    ///
+    /// Possible values: bool
+    #[arg(long = "profile-propagators", verbatim_doc_comment)]
+    profile_propagators: bool,
+
     /// Decides the sequence based on which the restarts are performed.
     ///
     /// - The "constant" approach uses a constant number of conflicts before another restart is
@@ -133,6 +287,9 @@ struct Args {
     /// - The "luby" approach uses a recursive sequence of the form 1, 1, 2, 1, 1, 2, 4, 1, 1, 2,
     ///   1, 1, 2, 4, 8, 1, 1, 2.... (see "Optimal speedup of Las Vegas algorithms - Luby et al.
     ///   (1993)")
+    /// - The "inner-outer-geometric" approach uses a geometrically increasing inner sequence which
+    ///   is reset whenever it exceeds a geometrically increasing outer limit (see "PicoSAT
+    ///   Essentials - Biere (2008)"); requires "--restart-geometric-coef"
     ///
     /// To be used in combination with "--restarts-base-interval".
     #[arg(long, value_enum, default_value_t)]
@@ -223,6 +380,43 @@ struct Args {
     #[arg(long = "restart-geometric-coef", verbatim_doc_comment)]
     restart_geometric_coef: Option<f64>,
 
+    /// Decides which search mode(s) the solver uses (see "CaDiCaL, Kissat, Paracooba, Plingeling
+    /// and Treengeling entering the SAT Competition 2020 - Biere et al. (2020)").
+    ///
+    /// - In "focused" mode, the solver restarts frequently based on the LBD of learned nogoods
+    /// - In "stable" mode, the solver restarts infrequently according to
+    ///   "--restart-stable-sequence" and uses the phase of the best-known solution
+    /// - The "alternating" approach switches between the two modes, starting with focused mode
+    #[arg(long, value_enum, default_value_t, verbatim_doc_comment)]
+    restart_search_mode: SearchModeStrategy,
+
+    /// The sequence based on which restarts are performed in stable mode; see
+    /// "--restart-sequence-generator-type" for the possible values.
+    #[arg(long, value_enum, default_value_t = SequenceGeneratorType::Luby)]
+    restart_stable_sequence: SequenceGeneratorType,
+
+    /// The base interval length of the restart sequence which is used in stable mode.
+    ///
+    /// Possible values: u64
+    #[arg(
+        long = "restart-stable-base-interval",
+        default_value_t = 1024,
+        verbatim_doc_comment
+    )]
+    restart_stable_base_interval: u64,
+
+    /// The number of conflicts after which the solver switches from focused to stable mode (and
+    /// vice versa) when "--restart-search-mode" is "alternating"; the interval is doubled after
+    /// every switch.
+    ///
+    /// Possible values: u64
+    #[arg(
+        long = "restart-mode-switch-interval",
+        default_value_t = 1000,
+        verbatim_doc_comment
+    )]
+    restart_mode_switch_interval: u64,
+
     /// The time budget for the solver, given in milliseconds.
     ///
     /// Possible values: u64 (Optional)
@@ -302,7 +496,7 @@ struct Args {
     ///   Saurabh et al. (2015)")
     /// - The "cardinality-network" value specifies that the solver should use the Cardinality
     ///   Network Encoding (see "Cardinality networks: a theoretical and empirical study - Asín et
-    ///   al. (2011)").
+    ///   al. (2011)")
     #[arg(long, value_enum, default_value_t)]
     upper_bound_encoding: PseudoBooleanEncoding,
 
@@ -356,6 +550,28 @@ struct Args {
     /// Determine what type of optimisation strategy is used by the solver
     #[arg(long = "optimisation-strategy", default_value_t)]
     optimisation_strategy: OptimisationStrategy,
+
+    /// The time budget for a single probe of the "bisection" optimisation strategy, given in
+    /// milliseconds.
+    ///
+    /// Possible values: u64
+    #[arg(
+        long = "bisection-probe-budget",
+        default_value_t = 10000,
+        verbatim_doc_comment
+    )]
+    bisection_probe_budget: u64,
+
+    /// The number of probes of the "bisection" optimisation strategy which can exceed their
+    /// budget before the strategy falls back to linear steps.
+    ///
+    /// Possible values: usize
+    #[arg(
+        long = "bisection-max-probe-timeouts",
+        default_value_t = 3,
+        verbatim_doc_comment
+    )]
+    bisection_max_probe_timeouts: usize,
 }
 
 fn configure_logging(
@@ -486,23 +702,45 @@ fn run() -> PumpkinResult<()> {
         warn!("Potential performance degradation: the Pumpkin assert level is set to {}, meaning many debug asserts are active which may result in performance degradation.", pumpkin_solver::asserts::PUMPKIN_ASSERT_LEVEL_DEFINITION);
     };
 
+    let is_veripb_proof = match file_format {
+        FileFormat::CnfDimacsPLine => args
+            .proof_path
+            .as_ref()
+            .is_some_and(|path| path.extension().is_some_and(|ext| ext == "pbp")),
+        FileFormat::WcnfDimacsPLine => args.proof_path.is_some(),
+        FileFormat::FlatZinc => false,
+    };
+    let pseudo_boolean_instance_path = args
+        .proof_path
+        .as_ref()
+        .filter(|_| is_veripb_proof)
+        .map(|path| path.with_extension("opb"));
+
     let proof_log = if let Some(path_buf) = args.proof_path {
         match file_format {
+            FileFormat::CnfDimacsPLine if is_veripb_proof => ProofLog::veripb(&path_buf)?,
             FileFormat::CnfDimacsPLine => ProofLog::dimacs(&path_buf)?,
-            FileFormat::WcnfDimacsPLine => {
-                return Err(PumpkinError::ProofGenerationNotSupported("wcnf".to_owned()))
-            }
+            FileFormat::WcnfDimacsPLine => ProofLog::veripb(&path_buf)?,
             FileFormat::FlatZinc => {
                 let log_inferences =
                     matches!(args.proof_type, ProofType::Full | ProofType::WithHints);
                 let log_hints = matches!(args.proof_type, ProofType::WithHints);
-                ProofLog::cp(&path_buf, Format::Text, log_inferences, log_hints)?
+                let format = match args.proof_format {
+                    ProofFormat::Text => Format::Text,
+                    ProofFormat::Binary => Format::Binary,
+                };
+                ProofLog::cp(&path_buf, format, log_inferences, log_hints)?
             }
         }
     } else {
         ProofLog::default()
     };
 
+    let conflict_trace = match args.conflict_trace_path {
+        Some(path) => ConflictTrace::json(&path)?,
+        None => ConflictTrace::default(),
+    };
+
     let restart_options = RestartOptions {
         sequence_generator_type: args.restart_sequence_generator_type,
         base_interval: args.restart_base_interval,
@@ -512,6 +750,10 @@ fn run() -> PumpkinResult<()> {
         num_assigned_window: args.restart_num_assigned_window,
         geometric_coef: args.restart_geometric_coef,
         no_restarts: args.no_restarts,
+        search_mode_strategy: args.restart_search_mode,
+        stable_sequence_generator_type: args.restart_stable_sequence,
+        stable_base_interval: args.restart_stable_base_interval,
+        mode_switch_interval: args.restart_mode_switch_interval,
     };
     let learning_options = LearningOptions {
         max_activity: 1e20,
@@ -520,15 +762,34 @@ fn run() -> PumpkinResult<()> {
         lbd_threshold: args.learning_lbd_threshold,
         nogood_sorting_strategy: args.learning_sorting_strategy,
         activity_bump_increment: 1.0,
+        inprocess_nogoods: args.learning_inprocessing,
+        inprocessing_interval: args.learning_inprocessing_interval,
+        max_num_vivified_nogoods: args.learning_max_num_vivified,
+    };
+    let extended_resolution_options = ExtendedResolutionOptions {
+        enabled: args.extended_resolution,
+        min_num_occurrences: args.extended_resolution_min_occurrences,
+        max_nogood_length: args.extended_resolution_max_clause_length,
+        ..Default::default()
     };
 
     let solver_options = SolverOptions {
         restart_options,
         learning_clause_minimisation: !args.no_learning_clause_minimisation,
+        learning_reason_generalisation: args.learning_generalise_reasons,
         random_generator: SmallRng::seed_from_u64(args.random_seed),
         proof_log,
         conflict_resolver: args.conflict_resolver,
         learning_options,
+        extended_resolution_options,
+        chronological_backtracking_threshold: args.chronological_backtracking_threshold,
+        presolve_options: PresolveOptions {
+            time_budget: args.presolve_time_budget.map(Duration::from_millis),
+            detect_equivalences: !args.no_presolve_equivalences,
+        },
+        expensive_propagator_delay: args.expensive_propagator_delay,
+        profile_propagators: args.profile_propagators,
+        conflict_trace,
     };
 
     let time_limit = args.time_limit.map(Duration::from_millis);
@@ -538,12 +799,18 @@ fn run() -> PumpkinResult<()> {
         .ok_or(PumpkinError::invalid_instance(args.instance_path.display()))?;
 
     match file_format {
-        FileFormat::CnfDimacsPLine => cnf_problem(solver_options, time_limit, instance_path)?,
+        FileFormat::CnfDimacsPLine => cnf_problem(
+            solver_options,
+            time_limit,
+            instance_path,
+            pseudo_boolean_instance_path,
+        )?,
         FileFormat::WcnfDimacsPLine => wcnf_problem(
             solver_options,
             time_limit,
             instance_path,
             args.upper_bound_encoding,
+            pseudo_boolean_instance_path,
         )?,
         FileFormat::FlatZinc => flatzinc::solve(
             Solver::with_options(solver_options),
@@ -561,6 +828,8 @@ fn run() -> PumpkinResult<()> {
                 ),
                 gcc_options: GccOptions::new(args.gcc_propagation_method),
                 optimisation_strategy: args.optimisation_strategy,
+                bisection_probe_budget: Duration::from_millis(args.bisection_probe_budget),
+                bisection_max_probe_timeouts: args.bisection_max_probe_timeouts,
             },
         )?,
     }
@@ -572,10 +841,17 @@ fn cnf_problem(
     solver_options: SolverOptions,
     time_limit: Option<Duration>,
     instance_path: impl AsRef<Path>,
+    pseudo_boolean_instance_path: Option<PathBuf>,
 ) -> Result<(), PumpkinError> {
     let instance_file = File::open(instance_path)?;
-    let mut solver =
-        parse_cnf::<SolverDimacsSink>(instance_file, SolverArgs::new(solver_options))?.solver;
+    let sink = parse_cnf::<SolverDimacsSink>(
+        instance_file,
+        SolverArgs::new(solver_options).record_clauses(pseudo_boolean_instance_path.is_some()),
+    )?;
+    if let Some(path) = pseudo_boolean_instance_path {
+        sink.write_pseudo_boolean_instance(BufWriter::new(File::create(path)?))?;
+    }
+    let mut solver = sink.solver;
 
     let mut termination =
         TimeBudget::starting_now(time_limit.unwrap_or(Duration::from_secs(u64::MAX)));
@@ -649,3 +925,21 @@ impl Display for ProofType {
         }
     }
 }
+
+#[derive(Default, Clone, Copy, Debug, ValueEnum)]
+enum ProofFormat {
+    /// Log the proof as text.
+    #[default]
+    Text,
+    /// Log the proof in the binary encoding.
+    Binary,
+}
+
+impl Display for ProofFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofFormat::Text => write!(f, "text"),
+            ProofFormat::Binary => write!(f, "binary"),
+        }
+    }
+}
@@ -1,8 +1,10 @@
+use std::num::NonZeroU64;
 use std::time::Instant;
 
 use pumpkin_solver::pumpkin_assert_eq_simple;
 use pumpkin_solver::pumpkin_assert_simple;
 use pumpkin_solver::variables::Literal;
+use pumpkin_solver::ConstraintOperationError;
 use pumpkin_solver::Solver;
 
 use super::pseudo_boolean_constraint_encoder::EncodingError;
@@ -14,19 +16,30 @@ use crate::maxsat::encoders::EncodingError::CannotStrengthen;
 /// the form `x1 + ... + xn <= k`. The encoding is arc-consistent and supports incremental
 /// strengthening of the upper bound.
 ///
+/// In a proof, each fresh output of a merger is defined as the reification of the number of true
+/// inputs of the merger, i.e. `c_j <-> \sum inputs >= j`. The clauses of a merger follow from
+/// these definitions and the fact that its inputs are sorted, which is derived for the outputs of
+/// every merger. The bound on the outputs then follows from the bound on the objective, since the
+/// number of true outputs of a sorter is at most the number of its true inputs.
+///
 /// Reference:
 /// Asín, Roberto, et al. Cardinality networks: a theoretical and empirical study.
 /// Constraints, 2011, 16: 195-221.
 #[derive(Debug)]
 pub(crate) struct CardinalityNetworkEncoder {
     literals: Vec<Literal>,
+    /// The weight of the literals in the objective.
+    weight: u64,
     output: Vec<Literal>,
+    /// The proof steps which derive that if `output[j]` is true, then at least `j + 1` literals
+    /// are true.
+    output_implies_count: Vec<NonZeroU64>,
     num_clauses_added: usize,
 }
 
 macro_rules! try_add_clause {
-    ($self:ident, $csp_solver:ident, $e:expr) => {
-        if $csp_solver.add_clause($e).is_err() {
+    ($self:ident, $csp_solver:ident, $e:expr, $antecedents:expr) => {
+        if $csp_solver.add_implied_clause($e, $antecedents).is_err() {
             return None;
         }
         $self.num_clauses_added += 1;
@@ -37,6 +50,7 @@ impl PseudoBooleanConstraintEncoderInterface for CardinalityNetworkEncoder {
     fn encode_at_most_k(
         weighted_literals: Vec<WeightedLiteral>,
         k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) -> Result<Self, EncodingError>
     where
@@ -44,27 +58,28 @@ impl PseudoBooleanConstraintEncoderInterface for CardinalityNetworkEncoder {
     {
         // The CNE only supports unweighted functions. This expression verifies that assumption is
         // met. If it is not met, and because we cannot return an error condition, we panic.
-        let literals = match weighted_literals.first().map(|wlit| wlit.weight) {
-            Some(weight) => {
-                pumpkin_assert_simple!(
-                    weighted_literals
-                        .iter()
-                        .all(|weighted_lit| weight == weighted_lit.weight),
-                    "Sorting network encoding is only supported on unweighted instances."
-                );
-
-                weighted_literals
-                    .into_iter()
-                    .map(|wlit| wlit.literal)
-                    .collect::<Vec<_>>()
-            }
-            None => vec![],
-        };
+        let weight = weighted_literals.first().map_or(1, |wlit| wlit.weight);
+        pumpkin_assert_simple!(
+            weighted_literals
+                .iter()
+                .all(|weighted_lit| weight == weighted_lit.weight),
+            "Sorting network encoding is only supported on unweighted instances."
+        );
+
+        let literals = weighted_literals
+            .into_iter()
+            .map(|wlit| wlit.literal)
+            .collect::<Vec<_>>();
 
-        CardinalityNetworkEncoder::new(literals, k, solver)
+        CardinalityNetworkEncoder::new(literals, weight, k, objective_bound, solver)
     }
 
-    fn strengthen_at_most_k(&mut self, k: u64, solver: &mut Solver) -> Result<(), EncodingError> {
+    fn strengthen_at_most_k(
+        &mut self,
+        k: u64,
+        objective_bound: NonZeroU64,
+        solver: &mut Solver,
+    ) -> Result<(), EncodingError> {
         if k == 0 && self.output.is_empty() {
             return Ok(());
         }
@@ -76,10 +91,7 @@ impl PseudoBooleanConstraintEncoderInterface for CardinalityNetworkEncoder {
 
         println!("c CNE k = {k}");
 
-        if solver
-            .add_clause([(!self.output[k as usize]).get_true_predicate()])
-            .is_err()
-        {
+        if self.add_upper_bound(k, objective_bound, solver).is_err() {
             Err(CannotStrengthen)
         } else {
             Ok(())
@@ -88,24 +100,35 @@ impl PseudoBooleanConstraintEncoderInterface for CardinalityNetworkEncoder {
 }
 
 impl CardinalityNetworkEncoder {
-    /// Create a new encoder from the given literals which form the left-hand side.
+    /// Create a new encoder from the given literals which form the left-hand side, which all have
+    /// the given weight in the objective. The `objective_bound` is the proof step which derives
+    /// the bound `p`.
     pub(crate) fn new(
         literals: Vec<Literal>,
+        weight: u64,
         p: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) -> Result<Self, EncodingError> {
         let mut encoder = CardinalityNetworkEncoder {
             literals,
+            weight,
             output: vec![],
+            output_implies_count: vec![],
             num_clauses_added: 0,
         };
 
-        encoder.create_encoding(p, solver)?;
+        encoder.create_encoding(p, objective_bound, solver)?;
 
         Ok(encoder)
     }
 
-    fn create_encoding(&mut self, p: u64, solver: &mut Solver) -> Result<(), EncodingError> {
+    fn create_encoding(
+        &mut self,
+        p: u64,
+        objective_bound: NonZeroU64,
+        solver: &mut Solver,
+    ) -> Result<(), EncodingError> {
         pumpkin_assert_simple!(
             self.output.is_empty(),
             "Can only generate the encoding once."
@@ -127,7 +150,7 @@ impl CardinalityNetworkEncoder {
         if result.is_err() {
             println!("c encoding detected conflict at the root!");
         } else if !self.output.is_empty() {
-            let r = solver.add_clause([(!self.output[p as usize]).get_true_predicate()]);
+            let r = self.add_upper_bound(p, objective_bound, solver);
             if r.is_err() {
                 return Err(EncodingError::RootPropagationConflict);
             }
@@ -136,6 +159,26 @@ impl CardinalityNetworkEncoder {
         result
     }
 
+    /// Adds the clause `~output[k]`. In the proof, this follows from the `objective_bound`, since
+    /// `output[k]` implies that more than `k` literals are true.
+    fn add_upper_bound(
+        &self,
+        k: u64,
+        objective_bound: NonZeroU64,
+        solver: &mut Solver,
+    ) -> Result<(), ConstraintOperationError> {
+        let k = k as usize;
+
+        let bound = solver.log_linear_combination_to_proof(
+            &[
+                (self.weight, self.output_implies_count[k]),
+                (1, objective_bound),
+            ],
+            1,
+        );
+        solver.add_implied_clause([(!self.output[k]).get_true_predicate()], &[bound])
+    }
+
     fn generate_clauses(&mut self, p: u64, solver: &mut Solver) -> Result<(), EncodingError> {
         let n = self.literals.len() as u64;
 
@@ -151,18 +194,21 @@ impl CardinalityNetworkEncoder {
             .collect::<Vec<_>>();
 
         for &lit in padding_lits.iter() {
+            let _ = solver.log_false_literal_to_proof(lit);
             if solver.add_clause([(!lit).get_true_predicate()]).is_err() {
                 return Err(EncodingError::RootPropagationConflict);
             }
         }
 
-        self.output = self
+        let output = self
             .card(
                 &[self.literals.as_slice(), padding_lits.as_slice()].concat(),
                 k,
                 solver,
             )
             .unwrap_or_default();
+        self.output = output.sequence.literals;
+        self.output_implies_count = output.at_least;
 
         if self.output.is_empty() {
             Err(EncodingError::RootPropagationConflict)
@@ -173,148 +219,297 @@ impl CardinalityNetworkEncoder {
 
     fn s_merge(
         &mut self,
-        a: &[Literal],
-        b: &[Literal],
+        a: &SortedSequence,
+        b: &SortedSequence,
         solver: &mut Solver,
-    ) -> Option<Vec<Literal>> {
-        pumpkin_assert_eq_simple!(a.len(), b.len());
+    ) -> Option<Outputs> {
+        pumpkin_assert_eq_simple!(a.literals.len(), b.literals.len());
 
-        if a.len() == 1 {
+        if a.literals.len() == 1 {
             let c = vec![solver.new_literal(), solver.new_literal()];
-            let a = a[0].get_true_predicate();
-            let b = b[0].get_true_predicate();
-
-            try_add_clause!(self, solver, vec![!a, !b, c[1].get_true_predicate()]);
-            try_add_clause!(self, solver, vec![!a, c[0].get_true_predicate()]);
-            try_add_clause!(self, solver, vec![!b, c[0].get_true_predicate()]);
-
-            return Some(c);
+            let input_terms = [(1, a.literals[0]), (1, b.literals[0])];
+            let definitions = c
+                .iter()
+                .enumerate()
+                .map(|(index, &literal)| {
+                    solver.log_reification_to_proof(literal, &input_terms, index as u64 + 1)
+                })
+                .collect::<Vec<_>>();
+
+            let a = a.literals[0].get_true_predicate();
+            let b = b.literals[0].get_true_predicate();
+
+            // These clauses follow from the definitions by unit propagation.
+            try_add_clause!(self, solver, vec![!a, !b, c[1].get_true_predicate()], &[]);
+            try_add_clause!(self, solver, vec![!a, c[0].get_true_predicate()], &[]);
+            try_add_clause!(self, solver, vec![!b, c[0].get_true_predicate()], &[]);
+
+            let sorted = solver.log_implied_constraint_to_proof(&[(1, !c[1]), (1, c[0])], 1);
+
+            return Some(Outputs {
+                sequence: SortedSequence {
+                    literals: c,
+                    sorted: vec![sorted],
+                },
+                at_least: definitions
+                    .iter()
+                    .map(|definition| definition.literal_implies_constraint)
+                    .collect(),
+            });
         }
 
-        pumpkin_assert_eq_simple!(0, a.len() & 1);
-        let a_even = even_literals(a);
-        let b_even = even_literals(b);
-        let a_odd = odd_literals(a);
-        let b_odd = odd_literals(b);
+        pumpkin_assert_eq_simple!(0, a.literals.len() & 1);
+        let a_even = a.even_literals(solver);
+        let b_even = b.even_literals(solver);
+        let a_odd = a.odd_literals(solver);
+        let b_odd = b.odd_literals(solver);
 
         let d = self.s_merge(&a_odd, &b_odd, solver)?;
         let e = self.s_merge(&a_even, &b_even, solver)?;
 
-        pumpkin_assert_eq_simple!((a.len() >> 1) + 1, d.len());
-        pumpkin_assert_eq_simple!((a.len() >> 1) + 1, e.len());
-
-        let mut c = (0..a.len())
-            .map(|_| solver.new_literal())
-            .collect::<Vec<_>>();
-        c.insert(0, d[0]);
-
-        for i in 0..(a.len() >> 1) {
-            try_add_clause!(
-                self,
-                solver,
-                vec![
-                    (!d[i + 1]).get_true_predicate(),
-                    (!e[i]).get_true_predicate(),
-                    (c[2 * (i + 1)]).get_true_predicate()
-                ]
-            );
-            try_add_clause!(
-                self,
-                solver,
-                vec![
-                    (!d[i + 1]).get_true_predicate(),
-                    (c[2 * (i + 1) - 1]).get_true_predicate()
-                ]
-            );
-            try_add_clause!(
-                self,
-                solver,
-                vec![
-                    (!e[i]).get_true_predicate(),
-                    (c[2 * (i + 1) - 1]).get_true_predicate()
-                ]
-            );
-        }
-
-        Some(c)
+        pumpkin_assert_eq_simple!((a.literals.len() >> 1) + 1, d.sequence.literals.len());
+        pumpkin_assert_eq_simple!((a.literals.len() >> 1) + 1, e.sequence.literals.len());
+
+        self.combine(
+            a,
+            b,
+            &d,
+            &e,
+            a.literals.len(),
+            false,
+            a.literals.len() >> 1,
+            solver,
+        )
     }
 
-    fn h_sort(&mut self, seq: &[Literal], solver: &mut Solver) -> Option<Vec<Literal>> {
+    fn h_sort(&mut self, seq: &[Literal], solver: &mut Solver) -> Option<Outputs> {
         pumpkin_assert_simple!(seq.len() & 1 == 0);
 
         let n = seq.len() >> 1;
 
         if n == 1 {
-            return self.h_merge(&[seq[0]], &[seq[1]], solver);
+            return self.h_merge(
+                &SortedSequence::single(seq[0]),
+                &SortedSequence::single(seq[1]),
+                solver,
+            );
         }
 
         let d = self.h_sort(&seq[..n], solver)?;
         let d_prime = self.h_sort(&seq[n..], solver)?;
 
-        self.h_merge(&d, &d_prime, solver)
+        let c = self.h_merge(&d.sequence, &d_prime.sequence, solver)?;
+
+        Some(c.count_sorter_inputs(&d, &d_prime, solver))
     }
 
     fn h_merge(
         &mut self,
-        a: &[Literal],
-        b: &[Literal],
+        a: &SortedSequence,
+        b: &SortedSequence,
         solver: &mut Solver,
-    ) -> Option<Vec<Literal>> {
-        pumpkin_assert_eq_simple!(a.len(), b.len());
+    ) -> Option<Outputs> {
+        pumpkin_assert_eq_simple!(a.literals.len(), b.literals.len());
 
-        let n = a.len();
+        let n = a.literals.len();
 
         if n == 1 {
             return self.s_merge(a, b, solver);
         }
 
-        let a_even = even_literals(a);
-        let b_even = even_literals(b);
-        let a_odd = odd_literals(a);
-        let b_odd = odd_literals(b);
+        let a_even = a.even_literals(solver);
+        let b_even = b.even_literals(solver);
+        let a_odd = a.odd_literals(solver);
+        let b_odd = b.odd_literals(solver);
 
         let d = self.h_merge(&a_odd, &b_odd, solver)?;
         let e = self.h_merge(&a_even, &b_even, solver)?;
 
-        let mut c = (0..2 * n - 2)
+        self.combine(a, b, &d, &e, 2 * n - 2, true, n - 1, solver)
+    }
+
+    /// Creates the outputs of the merger of `a` and `b` from the outputs `d` of the merger of
+    /// their odd literals and the outputs `e` of the merger of their even literals. The first
+    /// output is the first output of `d`, followed by `num_fresh` new literals, and, if
+    /// `reuse_last`, the last output of `e`.
+    #[allow(clippy::too_many_arguments, reason = "shared by both mergers")]
+    fn combine(
+        &mut self,
+        a: &SortedSequence,
+        b: &SortedSequence,
+        d: &Outputs,
+        e: &Outputs,
+        num_fresh: usize,
+        reuse_last: bool,
+        num_combinations: usize,
+        solver: &mut Solver,
+    ) -> Option<Outputs> {
+        let inputs = [a.literals.as_slice(), b.literals.as_slice()].concat();
+        let num_inputs = inputs.len() as u64;
+        let input_terms = inputs.iter().map(|&input| (1, input)).collect::<Vec<_>>();
+
+        let fresh = (0..num_fresh)
             .map(|_| solver.new_literal())
             .collect::<Vec<_>>();
+        let definitions = fresh
+            .iter()
+            .enumerate()
+            .map(|(index, &literal)| {
+                solver.log_reification_to_proof(literal, &input_terms, index as u64 + 2)
+            })
+            .collect::<Vec<_>>();
+        let constraint_implies_output =
+            |index: usize| definitions[index - 1].constraint_implies_literal;
+
+        // The first output is true if and only if an odd input is true, and since the inputs are
+        // sorted, if and only if an input is true.
+        let first = d.sequence.literals[0];
+        let first_implies_count = solver.log_implied_constraint_to_proof(
+            &[(1, !first)]
+                .into_iter()
+                .chain(input_terms.iter().copied())
+                .collect::<Vec<_>>(),
+            1,
+        );
+        let _ = solver.log_implied_constraint_to_proof(
+            &[(num_inputs, first)]
+                .into_iter()
+                .chain(inputs.iter().map(|&input| (1, !input)))
+                .collect::<Vec<_>>(),
+            num_inputs,
+        );
+
+        let mut c = vec![first];
+        c.extend(fresh);
+        let mut at_least = vec![first_implies_count];
+        at_least.extend(
+            definitions
+                .iter()
+                .map(|definition| definition.literal_implies_constraint),
+        );
+
+        if reuse_last {
+            // The last output implies that all even inputs are true, and since the inputs are
+            // sorted, that all inputs are true.
+            let last = *e.sequence.literals.last().unwrap();
+            let last_implies_count = solver.log_implied_constraint_to_proof(
+                &[(num_inputs, !last)]
+                    .into_iter()
+                    .chain(input_terms.iter().copied())
+                    .collect::<Vec<_>>(),
+                num_inputs,
+            );
+            c.push(last);
+            at_least.push(last_implies_count);
+        }
 
-        c.insert(0, d[0]);
-        c.push(e[e.len() - 1]);
+        // Since the inputs are sorted, the number of true odd inputs is at least the number of
+        // true even inputs, and at most one more per input sequence.
+        let at_most_one_more_odd = Self::sum(
+            a.sorted
+                .iter()
+                .skip(1)
+                .step_by(2)
+                .chain(b.sorted.iter().skip(1).step_by(2)),
+            solver,
+        );
+        let at_least_as_many_odd = Self::sum(
+            a.sorted.iter().step_by(2).chain(b.sorted.iter().step_by(2)),
+            solver,
+        );
 
-        for i in 0..(n - 1) {
+        for i in 0..num_combinations {
             try_add_clause!(
                 self,
                 solver,
                 vec![
-                    (!d[i + 1]).get_true_predicate(),
-                    (!e[i]).get_true_predicate(),
+                    (!d.sequence.literals[i + 1]).get_true_predicate(),
+                    (!e.sequence.literals[i]).get_true_predicate(),
                     (c[2 * (i + 1)]).get_true_predicate()
+                ],
+                &[
+                    d.at_least[i + 1],
+                    e.at_least[i],
+                    constraint_implies_output(2 * (i + 1))
                 ]
             );
+
+            let odd_implies_count = solver.log_linear_combination_to_proof(
+                &[(2, d.at_least[i + 1])]
+                    .into_iter()
+                    .chain(at_most_one_more_odd.map(|step| (1, step)))
+                    .chain([(1, constraint_implies_output(2 * (i + 1) - 1))])
+                    .collect::<Vec<_>>(),
+                1,
+            );
             try_add_clause!(
                 self,
                 solver,
                 vec![
-                    (!d[i + 1]).get_true_predicate(),
+                    (!d.sequence.literals[i + 1]).get_true_predicate(),
                     (c[2 * (i + 1) - 1]).get_true_predicate()
-                ]
+                ],
+                &[odd_implies_count]
+            );
+
+            let even_implies_count = solver.log_linear_combination_to_proof(
+                &[
+                    (2, e.at_least[i]),
+                    (1, at_least_as_many_odd.expect("there is an even input")),
+                    (1, constraint_implies_output(2 * (i + 1) - 1)),
+                ],
+                1,
             );
             try_add_clause!(
                 self,
                 solver,
                 vec![
-                    (!e[i]).get_true_predicate(),
+                    (!e.sequence.literals[i]).get_true_predicate(),
                     (c[2 * (i + 1) - 1]).get_true_predicate()
-                ]
+                ],
+                &[even_implies_count]
             );
         }
 
-        Some(c)
+        // The first and last output are sorted since the inputs are sorted, the fresh outputs are
+        // sorted by their definitions.
+        let mut sorted = vec![solver.log_implied_constraint_to_proof(&[(1, !c[1]), (1, c[0])], 1)];
+        for index in 1..num_fresh {
+            let divisor = (index as u64 + 2).max(num_inputs - index as u64);
+            sorted.push(solver.log_linear_combination_to_proof(
+                &[
+                    (1, at_least[index + 1]),
+                    (1, constraint_implies_output(index)),
+                ],
+                divisor,
+            ));
+        }
+        if reuse_last {
+            let last = c.len() - 1;
+            sorted.push(
+                solver.log_implied_constraint_to_proof(&[(1, !c[last]), (1, c[last - 1])], 1),
+            );
+        }
+
+        Some(Outputs {
+            sequence: SortedSequence {
+                literals: c,
+                sorted,
+            },
+            at_least,
+        })
+    }
+
+    /// Logs the sum of the constraints derived by the given proof steps, if there are any.
+    fn sum<'a>(
+        steps: impl Iterator<Item = &'a NonZeroU64>,
+        solver: &mut Solver,
+    ) -> Option<NonZeroU64> {
+        let terms = steps.map(|&step| (1, step)).collect::<Vec<_>>();
+        (!terms.is_empty()).then(|| solver.log_linear_combination_to_proof(&terms, 1))
     }
 
-    fn card(&mut self, a: &[Literal], k: u64, solver: &mut Solver) -> Option<Vec<Literal>> {
+    fn card(&mut self, a: &[Literal], k: u64, solver: &mut Solver) -> Option<Outputs> {
         let n = a.len() as u64;
         let m = n / k;
         pumpkin_assert_eq_simple!(n, m * k);
@@ -326,10 +521,119 @@ impl CardinalityNetworkEncoder {
         let d = self.card(&a[..k as usize], k, solver)?;
         let d_prime = self.card(&a[k as usize..], k, solver)?;
 
-        let mut c = self.s_merge(&d, &d_prime, solver)?;
-        let _ = c.remove(c.len() - 1);
+        let mut c = self.s_merge(&d.sequence, &d_prime.sequence, solver)?;
+        c.remove_last();
+
+        Some(c.count_sorter_inputs(&d, &d_prime, solver))
+    }
+}
+
+/// A sequence of literals which is sorted in decreasing order, i.e. every literal implies the
+/// literals before it.
+#[derive(Debug, Default)]
+struct SortedSequence {
+    literals: Vec<Literal>,
+    /// The proof steps which derive `literals[j + 1] -> literals[j]`.
+    sorted: Vec<NonZeroU64>,
+}
+
+impl SortedSequence {
+    fn single(literal: Literal) -> SortedSequence {
+        SortedSequence {
+            literals: vec![literal],
+            sorted: vec![],
+        }
+    }
+
+    fn odd_literals(&self, solver: &mut Solver) -> SortedSequence {
+        // Note: in the literature, sequences start at 1, hence the odd literals
+        // step by two but start at the first literal.
+        self.every_other_literal(0, solver)
+    }
 
-        Some(c)
+    fn even_literals(&self, solver: &mut Solver) -> SortedSequence {
+        // Note: in the literature, sequences start at 1, hence the even literals
+        // skip the first and then step by two.
+        self.every_other_literal(1, solver)
+    }
+
+    fn every_other_literal(&self, first: usize, solver: &mut Solver) -> SortedSequence {
+        // Literal `j + 2` implies literal `j + 1`, which implies literal `j`.
+        let sorted = (first..self.literals.len().saturating_sub(2))
+            .step_by(2)
+            .map(|j| {
+                solver.log_linear_combination_to_proof(
+                    &[(1, self.sorted[j]), (1, self.sorted[j + 1])],
+                    1,
+                )
+            })
+            .collect();
+
+        SortedSequence {
+            literals: self
+                .literals
+                .iter()
+                .copied()
+                .skip(first)
+                .step_by(2)
+                .collect(),
+            sorted,
+        }
+    }
+}
+
+/// The outputs of a merger or a sorter, which count its true inputs.
+#[derive(Debug, Default)]
+struct Outputs {
+    sequence: SortedSequence,
+    /// The proof steps which derive that if output `j` is true, then at least `j + 1` inputs are
+    /// true.
+    at_least: Vec<NonZeroU64>,
+}
+
+impl Outputs {
+    fn remove_last(&mut self) {
+        let _ = self.sequence.literals.pop();
+        let _ = self.sequence.sorted.pop();
+        let _ = self.at_least.pop();
+    }
+
+    /// Derives that the number of true outputs is at most the number of true inputs.
+    fn count(&self, solver: &mut Solver) -> NonZeroU64 {
+        // If the first `j` outputs count at most the true inputs, then so do the first `j + 1`
+        // outputs, which is derived by dividing `j` times the former plus the fact that output `j`
+        // implies `j + 1` true inputs by `j + 1`.
+        self.at_least
+            .iter()
+            .enumerate()
+            .skip(1)
+            .fold(self.at_least[0], |count, (j, &at_least)| {
+                solver.log_linear_combination_to_proof(
+                    &[(j as u64, count), (1, at_least)],
+                    j as u64 + 1,
+                )
+            })
+    }
+
+    /// Expresses the outputs of the merger of the outputs of the sorters `d` and `d_prime` in
+    /// terms of the inputs of those sorters.
+    fn count_sorter_inputs(self, d: &Outputs, d_prime: &Outputs, solver: &mut Solver) -> Outputs {
+        let counts = [d.count(solver), d_prime.count(solver)];
+        let at_least = self
+            .at_least
+            .iter()
+            .map(|&at_least| {
+                solver.log_linear_combination_to_proof(
+                    &[(1, at_least), (1, counts[0]), (1, counts[1])],
+                    1,
+                )
+            })
+            .collect();
+
+        Outputs {
+            sequence: self.sequence,
+            at_least,
+        }
     }
 }
 
@@ -357,18 +661,6 @@ fn round_up_to_multiple(k: u64, multiple: u64) -> u64 {
     }
 }
 
-fn odd_literals(lits: &[Literal]) -> Vec<Literal> {
-    // Note: in the literature, sequences start at 1, hence the odd literals
-    // step by two but start at the first literal.
-    lits.iter().copied().step_by(2).collect::<Vec<_>>()
-}
-
-fn even_literals(lits: &[Literal]) -> Vec<Literal> {
-    // Note: in the literature, sequences start at 1, hence the even literals
-    // skip the first and then step by two.
-    lits.iter().copied().skip(1).step_by(2).collect::<Vec<_>>()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -376,10 +668,10 @@ mod tests {
     #[test]
     fn test_cardinality_constraint_no_input_literals() {
         let mut solver = Solver::default();
-        let mut ub =
-            CardinalityNetworkEncoder::new(vec![], 0, &mut solver).expect("valid encoding");
+        let mut ub = CardinalityNetworkEncoder::new(vec![], 1, 0, NonZeroU64::MIN, &mut solver)
+            .expect("valid encoding");
 
-        ub.strengthen_at_most_k(0, &mut solver)
+        ub.strengthen_at_most_k(0, NonZeroU64::MIN, &mut solver)
             .expect("should not fail");
     }
 
@@ -388,7 +680,7 @@ mod tests {
         let mut solver = Solver::default();
         let xs = create_variables(&mut solver, 2);
 
-        let _ = CardinalityNetworkEncoder::new(xs.clone(), 1, &mut solver);
+        let _ = CardinalityNetworkEncoder::new(xs.clone(), 1, 1, NonZeroU64::MIN, &mut solver);
 
         assert!(solver.add_clause([xs[0].get_true_predicate()]).is_ok());
         assert!(solver.add_clause([xs[1].get_true_predicate()]).is_err());
@@ -399,7 +691,8 @@ mod tests {
         let mut solver = Solver::default();
         let xs = create_variables(&mut solver, 3);
 
-        let _ = CardinalityNetworkEncoder::new(xs.clone(), 2, &mut solver).expect("valid encoding");
+        let _ = CardinalityNetworkEncoder::new(xs.clone(), 1, 2, NonZeroU64::MIN, &mut solver)
+            .expect("valid encoding");
 
         assert!(solver.add_clause([xs[0].get_true_predicate()]).is_ok());
         assert!(solver.add_clause([xs[1].get_true_predicate()]).is_ok());
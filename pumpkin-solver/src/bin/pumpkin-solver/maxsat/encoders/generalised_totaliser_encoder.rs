@@ -1,4 +1,7 @@
+use std::num::NonZeroU64;
+
 use log::debug;
+use pumpkin_solver::proof::Reification;
 use pumpkin_solver::pumpkin_assert_moderate;
 use pumpkin_solver::pumpkin_assert_simple;
 use pumpkin_solver::variables::Literal;
@@ -11,6 +14,10 @@ use crate::Solver;
 
 /// Implementation of the generalized totalizer encoding for pseudo-boolean constraints.
 ///
+/// In a proof, each literal which represents a partial sum is defined as the reification of the
+/// partial sum over the input literals, i.e. `l_w <-> \sum w_i x_i >= w`. The clauses of the
+/// encoding follow from these definitions and the bound on the objective.
+///
 /// # Bibliography
 /// \[1] "Generalized totalizer encoding for pseudo-boolean constraints.", Joshi Saurabh, Ruben
 /// Martins, Vasco Manquinho; CP '15
@@ -19,12 +26,15 @@ pub(crate) struct GeneralisedTotaliserEncoder {
     index_last_added_weighted_literal: usize,
     layers: Vec<Layer>,
     num_clauses_added: usize,
+    /// The proof steps which define the partial sum literals.
+    reifications: HashMap<Literal, Reification>,
 }
 
 impl PseudoBooleanConstraintEncoderInterface for GeneralisedTotaliserEncoder {
     fn encode_at_most_k(
         mut weighted_literals: Vec<WeightedLiteral>,
         k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) -> Result<Self, EncodingError> {
         // a good heuristic is to sort the literals by weight with a stable ordering
@@ -35,8 +45,9 @@ impl PseudoBooleanConstraintEncoderInterface for GeneralisedTotaliserEncoder {
             index_last_added_weighted_literal: usize::MAX,
             layers: vec![],
             num_clauses_added: 0,
+            reifications: HashMap::default(),
         };
-        encoder.encode_at_most_k_standard_case(weighted_literals, k, solver);
+        encoder.encode_at_most_k_standard_case(weighted_literals, k, objective_bound, solver);
 
         Ok(encoder)
     }
@@ -44,6 +55,7 @@ impl PseudoBooleanConstraintEncoderInterface for GeneralisedTotaliserEncoder {
     fn strengthen_at_most_k(
         &mut self,
         new_k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) -> Result<(), EncodingError> {
         pumpkin_assert_simple!(self.index_last_added_weighted_literal > 0);
@@ -64,8 +76,17 @@ impl PseudoBooleanConstraintEncoderInterface for GeneralisedTotaliserEncoder {
                 self.num_clauses_added += 1;
                 self.index_last_added_weighted_literal = i;
 
+                let antecedents = self
+                    .literal_implies_sum(weighted_literals[i].literal)
+                    .into_iter()
+                    .chain([objective_bound])
+                    .collect::<Vec<_>>();
+
                 if solver
-                    .add_clause([(!weighted_literals[i].literal).get_true_predicate()])
+                    .add_implied_clause(
+                        [(!weighted_literals[i].literal).get_true_predicate()],
+                        &antecedents,
+                    )
                     .is_err()
                 {
                     return Err(EncodingError::CannotStrengthen);
@@ -94,10 +115,24 @@ impl GeneralisedTotaliserEncoder {
         log_value
     }
 
+    /// Get the proof step which derives that the literal implies its partial sum, if the literal
+    /// is not an input literal.
+    fn literal_implies_sum(&self, literal: Literal) -> Option<NonZeroU64> {
+        self.reifications
+            .get(&literal)
+            .map(|reification| reification.literal_implies_constraint)
+    }
+
+    /// Get the proof step which derives that the partial sum implies the literal.
+    fn sum_implies_literal(&self, literal: Literal) -> NonZeroU64 {
+        self.reifications[&literal].constraint_implies_literal
+    }
+
     fn encode_at_most_k_standard_case(
         &mut self,
         weighted_literals: Vec<WeightedLiteral>,
         k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) {
         // the generalised totaliser encoding can be visualised as a binary tree
@@ -116,6 +151,7 @@ impl GeneralisedTotaliserEncoder {
             .iter()
             .map(|wl| vec![*wl])
             .collect::<Vec<Vec<WeightedLiteral>>>();
+        self.layers[0].leaves = self.layers[0].nodes.clone();
 
         // these are to be used in the loop below
         //  will be reused to avoid allocating each iteration
@@ -177,11 +213,25 @@ impl GeneralisedTotaliserEncoder {
                 partial_sums.sort();
                 partial_sums.dedup();
 
+                // the partial sums are over the input literals in the leaves below both nodes
+                let next_layer_leaves = [
+                    self.layers[index_current_layer].leaves[index_node1].as_slice(),
+                    self.layers[index_current_layer].leaves[index_node2].as_slice(),
+                ]
+                .concat();
+                let leaf_terms = next_layer_leaves
+                    .iter()
+                    .map(|wl| (wl.weight, wl.literal))
+                    .collect::<Vec<_>>();
+
                 value_to_literal_map.clear();
                 //  then create the variables, one for each partial sum, and register the mapping
                 // between the partial sum value and the corresponding literal
                 for partial_sum in &partial_sums {
                     let literal = solver.new_literal();
+                    let reification =
+                        solver.log_reification_to_proof(literal, &leaf_terms, *partial_sum);
+                    let _ = self.reifications.insert(literal, reification);
                     let _ = value_to_literal_map.insert(*partial_sum, literal);
                     next_layer_node.push(WeightedLiteral {
                         literal,
@@ -194,24 +244,40 @@ impl GeneralisedTotaliserEncoder {
                 //  define sums of one literal from node1
                 //  node1[weight] -> next_layer_node[weight]
                 for weighted_literal in &self.layers[index_current_layer].nodes[index_node1] {
+                    let sum_literal = *value_to_literal_map.get(&weighted_literal.weight).unwrap();
+                    let antecedents = self
+                        .literal_implies_sum(weighted_literal.literal)
+                        .into_iter()
+                        .chain([self.sum_implies_literal(sum_literal)])
+                        .collect::<Vec<_>>();
                     solver
-                        .add_clause(vec![
-                            (!weighted_literal.literal).get_true_predicate(),
-                            (*value_to_literal_map.get(&weighted_literal.weight).unwrap())
-                                .get_true_predicate(),
-                        ])
+                        .add_implied_clause(
+                            vec![
+                                (!weighted_literal.literal).get_true_predicate(),
+                                sum_literal.get_true_predicate(),
+                            ],
+                            &antecedents,
+                        )
                         .expect("Adding encoding clause should not lead to conflict");
                     self.num_clauses_added += 1;
                 }
                 //  define sums of one literal from node2
                 //  node2[weight] -> next_layer_node[weight]
                 for weighted_literal in &self.layers[index_current_layer].nodes[index_node2] {
+                    let sum_literal = *value_to_literal_map.get(&weighted_literal.weight).unwrap();
+                    let antecedents = self
+                        .literal_implies_sum(weighted_literal.literal)
+                        .into_iter()
+                        .chain([self.sum_implies_literal(sum_literal)])
+                        .collect::<Vec<_>>();
                     solver
-                        .add_clause(vec![
-                            (!weighted_literal.literal).get_true_predicate(),
-                            (*value_to_literal_map.get(&weighted_literal.weight).unwrap())
-                                .get_true_predicate(),
-                        ])
+                        .add_implied_clause(
+                            vec![
+                                (!weighted_literal.literal).get_true_predicate(),
+                                sum_literal.get_true_predicate(),
+                            ],
+                            &antecedents,
+                        )
                         .expect("Adding encoding clause should not lead to conflict");
                     self.num_clauses_added += 1;
                 }
@@ -221,14 +287,24 @@ impl GeneralisedTotaliserEncoder {
                 for wl1 in &self.layers[index_current_layer].nodes[index_node1] {
                     for wl2 in &self.layers[index_current_layer].nodes[index_node2] {
                         let combined_weight = wl1.weight + wl2.weight;
+                        let antecedents = self
+                            .literal_implies_sum(wl1.literal)
+                            .into_iter()
+                            .chain(self.literal_implies_sum(wl2.literal));
                         if combined_weight <= k {
+                            let sum_literal = *value_to_literal_map.get(&combined_weight).unwrap();
+                            let antecedents = antecedents
+                                .chain([self.sum_implies_literal(sum_literal)])
+                                .collect::<Vec<_>>();
                             solver
-                                .add_clause(vec![
-                                    (!wl1.literal).get_true_predicate(),
-                                    (!wl2.literal).get_true_predicate(),
-                                    (*value_to_literal_map.get(&combined_weight).unwrap())
-                                        .get_true_predicate(),
-                                ])
+                                .add_implied_clause(
+                                    vec![
+                                        (!wl1.literal).get_true_predicate(),
+                                        (!wl2.literal).get_true_predicate(),
+                                        sum_literal.get_true_predicate(),
+                                    ],
+                                    &antecedents,
+                                )
                                 .expect("Adding encoding clause should not lead to conflict");
                             self.num_clauses_added += 1;
                         // explicitly forbid the assignment of both literals
@@ -237,11 +313,16 @@ impl GeneralisedTotaliserEncoder {
                         //  todo check if these clauses are necessary, and see if the trade-off
                         // makes sense      I think it is necessary
                         } else {
+                            let antecedents =
+                                antecedents.chain([objective_bound]).collect::<Vec<_>>();
                             solver
-                                .add_clause(vec![
-                                    (!wl1.literal).get_true_predicate(),
-                                    (!wl2.literal).get_true_predicate(),
-                                ])
+                                .add_implied_clause(
+                                    vec![
+                                        (!wl1.literal).get_true_predicate(),
+                                        (!wl2.literal).get_true_predicate(),
+                                    ],
+                                    &antecedents,
+                                )
                                 .expect("Adding encoding clause should not lead to conflict");
                             self.num_clauses_added += 1;
                         }
@@ -251,6 +332,9 @@ impl GeneralisedTotaliserEncoder {
                 self.layers[index_current_layer + 1]
                     .nodes
                     .push(next_layer_node);
+                self.layers[index_current_layer + 1]
+                    .leaves
+                    .push(next_layer_leaves);
             } // node merging done
 
             // copy over the odd-numbered node that will not merge this round
@@ -268,6 +352,15 @@ impl GeneralisedTotaliserEncoder {
                 self.layers[index_current_layer + 1]
                     .nodes
                     .push(unmerged_node);
+
+                let unmerged_leaves = self.layers[index_current_layer]
+                    .leaves
+                    .last()
+                    .unwrap()
+                    .clone();
+                self.layers[index_current_layer + 1]
+                    .leaves
+                    .push(unmerged_leaves);
             }
         }
 
@@ -292,10 +385,15 @@ impl GeneralisedTotaliserEncoder {
 #[derive(Debug)]
 struct Layer {
     nodes: Vec<Vec<WeightedLiteral>>,
+    /// For every node, the input literals of which the literals in the node are partial sums.
+    leaves: Vec<Vec<WeightedLiteral>>,
 }
 
 impl Layer {
     fn new() -> Layer {
-        Layer { nodes: vec![] }
+        Layer {
+            nodes: vec![],
+            leaves: vec![],
+        }
     }
 }
@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::num::NonZeroU64;
 use std::time::Instant;
 
 use clap::ValueEnum;
@@ -50,9 +51,13 @@ pub(crate) trait PseudoBooleanConstraintEncoderInterface {
     ///
     /// Recall that this trait is used in combination with [`PseudoBooleanConstraintEncoder`],
     /// which ensures the above conditions are met
+    ///
+    /// The `objective_bound` is the proof step which derives the bound that is encoded, and can
+    /// be used to justify the clauses of the encoding in the proof.
     fn encode_at_most_k(
         weighted_literals: Vec<WeightedLiteral>,
         k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) -> Result<Self, EncodingError>
     where
@@ -62,7 +67,12 @@ pub(crate) trait PseudoBooleanConstraintEncoderInterface {
     /// Assumes the k is smaller than the previous k, and that
     /// [`PseudoBooleanConstraintEncoderInterface::encode_at_most_k`] has been
     /// called some time before
-    fn strengthen_at_most_k(&mut self, k: u64, solver: &mut Solver) -> Result<(), EncodingError>;
+    fn strengthen_at_most_k(
+        &mut self,
+        k: u64,
+        objective_bound: NonZeroU64,
+        solver: &mut Solver,
+    ) -> Result<(), EncodingError>;
 }
 
 /// Specifies the type of pseudo-boolean encoding which is used by the
@@ -180,15 +190,18 @@ impl PseudoBooleanConstraintEncoder {
         weighted_literals
     }
 
+    /// Constrain the function to be at most `k`. The `objective_bound` is the proof step which
+    /// derives this bound, which is used to justify the encoding in the proof.
     pub(crate) fn constrain_at_most_k(
         &mut self,
         k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) -> Result<(), EncodingError> {
         match self.state {
             State::New(ref mut weighted_literals) => {
                 let literals = std::mem::take(weighted_literals);
-                self.create_encoding(literals, k, solver)?
+                self.create_encoding(literals, k, objective_bound, solver)?
             }
             State::Encoded(ref mut encoder) => {
                 pumpkin_assert_simple!(
@@ -204,7 +217,7 @@ impl PseudoBooleanConstraintEncoder {
                     self.constant_term
                 );
 
-                encoder.strengthen_at_most_k(k - self.constant_term, solver)?;
+                encoder.strengthen_at_most_k(k - self.constant_term, objective_bound, solver)?;
             }
 
             State::Preprocessed(ref mut literals) => {
@@ -215,6 +228,7 @@ impl PseudoBooleanConstraintEncoder {
                     self.state = State::Encoded(Self::create_encoder(
                         literals,
                         k,
+                        objective_bound,
                         solver,
                         self.encoding_algorithm,
                     )?);
@@ -231,12 +245,13 @@ impl PseudoBooleanConstraintEncoder {
         &mut self,
         weighted_literals: Vec<WeightedLiteral>,
         initial_k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) -> Result<(), EncodingError> {
         let time_start = Instant::now();
 
         let preprocessed_weighted_literals =
-            self.initialise_and_preprocess(weighted_literals, initial_k, solver)?;
+            self.initialise_and_preprocess(weighted_literals, initial_k, objective_bound, solver)?;
 
         let sum_weight = preprocessed_weighted_literals
             .iter()
@@ -258,6 +273,7 @@ impl PseudoBooleanConstraintEncoder {
             self.state = State::Encoded(Self::create_encoder(
                 preprocessed_weighted_literals,
                 initial_k - self.constant_term,
+                objective_bound,
                 solver,
                 self.encoding_algorithm,
             )?);
@@ -277,6 +293,7 @@ impl PseudoBooleanConstraintEncoder {
         &mut self,
         weighted_literals: Vec<WeightedLiteral>,
         k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
     ) -> Result<Vec<WeightedLiteral>, EncodingError> {
         // preprocess the input before the initial encoding considering the following:
@@ -318,7 +335,10 @@ impl PseudoBooleanConstraintEncoder {
                 {
                     has_assigned = true;
 
-                    let result = solver.add_clause([(!term.literal).get_true_predicate()]);
+                    let result = solver.add_implied_clause(
+                        [(!term.literal).get_true_predicate()],
+                        &[objective_bound],
+                    );
                     if result.is_err() {
                         return Err(EncodingError::RootPropagationConflict);
                     }
@@ -345,18 +365,27 @@ impl PseudoBooleanConstraintEncoder {
     fn create_encoder(
         weighted_literals: Vec<WeightedLiteral>,
         k: u64,
+        objective_bound: NonZeroU64,
         solver: &mut Solver,
         encoding_algorithm: PseudoBooleanEncoding,
     ) -> Result<Box<dyn PseudoBooleanConstraintEncoderInterface>, EncodingError> {
         match encoding_algorithm {
             PseudoBooleanEncoding::GeneralizedTotalizer => {
-                let encoder =
-                    GeneralisedTotaliserEncoder::encode_at_most_k(weighted_literals, k, solver)?;
+                let encoder = GeneralisedTotaliserEncoder::encode_at_most_k(
+                    weighted_literals,
+                    k,
+                    objective_bound,
+                    solver,
+                )?;
                 Ok(Box::new(encoder))
             }
             PseudoBooleanEncoding::CardinalityNetwork => {
-                let encoder =
-                    CardinalityNetworkEncoder::encode_at_most_k(weighted_literals, k, solver)?;
+                let encoder = CardinalityNetworkEncoder::encode_at_most_k(
+                    weighted_literals,
+                    k,
+                    objective_bound,
+                    solver,
+                )?;
                 Ok(Box::new(encoder))
             }
         }
@@ -1,5 +1,7 @@
 use std::fs::File;
+use std::io::BufWriter;
 use std::path::Path;
+use std::path::PathBuf;
 use std::time::Duration;
 
 pub(crate) mod encoders;
@@ -22,32 +24,40 @@ pub(crate) fn wcnf_problem(
     time_limit: Option<Duration>,
     instance_path: impl AsRef<Path>,
     encoding: PseudoBooleanEncoding,
+    pseudo_boolean_instance_path: Option<PathBuf>,
 ) -> Result<(), PumpkinError> {
     let instance_file = File::open(instance_path)?;
+    let sink = parse_wcnf::<SolverDimacsSink>(
+        instance_file,
+        SolverArgs::new(solver_options).record_clauses(pseudo_boolean_instance_path.is_some()),
+    )?;
+    if let Some(path) = pseudo_boolean_instance_path {
+        sink.write_pseudo_boolean_instance(BufWriter::new(File::create(path)?))?;
+    }
     let SolverDimacsSink {
         solver,
         objective,
         variables,
-    } = parse_wcnf::<SolverDimacsSink>(instance_file, SolverArgs::new(solver_options))?;
+        ..
+    } = sink;
+    let num_variables = variables.len();
 
     let brancher = solver.default_brancher();
     let mut termination = time_limit.map(TimeBudget::starting_now);
 
-    let mut solver = OptimisationSolver::new(solver, objective, LinearSearch::new(encoding));
+    let mut solver =
+        OptimisationSolver::new(solver, objective, LinearSearch::new(encoding, variables));
 
     match solver.solve(&mut termination, brancher) {
         MaxSatOptimisationResult::Optimal { solution } => {
             println!("s OPTIMUM FOUND");
-            println!(
-                "v {}",
-                stringify_solution(&solution, variables.len(), false)
-            );
+            println!("v {}", stringify_solution(&solution, num_variables, false));
         }
         MaxSatOptimisationResult::Satisfiable { best_solution } => {
             println!("s SATISFIABLE");
             println!(
                 "v {}",
-                stringify_solution(&best_solution, variables.len(), false)
+                stringify_solution(&best_solution, num_variables, false)
             );
         }
         MaxSatOptimisationResult::Infeasible => {
@@ -1,9 +1,13 @@
+use std::num::NonZeroU64;
+
 use log::info;
 use pumpkin_solver::asserts::pumpkin_assert_moderate;
 use pumpkin_solver::branching::Brancher;
+use pumpkin_solver::results::ProblemSolution;
 use pumpkin_solver::results::SatisfactionResult;
 use pumpkin_solver::results::Solution;
 use pumpkin_solver::termination::TerminationCondition;
+use pumpkin_solver::variables::Literal;
 use pumpkin_solver::Function;
 use pumpkin_solver::Solver;
 
@@ -12,14 +16,19 @@ use super::stopwatch::Stopwatch;
 use crate::maxsat::encoders::PseudoBooleanConstraintEncoder;
 use crate::maxsat::encoders::PseudoBooleanEncoding;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub(crate) struct LinearSearch {
     encoding: PseudoBooleanEncoding,
+    /// The variables of the instance, which are assigned by the solutions logged to the proof.
+    variables: Vec<Literal>,
 }
 
 impl LinearSearch {
-    pub(crate) fn new(encoding: PseudoBooleanEncoding) -> LinearSearch {
-        LinearSearch { encoding }
+    pub(crate) fn new(encoding: PseudoBooleanEncoding, variables: Vec<Literal>) -> LinearSearch {
+        LinearSearch {
+            encoding,
+            variables,
+        }
     }
 
     pub(crate) fn solve(
@@ -35,6 +44,12 @@ impl LinearSearch {
 
         let mut best_solution: Solution = initial_solution;
         let mut best_objective_value = objective_function.evaluate_assignment(&best_solution);
+        let mut objective_bound = self.log_solution(
+            solver,
+            objective_function,
+            &best_solution,
+            best_objective_value,
+        );
 
         solver.log_statistics_with_objective(best_objective_value as i64);
         println!("o {best_objective_value}");
@@ -53,18 +68,25 @@ impl LinearSearch {
         loop {
             if best_objective_value == objective_function.get_constant_term() {
                 solver.log_statistics_with_objective(best_objective_value as i64);
+                // No solution can improve on the logged solution, which the proof concludes as the
+                // unsatisfiability of the objective bound.
+                solver.conclude_proof_unsat();
                 return MaxSatOptimisationResult::Optimal {
                     solution: best_solution,
                 };
             }
 
-            let encoding_status =
-                upper_bound_encoder.constrain_at_most_k(best_objective_value - 1, solver);
+            let encoding_status = upper_bound_encoder.constrain_at_most_k(
+                best_objective_value - 1,
+                objective_bound,
+                solver,
+            );
 
             // in case some cases infeasibility can be detected while constraining the upper bound
             //  meaning the current best solution is optimal
             if encoding_status.is_err() {
                 solver.log_statistics_with_objective(best_objective_value as i64);
+                solver.conclude_proof_unsat();
                 return MaxSatOptimisationResult::Optimal {
                     solution: best_solution,
                 };
@@ -86,6 +108,12 @@ impl LinearSearch {
 
                     best_objective_value = new_objective_value;
                     best_solution = solution;
+                    objective_bound = self.log_solution(
+                        solver,
+                        objective_function,
+                        &best_solution,
+                        best_objective_value,
+                    );
 
                     solver.log_statistics_with_objective(best_objective_value as i64);
 
@@ -111,4 +139,41 @@ impl LinearSearch {
             }
         }
     }
+
+    /// Log the solution to the proof, after which only better solutions are sought. Returns the
+    /// id of the proof step which derives that the objective value is smaller than
+    /// `objective_value`.
+    fn log_solution(
+        &self,
+        solver: &mut Solver,
+        objective_function: &Function,
+        solution: &Solution,
+        objective_value: u64,
+    ) -> NonZeroU64 {
+        let mut assignment = self
+            .variables
+            .iter()
+            .chain(
+                objective_function
+                    .get_literal_terms()
+                    .map(|(literal, _)| literal),
+            )
+            .map(|&literal| {
+                if solution.get_literal_value(literal) {
+                    literal
+                } else {
+                    !literal
+                }
+            })
+            .collect::<Vec<_>>();
+        // The objective literals may be variables of the instance as well.
+        assignment.sort_by_key(|literal| literal.get_true_predicate().get_domain().id);
+        assignment.dedup();
+
+        // The objective in the proof does not contain the constant term.
+        solver.log_improving_solution_to_proof(
+            assignment,
+            objective_value - objective_function.get_constant_term(),
+        )
+    }
 }
@@ -15,6 +15,7 @@
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Read;
+use std::io::Write;
 use std::num::NonZeroI32;
 use std::num::NonZeroU32;
 use std::str::FromStr;
@@ -434,6 +435,9 @@ pub(crate) struct SolverDimacsSink {
     pub(crate) solver: Solver,
     pub(crate) objective: Function,
     pub(crate) variables: Vec<Literal>,
+    /// The clauses of the formula, in which the soft clauses are relaxed by the literal that
+    /// occurs in the objective. Only recorded if requested in the [`SolverArgs`].
+    clauses: Option<Vec<Vec<Literal>>>,
 }
 
 /// The arguments to construct a [`Solver`]. Forwarded to
@@ -441,11 +445,22 @@ pub(crate) struct SolverDimacsSink {
 pub(crate) struct SolverArgs {
     // todo: add back the learning options
     solver_options: SolverOptions,
+    record_clauses: bool,
 }
 
 impl SolverArgs {
     pub(crate) fn new(solver_options: SolverOptions) -> SolverArgs {
-        SolverArgs { solver_options }
+        SolverArgs {
+            solver_options,
+            record_clauses: false,
+        }
+    }
+
+    /// Record the clauses of the formula, such that the pseudo-Boolean translation of the formula
+    /// can be written (see [`SolverDimacsSink::write_pseudo_boolean_instance`]).
+    pub(crate) fn record_clauses(mut self, record_clauses: bool) -> SolverArgs {
+        self.record_clauses = record_clauses;
+        self
     }
 }
 
@@ -462,13 +477,82 @@ impl SolverDimacsSink {
             })
             .collect()
     }
+
+    /// Write the pseudo-Boolean translation of the formula in the OPB format, which is the
+    /// instance against which a VeriPB proof is checked. The variables are named `x<id>` after
+    /// the ids of their domains.
+    ///
+    /// Does nothing if the clauses are not recorded.
+    pub(crate) fn write_pseudo_boolean_instance(
+        &self,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        let Some(clauses) = self.clauses.as_ref() else {
+            return Ok(());
+        };
+
+        let num_variables = clauses
+            .iter()
+            .flatten()
+            .chain(
+                self.objective
+                    .get_literal_terms()
+                    .map(|(literal, _)| literal),
+            )
+            .chain(self.variables.iter())
+            .map(|literal| literal.get_true_predicate().get_domain().id)
+            .max()
+            .unwrap_or(0);
+
+        writeln!(
+            writer,
+            "* #variable= {num_variables} #constraint= {}",
+            clauses.len()
+        )?;
+
+        let mut objective_terms = self.objective.get_literal_terms().collect::<Vec<_>>();
+        if !objective_terms.is_empty() {
+            objective_terms
+                .sort_by_key(|(literal, _)| literal.get_true_predicate().get_domain().id);
+
+            write!(writer, "min:")?;
+            for (literal, weight) in objective_terms {
+                write!(writer, " {weight} {}", opb_literal(*literal))?;
+            }
+            writeln!(writer, " ;")?;
+        }
+
+        for clause in clauses {
+            for &literal in clause {
+                write!(writer, "1 {} ", opb_literal(literal))?;
+            }
+            writeln!(writer, ">= 1 ;")?;
+        }
+
+        writer.flush()
+    }
+}
+
+/// Get the name of the literal in the OPB format.
+fn opb_literal(literal: Literal) -> String {
+    let predicate = literal.get_true_predicate();
+    let prefix = if predicate.is_lower_bound_predicate() {
+        ""
+    } else {
+        "~"
+    };
+
+    format!("{prefix}x{}", predicate.get_domain().id)
 }
 
 impl DimacsSink for SolverDimacsSink {
     type ConstructorArgs = SolverArgs;
 
     fn empty(args: Self::ConstructorArgs, num_variables: usize) -> Self {
-        let SolverArgs { solver_options } = args;
+        let SolverArgs {
+            solver_options,
+            record_clauses,
+        } = args;
 
         let mut solver = Solver::with_options(solver_options);
         let variables = (0..num_variables)
@@ -479,15 +563,19 @@ impl DimacsSink for SolverDimacsSink {
             solver,
             objective: Function::default(),
             variables,
+            clauses: record_clauses.then(Vec::new),
         }
     }
 
     fn add_hard_clause(&mut self, clause: &[NonZeroI32]) {
-        let mapped = self
-            .mapped_clause(clause)
-            .into_iter()
-            .map(|literal| literal.get_true_predicate());
-        let _ = self.solver.add_clause(mapped);
+        let mapped = self.mapped_clause(clause);
+        let _ = self
+            .solver
+            .add_clause(mapped.iter().map(|literal| literal.get_true_predicate()));
+
+        if let Some(clauses) = self.clauses.as_mut() {
+            clauses.push(mapped);
+        }
     }
 
     fn add_soft_clause(&mut self, weight: NonZeroU32, clause: &[NonZeroI32]) {
@@ -509,11 +597,13 @@ impl DimacsSink for SolverDimacsSink {
             // General case, a soft clause with more than one literal.
             let soft_literal = self.solver.new_literal();
             clause.push(soft_literal);
-            let _ = self.solver.add_clause(
-                clause
-                    .into_iter()
-                    .map(|literal| literal.get_true_predicate()),
-            );
+            let _ = self
+                .solver
+                .add_clause(clause.iter().map(|literal| literal.get_true_predicate()));
+
+            if let Some(clauses) = self.clauses.as_mut() {
+                clauses.push(clause);
+            }
 
             self.objective
                 .add_weighted_literal(!soft_literal, weight.get().into());
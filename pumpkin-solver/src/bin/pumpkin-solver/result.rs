@@ -18,8 +18,6 @@ pub(crate) enum PumpkinError {
     InvalidDimacs(#[from] DimacsParseError),
     #[error("Failed to run flatzinc model, more details: {0}")]
     FlatZinc(#[from] FlatZincError),
-    #[error("Proof generation for {0} is not supported.")]
-    ProofGenerationNotSupported(String),
 }
 
 impl PumpkinError {
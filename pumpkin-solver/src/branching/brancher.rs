@@ -17,6 +17,9 @@ use crate::create_statistics_struct;
 use crate::engine::predicates::predicate::Predicate;
 use crate::engine::variables::DomainId;
 use crate::engine::Assignments;
+use crate::engine::SearchMode;
+#[cfg(doc)]
+use crate::engine::SearchModeStrategy;
 #[cfg(doc)]
 use crate::results::solution_iterator::SolutionIterator;
 use crate::statistics::StatisticLogger;
@@ -93,6 +96,15 @@ pub trait Brancher {
     /// [`Self::subscribe_to_events`]
     fn on_restart(&mut self) {}
 
+    /// This method is called whenever the solver switches between search modes (see
+    /// [`SearchModeStrategy::Alternating`]); this can be used to adjust how the phase of decisions
+    /// is determined in the different modes. If the solver can be in [`SearchMode::Stable`], this
+    /// method is also called at the start of every solve with the mode in which the search starts.
+    ///
+    /// To receive information about this event, use [`BrancherEvent::SearchModeSwitch`] in
+    /// [`Self::subscribe_to_events`]
+    fn on_search_mode_switch(&mut self, _search_mode: SearchMode) {}
+
     /// Called after backtracking.
     /// Used to reset internal data structures to account for the backtrack.
     ///
@@ -140,4 +152,6 @@ pub enum BrancherEvent {
     Restart,
     /// Event which is called with the new state after a backtrack has occurred
     Synchronise,
+    /// Event for when the solver switches between search modes
+    SearchModeSwitch,
 }
@@ -8,6 +8,7 @@ use crate::branching::SelectionContext;
 use crate::engine::predicates::predicate::Predicate;
 use crate::engine::variables::DomainId;
 use crate::engine::Assignments;
+use crate::engine::SearchMode;
 use crate::statistics::StatisticLogger;
 use crate::DefaultBrancher;
 use crate::Solver;
@@ -177,6 +178,13 @@ impl<OtherBrancher: Brancher> Brancher for AlternatingBrancher<OtherBrancher> {
         }
     }
 
+    fn on_search_mode_switch(&mut self, search_mode: SearchMode) {
+        self.default_brancher.on_search_mode_switch(search_mode);
+        if !self.will_always_use_default() {
+            self.other_brancher.on_search_mode_switch(search_mode);
+        }
+    }
+
     fn is_restart_pointless(&mut self) -> bool {
         match self.strategy {
             AlternatingStrategy::EveryRestart => {
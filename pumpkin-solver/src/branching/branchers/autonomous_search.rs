@@ -14,6 +14,7 @@ use crate::containers::StorageKey;
 use crate::create_statistics_struct;
 use crate::engine::predicates::predicate::Predicate;
 use crate::engine::Assignments;
+use crate::engine::SearchMode;
 use crate::results::Solution;
 use crate::statistics::Statistic;
 use crate::statistics::StatisticLogger;
@@ -84,6 +85,9 @@ pub struct AutonomousSearch<BackupBrancher> {
     decay_factor: f64,
     /// Contains the best-known solution or [`None`] if no solution has been found.
     best_known_solution: Option<Solution>,
+    /// Whether the polarity is determined by the best-known solution; if the solver can be in
+    /// [`SearchMode::Stable`], this is only the case in that mode.
+    use_solution_phase: bool,
     /// If the heap does not contain any more unfixed predicates then this backup_brancher will be
     /// used instead.
     backup_brancher: BackupBrancher,
@@ -121,6 +125,7 @@ impl DefaultBrancher {
             max_threshold: DEFAULT_VSIDS_MAX_THRESHOLD,
             decay_factor: DEFAULT_VSIDS_DECAY_FACTOR,
             best_known_solution: None,
+            use_solution_phase: true,
             backup_brancher: IndependentVariableValueBrancher::new(
                 RandomSelector::new(assignments.get_domains()),
                 RandomSplitter,
@@ -145,6 +150,7 @@ impl<BackupSelector> AutonomousSearch<BackupSelector> {
             max_threshold: DEFAULT_VSIDS_MAX_THRESHOLD,
             decay_factor: DEFAULT_VSIDS_DECAY_FACTOR,
             best_known_solution: None,
+            use_solution_phase: true,
             backup_brancher,
             statistics: Default::default(),
         }
@@ -224,7 +230,11 @@ impl<BackupSelector> AutonomousSearch<BackupSelector> {
     /// For example, if we have found the solution `x = 5` then the call `determine_polarity([x >=
     /// 3])` would return `true`.
     fn determine_polarity(&self, predicate: Predicate) -> Predicate {
-        if let Some(solution) = &self.best_known_solution {
+        if let Some(solution) = self
+            .best_known_solution
+            .as_ref()
+            .filter(|_| self.use_solution_phase)
+        {
             // We have a solution
             if !solution.contains_domain_id(predicate.get_domain()) {
                 // This can occur if an encoding is used
@@ -320,6 +330,13 @@ impl<BackupBrancher: Brancher> Brancher for AutonomousSearch<BackupBrancher> {
         self.backup_brancher.on_unassign_integer(variable, value)
     }
 
+    fn on_search_mode_switch(&mut self, search_mode: SearchMode) {
+        // The phase of the best-known solution is only used in stable mode; in focused mode, the
+        // solver explores more diverse parts of the search space.
+        self.use_solution_phase = search_mode == SearchMode::Stable;
+        self.backup_brancher.on_search_mode_switch(search_mode)
+    }
+
     fn is_restart_pointless(&mut self) -> bool {
         false
     }
@@ -331,6 +348,7 @@ impl<BackupBrancher: Brancher> Brancher for AutonomousSearch<BackupBrancher> {
             BrancherEvent::Backtrack,
             BrancherEvent::Synchronise,
             BrancherEvent::AppearanceInConflictPredicate,
+            BrancherEvent::SearchModeSwitch,
         ]
         .into_iter()
         .chain(self.backup_brancher.subscribe_to_events())
@@ -345,6 +363,7 @@ mod tests {
     use crate::branching::Brancher;
     use crate::branching::SelectionContext;
     use crate::engine::Assignments;
+    use crate::engine::SearchMode;
     use crate::predicate;
     use crate::results::SolutionReference;
 
@@ -478,4 +497,31 @@ mod tests {
         ));
         assert_eq!(result, Some(predicate!(x >= 5)));
     }
+
+    #[test]
+    fn stored_solution_is_only_used_in_stable_mode() {
+        let mut assignments = Assignments::default();
+        let x = assignments.grow(0, 10);
+
+        assignments.increase_decision_level();
+        let _ = assignments.make_assignment(x, 7, None);
+
+        let mut brancher = AutonomousSearch::default_over_all_variables(&assignments);
+
+        brancher.on_solution(SolutionReference::new(&assignments));
+
+        let _ = assignments.synchronise(0, usize::MAX, false);
+
+        brancher.on_search_mode_switch(SearchMode::Focused);
+        assert_eq!(
+            predicate!(x >= 10),
+            brancher.determine_polarity(predicate!(x >= 10))
+        );
+
+        brancher.on_search_mode_switch(SearchMode::Stable);
+        assert_eq!(
+            !predicate!(x >= 10),
+            brancher.determine_polarity(predicate!(x >= 10))
+        );
+    }
 }
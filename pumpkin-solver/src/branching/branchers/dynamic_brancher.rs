@@ -16,6 +16,7 @@ use crate::branching::SelectionContext;
 use crate::engine::predicates::predicate::Predicate;
 use crate::engine::variables::DomainId;
 use crate::engine::Assignments;
+use crate::engine::SearchMode;
 use crate::statistics::StatisticLogger;
 
 /// An implementation of a [`Brancher`] which takes a [`Vec`] of `Box<dyn Brancher>` and
@@ -149,6 +150,14 @@ impl Brancher for DynamicBrancher {
             .for_each(|&brancher_index| self.branchers[brancher_index].on_restart());
     }
 
+    fn on_search_mode_switch(&mut self, search_mode: SearchMode) {
+        self.relevant_event_to_index[BrancherEvent::SearchModeSwitch]
+            .iter()
+            .for_each(|&brancher_index| {
+                self.branchers[brancher_index].on_search_mode_switch(search_mode)
+            });
+    }
+
     fn synchronise(&mut self, assignments: &Assignments) {
         self.relevant_event_to_index[BrancherEvent::Synchronise]
             .iter()
@@ -28,9 +28,10 @@ impl Constraint for Clause {
         solver: &mut Solver,
         tag: Option<NonZero<u32>>,
     ) -> Result<(), ConstraintOperationError> {
-        assert!(tag.is_none(), "tagging clauses is not implemented");
-
-        solver.add_clause(self.0.iter().map(|literal| literal.get_true_predicate()))
+        solver.add_tagged_clause(
+            self.0.iter().map(|literal| literal.get_true_predicate()),
+            tag,
+        )
     }
 
     fn implied_by(
@@ -39,13 +40,12 @@ impl Constraint for Clause {
         reification_literal: Literal,
         tag: Option<NonZero<u32>>,
     ) -> Result<(), ConstraintOperationError> {
-        assert!(tag.is_none(), "tagging clauses is not implemented");
-
-        solver.add_clause(
+        solver.add_tagged_clause(
             self.0
                 .into_iter()
                 .chain(std::iter::once(!reification_literal))
                 .map(|literal| literal.get_true_predicate()),
+            tag,
         )
     }
 }
@@ -66,11 +66,9 @@ impl Constraint for Conjunction {
         solver: &mut Solver,
         tag: Option<NonZero<u32>>,
     ) -> Result<(), ConstraintOperationError> {
-        assert!(tag.is_none(), "tagging clauses is not implemented");
-
         self.0
             .into_iter()
-            .try_for_each(|lit| solver.add_clause([lit.get_true_predicate()]))
+            .try_for_each(|lit| solver.add_tagged_clause([lit.get_true_predicate()], tag))
     }
 
     fn implied_by(
@@ -79,13 +77,14 @@ impl Constraint for Conjunction {
         reification_literal: Literal,
         tag: Option<NonZero<u32>>,
     ) -> Result<(), ConstraintOperationError> {
-        assert!(tag.is_none(), "tagging clauses is not implemented");
-
         self.0.into_iter().try_for_each(|lit| {
-            solver.add_clause([
-                (!(reification_literal)).get_true_predicate(),
-                lit.get_true_predicate(),
-            ])
+            solver.add_tagged_clause(
+                [
+                    (!(reification_literal)).get_true_predicate(),
+                    lit.get_true_predicate(),
+                ],
+                tag,
+            )
         })
     }
 }
@@ -0,0 +1,50 @@
+use super::Constraint;
+use crate::propagators::lexicographic::LexLessOrEqualPropagator;
+use crate::variables::IntegerVariable;
+
+/// Creates the [lex_lesseq](https://sofdem.github.io/gccat/gccat/Clex_lesseq.html) [`Constraint`]
+/// which states that `x` is lexicographically smaller than or equal to `y`.
+///
+/// The arrays are allowed to have different lengths; if `x` is a prefix of `y`, then `x` is
+/// considered to be smaller than `y`.
+pub fn lex_less_or_equal<Var: IntegerVariable + 'static>(
+    x: impl Into<Box<[Var]>>,
+    y: impl Into<Box<[Var]>>,
+) -> impl Constraint {
+    lexicographic(x.into(), y.into(), false)
+}
+
+/// Creates the [lex_less](https://sofdem.github.io/gccat/gccat/Clex_less.html) [`Constraint`]
+/// which states that `x` is lexicographically strictly smaller than `y`.
+///
+/// The arrays are allowed to have different lengths; if `x` is a prefix of `y`, then `x` is
+/// considered to be smaller than `y`.
+pub fn lex_less<Var: IntegerVariable + 'static>(
+    x: impl Into<Box<[Var]>>,
+    y: impl Into<Box<[Var]>>,
+) -> impl Constraint {
+    lexicographic(x.into(), y.into(), true)
+}
+
+/// Creates the propagator for the lexicographic ordering over arrays of the same length.
+///
+/// If `x` is shorter than `y`, then `x` is smaller whenever it is equal to the prefix of `y`,
+/// hence only the prefix of `y` is considered (and the constraint is non-strict). If `x` is at
+/// least as long as `y`, then the prefix of `x` should be strictly smaller than `y` whenever `x`
+/// is longer than `y`.
+fn lexicographic<Var: IntegerVariable + 'static>(
+    mut x: Box<[Var]>,
+    mut y: Box<[Var]>,
+    is_strict: bool,
+) -> LexLessOrEqualPropagator<Var> {
+    let is_strict = if x.len() < y.len() {
+        y = y[..x.len()].into();
+        false
+    } else {
+        let is_longer = x.len() > y.len();
+        x = x[..y.len()].into();
+        is_strict || is_longer
+    };
+
+    LexLessOrEqualPropagator::new(x, y, is_strict)
+}
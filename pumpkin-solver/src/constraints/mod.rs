@@ -30,6 +30,7 @@ mod constraint_poster;
 mod cumulative;
 mod element;
 mod global_cardinality;
+mod lexicographic;
 use std::num::NonZero;
 
 pub use all_different::*;
@@ -40,6 +41,7 @@ pub use constraint_poster::*;
 pub use cumulative::*;
 pub use element::*;
 pub use global_cardinality::*;
+pub use lexicographic::*;
 
 use crate::engine::propagation::Propagator;
 use crate::propagators::ReifiedPropagator;
@@ -3,6 +3,8 @@ use std::fmt::Debug;
 use drcp_format::steps::StepId;
 
 use super::minimisers::SemanticMinimiser;
+use super::ConflictTrace;
+use super::TracedPropagator;
 use crate::basic_types::HashMap;
 use crate::basic_types::StoredConflictInfo;
 use crate::branching::Brancher;
@@ -47,7 +49,11 @@ pub(crate) struct ConflictAnalysisContext<'a> {
     pub(crate) counters: &'a mut SolverStatistics,
 
     pub(crate) proof_log: &'a mut ProofLog,
+    pub(crate) conflict_trace: &'a mut ConflictTrace,
     pub(crate) should_minimise: bool,
+    /// Whether minimisation should ask the propagators for generalised reasons (see
+    /// [`ReasonGeneralisationMinimiser`](crate::engine::conflict_analysis::minimisers::ReasonGeneralisationMinimiser)).
+    pub(crate) should_generalise_reasons: bool,
 
     pub(crate) unit_nogood_step_ids: &'a HashMap<Predicate, StepId>,
     pub(crate) trailed_values: &'a mut TrailedValues,
@@ -99,13 +105,32 @@ impl ConflictAnalysisContext<'_> {
             } => {
                 let _ = self.proof_log.log_inference(
                     self.propagators.get_tag(propagator_id),
+                    self.propagators.get_inference_label(propagator_id),
                     conflict_nogood.iter().copied(),
                     None,
                 );
 
+                if self.conflict_trace.is_tracing() {
+                    self.conflict_trace.start_conflict(
+                        self.assignments.get_decision_level(),
+                        Some(TracedPropagator::new(self.propagators, propagator_id)),
+                        conflict_nogood.as_slice(),
+                    );
+                }
+
+                conflict_nogood
+            }
+            StoredConflictInfo::EmptyDomain { conflict_nogood } => {
+                if self.conflict_trace.is_tracing() {
+                    self.conflict_trace.start_conflict(
+                        self.assignments.get_decision_level(),
+                        None,
+                        conflict_nogood.as_slice(),
+                    );
+                }
+
                 conflict_nogood
             }
-            StoredConflictInfo::EmptyDomain { conflict_nogood } => conflict_nogood,
             StoredConflictInfo::RootLevelConflict(_) => {
                 unreachable!("Should never attempt to learn a nogood from a root level conflict")
             }
@@ -142,6 +167,23 @@ impl ConflictAnalysisContext<'_> {
             .collect()
     }
 
+    /// Records in the conflict trace that `predicate` was replaced by `reason` during conflict
+    /// analysis.
+    pub(crate) fn trace_resolution(&mut self, predicate: Predicate, reason: &[Predicate]) {
+        if !self.conflict_trace.is_tracing() {
+            return;
+        }
+
+        let propagator = TracedPropagator::of_predicate(
+            predicate,
+            self.assignments,
+            self.reason_store,
+            self.propagators,
+        );
+        self.conflict_trace
+            .record_resolution(predicate, propagator, reason);
+    }
+
     /// Compute the reason for `predicate` being true. The reason will be stored in
     /// `reason_buffer`.
     ///
@@ -193,10 +235,12 @@ impl ConflictAnalysisContext<'_> {
                 .expect("Cannot be a null reason for propagation.");
 
             let propagator_id = reason_store.get_propagator(reason_ref);
-            let constraint_tag = propagators.get_tag(propagator_id);
+            let constraint_tag = reason_store.get_constraint_tag(reason_ref, propagators);
+            let inference_label = propagators.get_inference_label(propagator_id);
 
             let explanation_context = ExplanationContext::new(assignments, current_nogood);
 
+            let num_predicates_before = reason_buffer.as_ref().len();
             let reason_exists = reason_store.get_or_compute(
                 reason_ref,
                 explanation_context,
@@ -206,6 +250,10 @@ impl ConflictAnalysisContext<'_> {
 
             assert!(reason_exists, "reason reference should not be stale");
 
+            propagators
+                .get_statistics_mut(propagator_id)
+                .record_explanation(reason_buffer.as_ref().len() - num_predicates_before);
+
             if propagator_id == ConstraintSatisfactionSolver::get_nogood_propagator_id()
                 && reason_buffer.as_ref().is_empty()
             {
@@ -231,6 +279,7 @@ impl ConflictAnalysisContext<'_> {
                 // Otherwise we log the inference which was used to derive the nogood
                 let _ = proof_log.log_inference(
                     constraint_tag,
+                    inference_label,
                     reason_buffer.as_ref().iter().copied(),
                     Some(predicate),
                 );
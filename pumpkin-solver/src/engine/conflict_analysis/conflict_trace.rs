@@ -0,0 +1,292 @@
+use std::io::BufWriter;
+use std::io::Write;
+use std::num::NonZero;
+use std::path::Path;
+
+use drcp_format::compression;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::engine::propagation::store::PropagatorStore;
+use crate::engine::propagation::PropagatorId;
+use crate::engine::reason::ReasonStore;
+use crate::engine::Assignments;
+use crate::predicates::Predicate;
+
+/// A trace of the conflicts which are analysed by the solver, which is meant for debugging the
+/// explanations of propagators.
+///
+/// For every conflict, the trace records the propagator which reported the conflict, every
+/// resolution step performed by the
+/// [`ResolutionResolver`](crate::engine::conflict_analysis::ResolutionResolver) together with the
+/// propagator whose explanation was used, the nogood after each of the minimisation steps, and the
+/// learned nogood. The trace is written in the JSON Lines format, i.e. one JSON object per
+/// conflict on a separate line, and can be browsed with the `explore` command of `drcp-debugger`.
+///
+/// When no trace should be written, use the implementation of [`Default`].
+#[derive(Debug, Default)]
+pub struct ConflictTrace {
+    internal_trace: Option<TraceWriter>,
+}
+
+#[derive(Debug)]
+struct TraceWriter {
+    writer: BufWriter<compression::CompressedWriter<std::fs::File>>,
+    num_conflicts: u64,
+    /// The conflict which is currently being analysed, which is written once its learned nogood
+    /// is known.
+    conflict: Option<TracedConflict>,
+}
+
+#[derive(Debug, Serialize)]
+struct TracedConflict {
+    decision_level: usize,
+    /// The propagator which reported the conflict, or [`None`] if the conflict is an empty domain.
+    propagator: Option<TracedPropagator>,
+    #[serde(serialize_with = "serialize_predicates")]
+    conflict_nogood: Vec<Predicate>,
+    #[serde(rename = "resolution")]
+    resolution_steps: Vec<ResolutionStep>,
+    #[serde(rename = "minimisation")]
+    minimisation_steps: Vec<MinimisationStep>,
+}
+
+/// A line of the trace, i.e. a conflict which has been analysed.
+#[derive(Debug, Serialize)]
+struct FinishedConflict<'a> {
+    conflict: u64,
+    #[serde(flatten)]
+    analysis: &'a TracedConflict,
+    #[serde(serialize_with = "serialize_predicates")]
+    learned_nogood: &'a [Predicate],
+    backjump_level: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct ResolutionStep {
+    #[serde(serialize_with = "serialize_predicate")]
+    predicate: Predicate,
+    /// The propagator which propagated the predicate, or [`None`] if the predicate is implied by
+    /// a stronger predicate on the trail.
+    propagator: Option<TracedPropagator>,
+    #[serde(serialize_with = "serialize_predicates")]
+    reason: Vec<Predicate>,
+}
+
+#[derive(Debug, Serialize)]
+struct MinimisationStep {
+    minimiser: &'static str,
+    #[serde(serialize_with = "serialize_predicates")]
+    nogood: Vec<Predicate>,
+}
+
+/// The information about a propagator which is included in a [`ConflictTrace`].
+#[derive(Debug, Serialize)]
+pub(crate) struct TracedPropagator {
+    #[serde(serialize_with = "serialize_propagator_id")]
+    id: PropagatorId,
+    name: String,
+    label: &'static str,
+    tag: Option<NonZero<u32>>,
+}
+
+impl TracedPropagator {
+    pub(crate) fn new(propagators: &PropagatorStore, propagator_id: PropagatorId) -> Self {
+        TracedPropagator {
+            id: propagator_id,
+            name: propagators[propagator_id].name().to_owned(),
+            label: propagators.get_inference_label(propagator_id),
+            tag: propagators.get_tag(propagator_id),
+        }
+    }
+
+    /// Returns the propagator which propagated `predicate`, or [`None`] if the predicate is not
+    /// explicitly on the trail (e.g. because it is implied by a stronger bound).
+    pub(crate) fn of_predicate(
+        predicate: Predicate,
+        assignments: &Assignments,
+        reason_store: &ReasonStore,
+        propagators: &PropagatorStore,
+    ) -> Option<Self> {
+        let trail_position = assignments.get_trail_position(&predicate)?;
+        let trail_entry = assignments.get_trail_entry(trail_position);
+
+        if trail_entry.predicate != predicate {
+            return None;
+        }
+
+        let propagator_id = reason_store.get_propagator(trail_entry.reason?);
+        Some(TracedPropagator::new(propagators, propagator_id))
+    }
+}
+
+impl ConflictTrace {
+    /// Create a conflict trace which is written to `file_path`. If the extension of `file_path`
+    /// is `gz` or `zst`, the trace is compressed (see [`drcp_format::compression`]).
+    pub fn json(file_path: &Path) -> std::io::Result<ConflictTrace> {
+        let file = compression::create(file_path)?;
+
+        Ok(ConflictTrace {
+            internal_trace: Some(TraceWriter {
+                writer: BufWriter::new(file),
+                num_conflicts: 0,
+                conflict: None,
+            }),
+        })
+    }
+
+    /// Returns whether the conflicts are traced.
+    pub(crate) fn is_tracing(&self) -> bool {
+        self.internal_trace.is_some()
+    }
+
+    /// Start tracing a new conflict, which is described by the conflict nogood including its
+    /// root-level predicates.
+    pub(crate) fn start_conflict(
+        &mut self,
+        decision_level: usize,
+        propagator: Option<TracedPropagator>,
+        conflict_nogood: &[Predicate],
+    ) {
+        let Some(trace) = self.internal_trace.as_mut() else {
+            return;
+        };
+
+        trace.conflict = Some(TracedConflict {
+            decision_level,
+            propagator,
+            conflict_nogood: conflict_nogood.to_vec(),
+            resolution_steps: vec![],
+            minimisation_steps: vec![],
+        });
+    }
+
+    /// Record that `predicate` was replaced by its `reason` in the current conflict.
+    pub(crate) fn record_resolution(
+        &mut self,
+        predicate: Predicate,
+        propagator: Option<TracedPropagator>,
+        reason: &[Predicate],
+    ) {
+        let Some(conflict) = self.current_conflict() else {
+            return;
+        };
+
+        conflict.resolution_steps.push(ResolutionStep {
+            predicate,
+            propagator,
+            reason: reason.to_vec(),
+        });
+    }
+
+    /// Record the nogood which resulted from the minimisation step with the given name.
+    pub(crate) fn record_minimisation(&mut self, minimiser: &'static str, nogood: &[Predicate]) {
+        let Some(conflict) = self.current_conflict() else {
+            return;
+        };
+
+        conflict.minimisation_steps.push(MinimisationStep {
+            minimiser,
+            nogood: nogood.to_vec(),
+        });
+    }
+
+    /// Write the current conflict to the trace, together with the nogood which was learned from
+    /// it.
+    pub(crate) fn finish_conflict(
+        &mut self,
+        learned_nogood: &[Predicate],
+        backjump_level: usize,
+    ) -> std::io::Result<()> {
+        let Some(trace) = self.internal_trace.as_mut() else {
+            return Ok(());
+        };
+        let Some(conflict) = trace.conflict.take() else {
+            return Ok(());
+        };
+
+        trace.num_conflicts += 1;
+        let line = FinishedConflict {
+            conflict: trace.num_conflicts,
+            analysis: &conflict,
+            learned_nogood,
+            backjump_level,
+        };
+        serde_json::to_writer(&mut trace.writer, &line)?;
+        writeln!(trace.writer)
+    }
+
+    fn current_conflict(&mut self) -> Option<&mut TracedConflict> {
+        self.internal_trace.as_mut()?.conflict.as_mut()
+    }
+}
+
+fn serialize_predicate<S: Serializer>(
+    predicate: &Predicate,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(predicate)
+}
+
+fn serialize_predicates<S: Serializer>(
+    predicates: &[Predicate],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(predicates.iter().map(ToString::to_string))
+}
+
+fn serialize_propagator_id<S: Serializer>(
+    propagator_id: &PropagatorId,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u32(propagator_id.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::variables::DomainId;
+    use crate::predicate;
+
+    #[test]
+    fn conflict_is_written_as_single_json_object() {
+        let x = DomainId::new(1);
+        let y = DomainId::new(2);
+
+        let conflict = TracedConflict {
+            decision_level: 2,
+            propagator: Some(TracedPropagator {
+                id: PropagatorId(3),
+                name: "LinearLeq".to_owned(),
+                label: "linear_bound",
+                tag: NonZero::new(4),
+            }),
+            conflict_nogood: vec![predicate![x >= 2], predicate![y >= 1]],
+            resolution_steps: vec![ResolutionStep {
+                predicate: predicate![x >= 2],
+                propagator: None,
+                reason: vec![predicate![x >= 3]],
+            }],
+            minimisation_steps: vec![MinimisationStep {
+                minimiser: "recursive",
+                nogood: vec![predicate![x >= 3]],
+            }],
+        };
+        let line = FinishedConflict {
+            conflict: 1,
+            analysis: &conflict,
+            learned_nogood: &[predicate![x >= 3]],
+            backjump_level: 0,
+        };
+
+        assert_eq!(
+            "{\"conflict\":1,\"decision_level\":2,\
+             \"propagator\":{\"id\":3,\"name\":\"LinearLeq\",\"label\":\"linear_bound\",\"tag\":4},\
+             \"conflict_nogood\":[\"[x1 >= 2]\",\"[x2 >= 1]\"],\
+             \"resolution\":[{\"predicate\":\"[x1 >= 2]\",\"propagator\":null,\"reason\":[\"[x1 >= 3]\"]}],\
+             \"minimisation\":[{\"minimiser\":\"recursive\",\"nogood\":[\"[x1 >= 3]\"]}],\
+             \"learned_nogood\":[\"[x1 >= 3]\"],\"backjump_level\":0}",
+            serde_json::to_string(&line).expect("serialisable")
+        );
+    }
+}
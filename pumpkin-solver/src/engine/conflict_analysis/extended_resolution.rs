@@ -0,0 +1,308 @@
+use drcp_format::steps::StepId;
+
+use crate::basic_types::HashMap;
+use crate::basic_types::HashSet;
+use crate::engine::Assignments;
+use crate::predicates::Predicate;
+use crate::proof::ProofLog;
+use crate::variables::Literal;
+
+/// The maximum number of pairs of predicates for which the number of occurrences is tracked; when
+/// this limit is exceeded, the occurrences are reset.
+const MAX_NUM_TRACKED_PAIRS: usize = 100_000;
+
+/// Options which determine when extension literals are introduced by [`ExtendedResolution`].
+#[derive(Debug, Clone, Copy)]
+pub struct ExtendedResolutionOptions {
+    /// Whether extension literals are introduced during search.
+    pub enabled: bool,
+    /// The number of learned nogoods in which a pair of predicates should occur before an
+    /// extension literal is defined for the pair.
+    pub min_num_occurrences: u32,
+    /// The maximum length of a learned nogood for which the pairs of predicates are counted;
+    /// since the number of pairs is quadratic in the length of the nogood, longer nogoods are
+    /// ignored.
+    pub max_nogood_length: usize,
+    /// The maximum number of extension literals which are defined.
+    pub max_num_extension_literals: usize,
+}
+
+impl Default for ExtendedResolutionOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_num_occurrences: 10,
+            max_nogood_length: 10,
+            max_num_extension_literals: 10_000,
+        }
+    }
+}
+
+/// Introduces extension literals during conflict analysis, based on \[1\].
+///
+/// The pairs of predicates which occur in the learned nogoods are counted; once a pair `(p, q)`
+/// has occurred in sufficiently many learned nogoods, it becomes a candidate for which the solver
+/// defines a fresh literal `d <-> (p /\ q)` at the root level (see
+/// [`ExtendedResolution::take_candidates`]). Afterwards, the occurrences of both `p` and `q` in a
+/// learned nogood are replaced by `d`.
+///
+/// # Bibliography
+/// \[1\] G. Audemard, G. Katsirelos, and L. Simon, ‘A restriction of extended resolution for
+/// clause learning SAT solvers’, in Proceedings of the AAAI Conference on Artificial
+/// Intelligence, 2010.
+#[derive(Debug, Clone)]
+pub(crate) struct ExtendedResolution {
+    options: ExtendedResolutionOptions,
+    /// The number of learned nogoods in which a pair of predicates has occurred.
+    pair_occurrences: HashMap<(Predicate, Predicate), u32>,
+    /// The pairs of predicates for which an extension literal should be defined.
+    candidates: Vec<(Predicate, Predicate)>,
+    /// Maps a predicate to the definitions in which it occurs, together with the other predicate
+    /// in the definition.
+    definitions: HashMap<Predicate, Vec<(Predicate, ExtensionLiteral)>>,
+    /// The number of extension literals which have been defined.
+    num_extension_literals: usize,
+    /// Re-usable buffer which contains the predicates of the nogood during substitution.
+    nogood_predicates: HashSet<Predicate>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ExtensionLiteral {
+    literal: Literal,
+    /// The proof step of the definition of the literal.
+    step_id: StepId,
+}
+
+impl ExtendedResolution {
+    pub(crate) fn new(options: ExtendedResolutionOptions) -> Self {
+        Self {
+            options,
+            pair_occurrences: HashMap::default(),
+            candidates: vec![],
+            definitions: HashMap::default(),
+            num_extension_literals: 0,
+            nogood_predicates: HashSet::default(),
+        }
+    }
+
+    /// Replaces the pairs of predicates in the nogood for which an extension literal has been
+    /// defined by the extension literal, and returns the number of replaced pairs.
+    ///
+    /// A pair is only replaced if the extension literal is assigned; since the definition is
+    /// propagated, this means that the extension literal is assigned at a decision level which is
+    /// not higher than the decision levels of the predicates in the pair. The definitions which
+    /// are used are recorded as propagations in the proof.
+    pub(crate) fn substitute_extension_literals(
+        &mut self,
+        nogood: &mut Vec<Predicate>,
+        assignments: &Assignments,
+        proof_log: &mut ProofLog,
+    ) -> usize {
+        if self.definitions.is_empty() {
+            return 0;
+        }
+
+        self.nogood_predicates.clear();
+        self.nogood_predicates.extend(nogood.iter().copied());
+
+        let mut num_substitutions = 0;
+        let mut index = 0;
+        while index < nogood.len() {
+            let predicate = nogood[index];
+
+            let substitution = self.definitions.get(&predicate).and_then(|definitions| {
+                definitions.iter().find(|(other, extension_literal)| {
+                    self.nogood_predicates.contains(other)
+                        && assignments
+                            .is_predicate_satisfied(extension_literal.literal.get_true_predicate())
+                })
+            });
+
+            let Some(&(other, extension_literal)) = substitution else {
+                index += 1;
+                continue;
+            };
+
+            let defined_predicate = extension_literal.literal.get_true_predicate();
+            nogood.retain(|&element| element != predicate && element != other);
+            let _ = self.nogood_predicates.remove(&predicate);
+            let _ = self.nogood_predicates.remove(&other);
+
+            if self.nogood_predicates.insert(defined_predicate) {
+                nogood.push(defined_predicate);
+            }
+
+            proof_log.add_propagation(extension_literal.step_id);
+            num_substitutions += 1;
+
+            // The predicates have been moved by the substitution, and the extension literal can
+            // itself be part of a definition, so the search starts from the beginning again.
+            index = 0;
+        }
+
+        num_substitutions
+    }
+
+    /// Records the pairs of predicates which occur in the learned nogood; if a pair has occurred
+    /// sufficiently often, it becomes a candidate for an extension literal.
+    pub(crate) fn record_learned_nogood(&mut self, nogood: &[Predicate]) {
+        if nogood.len() < 2
+            || nogood.len() > self.options.max_nogood_length
+            || self.num_extension_literals + self.candidates.len()
+                >= self.options.max_num_extension_literals
+        {
+            return;
+        }
+
+        if self.pair_occurrences.len() > MAX_NUM_TRACKED_PAIRS {
+            self.pair_occurrences.clear();
+        }
+
+        for (index, &first) in nogood.iter().enumerate() {
+            for &second in &nogood[index + 1..] {
+                let pair = ordered_pair(first, second);
+
+                let num_occurrences = self.pair_occurrences.entry(pair).or_insert(0);
+                *num_occurrences += 1;
+
+                if *num_occurrences >= self.options.min_num_occurrences {
+                    let _ = self.pair_occurrences.remove(&pair);
+
+                    if !self.is_defined(pair) && !self.candidates.contains(&pair) {
+                        self.candidates.push(pair);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the pairs of predicates for which an extension literal should be defined; the
+    /// definitions should be registered using [`ExtendedResolution::add_definition`].
+    pub(crate) fn take_candidates(&mut self) -> Vec<(Predicate, Predicate)> {
+        std::mem::take(&mut self.candidates)
+    }
+
+    /// Registers the definition `literal <-> (pair.0 /\ pair.1)` which has been logged in the
+    /// proof with the provided `step_id`.
+    pub(crate) fn add_definition(
+        &mut self,
+        pair: (Predicate, Predicate),
+        literal: Literal,
+        step_id: StepId,
+    ) {
+        let extension_literal = ExtensionLiteral { literal, step_id };
+
+        self.definitions
+            .entry(pair.0)
+            .or_default()
+            .push((pair.1, extension_literal));
+        self.definitions
+            .entry(pair.1)
+            .or_default()
+            .push((pair.0, extension_literal));
+
+        self.num_extension_literals += 1;
+    }
+
+    fn is_defined(&self, pair: (Predicate, Predicate)) -> bool {
+        self.definitions
+            .get(&pair.0)
+            .is_some_and(|definitions| definitions.iter().any(|(other, _)| *other == pair.1))
+    }
+}
+
+/// Returns the pair of predicates in a canonical order, such that the same pair is obtained
+/// regardless of the order of the predicates in the nogood.
+fn ordered_pair(first: Predicate, second: Predicate) -> (Predicate, Predicate) {
+    let key = |predicate: Predicate| {
+        (
+            predicate.get_domain().id,
+            predicate.get_right_hand_side(),
+            predicate.is_lower_bound_predicate(),
+            predicate.is_upper_bound_predicate(),
+            predicate.is_equality_predicate(),
+        )
+    };
+
+    if key(first) <= key(second) {
+        (first, second)
+    } else {
+        (second, first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicate;
+
+    fn options() -> ExtendedResolutionOptions {
+        ExtendedResolutionOptions {
+            enabled: true,
+            min_num_occurrences: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn frequently_occurring_pairs_become_candidates() {
+        let mut assignments = Assignments::default();
+        let x = assignments.grow(0, 10);
+        let y = assignments.grow(0, 10);
+        let z = assignments.grow(0, 10);
+
+        let mut extended_resolution = ExtendedResolution::new(options());
+        extended_resolution.record_learned_nogood(&[predicate!(x >= 5), predicate!(y <= 3)]);
+        assert!(extended_resolution.take_candidates().is_empty());
+
+        extended_resolution.record_learned_nogood(&[
+            predicate!(z >= 1),
+            predicate!(y <= 3),
+            predicate!(x >= 5),
+        ]);
+        assert_eq!(
+            extended_resolution.take_candidates(),
+            vec![ordered_pair(predicate!(x >= 5), predicate!(y <= 3))]
+        );
+    }
+
+    #[test]
+    fn defined_pairs_are_substituted() {
+        let mut assignments = Assignments::default();
+        let x = assignments.grow(0, 10);
+        let y = assignments.grow(0, 10);
+        let z = assignments.grow(0, 10);
+        let literal = Literal::new(assignments.grow(0, 1));
+
+        let mut extended_resolution = ExtendedResolution::new(options());
+        extended_resolution.add_definition(
+            (predicate!(x >= 5), predicate!(y <= 3)),
+            literal,
+            StepId::new(1).unwrap(),
+        );
+
+        let mut nogood = vec![predicate!(x >= 5), predicate!(z >= 1), predicate!(y <= 3)];
+
+        // The extension literal is not assigned, so it cannot be substituted.
+        let num_substitutions = extended_resolution.substitute_extension_literals(
+            &mut nogood,
+            &assignments,
+            &mut ProofLog::default(),
+        );
+        assert_eq!(num_substitutions, 0);
+
+        assignments
+            .post_predicate(literal.get_true_predicate(), None)
+            .expect("non-empty domain");
+        let num_substitutions = extended_resolution.substitute_extension_literals(
+            &mut nogood,
+            &assignments,
+            &mut ProofLog::default(),
+        );
+        assert_eq!(num_substitutions, 1);
+        assert_eq!(
+            nogood,
+            vec![predicate!(z >= 1), literal.get_true_predicate()]
+        );
+    }
+}
@@ -1,5 +1,7 @@
+mod reason_generalisation_minimiser;
 mod recursive_minimiser;
 mod semantic_minimiser;
 
+pub(crate) use reason_generalisation_minimiser::*;
 pub(crate) use recursive_minimiser::*;
 pub(crate) use semantic_minimiser::*;
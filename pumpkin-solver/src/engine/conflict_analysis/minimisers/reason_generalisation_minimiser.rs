@@ -0,0 +1,151 @@
+use crate::basic_types::moving_averages::MovingAverage;
+use crate::engine::conflict_analysis::ConflictAnalysisContext;
+use crate::engine::propagation::GeneralisationContext;
+#[cfg(doc)]
+use crate::engine::propagation::Propagator;
+use crate::engine::ConstraintSatisfactionSolver;
+use crate::predicates::Predicate;
+use crate::proof::explain_root_assignment;
+use crate::proof::RootExplanationContext;
+use crate::pumpkin_assert_moderate;
+
+/// A minimiser which removes predicates from a learned nogood by asking the propagators which
+/// propagated them for a generalised reason (see
+/// [`Propagator::explain_with_generalised_reason`]).
+///
+/// Where the [`RecursiveMinimiser`](super::RecursiveMinimiser) only considers the reasons which
+/// were provided at the time of propagation, a generalised reason can be weaker; e.g. the
+/// nogood could contain `[x >= 3]` while the original reason for a propagation relied on `[x ==
+/// 5]`. If the generalised reason only consists of predicates which are implied by the other
+/// predicates in the nogood (or which hold at the root), then the propagated predicate is
+/// redundant.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReasonGeneralisationMinimiser {
+    /// Re-usable buffer containing the predicates of the nogood without the predicate which is
+    /// being explained.
+    available_predicates: Vec<Predicate>,
+}
+
+impl ReasonGeneralisationMinimiser {
+    /// Removes the predicates from the learned nogood which are implied by the other predicates
+    /// in the nogood according to a generalised reason.
+    ///
+    /// The asserting predicate (i.e. the predicate with the highest trail position) is never
+    /// removed.
+    pub(crate) fn remove_generalisable_predicates(
+        &mut self,
+        nogood: &mut Vec<Predicate>,
+        context: &mut ConflictAnalysisContext,
+    ) {
+        let num_predicates_before_minimisation = nogood.len();
+
+        let asserting_predicate = nogood
+            .iter()
+            .copied()
+            .max_by_key(|predicate| context.assignments.get_trail_position(predicate));
+
+        let mut index = 0;
+        while index < nogood.len() {
+            let predicate = nogood[index];
+            if Some(predicate) != asserting_predicate && self.is_redundant(index, nogood, context) {
+                let _ = nogood.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+
+        context
+            .counters
+            .learned_clause_statistics
+            .average_number_of_removed_literals_generalised
+            .add_term((num_predicates_before_minimisation - nogood.len()) as u64);
+    }
+
+    /// Returns whether the predicate at `index` in the nogood is implied by the other predicates
+    /// based on a generalised reason of the propagator which propagated it; if this is the case,
+    /// then the corresponding inference is logged to the proof.
+    fn is_redundant(
+        &mut self,
+        index: usize,
+        nogood: &[Predicate],
+        context: &mut ConflictAnalysisContext,
+    ) -> bool {
+        let predicate = nogood[index];
+
+        // Only predicates which are explicitly on the trail have been propagated by a single
+        // propagator which can be asked for a generalised reason.
+        let Some(trail_position) = context.assignments.get_trail_position(&predicate) else {
+            return false;
+        };
+        let trail_entry = context.assignments.get_trail_entry(trail_position);
+        if trail_entry.predicate != predicate {
+            return false;
+        }
+        let Some(reason_ref) = trail_entry.reason else {
+            return false;
+        };
+        let propagator_id = context.reason_store.get_propagator(reason_ref);
+        if propagator_id == ConstraintSatisfactionSolver::get_nogood_propagator_id() {
+            return false;
+        }
+
+        self.available_predicates.clear();
+        self.available_predicates.extend(
+            nogood
+                .iter()
+                .enumerate()
+                .filter(|&(other_index, _)| other_index != index)
+                .map(|(_, &other_predicate)| other_predicate),
+        );
+
+        let generalisation_context =
+            GeneralisationContext::new(context.assignments, &self.available_predicates);
+        let Some(reason) = context.propagators[propagator_id]
+            .explain_with_generalised_reason(predicate, generalisation_context)
+        else {
+            return false;
+        };
+
+        let generalisation_context =
+            GeneralisationContext::new(context.assignments, &self.available_predicates);
+        pumpkin_assert_moderate!(
+            reason
+                .iter()
+                .all(|&reason_predicate| generalisation_context.is_implied(reason_predicate)
+                    && context.assignments.is_predicate_satisfied(reason_predicate)),
+            "The generalised reason {reason} for {predicate} should only contain predicates which are implied by the nogood"
+        );
+
+        // The generalised reason can contain root-level predicates which are not part of the
+        // nogood; the proof needs to be aware that these are used.
+        for &reason_predicate in reason.iter() {
+            if context
+                .assignments
+                .get_decision_level_for_predicate(&reason_predicate)
+                == Some(0)
+            {
+                explain_root_assignment(
+                    &mut RootExplanationContext {
+                        propagators: context.propagators,
+                        proof_log: context.proof_log,
+                        unit_nogood_step_ids: context.unit_nogood_step_ids,
+                        assignments: context.assignments,
+                        reason_store: context.reason_store,
+                    },
+                    reason_predicate,
+                );
+            }
+        }
+
+        let constraint_tag = context.propagators.get_tag(propagator_id);
+        let inference_label = context.propagators.get_inference_label(propagator_id);
+        let _ = context.proof_log.log_inference(
+            constraint_tag,
+            inference_label,
+            reason.iter().copied(),
+            Some(predicate),
+        );
+
+        true
+    }
+}
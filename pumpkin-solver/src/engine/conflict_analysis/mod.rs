@@ -2,11 +2,17 @@
 //! The algorithms use resolution and implement the 1uip and all decision literal learning schemes
 
 mod conflict_analysis_context;
+mod conflict_trace;
+mod extended_resolution;
 mod learned_nogood;
 mod minimisers;
 mod resolvers;
 
 pub(crate) use conflict_analysis_context::ConflictAnalysisContext;
+pub use conflict_trace::ConflictTrace;
+pub(crate) use conflict_trace::TracedPropagator;
+pub(crate) use extended_resolution::ExtendedResolution;
+pub use extended_resolution::ExtendedResolutionOptions;
 pub(crate) use learned_nogood::*;
 pub(crate) use minimisers::*;
 pub(crate) use resolvers::*;
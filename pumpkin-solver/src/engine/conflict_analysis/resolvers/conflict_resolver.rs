@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 use crate::engine::conflict_analysis::ConflictAnalysisContext;
+use crate::engine::conflict_analysis::ExtendedResolution;
 use crate::engine::conflict_analysis::LearnedNogood;
 
 pub(crate) trait ConflictResolver: Debug {
@@ -12,4 +13,10 @@ pub(crate) trait ConflictResolver: Debug {
         context: &mut ConflictAnalysisContext,
         learned_nogood: &Option<LearnedNogood>,
     ) -> Result<(), ()>;
+
+    /// Returns the component which introduces extension literals if the resolver uses extended
+    /// resolution, and [`None`] otherwise.
+    fn extended_resolution(&mut self) -> Option<&mut ExtendedResolution> {
+        None
+    }
 }
@@ -6,8 +6,11 @@ use crate::branching::Brancher;
 use crate::containers::KeyValueHeap;
 use crate::containers::StorageKey;
 use crate::engine::conflict_analysis::minimisers::Mode;
+use crate::engine::conflict_analysis::minimisers::ReasonGeneralisationMinimiser;
 use crate::engine::conflict_analysis::minimisers::RecursiveMinimiser;
 use crate::engine::conflict_analysis::ConflictAnalysisContext;
+use crate::engine::conflict_analysis::ExtendedResolution;
+use crate::engine::conflict_analysis::ExtendedResolutionOptions;
 use crate::engine::conflict_analysis::LearnedNogood;
 use crate::engine::propagation::CurrentNogood;
 use crate::engine::Assignments;
@@ -39,10 +42,16 @@ pub(crate) struct ResolutionResolver {
     processed_nogood_predicates: Vec<Predicate>,
     /// A minimiser which recursively determines whether a predicate is redundant in the nogood
     recursive_minimiser: RecursiveMinimiser,
+    /// A minimiser which removes predicates that are implied by the rest of the nogood according
+    /// to the generalised reasons of the propagators
+    reason_generalisation_minimiser: ReasonGeneralisationMinimiser,
     /// Whether the resolver employs 1-UIP or all-decision learning.
     mode: AnalysisMode,
     /// Re-usable buffer which reasons are written into.
     reason_buffer: Vec<Predicate>,
+    /// If extended resolution is enabled, this introduces the extension literals and substitutes
+    /// them in the learned nogoods.
+    extended_resolution: Option<ExtendedResolution>,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -65,6 +74,15 @@ impl ResolutionResolver {
             ..Default::default()
         }
     }
+
+    /// Creates a 1-UIP resolver which introduces extension literals if this is enabled in the
+    /// provided options (see [`ExtendedResolution`]).
+    pub(crate) fn with_extended_resolution(options: ExtendedResolutionOptions) -> Self {
+        Self {
+            extended_resolution: options.enabled.then(|| ExtendedResolution::new(options)),
+            ..Default::default()
+        }
+    }
 }
 
 impl ConflictResolver for ResolutionResolver {
@@ -169,6 +187,7 @@ impl ConflictResolver for ResolutionResolver {
                             context.unit_nogood_step_ids,
                             &mut self.reason_buffer,
                         );
+                        context.trace_resolution(predicate, &self.reason_buffer);
 
                         if self.reason_buffer.is_empty() {
                             predicate
@@ -229,6 +248,7 @@ impl ConflictResolver for ResolutionResolver {
                         context.unit_nogood_step_ids,
                         &mut self.reason_buffer,
                     );
+                    context.trace_resolution(predicate, &self.reason_buffer);
                     pumpkin_assert_simple!(predicate.is_lower_bound_predicate() || predicate.is_not_equal_predicate() , "If the final predicate in the conflict nogood is not a decision predicate then it should be either a lower-bound predicate or a not-equals predicate but was {predicate}");
                     pumpkin_assert_simple!(
                         self.reason_buffer.len() == 1 && self.reason_buffer[0].is_lower_bound_predicate(),
@@ -258,6 +278,7 @@ impl ConflictResolver for ResolutionResolver {
                 context.unit_nogood_step_ids,
                 &mut self.reason_buffer,
             );
+            context.trace_resolution(next_predicate, &self.reason_buffer);
 
             let mut root_explanation_context = if context.proof_log.is_logging_inferences() {
                 Some(RootExplanationContext {
@@ -295,6 +316,10 @@ impl ConflictResolver for ResolutionResolver {
         context.backtrack(learned_nogood.backjump_level);
         Ok(())
     }
+
+    fn extended_resolution(&mut self) -> Option<&mut ExtendedResolution> {
+        self.extended_resolution.as_mut()
+    }
 }
 
 impl ResolutionResolver {
@@ -402,7 +427,8 @@ impl ResolutionResolver {
                     .increment(predicate_id, heap_value as u32);
 
                 pumpkin_assert_moderate!(
-                    *self.to_process_heap.get_value(predicate_id) == heap_value.try_into().unwrap(),
+                    *self.to_process_heap.get_value(predicate_id)
+                        == u32::try_from(heap_value).unwrap(),
                     "The value in the heap should be the same as was added"
                 )
             }
@@ -449,6 +475,9 @@ impl ResolutionResolver {
         } else {
             pumpkin_assert_simple!(matches!(self.mode, AnalysisMode::AllDecision), "If the heap is empty when extracting the final nogood then we should be performing all decision learning")
         }
+        context
+            .conflict_trace
+            .record_minimisation("resolution", &self.processed_nogood_predicates);
 
         // First we minimise the nogood using semantic minimisation to remove duplicates but we
         // avoid equality merging (since some of these literals could potentailly be removed by
@@ -465,11 +494,27 @@ impl ResolutionResolver {
                 Mode::DisableEqualityMerging
             },
         );
+        context
+            .conflict_trace
+            .record_minimisation("semantic", &clean_nogood);
 
         if context.should_minimise {
             // Then we perform recursive minimisation to remove the dominated predicates
             self.recursive_minimiser
                 .remove_dominated_predicates(&mut clean_nogood, context);
+            context
+                .conflict_trace
+                .record_minimisation("recursive", &clean_nogood);
+
+            if context.should_generalise_reasons {
+                // Then we remove the predicates which are implied by the remaining predicates
+                // according to a generalised reason of the propagator which propagated them
+                self.reason_generalisation_minimiser
+                    .remove_generalisable_predicates(&mut clean_nogood, context);
+                context
+                    .conflict_trace
+                    .record_minimisation("reason_generalisation", &clean_nogood);
+            }
 
             // We perform a final semantic minimisation call which allows the merging of the
             // equality predicates which remain in the nogood
@@ -479,6 +524,9 @@ impl ResolutionResolver {
                 context.assignments,
                 Mode::EnableEqualityMerging,
             );
+            context
+                .conflict_trace
+                .record_minimisation("semantic_equality_merging", &clean_nogood);
             context
                 .counters
                 .learned_clause_statistics
@@ -486,6 +534,23 @@ impl ResolutionResolver {
                 .add_term((size_before_semantic_minimisation - clean_nogood.len()) as u64);
         }
 
+        // The pairs of predicates for which an extension literal has been defined are replaced
+        // by the extension literal.
+        if let Some(extended_resolution) = self.extended_resolution.as_mut() {
+            let num_substitutions = extended_resolution.substitute_extension_literals(
+                &mut clean_nogood,
+                context.assignments,
+                context.proof_log,
+            );
+            context
+                .counters
+                .learned_clause_statistics
+                .num_extension_literal_substitutions += num_substitutions as u64;
+            context
+                .conflict_trace
+                .record_minimisation("extended_resolution", &clean_nogood);
+        }
+
         // Sorting does the trick with placing the correct predicates at the first two positions,
         // however this can be done more efficiently, since we only need the first two positions
         // to be properly sorted.
@@ -510,6 +575,10 @@ impl ResolutionResolver {
             .skip(1)
             .all(|p| context.assignments.is_predicate_satisfied(*p)));
 
+        if let Some(extended_resolution) = self.extended_resolution.as_mut() {
+            extended_resolution.record_learned_nogood(&clean_nogood);
+        }
+
         // TODO: asserting predicate may be bumped twice, probably not a problem.
         for predicate in clean_nogood.iter() {
             context
@@ -517,6 +586,10 @@ impl ResolutionResolver {
                 .on_appearance_in_conflict_predicate(*predicate);
         }
 
+        let _ = context
+            .conflict_trace
+            .finish_conflict(&clean_nogood, backjump_level);
+
         LearnedNogood {
             backjump_level,
             predicates: clean_nogood,
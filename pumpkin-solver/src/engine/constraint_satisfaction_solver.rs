@@ -3,26 +3,35 @@
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::num::NonZero;
+use std::num::NonZeroU64;
+use std::time::Duration;
 use std::time::Instant;
 
 use clap::ValueEnum;
 use drcp_format::steps::StepId;
+use itertools::Itertools;
+use log::warn;
 use rand::rngs::SmallRng;
 use rand::SeedableRng;
 
 use super::conflict_analysis::AnalysisMode;
 use super::conflict_analysis::ConflictAnalysisContext;
+use super::conflict_analysis::ConflictTrace;
+use super::conflict_analysis::ExtendedResolutionOptions;
 use super::conflict_analysis::LearnedNogood;
 use super::conflict_analysis::NoLearningResolver;
 use super::conflict_analysis::SemanticMinimiser;
 use super::propagation::contexts::PropagationContextWithTrailedValues;
 use super::propagation::store::PropagatorStore;
 use super::propagation::PropagatorId;
+use super::solver_statistics::PropagatorStatistics;
 use super::solver_statistics::SolverStatistics;
+use super::symmetry::Symmetry;
 use super::termination::TerminationCondition;
 use super::variables::IntegerVariable;
 use super::variables::Literal;
 use super::Lbd;
+use super::PresolveOptions;
 use super::ResolutionResolver;
 use super::TrailedValues;
 use super::VariableNames;
@@ -30,12 +39,14 @@ use crate::basic_types::moving_averages::MovingAverage;
 use crate::basic_types::CSPSolverExecutionFlag;
 use crate::basic_types::ConstraintOperationError;
 use crate::basic_types::HashMap;
+use crate::basic_types::HashSet;
 use crate::basic_types::Inconsistency;
 use crate::basic_types::PropagationStatusCP;
 use crate::basic_types::PropositionalConjunction;
 use crate::basic_types::Random;
 use crate::basic_types::SolutionReference;
 use crate::basic_types::StoredConflictInfo;
+use crate::branching::branchers::dynamic_brancher::DynamicBrancher;
 use crate::branching::Brancher;
 use crate::branching::SelectionContext;
 use crate::engine::conflict_analysis::ConflictResolver as Resolver;
@@ -48,8 +59,10 @@ use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
+use crate::engine::propagation::PropagationDelay;
 use crate::engine::propagation::Propagator;
 use crate::engine::propagation::PropagatorInitialisationContext;
+use crate::engine::reason::ReasonRef;
 use crate::engine::reason::ReasonStore;
 use crate::engine::variables::DomainId;
 use crate::engine::Assignments;
@@ -62,7 +75,9 @@ use crate::proof::explain_root_assignment;
 use crate::proof::finalize_proof;
 use crate::proof::FinalizingContext;
 use crate::proof::ProofLog;
+use crate::proof::Reification;
 use crate::proof::RootExplanationContext;
+use crate::proof::DUMMY_STEP_ID;
 use crate::propagators::nogoods::LearningOptions;
 use crate::propagators::nogoods::NogoodPropagator;
 use crate::pumpkin_assert_advanced;
@@ -119,6 +134,9 @@ pub struct ConstraintSatisfactionSolver {
     restart_strategy: RestartStrategy,
     /// Holds the assumptions when the solver is queried to solve under assumptions.
     assumptions: Vec<Predicate>,
+    /// The activation literals of the scopes which are currently open (see
+    /// [`Solver::push_scope`]). The activation literals are implicitly assumed when solving.
+    scopes: Vec<Literal>,
     semantic_minimiser: SemanticMinimiser,
     /// Tracks information related to the assignments of integer variables.
     pub(crate) assignments: Assignments,
@@ -150,6 +168,12 @@ pub struct ConstraintSatisfactionSolver {
     unit_nogood_step_ids: HashMap<Predicate, StepId>,
     /// The resolver which is used upon a conflict.
     conflict_resolver: Box<dyn Resolver>,
+    /// Whether the model still has to be presolved; the model is only presolved before the first
+    /// call to solve, constraints which are added afterwards are not presolved.
+    requires_presolve: bool,
+    /// The symmetries of the problem which are used to derive symmetric nogoods from the learned
+    /// nogoods (see [`Symmetry`]).
+    symmetries: Vec<Box<dyn Symmetry>>,
 
     pub(crate) trailed_values: TrailedValues,
 }
@@ -177,6 +201,17 @@ pub enum CoreExtractionResult {
     Core(Vec<Predicate>),
 }
 
+/// The result of probing a predicate during presolving (see
+/// [`ConstraintSatisfactionSolver::presolve`]).
+#[derive(Debug)]
+enum ProbingResult {
+    /// Propagating the predicate did not lead to a conflict; contains the values of the Boolean
+    /// variables which have been fixed by propagation.
+    Consistent(HashMap<DomainId, i32>),
+    /// Propagating the predicate led to a conflict; contains the nogood derived from the conflict.
+    Failed(Vec<Predicate>),
+}
+
 /// During search, the CP solver will inevitably evaluate partial assignments that violate at
 /// least one constraint. When this happens, conflict resolution is applied to restore the
 /// solver to a state from which it can continue the search.
@@ -197,6 +232,10 @@ pub struct SatisfactionSolverOptions {
     pub restart_options: RestartOptions,
     /// Whether learned clause minimisation should take place
     pub learning_clause_minimisation: bool,
+    /// Whether learned clause minimisation asks the propagators for generalised reasons to remove
+    /// further predicates; this only takes place if [`Self::learning_clause_minimisation`] is
+    /// enabled.
+    pub learning_reason_generalisation: bool,
     /// A random number generator which is used by the [`Solver`] to determine randomised values.
     pub random_generator: SmallRng,
     /// The proof log for the solver.
@@ -205,6 +244,32 @@ pub struct SatisfactionSolverOptions {
     pub conflict_resolver: ConflictResolver,
     /// The options which influence the learning of the solver.
     pub learning_options: LearningOptions,
+    /// The options which determine whether and when extension literals are introduced during
+    /// conflict analysis.
+    pub extended_resolution_options: ExtendedResolutionOptions,
+    /// If set, the solver backtracks chronologically (i.e. a single decision level) after a
+    /// conflict whenever the backjump to the asserting level would undo more than the provided
+    /// number of decision levels (see \[1\]).
+    ///
+    /// # Bibliography
+    /// \[1\] A. Nadel and V. Ryvchin, ‘Chronological backtracking’, in Theory and Applications of
+    /// Satisfiability Testing (SAT), 2018.
+    pub chronological_backtracking_threshold: Option<usize>,
+    /// The options which determine whether and for how long the model is presolved at the root
+    /// level before searching.
+    pub presolve_options: PresolveOptions,
+    /// Determines until when the propagation of expensive propagators (e.g. the cumulative and
+    /// global cardinality propagators) is delayed once they are enqueued; by default, these are
+    /// propagated once all cheaper propagators have reached a fixed point.
+    pub expensive_propagator_delay: PropagationDelay,
+    /// Whether the time spent in every call to a propagator is measured and reported in the
+    /// statistics, in which case the profile of every propagator instance is reported as well
+    /// (rather than only the profiles aggregated per propagator name); since this adds overhead
+    /// to every call, it is disabled by default.
+    pub profile_propagators: bool,
+    /// The trace of the analysed conflicts, which is used to debug the explanations of
+    /// propagators.
+    pub conflict_trace: ConflictTrace,
 }
 
 impl Default for SatisfactionSolverOptions {
@@ -212,10 +277,17 @@ impl Default for SatisfactionSolverOptions {
         SatisfactionSolverOptions {
             restart_options: RestartOptions::default(),
             learning_clause_minimisation: true,
+            learning_reason_generalisation: false,
             random_generator: SmallRng::seed_from_u64(42),
             proof_log: ProofLog::default(),
             conflict_resolver: ConflictResolver::default(),
             learning_options: LearningOptions::default(),
+            extended_resolution_options: ExtendedResolutionOptions::default(),
+            chronological_backtracking_threshold: None,
+            presolve_options: PresolveOptions::default(),
+            expensive_propagator_delay: PropagationDelay::default(),
+            profile_propagators: false,
+            conflict_trace: ConflictTrace::default(),
         }
     }
 }
@@ -295,8 +367,7 @@ impl ConstraintSatisfactionSolver {
         let enqueue_decision = propagators[propagator_id].notify(context, local_id, event.into());
 
         if enqueue_decision == EnqueueDecision::Enqueue {
-            propagator_queue
-                .enqueue_propagator(propagator_id, propagators[propagator_id].priority());
+            propagator_queue.enqueue_propagator(propagator_id, &propagators[propagator_id]);
         }
     }
 
@@ -344,10 +415,19 @@ impl ConstraintSatisfactionSolver {
         SolutionReference::new(&self.assignments)
     }
 
+    /// Returns whether a proof is being logged.
+    pub(crate) fn is_logging_proof(&self) -> bool {
+        self.internal_parameters.proof_log.is_logging_proof()
+    }
+
     /// Conclude the proof with the unsatisfiable claim.
     ///
     /// This method will finish the proof. Any new operation will not be logged to the proof.
+    ///
+    /// Returns an error, and leaves the proof unfinished, if a scope is open.
     pub fn conclude_proof_unsat(&mut self) -> std::io::Result<()> {
+        self.ensure_no_open_scopes_in_proof()?;
+
         let proof = std::mem::take(&mut self.internal_parameters.proof_log);
         proof.unsat(&self.variable_names)
     }
@@ -355,11 +435,144 @@ impl ConstraintSatisfactionSolver {
     /// Conclude the proof with the optimality claim.
     ///
     /// This method will finish the proof. Any new operation will not be logged to the proof.
+    ///
+    /// Returns an error, and leaves the proof unfinished, if a scope is open.
     pub fn conclude_proof_optimal(&mut self, bound: Predicate) -> std::io::Result<()> {
+        self.ensure_no_open_scopes_in_proof()?;
+
         let proof = std::mem::take(&mut self.internal_parameters.proof_log);
         proof.optimal(bound, &self.variable_names)
     }
 
+    /// The proof cannot be concluded while a scope is open, since the conclusion depends on
+    /// constraints which can still be retracted. As the callers of the conclusion methods
+    /// typically ignore the result, a warning is logged as well.
+    fn ensure_no_open_scopes_in_proof(&self) -> std::io::Result<()> {
+        if self.scopes.is_empty() || !self.internal_parameters.proof_log.is_logging_proof() {
+            return Ok(());
+        }
+
+        warn!(
+            "The proof is not concluded since {} scope(s) are open",
+            self.scopes.len()
+        );
+        Err(std::io::Error::other(
+            "the proof cannot be concluded while a scope is open",
+        ))
+    }
+
+    /// Log the current solution to the proof, as the values of the named variables.
+    ///
+    /// The solution justifies the next objective bound which is logged with
+    /// [`ConstraintSatisfactionSolver::log_objective_bound_to_proof`].
+    pub fn log_solution_to_proof(&mut self) {
+        if !self.internal_parameters.proof_log.is_logging_proof() {
+            return;
+        }
+
+        let assignment = self
+            .variable_names
+            .named_integers()
+            .into_iter()
+            .filter_map(|domain_id| {
+                self.assignments
+                    .get_assigned_value(&domain_id)
+                    .map(|value| predicate!(domain_id == value))
+            })
+            .collect::<Vec<_>>();
+
+        let _ = self.internal_parameters.proof_log.log_solution(assignment);
+    }
+
+    /// Log the nogood which tightens the bound on the objective to `bound` to the proof. The
+    /// nogood is justified by the last solution which was logged with
+    /// [`ConstraintSatisfactionSolver::log_solution_to_proof`].
+    pub fn log_objective_bound_to_proof(&mut self, bound: Predicate) {
+        let _ = self
+            .internal_parameters
+            .proof_log
+            .log_objective_bound(bound);
+    }
+
+    /// Log a solution with the given objective value to the proof, after which only better
+    /// solutions are sought. Returns the id of the constraint which states that the objective
+    /// value is smaller than `objective_value`.
+    pub fn log_improving_solution_to_proof(
+        &mut self,
+        assignment: impl IntoIterator<Item = Literal>,
+        objective_value: u64,
+    ) -> NonZeroU64 {
+        self.internal_parameters
+            .proof_log
+            .log_improving_solution(
+                assignment
+                    .into_iter()
+                    .map(|literal| literal.get_true_predicate()),
+                objective_value,
+            )
+            .unwrap_or(DUMMY_STEP_ID)
+    }
+
+    /// Log the definition of the fresh `literal` as the reification of `\sum w_i * l_i >=
+    /// degree` to the proof.
+    pub fn log_reification_to_proof(
+        &mut self,
+        literal: Literal,
+        terms: &[(u64, Literal)],
+        degree: u64,
+    ) -> Reification {
+        self.internal_parameters
+            .proof_log
+            .log_reification(literal, terms, degree)
+            .unwrap_or(Reification::DUMMY)
+    }
+
+    /// Log the constraint `\sum w_i * l_i >= degree`, which is implied by unit propagation, to
+    /// the proof.
+    pub fn log_implied_constraint_to_proof(
+        &mut self,
+        terms: &[(u64, Literal)],
+        degree: u64,
+    ) -> NonZeroU64 {
+        self.internal_parameters
+            .proof_log
+            .log_implied_constraint(terms, degree)
+            .unwrap_or(DUMMY_STEP_ID)
+    }
+
+    /// Log the linear combination of the constraints derived by the given steps to the proof.
+    pub fn log_linear_combination_to_proof(
+        &mut self,
+        terms: &[(u64, NonZeroU64)],
+        divisor: u64,
+    ) -> NonZeroU64 {
+        self.internal_parameters
+            .proof_log
+            .log_linear_combination(terms, divisor)
+            .unwrap_or(DUMMY_STEP_ID)
+    }
+
+    /// Log the definition of the fresh `literal` as false to the proof.
+    pub fn log_false_literal_to_proof(&mut self, literal: Literal) -> NonZeroU64 {
+        self.internal_parameters
+            .proof_log
+            .log_false_literal(literal)
+            .unwrap_or(DUMMY_STEP_ID)
+    }
+
+    /// Log the clause which is implied by the sum of the `antecedents` and unit propagation to
+    /// the proof.
+    pub fn log_implied_clause_to_proof(
+        &mut self,
+        predicates: impl IntoIterator<Item = Predicate>,
+        antecedents: &[NonZeroU64],
+    ) {
+        let _ = self
+            .internal_parameters
+            .proof_log
+            .log_implied_clause(predicates, antecedents);
+    }
+
     fn complete_proof(&mut self) {
         let conflict = match self.state.get_conflict_info() {
             StoredConflictInfo::Propagator {
@@ -368,6 +581,7 @@ impl ConstraintSatisfactionSolver {
             } => {
                 let _ = self.internal_parameters.proof_log.log_inference(
                     self.propagators.get_tag(propagator_id),
+                    self.propagators.get_inference_label(propagator_id),
                     conflict_nogood.iter().copied(),
                     None,
                 );
@@ -405,9 +619,10 @@ impl ConstraintSatisfactionSolver {
             last_notified_cp_trail_index: 0,
             state: CSPSolverState::default(),
             assumptions: Vec::default(),
+            scopes: Vec::default(),
             assignments: Assignments::default(),
             watch_list_cp: WatchListCP::default(),
-            propagator_queue: PropagatorQueue::new(5),
+            propagator_queue: PropagatorQueue::new(5, solver_options.expensive_propagator_delay),
             reason_store: ReasonStore::default(),
             event_drain: vec![],
             backtrack_event_drain: vec![],
@@ -420,10 +635,14 @@ impl ConstraintSatisfactionSolver {
             unit_nogood_step_ids: Default::default(),
             conflict_resolver: match solver_options.conflict_resolver {
                 ConflictResolver::NoLearning => Box::new(NoLearningResolver),
-                ConflictResolver::UIP => Box::new(ResolutionResolver::default()),
+                ConflictResolver::UIP => Box::new(ResolutionResolver::with_extended_resolution(
+                    solver_options.extended_resolution_options,
+                )),
             },
             internal_parameters: solver_options,
             trailed_values: TrailedValues::default(),
+            requires_presolve: true,
+            symmetries: vec![],
         };
 
         // As a convention, the assignments contain a dummy domain_id=0, which represents a 0-1
@@ -470,6 +689,23 @@ impl ConstraintSatisfactionSolver {
         let start_time = Instant::now();
 
         self.initialise(assumptions);
+
+        // The brancher is informed of the initial search mode, since it does not know whether
+        // the search starts in focused mode when the modes alternate.
+        if let Some(search_mode) = self.restart_strategy.initial_search_mode() {
+            brancher.on_search_mode_switch(search_mode);
+        }
+
+        if self.requires_presolve
+            && self
+                .internal_parameters
+                .presolve_options
+                .time_budget
+                .is_some()
+        {
+            self.presolve(termination, brancher);
+        }
+
         let result = self.solve_internal(termination, brancher);
 
         self.solver_statistics
@@ -492,12 +728,38 @@ impl ConstraintSatisfactionSolver {
         // going through all the propagators
         if should_log_statistics() {
             self.solver_statistics.log(StatisticLogger::default());
+            self.restart_strategy
+                .log_statistics(StatisticLogger::new(["restartStatistics"]));
+            // The profiling statistics are aggregated over all the propagators with the same name;
+            // if the propagators are profiled, then they are also logged per propagator instance.
+            let mut statistics_per_name: HashMap<&str, PropagatorStatistics> = HashMap::default();
             for (index, propagator) in self.propagators.iter_propagators().enumerate() {
                 propagator.log_statistics(StatisticLogger::new([
                     propagator.name(),
                     "number",
                     index.to_string().as_str(),
                 ]));
+
+                let statistics = self.propagators.get_statistics(PropagatorId(index as u32));
+                if self.internal_parameters.profile_propagators {
+                    statistics.log(StatisticLogger::new([
+                        "propagatorProfile",
+                        propagator.name(),
+                        "number",
+                        index.to_string().as_str(),
+                    ]));
+                }
+
+                statistics_per_name
+                    .entry(propagator.name())
+                    .or_default()
+                    .merge(statistics);
+            }
+            for (name, statistics) in statistics_per_name
+                .into_iter()
+                .sorted_by_key(|(name, _)| *name)
+            {
+                statistics.log(StatisticLogger::new(["propagatorProfile", name, "total"]));
             }
         }
     }
@@ -549,6 +811,11 @@ impl ConstraintSatisfactionSolver {
         domain_id
     }
 
+    /// Give an additional name to the integer variable, see [`Solver::add_variable_alias`].
+    pub fn add_variable_alias(&mut self, domain_id: DomainId, alias: String) {
+        self.variable_names.add_alias(domain_id, alias);
+    }
+
     /// Creates an integer variable with a domain containing only the values in `values`
     pub fn create_new_integer_variable_sparse(
         &mut self,
@@ -574,7 +841,9 @@ impl ConstraintSatisfactionSolver {
     /// assumptions which cannot be satisfied together with the rest of the formula. The clause is
     /// not necessarily unique or minimal.
     ///
-    /// The unsatisfiable core can be verified with reverse unit propagation (RUP).
+    /// The unsatisfiable core can be verified with reverse unit propagation (RUP). If a proof is
+    /// logged, the core is logged to it as a nogood, together with the inferences from which it is
+    /// derived.
     ///
     /// *Notes:*
     ///   - If the solver is not in an unsatisfied state, this method will panic.
@@ -661,7 +930,11 @@ impl ConstraintSatisfactionSolver {
                     event_drain: &mut self.event_drain,
                     backtrack_event_drain: &mut self.backtrack_event_drain,
                     should_minimise: self.internal_parameters.learning_clause_minimisation,
+                    should_generalise_reasons: self
+                        .internal_parameters
+                        .learning_reason_generalisation,
                     proof_log: &mut self.internal_parameters.proof_log,
+                    conflict_trace: &mut self.internal_parameters.conflict_trace,
                     unit_nogood_step_ids: &self.unit_nogood_step_ids,
                     trailed_values: &mut self.trailed_values,
                 };
@@ -671,7 +944,36 @@ impl ConstraintSatisfactionSolver {
                     .resolve_conflict(&mut conflict_analysis_context)
                     .expect("Expected core extraction to be able to extract a core");
 
-                CoreExtractionResult::Core(learned_nogood.predicates.clone())
+                // The core is logged as a nogood, which is derived from the inferences logged
+                // during conflict analysis. The activation literals of the open scopes remain in
+                // the logged nogood, since the constraints in those scopes are conditional on them.
+                let _ = self
+                    .internal_parameters
+                    .proof_log
+                    .log_learned_clause(
+                        learned_nogood
+                            .predicates
+                            .iter()
+                            .map(|&predicate| !predicate),
+                        &self.variable_names,
+                    )
+                    .expect("Failed to write proof log");
+
+                // The activation literals of the open scopes are not part of the core, as the
+                // core is expressed in terms of the assumptions provided by the user.
+                let scopes = &self.scopes;
+                CoreExtractionResult::Core(
+                    learned_nogood
+                        .predicates
+                        .iter()
+                        .copied()
+                        .filter(|&predicate| {
+                            !scopes
+                                .iter()
+                                .any(|scope| scope.get_true_predicate() == predicate)
+                        })
+                        .collect(),
+                )
             })
     }
 
@@ -722,23 +1024,62 @@ impl ConstraintSatisfactionSolver {
     }
 
     pub fn restore_state_at_root(&mut self, brancher: &mut impl Brancher) {
-        if self.assignments.get_decision_level() != 0 {
-            ConstraintSatisfactionSolver::backtrack(
-                &mut self.assignments,
-                &mut self.last_notified_cp_trail_index,
-                &mut self.reason_store,
-                &mut self.propagator_queue,
-                &mut self.watch_list_cp,
-                &mut self.propagators,
-                &mut self.event_drain,
-                &mut self.backtrack_event_drain,
-                0,
-                brancher,
-                &mut self.trailed_values,
-            );
+        if self.assignments.get_decision_level() == 0 {
+            // A solution or timeout can also be reached at the root; the solver should then be
+            // ready to be used again without backtracking.
+            if !self.state.is_inconsistent() {
+                self.state.declare_ready();
+            }
+        } else {
+            self.backtrack_to_level(0, brancher);
             self.state.declare_ready();
         }
     }
+
+    /// Backtracks to the provided decision level, after which the solver is ready to make new
+    /// decisions and propagate again; this is used to test propagators outside of search.
+    pub(crate) fn restore_state_at_decision_level(
+        &mut self,
+        decision_level: usize,
+        brancher: &mut impl Brancher,
+    ) {
+        pumpkin_assert_simple!(decision_level < self.get_decision_level());
+        self.backtrack_to_level(decision_level, brancher);
+        self.state.declare_ready();
+    }
+
+    /// Returns the propagators which have been added to the solver; this is used to test
+    /// propagators outside of search.
+    pub(crate) fn propagator_store(&self) -> &PropagatorStore {
+        &self.propagators
+    }
+
+    /// Calls [`Propagator::propagate`] on a single propagator, without notifying the other
+    /// propagators of the domain changes; this is used to test propagators outside of search.
+    pub(crate) fn propagate_single_propagator(
+        &mut self,
+        propagator_id: PropagatorId,
+    ) -> PropagationStatusCP {
+        let context = PropagationContextMut::new(
+            &mut self.trailed_values,
+            &mut self.assignments,
+            &mut self.reason_store,
+            &mut self.semantic_minimiser,
+            propagator_id,
+        );
+        self.propagators[propagator_id].propagate(context)
+    }
+
+    /// Computes the reason of the propagation which is identified by `reason_ref` and appends it
+    /// to `reason`; this is used to test propagators outside of search.
+    pub(crate) fn compute_reason(&mut self, reason_ref: ReasonRef, reason: &mut Vec<Predicate>) {
+        let _ = self.reason_store.get_or_compute(
+            reason_ref,
+            ExplanationContext::from(&self.assignments),
+            &mut self.propagators,
+            reason,
+        );
+    }
 }
 
 // methods that serve as the main building blocks
@@ -750,7 +1091,12 @@ impl ConstraintSatisfactionSolver {
              Missed extracting the core?"
         );
         self.state.declare_solving();
-        assumptions.clone_into(&mut self.assumptions);
+
+        // The activation literals of the open scopes are posted before the provided assumptions.
+        self.assumptions.clear();
+        self.assumptions
+            .extend(self.scopes.iter().map(|scope| scope.get_true_predicate()));
+        self.assumptions.extend_from_slice(assumptions);
     }
 
     fn solve_internal(
@@ -766,24 +1112,28 @@ impl ConstraintSatisfactionSolver {
 
             self.propagate();
 
-            if self.state.no_conflict() {
-                // Restarts should only occur after a new decision level has been declared to
-                // account for the fact that all assumptions should be assigned when restarts take
-                // place. Since one assumption is posted per decision level, all assumptions are
-                // assigned when the decision level is strictly larger than the number of
-                // assumptions.
-                if self.get_decision_level() > self.assumptions.len()
-                    && self.restart_strategy.should_restart()
-                {
-                    self.restart_during_search(brancher);
-                }
+            // Restarts should only occur after a new decision level has been declared to account
+            // for the fact that all assumptions should be assigned when restarts take place. Since
+            // one assumption is posted per decision level, all assumptions are assigned when the
+            // decision level is strictly larger than the number of assumptions.
+            //
+            // Note that inprocessing during a restart can lead to a conflict at the root level.
+            if self.state.no_conflict()
+                && self.get_decision_level() > self.assumptions.len()
+                && self.restart_strategy.should_restart()
+            {
+                self.restart_during_search(brancher);
+            }
 
+            if self.state.no_conflict() {
                 let branching_result = self.make_next_decision(brancher);
 
                 if let Err(flag) = branching_result {
                     return flag;
                 }
             } else {
+                self.backtrack_to_conflict_level(brancher);
+
                 if self.get_decision_level() == 0 {
                     if self.assumptions.is_empty() {
                         // Only complete the proof when _not_ solving under assumptions. It is
@@ -806,6 +1156,32 @@ impl ConstraintSatisfactionSolver {
         }
     }
 
+    /// Backtracks to the highest decision level of the predicates in the current conflict if this
+    /// level is lower than the current decision level, since conflict analysis requires the
+    /// conflict to contain a predicate from the current decision level.
+    ///
+    /// This can only occur when the propagation of propagators is delayed until a leaf of the
+    /// search tree (see [`PropagationDelay::UntilLeaf`]).
+    fn backtrack_to_conflict_level(&mut self, brancher: &mut impl Brancher) {
+        let conflict_nogood = match self.state.get_conflict_info() {
+            StoredConflictInfo::Propagator {
+                conflict_nogood, ..
+            }
+            | StoredConflictInfo::EmptyDomain { conflict_nogood } => conflict_nogood,
+            StoredConflictInfo::RootLevelConflict(_) => return,
+        };
+
+        let conflict_level = conflict_nogood
+            .iter()
+            .filter_map(|predicate| self.assignments.get_decision_level_for_predicate(predicate))
+            .max()
+            .unwrap_or(0);
+
+        if conflict_level < self.get_decision_level() {
+            self.backtrack_to_level(conflict_level, brancher);
+        }
+    }
+
     fn decay_nogood_activities(&mut self) {
         match self.propagators[Self::get_nogood_propagator_id()].downcast_mut::<NogoodPropagator>()
         {
@@ -844,6 +1220,15 @@ impl ConstraintSatisfactionSolver {
 
         // If there is a next decision, make the decision.
         let Some(decision_predicate) = brancher.next_decision(context) else {
+            // The propagators which are delayed until a leaf of the search tree still need to be
+            // propagated before the assignment can be declared a solution.
+            if self.propagator_queue.release_leaf_propagators() {
+                self.solver_statistics
+                    .propagation_statistics
+                    .num_leaf_releases += 1;
+                return Ok(());
+            }
+
             // Otherwise there are no more decisions to be made,
             // all predicates have been applied without a conflict,
             // meaning the problem is feasible.
@@ -904,15 +1289,34 @@ impl ConstraintSatisfactionSolver {
             event_drain: &mut self.event_drain,
             backtrack_event_drain: &mut self.backtrack_event_drain,
             should_minimise: self.internal_parameters.learning_clause_minimisation,
+            should_generalise_reasons: self.internal_parameters.learning_reason_generalisation,
             proof_log: &mut self.internal_parameters.proof_log,
+            conflict_trace: &mut self.internal_parameters.conflict_trace,
             unit_nogood_step_ids: &self.unit_nogood_step_ids,
             trailed_values: &mut self.trailed_values,
         };
 
-        let learned_nogood = self
+        let mut learned_nogood = self
             .conflict_resolver
             .resolve_conflict(&mut conflict_analysis_context);
 
+        if let Some(learned_nogood) = learned_nogood.as_mut() {
+            if Self::should_backtrack_chronologically(
+                self.internal_parameters
+                    .chronological_backtracking_threshold,
+                current_decision_level,
+                learned_nogood,
+            ) {
+                // The asserting predicate is propagated at the decision level below the
+                // conflict, rather than at the (lower) backjump level.
+                learned_nogood.backjump_level = current_decision_level - 1;
+                conflict_analysis_context
+                    .counters
+                    .learned_clause_statistics
+                    .num_chronological_backtracks += 1;
+            }
+        }
+
         // important to notify about the conflict _before_ backtracking removes literals from
         // the trail -> although in the current version this does nothing but notify that a
         // conflict happened
@@ -967,12 +1371,39 @@ impl ConstraintSatisfactionSolver {
                 .average_learned_clause_length
                 .add_term(learned_nogood.predicates.len() as u64);
 
+            let learned_predicates =
+                (!self.symmetries.is_empty()).then(|| learned_nogood.predicates.clone());
+
             self.add_learned_nogood(learned_nogood);
+
+            if let Some(learned_predicates) = learned_predicates {
+                self.add_symmetric_nogoods(&learned_predicates);
+            }
         }
 
         self.state.declare_solving();
     }
 
+    /// Returns whether the solver should backtrack a single decision level after learning the
+    /// provided nogood, rather than backjumping to the backjump level of the nogood.
+    ///
+    /// Unit nogoods are always added at the root. For other nogoods, the asserting predicate is
+    /// then propagated at a decision level which is higher than the levels of the other predicates
+    /// in the nogood. Rather than maintaining a trail with out-of-order decision levels, the
+    /// propagation is assigned the current decision level; if the solver later backtracks below
+    /// the current decision level (but not below the backjump level), then the propagation is
+    /// re-implied after backtracking (see [`Self::backtrack_to_level`]).
+    fn should_backtrack_chronologically(
+        threshold: Option<usize>,
+        current_decision_level: usize,
+        learned_nogood: &LearnedNogood,
+    ) -> bool {
+        threshold.is_some_and(|threshold| {
+            learned_nogood.predicates.len() > 1
+                && current_decision_level - learned_nogood.backjump_level > threshold
+        })
+    }
+
     fn add_learned_nogood(&mut self, learned_nogood: LearnedNogood) {
         let mut context = PropagationContextMut::new(
             &mut self.trailed_values,
@@ -987,7 +1418,59 @@ impl ConstraintSatisfactionSolver {
             learned_nogood.predicates,
             &mut context,
             &mut self.solver_statistics,
-        )
+        );
+
+        // The conflict resolver has backtracked to the backjump level; the nogoods which have
+        // been asserted after backtracking chronologically are only re-implied after the
+        // learned nogood has asserted its predicate.
+        self.reimply_chronologically_asserted_nogoods();
+    }
+
+    /// Adds the images of the learned nogood under the registered symmetries as learned nogoods.
+    ///
+    /// An image is only added if it is neither propagating nor conflicting in the current state
+    /// (see [`NogoodPropagator::add_non_propagating_learned_nogood`]). Symmetric nogoods cannot be
+    /// justified in the proof, and the definitions of extension literals are not symmetric; hence
+    /// no symmetric nogoods are derived when proof logging or extended resolution is enabled.
+    fn add_symmetric_nogoods(&mut self, learned_nogood: &[Predicate]) {
+        if self.internal_parameters.proof_log.is_logging_proof()
+            || self.conflict_resolver.extended_resolution().is_some()
+        {
+            return;
+        }
+
+        let context = PropagationContextMut::new(
+            &mut self.trailed_values,
+            &mut self.assignments,
+            &mut self.reason_store,
+            &mut self.semantic_minimiser,
+            Self::get_nogood_propagator_id(),
+        );
+        let nogood_propagator = Self::get_nogood_propagator(&mut self.propagators);
+
+        for symmetry in self.symmetries.iter() {
+            let Some(image) = learned_nogood
+                .iter()
+                .map(|&predicate| symmetry.map_predicate(predicate))
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+
+            // The symmetry could map the nogood onto itself.
+            if image
+                .iter()
+                .all(|predicate| learned_nogood.contains(predicate))
+            {
+                continue;
+            }
+
+            if nogood_propagator.add_non_propagating_learned_nogood(image, &context) {
+                self.solver_statistics
+                    .learned_clause_statistics
+                    .num_symmetric_nogoods += 1;
+            }
+        }
     }
 
     pub(crate) fn add_asserting_nogood_to_nogood_propagator(
@@ -1019,19 +1502,730 @@ impl ConstraintSatisfactionSolver {
             "Sanity check: restarts should not trigger whilst assigning assumptions"
         );
 
-        // no point backtracking past the assumption level
-        if self.get_decision_level() <= self.assumptions.len() {
-            return;
+        // no point backtracking past the assumption level
+        if self.get_decision_level() <= self.assumptions.len() {
+            return;
+        }
+
+        if brancher.is_restart_pointless() {
+            // If the brancher is static then there is no point in restarting as it would make the
+            // exact same decision
+            return;
+        }
+
+        self.solver_statistics.engine_statistics.num_restarts += 1;
+
+        self.backtrack_to_level(0, brancher);
+
+        let learning_options = self.internal_parameters.learning_options;
+        if learning_options.inprocess_nogoods
+            && self
+                .solver_statistics
+                .engine_statistics
+                .num_restarts
+                .is_multiple_of(learning_options.inprocessing_interval.max(1))
+        {
+            self.inprocess_learned_nogoods(brancher);
+        }
+
+        if self.state.no_conflict() {
+            self.define_extension_literals();
+        }
+
+        if let Some(search_mode) = self.restart_strategy.notify_restart() {
+            brancher.on_search_mode_switch(search_mode);
+        }
+    }
+
+    /// Defines the extension literals for the candidate pairs of predicates which have been
+    /// identified by the conflict resolver (see
+    /// [`ExtendedResolution`](crate::engine::conflict_analysis::ExtendedResolution)).
+    ///
+    /// For a pair `(p, q)`, the fresh literal `d` is defined by the clauses `d -> p`, `d -> q`,
+    /// and `(p /\ q) -> d`.
+    ///
+    /// The definitions are added at the root level, and logged to the proof.
+    fn define_extension_literals(&mut self) {
+        pumpkin_assert_simple!(self.get_decision_level() == 0);
+
+        let Some(extended_resolution) = self.conflict_resolver.extended_resolution() else {
+            return;
+        };
+
+        for (first, second) in extended_resolution.take_candidates() {
+            // If either of the predicates is assigned at the root, then it will not occur in
+            // learned nogoods anymore.
+            if [first, second].into_iter().any(|predicate| {
+                self.assignments.is_predicate_satisfied(predicate)
+                    || self.assignments.is_predicate_falsified(predicate)
+            }) {
+                continue;
+            }
+
+            let literal = self.create_new_literal(None);
+            let step_id = self
+                .internal_parameters
+                .proof_log
+                .log_definition(literal, &[first, second], &mut self.variable_names)
+                .expect("Failed to write proof log");
+
+            for clause in [
+                vec![literal.get_false_predicate(), first],
+                vec![literal.get_false_predicate(), second],
+                vec![literal.get_true_predicate(), !first, !second],
+            ] {
+                self.add_clause(clause)
+                    .expect("Defining a fresh literal cannot lead to a conflict");
+            }
+
+            self.conflict_resolver
+                .extended_resolution()
+                .expect("Extended resolution is enabled")
+                .add_definition((first, second), literal, step_id);
+            self.solver_statistics
+                .learned_clause_statistics
+                .num_extension_literals += 1;
+        }
+    }
+
+    /// Inprocesses the learned nogoods at the root level, which consists of the following steps:
+    /// 1. The learned nogoods which are satisfied at the root are removed.
+    /// 2. The learned nogoods which are subsumed by another nogood are removed, and the learned
+    ///    nogoods which can be strengthened using self-subsuming resolution are strengthened (see
+    ///    [`NogoodPropagator::remove_subsumed_learned_nogoods`]).
+    /// 3. The learned nogoods are vivified (see \[1\]); the predicates of a nogood are decided one
+    ///    at a time, and if this leads to a conflict (or falsifies a predicate in the nogood)
+    ///    before all predicates have been decided, the nogood is replaced by the nogood derived
+    ///    from the conflict, which only contains (a subset of) the decided predicates.
+    ///
+    /// The rewritten nogoods are logged to the proof.
+    ///
+    /// # Bibliography
+    /// \[1\] C.-M. Li, F. Xiao, M. Luo, F. Manyà, Z. Lü, and Y. Li, ‘Clause vivification by unit
+    /// propagation in CDCL SAT solvers’, Artificial Intelligence, vol. 279, 2020.
+    fn inprocess_learned_nogoods(&mut self, brancher: &mut impl Brancher) {
+        pumpkin_assert_simple!(self.get_decision_level() == 0);
+
+        self.solver_statistics.inprocessing_statistics.num_rounds += 1;
+
+        let context = PropagationContext::new(&self.assignments);
+        let nogood_propagator = Self::get_nogood_propagator(&mut self.propagators);
+
+        let num_satisfied =
+            nogood_propagator.remove_satisfied_learned_nogoods(context, &self.reason_store);
+        let (num_subsumed, strengthened_nogoods) =
+            nogood_propagator.remove_subsumed_learned_nogoods(context, &self.reason_store);
+        let vivification_candidates =
+            nogood_propagator.get_vivification_candidates(context, &self.reason_store);
+
+        self.solver_statistics
+            .inprocessing_statistics
+            .num_satisfied_nogoods += num_satisfied as u64;
+        self.solver_statistics
+            .inprocessing_statistics
+            .num_subsumed_nogoods += num_subsumed as u64;
+        self.solver_statistics
+            .inprocessing_statistics
+            .num_strengthened_nogoods += strengthened_nogoods.len() as u64;
+
+        let tag = self.propagators.get_tag(Self::get_nogood_propagator_id());
+        let label = self
+            .propagators
+            .get_inference_label(Self::get_nogood_propagator_id());
+        for strengthened_nogood in strengthened_nogoods {
+            // The subsuming nogood propagates the negation of the resolved predicate, which
+            // implies the removed predicate; then the original nogood is violated. Note that the
+            // inferences are logged in reverse-application order.
+            let _ = self.internal_parameters.proof_log.log_inference(
+                tag,
+                label,
+                strengthened_nogood.original_nogood.iter().copied(),
+                None,
+            );
+            let _ = self.internal_parameters.proof_log.log_inference(
+                tag,
+                label,
+                strengthened_nogood
+                    .subsuming_nogood
+                    .iter()
+                    .copied()
+                    .filter(|&predicate| predicate != strengthened_nogood.resolved_predicate),
+                Some(!strengthened_nogood.resolved_predicate),
+            );
+
+            self.add_inprocessed_nogood(
+                strengthened_nogood.strengthened_nogood,
+                Some(strengthened_nogood.lbd),
+                true,
+            );
+            if self.state.is_conflicting() {
+                return;
+            }
+        }
+
+        for id in vivification_candidates {
+            let nogood = Self::get_nogood_propagator(&mut self.propagators)
+                .get_nogood_predicates(id)
+                .to_vec();
+
+            // Root-level propagations during inprocessing could have satisfied the nogood.
+            if nogood
+                .iter()
+                .any(|&predicate| self.assignments.is_predicate_falsified(predicate))
+            {
+                let _ =
+                    Self::get_nogood_propagator(&mut self.propagators).remove_learned_nogood(id);
+                self.solver_statistics
+                    .inprocessing_statistics
+                    .num_satisfied_nogoods += 1;
+                continue;
+            }
+
+            let lbd = Self::get_nogood_propagator(&mut self.propagators)
+                .remove_learned_nogood(id)
+                .lbd;
+            self.solver_statistics
+                .inprocessing_statistics
+                .num_vivified_nogoods += 1;
+
+            match self.vivify_nogood(&nogood, brancher) {
+                Some(vivified_nogood) => {
+                    let num_removed_predicates = nogood.len() - vivified_nogood.len();
+                    if num_removed_predicates > 0 {
+                        self.solver_statistics
+                            .inprocessing_statistics
+                            .num_shortened_nogoods += 1;
+                        self.solver_statistics
+                            .inprocessing_statistics
+                            .num_removed_predicates += num_removed_predicates as u64;
+                    }
+
+                    let lbd = lbd.min(vivified_nogood.len() as u32);
+                    self.add_inprocessed_nogood(vivified_nogood, Some(lbd), true);
+                }
+                // The nogood could not be vivified, so it is added back unchanged; it is already
+                // present in the proof.
+                None => self.add_inprocessed_nogood(nogood, Some(lbd), false),
+            }
+
+            if self.state.is_conflicting() {
+                return;
+            }
+        }
+    }
+
+    /// Attempts to vivify the provided nogood by deciding its predicates one at a time. Returns
+    /// the vivified nogood if a conflict was found (or a predicate in the nogood was falsified)
+    /// before all predicates were decided, and [`None`] otherwise.
+    ///
+    /// Note that the provided nogood should not be present in the nogood propagator, and that the
+    /// solver is backtracked to the root level afterwards.
+    fn vivify_nogood(
+        &mut self,
+        nogood: &[Predicate],
+        brancher: &mut impl Brancher,
+    ) -> Option<Vec<Predicate>> {
+        let mut vivified_nogood = None;
+
+        for &predicate in nogood {
+            // If the predicate is implied by the previously decided predicates, then it is
+            // redundant.
+            if self.assignments.is_predicate_satisfied(predicate) {
+                continue;
+            }
+
+            // If the negation of the predicate is implied by the previously decided predicates,
+            // then the vivified nogood consists of the decided predicates which imply the
+            // negation together with the predicate itself.
+            if self.assignments.is_predicate_falsified(predicate) {
+                self.state
+                    .declare_conflict(StoredConflictInfo::EmptyDomain {
+                        conflict_nogood: vec![!predicate].into(),
+                    });
+
+                let mut derived_nogood = self.derive_nogood_from_decisions();
+                derived_nogood.push(predicate);
+                vivified_nogood = Some(derived_nogood);
+                break;
+            }
+
+            self.declare_new_decision_level();
+            self.assignments
+                .post_predicate(predicate, None)
+                .expect("Deciding an unassigned predicate cannot fail");
+            self.propagate();
+
+            if self.state.is_conflicting() {
+                vivified_nogood = Some(self.derive_nogood_from_decisions());
+                break;
+            }
+        }
+
+        self.backtrack_to_level(0, brancher);
+        if self.state.is_conflicting() {
+            self.state.declare_solving();
+        }
+
+        vivified_nogood
+    }
+
+    /// Derives a nogood which only contains decision predicates from the current conflict.
+    fn derive_nogood_from_decisions(&mut self) -> Vec<Predicate> {
+        // The brancher is not informed about the predicates in the conflict since the decisions
+        // during inprocessing are not made by the brancher.
+        let mut brancher = DynamicBrancher::new(vec![]);
+
+        let mut conflict_analysis_context = ConflictAnalysisContext {
+            assignments: &mut self.assignments,
+            counters: &mut self.solver_statistics,
+            solver_state: &mut self.state,
+            reason_store: &mut self.reason_store,
+            brancher: &mut brancher,
+            semantic_minimiser: &mut self.semantic_minimiser,
+            propagators: &mut self.propagators,
+            last_notified_cp_trail_index: &mut self.last_notified_cp_trail_index,
+            watch_list_cp: &mut self.watch_list_cp,
+            propagator_queue: &mut self.propagator_queue,
+            event_drain: &mut self.event_drain,
+            backtrack_event_drain: &mut self.backtrack_event_drain,
+            should_minimise: self.internal_parameters.learning_clause_minimisation,
+            should_generalise_reasons: self.internal_parameters.learning_reason_generalisation,
+            proof_log: &mut self.internal_parameters.proof_log,
+            conflict_trace: &mut self.internal_parameters.conflict_trace,
+            unit_nogood_step_ids: &self.unit_nogood_step_ids,
+            trailed_values: &mut self.trailed_values,
+        };
+
+        ResolutionResolver::with_mode(AnalysisMode::AllDecision)
+            .resolve_conflict(&mut conflict_analysis_context)
+            .expect("Expected to be able to derive a nogood from the conflict")
+            .predicates
+    }
+
+    /// Adds a nogood which has been derived during inprocessing to the nogood propagator at the
+    /// root level, and propagates it. If `log_to_proof` is true, then the nogood is logged to the
+    /// proof as well.
+    ///
+    /// If an LBD is provided then the nogood is added as a learned nogood, otherwise it is added as
+    /// a permanent nogood which cannot be removed by clause management.
+    fn add_inprocessed_nogood(
+        &mut self,
+        nogood: Vec<Predicate>,
+        lbd: Option<u32>,
+        log_to_proof: bool,
+    ) {
+        pumpkin_assert_simple!(self.get_decision_level() == 0);
+
+        if log_to_proof {
+            let step_id = self
+                .internal_parameters
+                .proof_log
+                .log_learned_clause(
+                    nogood.iter().map(|&predicate| !predicate),
+                    &self.variable_names,
+                )
+                .expect("Failed to write proof log");
+
+            if nogood.len() == 1 {
+                let _ = self.unit_nogood_step_ids.insert(!nogood[0], step_id);
+            }
+        }
+
+        let mut context = PropagationContextMut::new(
+            &mut self.trailed_values,
+            &mut self.assignments,
+            &mut self.reason_store,
+            &mut self.semantic_minimiser,
+            Self::get_nogood_propagator_id(),
+        );
+        let nogood_propagator = Self::get_nogood_propagator(&mut self.propagators);
+        let result = match lbd {
+            Some(lbd) => nogood_propagator.add_learned_nogood_at_root(nogood, lbd, &mut context),
+            None => nogood_propagator.add_nogood(nogood, None, &mut context),
+        };
+
+        if result.is_err() {
+            self.prepare_for_conflict_resolution();
+            return;
+        }
+
+        // The nogood could have propagated at the root, which should be propagated before making
+        // any further decisions.
+        self.propagate();
+    }
+
+    /// Presolves the model at the root level by probing the bounds of every variable, within the
+    /// time budget of the [`PresolveOptions`].
+    ///
+    /// For every variable `x` with bounds `[l, u]`, the predicate `[x <= l]` is decided and
+    /// propagated; if this leads to a conflict, then `[x >= l + 1]` holds at the root and the
+    /// lower bound is shaved, after which the new lower bound is probed (and analogously for the
+    /// upper bound). This establishes singleton consistency on the bounds of the variables.
+    ///
+    /// For a Boolean variable `x`, probing both bounds corresponds to failed-literal probing on
+    /// both polarities of `x`. If `x` can take either value, then the Boolean variables which are
+    /// fixed by propagation under both polarities are compared (see \[1\]):
+    /// - If a Boolean variable `y` takes the same value `v` under both polarities, then `[y == v]`
+    ///   is implied at the root.
+    /// - If `y` takes different values under both polarities, then `y` is equivalent to either `x`
+    ///   or its negation, and `y` is substituted by the equivalent literal over `x` in the nogoods.
+    ///   The propagators refer to the variables directly, so the equivalence itself is enforced by
+    ///   two permanent binary nogoods.
+    ///
+    /// All derived nogoods are logged to the proof; each of them is implied by propagation
+    /// under the probed predicate. The model is only presolved before the first call to solve.
+    ///
+    /// # Bibliography
+    /// \[1\] I. Lynce and J. Marques-Silva, ‘Probing-based preprocessing techniques for
+    /// propositional satisfiability’, in Proceedings of the IEEE International Conference on Tools
+    /// with Artificial Intelligence, 2003.
+    fn presolve(
+        &mut self,
+        termination: &mut impl TerminationCondition,
+        brancher: &mut impl Brancher,
+    ) {
+        pumpkin_assert_simple!(self.get_decision_level() == 0);
+
+        let Some(time_budget) = self.internal_parameters.presolve_options.time_budget else {
+            return;
+        };
+        let start_time = Instant::now();
+
+        self.requires_presolve = false;
+        self.solver_statistics.presolve_statistics.num_rounds += 1;
+
+        self.propagate();
+        if self.state.is_conflicting() {
+            return;
+        }
+
+        let mut eliminated_variables = HashSet::default();
+        let domains = self.assignments.get_domains().collect::<Vec<_>>();
+        for domain in domains {
+            if termination.should_stop() || start_time.elapsed() >= time_budget {
+                break;
+            }
+
+            self.probe_domain(
+                domain,
+                start_time,
+                time_budget,
+                &mut eliminated_variables,
+                brancher,
+            );
+            if self.state.is_conflicting() {
+                break;
+            }
+        }
+
+        self.solver_statistics
+            .presolve_statistics
+            .time_spent_in_presolve += start_time.elapsed().as_millis() as u64;
+    }
+
+    /// Probes the lower and upper bound of the provided domain until a bound is found which does
+    /// not lead to a conflict, or the time budget is exceeded (see
+    /// [`ConstraintSatisfactionSolver::presolve`]).
+    fn probe_domain(
+        &mut self,
+        domain: DomainId,
+        start_time: Instant,
+        time_budget: Duration,
+        eliminated_variables: &mut HashSet<DomainId>,
+        brancher: &mut impl Brancher,
+    ) {
+        let is_boolean = self.assignments.get_lower_bound(domain) == 0
+            && self.assignments.get_upper_bound(domain) == 1;
+
+        let mut fixed_when_false = None;
+        while self.assignments.get_lower_bound(domain) < self.assignments.get_upper_bound(domain) {
+            if start_time.elapsed() >= time_budget {
+                return;
+            }
+
+            let lower_bound = self.assignments.get_lower_bound(domain);
+            match self.probe(predicate!(domain <= lower_bound), brancher) {
+                ProbingResult::Consistent(fixed_variables) => {
+                    fixed_when_false = Some(fixed_variables);
+                    break;
+                }
+                ProbingResult::Failed(nogood) => {
+                    self.solver_statistics.presolve_statistics.num_shaved_values += 1;
+                    self.add_inprocessed_nogood(nogood, Some(1), true);
+                    if self.state.is_conflicting() {
+                        return;
+                    }
+                }
+            }
+        }
+
+        let mut fixed_when_true = None;
+        while self.assignments.get_lower_bound(domain) < self.assignments.get_upper_bound(domain) {
+            if start_time.elapsed() >= time_budget {
+                return;
+            }
+
+            let upper_bound = self.assignments.get_upper_bound(domain);
+            match self.probe(predicate!(domain >= upper_bound), brancher) {
+                ProbingResult::Consistent(fixed_variables) => {
+                    fixed_when_true = Some(fixed_variables);
+                    break;
+                }
+                ProbingResult::Failed(nogood) => {
+                    self.solver_statistics.presolve_statistics.num_shaved_values += 1;
+                    self.add_inprocessed_nogood(nogood, Some(1), true);
+                    if self.state.is_conflicting() {
+                        return;
+                    }
+                }
+            }
+        }
+
+        if let (true, Some(fixed_when_false), Some(fixed_when_true)) =
+            (is_boolean, fixed_when_false, fixed_when_true)
+        {
+            self.add_implied_literals(
+                domain,
+                &fixed_when_false,
+                &fixed_when_true,
+                eliminated_variables,
+            );
+        }
+    }
+
+    /// Adds the implied literals and equivalences which are derived from probing both polarities of
+    /// the Boolean variable `domain` (see [`ConstraintSatisfactionSolver::presolve`]).
+    ///
+    /// The provided maps contain the values of the Boolean variables which are fixed by
+    /// propagation when `domain` is set to false and true, respectively. The variables which have
+    /// been substituted by an equivalent literal are kept track of in `eliminated_variables`.
+    fn add_implied_literals(
+        &mut self,
+        domain: DomainId,
+        fixed_when_false: &HashMap<DomainId, i32>,
+        fixed_when_true: &HashMap<DomainId, i32>,
+        eliminated_variables: &mut HashSet<DomainId>,
+    ) {
+        let when_false = predicate!(domain <= 0);
+        let when_true = predicate!(domain >= 1);
+
+        let mut implied_variables = fixed_when_false
+            .iter()
+            .filter_map(|(&other, &value_when_false)| {
+                fixed_when_true
+                    .get(&other)
+                    .map(|&value_when_true| (other, value_when_false, value_when_true))
+            })
+            .collect::<Vec<_>>();
+        // The order of the derived nogoods should not depend on the iteration order of the map.
+        implied_variables.sort_by_key(|&(other, _, _)| other.id);
+
+        for (other, value_when_false, value_when_true) in implied_variables {
+            // The implications could have been fixed by the previously derived nogoods.
+            if self.assignments.is_domain_assigned(&other)
+                || self.assignments.is_domain_assigned(&domain)
+            {
+                continue;
+            }
+
+            if value_when_false == value_when_true {
+                // Both `[domain <= 0] -> [other == v]` and `[domain >= 1] -> [other == v]` are
+                // implied by propagation, from which `[other == v]` follows.
+                for probed_predicate in [when_false, when_true] {
+                    let _ = self
+                        .internal_parameters
+                        .proof_log
+                        .log_learned_clause(
+                            [!probed_predicate, predicate!(other == value_when_false)],
+                            &self.variable_names,
+                        )
+                        .expect("Failed to write proof log");
+                }
+
+                self.solver_statistics
+                    .presolve_statistics
+                    .num_implied_literals += 1;
+                self.add_inprocessed_nogood(
+                    vec![predicate!(other != value_when_false)],
+                    Some(1),
+                    true,
+                );
+            } else if domain.id < other.id
+                && !eliminated_variables.contains(&domain)
+                && !eliminated_variables.contains(&other)
+            {
+                // The equivalence is only handled once for every pair of variables, and a
+                // variable which has been substituted is not substituted again since its only
+                // remaining nogoods are those which encode its equivalence.
+                self.solver_statistics
+                    .presolve_statistics
+                    .num_equivalent_literals += 1;
+
+                // `other` is true if and only if the probed predicate under which it is true holds.
+                let representative = if value_when_true == 1 {
+                    when_true
+                } else {
+                    when_false
+                };
+                let _ = eliminated_variables.insert(other);
+                self.substitute_equivalent_literal(other, representative);
+            }
+
+            if self.state.is_conflicting() {
+                return;
+            }
+        }
+    }
+
+    /// Substitutes the Boolean variable `eliminated` by the equivalent literal `representative`
+    /// (i.e. `[eliminated == 1]` holds if and only if `representative` holds) in the nogoods (see
+    /// [`ConstraintSatisfactionSolver::presolve`]).
+    ///
+    /// The substituted nogoods are logged to the proof; each of them follows from the original
+    /// nogood and the equivalence by propagation.
+    fn substitute_equivalent_literal(&mut self, eliminated: DomainId, representative: Predicate) {
+        pumpkin_assert_simple!(!self.assignments.is_domain_assigned(&eliminated));
+
+        let nogoods = Self::get_nogood_propagator(&mut self.propagators)
+            .remove_nogoods_over_domain(eliminated);
+
+        // The propagators still refer to `eliminated`, so the equivalence itself is added as
+        // permanent nogoods.
+        self.add_inprocessed_nogood(
+            vec![representative, predicate!(eliminated <= 0)],
+            None,
+            true,
+        );
+        if self.state.is_conflicting() {
+            return;
+        }
+        self.add_inprocessed_nogood(
+            vec![!representative, predicate!(eliminated >= 1)],
+            None,
+            true,
+        );
+        if self.state.is_conflicting() {
+            return;
+        }
+
+        let substitute = |predicate: Predicate| {
+            if predicate.get_domain() != eliminated {
+                return predicate;
+            }
+
+            match (
+                predicate!(eliminated == 0).implies(predicate),
+                predicate!(eliminated == 1).implies(predicate),
+            ) {
+                (false, true) => representative,
+                (true, false) => !representative,
+                _ => predicate,
+            }
+        };
+
+        for nogood in nogoods {
+            let mut substituted_nogood: Vec<Predicate> = vec![];
+            for &predicate in nogood.predicates.iter() {
+                let predicate = substitute(predicate);
+                if !substituted_nogood.contains(&predicate) {
+                    substituted_nogood.push(predicate);
+                }
+            }
+
+            // A nogood which contains both a literal and its negation can never be violated.
+            if substituted_nogood.contains(&representative)
+                && substituted_nogood.contains(&!representative)
+            {
+                continue;
+            }
+
+            self.solver_statistics
+                .presolve_statistics
+                .num_substituted_nogoods += 1;
+            self.add_inprocessed_nogood(
+                substituted_nogood,
+                nogood.is_learned.then_some(nogood.lbd),
+                true,
+            );
+            if self.state.is_conflicting() {
+                return;
+            }
         }
+    }
 
-        if brancher.is_restart_pointless() {
-            // If the brancher is static then there is no point in restarting as it would make the
-            // exact same decision
-            return;
+    /// Decides the provided predicate at the root level and propagates it, after which the solver
+    /// is backtracked to the root level again.
+    ///
+    /// If propagation leads to a conflict, then the nogood derived from the conflict is returned;
+    /// otherwise, the values of the Boolean variables which have been fixed by propagation are
+    /// returned if the detection of equivalences is enabled.
+    fn probe(&mut self, predicate: Predicate, brancher: &mut impl Brancher) -> ProbingResult {
+        pumpkin_assert_simple!(self.get_decision_level() == 0);
+        self.solver_statistics.presolve_statistics.num_probes += 1;
+
+        let num_trail_entries = self.assignments.num_trail_entries();
+
+        self.declare_new_decision_level();
+        self.assignments
+            .post_predicate(predicate, None)
+            .expect("Deciding an unassigned predicate cannot fail");
+        self.propagate();
+
+        let result = if self.state.is_conflicting() {
+            ProbingResult::Failed(self.derive_nogood_from_decisions())
+        } else {
+            let mut fixed_variables = HashMap::default();
+            if self
+                .internal_parameters
+                .presolve_options
+                .detect_equivalences
+            {
+                for trail_index in num_trail_entries..self.assignments.num_trail_entries() {
+                    let other = self
+                        .assignments
+                        .get_trail_entry(trail_index)
+                        .predicate
+                        .get_domain();
+                    let is_boolean_at_root = self
+                        .assignments
+                        .get_lower_bound_at_trail_position(other, num_trail_entries)
+                        == 0
+                        && self
+                            .assignments
+                            .get_upper_bound_at_trail_position(other, num_trail_entries)
+                            == 1;
+
+                    if other != predicate.get_domain()
+                        && is_boolean_at_root
+                        && self.assignments.is_domain_assigned(&other)
+                    {
+                        let _ =
+                            fixed_variables.insert(other, self.assignments.get_lower_bound(other));
+                    }
+                }
+            }
+            ProbingResult::Consistent(fixed_variables)
+        };
+
+        self.backtrack_to_level(0, brancher);
+        if self.state.is_conflicting() {
+            self.state.declare_solving();
         }
 
-        self.solver_statistics.engine_statistics.num_restarts += 1;
+        result
+    }
+
+    fn get_nogood_propagator(propagators: &mut PropagatorStore) -> &mut NogoodPropagator {
+        match propagators[Self::get_nogood_propagator_id()].downcast_mut::<NogoodPropagator>() {
+            Some(nogood_propagator) => nogood_propagator,
+            None => panic!("Provided propagator should be the nogood propagator"),
+        }
+    }
 
+    /// Backtracks the solver to the provided decision level, after which the learned nogoods
+    /// which have lost their propagation while backtracking are re-implied (see
+    /// [`NogoodPropagator::reimply_chronologically_asserted_nogoods`]).
+    fn backtrack_to_level(&mut self, backtrack_level: usize, brancher: &mut impl Brancher) {
         ConstraintSatisfactionSolver::backtrack(
             &mut self.assignments,
             &mut self.last_notified_cp_trail_index,
@@ -1041,12 +2235,33 @@ impl ConstraintSatisfactionSolver {
             &mut self.propagators,
             &mut self.event_drain,
             &mut self.backtrack_event_drain,
-            0,
+            backtrack_level,
             brancher,
             &mut self.trailed_values,
         );
 
-        self.restart_strategy.notify_restart();
+        self.reimply_chronologically_asserted_nogoods();
+    }
+
+    /// Propagates the asserting predicates of the learned nogoods which have been asserted after
+    /// backtracking chronologically, and which are unit but not propagating after backtracking.
+    fn reimply_chronologically_asserted_nogoods(&mut self) {
+        let num_trail_entries_before = self.assignments.num_trail_entries();
+
+        let mut context = PropagationContextMut::new(
+            &mut self.trailed_values,
+            &mut self.assignments,
+            &mut self.reason_store,
+            &mut self.semantic_minimiser,
+            Self::get_nogood_propagator_id(),
+        );
+        Self::get_nogood_propagator(&mut self.propagators)
+            .reimply_chronologically_asserted_nogoods(&mut context);
+
+        if self.get_decision_level() == 0 {
+            let tag = self.propagators.get_tag(Self::get_nogood_propagator_id());
+            self.log_root_propagation_to_proof(num_trail_entries_before, tag);
+        }
     }
 
     #[allow(
@@ -1138,10 +2353,14 @@ impl ConstraintSatisfactionSolver {
 
         // We also need to log this last propagation to the proof log as an inference.
         let propagator = self.reason_store.get_propagator(entry_reason);
-        let constraint_tag = self.propagators.get_tag(propagator);
+        let constraint_tag = self
+            .reason_store
+            .get_constraint_tag(entry_reason, &self.propagators);
+        let inference_label = self.propagators.get_inference_label(propagator);
 
         let _ = self.internal_parameters.proof_log.log_inference(
             constraint_tag,
+            inference_label,
             empty_domain_reason.iter().copied(),
             Some(entry.predicate),
         );
@@ -1165,8 +2384,13 @@ impl ConstraintSatisfactionSolver {
             let tag = self.propagators.get_tag(propagator_id);
             let num_trail_entries_before = self.assignments.num_trail_entries();
 
+            let propagation_start_time = self
+                .internal_parameters
+                .profile_propagators
+                .then(Instant::now);
+            let propagator = &mut self.propagators[propagator_id];
+            let cost_class = propagator.cost_class();
             let propagation_status = {
-                let propagator = &mut self.propagators[propagator_id];
                 let context = PropagationContextMut::new(
                     &mut self.trailed_values,
                     &mut self.assignments,
@@ -1176,6 +2400,21 @@ impl ConstraintSatisfactionSolver {
                 );
                 propagator.propagate(context)
             };
+            let propagation_time = propagation_start_time
+                .map(|start_time| start_time.elapsed())
+                .unwrap_or_default();
+            self.solver_statistics
+                .propagation_statistics
+                .record_propagation(cost_class, propagation_time);
+            let propagator_statistics = self.propagators.get_statistics_mut(propagator_id);
+            propagator_statistics.record_propagation(
+                propagation_time,
+                self.assignments.num_trail_entries() - num_trail_entries_before,
+                propagation_status.is_err(),
+            );
+            if let Err(Inconsistency::Conflict(ref conflict_nogood)) = propagation_status {
+                propagator_statistics.record_explanation(conflict_nogood.len());
+            }
 
             if self.assignments.get_decision_level() == 0 {
                 self.log_root_propagation_to_proof(num_trail_entries_before, tag);
@@ -1230,8 +2469,12 @@ impl ConstraintSatisfactionSolver {
             self.assignments.num_trail_entries() as u64 - num_assigned_variables_old as u64;
         // Only check fixed point propagation if there was no reported conflict,
         // since otherwise the state may be inconsistent.
+        //
+        // Propagators which are delayed until a leaf of the search tree are not necessarily at a
+        // fixed point, in which case the check is skipped.
         pumpkin_assert_extreme!(
             self.state.is_conflicting()
+                || self.propagator_queue.has_leaf_propagators()
                 || DebugHelper::debug_fixed_point_propagation(
                     &self.trailed_values,
                     &self.assignments,
@@ -1246,6 +2489,9 @@ impl ConstraintSatisfactionSolver {
     /// The inference `R -> l` is logged to the proof as follows:
     /// 1. Infernce `R /\ ~l -> false`
     /// 2. Nogood (clause) `l`
+    ///
+    /// The inferences are tagged with the constraint of their reason, or with `tag` if the reason
+    /// does not identify a tagged constraint (e.g. a unit clause of the model).
     fn log_root_propagation_to_proof(
         &mut self,
         start_trail_index: usize,
@@ -1273,13 +2519,22 @@ impl ConstraintSatisfactionSolver {
             );
 
             let propagated = entry.predicate;
+            let inference_label = self
+                .propagators
+                .get_inference_label(self.reason_store.get_propagator(reason_ref));
+            let tag = self
+                .reason_store
+                .get_constraint_tag(reason_ref, &self.propagators)
+                .or(tag);
 
             // The proof inference for the propagation `R -> l` is `R /\ ~l -> false`.
             let inference_premises = reason.iter().copied().chain(std::iter::once(!propagated));
-            let _ = self
-                .internal_parameters
-                .proof_log
-                .log_inference(tag, inference_premises, None);
+            let _ = self.internal_parameters.proof_log.log_inference(
+                tag,
+                inference_label,
+                inference_premises,
+                None,
+            );
 
             // Since inference steps are only related to the nogood they directly precede,
             // facts derived at the root are also logged as nogoods so they can be used in the
@@ -1379,7 +2634,7 @@ impl ConstraintSatisfactionSolver {
             Err(ConstraintOperationError::InfeasiblePropagator)
         } else {
             self.propagator_queue
-                .enqueue_propagator(new_propagator_id, new_propagator.priority());
+                .enqueue_propagator(new_propagator_id, new_propagator);
 
             self.propagate();
 
@@ -1409,7 +2664,32 @@ impl ConstraintSatisfactionSolver {
         }
     }
 
+    /// Registers a symmetry of the problem; the images of the learned nogoods under the symmetry
+    /// are added as learned nogoods as well.
+    pub(crate) fn add_symmetry(&mut self, symmetry: Box<dyn Symmetry>) {
+        self.symmetries.push(symmetry);
+    }
+
+    /// Stops deriving symmetric nogoods; this is required once constraints are added which are not
+    /// symmetric under the registered symmetries, such as the nogoods which block solutions.
+    ///
+    /// The symmetric nogoods which have been derived before remain valid, since they are derived
+    /// from nogoods which are implied by the symmetric constraints.
+    pub(crate) fn remove_symmetries(&mut self) {
+        self.symmetries.clear();
+    }
+
     pub fn add_nogood(&mut self, nogood: Vec<Predicate>) -> Result<(), ConstraintOperationError> {
+        self.add_tagged_nogood(nogood, None)
+    }
+
+    /// Adds a nogood for the constraint with the given tag; see
+    /// [`ConstraintSatisfactionSolver::add_tagged_clause`].
+    fn add_tagged_nogood(
+        &mut self,
+        nogood: Vec<Predicate>,
+        tag: Option<NonZero<u32>>,
+    ) -> Result<(), ConstraintOperationError> {
         pumpkin_assert_eq_simple!(self.get_decision_level(), 0);
         let num_trail_entries = self.assignments.num_trail_entries();
 
@@ -1425,24 +2705,25 @@ impl ConstraintSatisfactionSolver {
         let addition_result = ConstraintSatisfactionSolver::add_nogood_to_nogood_propagator(
             &mut self.propagators[nogood_propagator_id],
             nogood,
+            tag,
             &mut propagation_context,
         );
 
         if addition_result.is_err() || self.state.is_conflicting() {
             self.prepare_for_conflict_resolution();
-            self.log_root_propagation_to_proof(num_trail_entries, None);
+            self.log_root_propagation_to_proof(num_trail_entries, tag);
             self.complete_proof();
             return Err(ConstraintOperationError::InfeasibleNogood);
         }
 
-        self.log_root_propagation_to_proof(num_trail_entries, None);
+        self.log_root_propagation_to_proof(num_trail_entries, tag);
 
         // temporary hack for the nogood propagator that does propagation from scratch
-        self.propagator_queue.enqueue_propagator(PropagatorId(0), 0);
+        self.propagator_queue
+            .enqueue_propagator(PropagatorId(0), &self.propagators[PropagatorId(0)]);
+        // The propagations are logged to the proof by the propagation loop.
         self.propagate();
 
-        self.log_root_propagation_to_proof(num_trail_entries, None);
-
         if self.state.is_infeasible() {
             self.prepare_for_conflict_resolution();
             self.complete_proof();
@@ -1474,10 +2755,11 @@ impl ConstraintSatisfactionSolver {
     fn add_nogood_to_nogood_propagator(
         nogood_propagator: &mut dyn Propagator,
         nogood: Vec<Predicate>,
+        tag: Option<NonZero<u32>>,
         context: &mut PropagationContextMut,
     ) -> PropagationStatusCP {
         match nogood_propagator.downcast_mut::<NogoodPropagator>() {
-            Some(nogood_propagator) => nogood_propagator.add_nogood(nogood, context),
+            Some(nogood_propagator) => nogood_propagator.add_nogood(nogood, tag, context),
             None => {
                 panic!("Provided propagator should be the nogood propagator",)
             }
@@ -1492,6 +2774,17 @@ impl ConstraintSatisfactionSolver {
     pub fn add_clause(
         &mut self,
         predicates: impl IntoIterator<Item = Predicate>,
+    ) -> Result<(), ConstraintOperationError> {
+        self.add_tagged_clause(predicates, None)
+    }
+
+    /// Adds a clause for the constraint with the given tag, see
+    /// [`ConstraintSatisfactionSolver::add_clause`]. The tag is logged with the inferences of the
+    /// clause in the proof.
+    pub(crate) fn add_tagged_clause(
+        &mut self,
+        predicates: impl IntoIterator<Item = Predicate>,
+        tag: Option<NonZero<u32>>,
     ) -> Result<(), ConstraintOperationError> {
         pumpkin_assert_simple!(
             self.get_decision_level() == 0,
@@ -1541,7 +2834,7 @@ impl ConstraintSatisfactionSolver {
             return Err(ConstraintOperationError::InfeasibleClause);
         }
 
-        if let Err(constraint_operation_error) = self.add_nogood(predicates) {
+        if let Err(constraint_operation_error) = self.add_tagged_nogood(predicates, tag) {
             let _ = self.conclude_proof_unsat();
 
             self.state
@@ -1558,6 +2851,49 @@ impl ConstraintSatisfactionSolver {
     }
 }
 
+// methods for incremental solving with scoped constraints
+impl ConstraintSatisfactionSolver {
+    /// Opens a new scope and returns its activation literal. See [`Solver::push_scope`].
+    pub(crate) fn push_scope(&mut self) -> Literal {
+        pumpkin_assert_eq_simple!(self.get_decision_level(), 0);
+
+        let activation_literal = self.create_new_literal(None);
+        if self.internal_parameters.proof_log.is_logging_proof() {
+            // The activation literal occurs in the nogoods which are learned from the scoped
+            // constraints, so it needs a name in the proof.
+            let domain_id = activation_literal.get_true_predicate().get_domain();
+            self.variable_names
+                .add_integer(domain_id, format!("__scope_{}", domain_id.id));
+        }
+        self.scopes.push(activation_literal);
+
+        activation_literal
+    }
+
+    /// Closes the innermost scope by permanently falsifying its activation literal. See
+    /// [`Solver::pop_scope`].
+    pub(crate) fn pop_scope(&mut self) -> Result<(), ConstraintOperationError> {
+        pumpkin_assert_eq_simple!(self.get_decision_level(), 0);
+
+        let activation_literal = self
+            .scopes
+            .pop()
+            .expect("Cannot pop a scope when no scope is open");
+
+        self.add_clause([!activation_literal.get_true_predicate()])
+    }
+
+    /// Returns the activation literal of the innermost open scope, if there is one.
+    pub(crate) fn innermost_scope(&self) -> Option<Literal> {
+        self.scopes.last().copied()
+    }
+
+    /// Returns the number of scopes which are currently open.
+    pub(crate) fn num_open_scopes(&self) -> usize {
+        self.scopes.len()
+    }
+}
+
 #[derive(Default, Debug)]
 enum CSPSolverStateInternal {
     #[default]
@@ -1690,13 +3026,29 @@ impl CSPSolverState {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::ConstraintSatisfactionSolver;
     use super::CoreExtractionResult;
+    use super::LearnedNogood;
+    use super::PresolveOptions;
+    use super::PropagatorId;
+    use super::SatisfactionSolverOptions;
+    use crate::basic_types::sequence_generators::SequenceGeneratorType;
     use crate::basic_types::CSPSolverExecutionFlag;
+    use crate::branching::Brancher;
+    use crate::branching::BrancherEvent;
+    use crate::branching::SelectionContext;
+    use crate::engine::symmetry::VariablePermutation;
+    use crate::engine::RestartOptions;
+    use crate::engine::SearchMode;
+    use crate::engine::SearchModeStrategy;
     use crate::predicate;
     use crate::predicates::Predicate;
     use crate::propagators::linear_not_equal::LinearNotEqualPropagator;
+    use crate::propagators::nogoods::LearningOptions;
     use crate::termination::Indefinite;
+    use crate::variables::DomainId;
     use crate::variables::TransformableVariable;
     use crate::DefaultBrancher;
 
@@ -1909,6 +3261,60 @@ mod tests {
         )
     }
 
+    #[test]
+    fn propagations_are_recorded_in_propagator_statistics() {
+        let mut solver = ConstraintSatisfactionSolver::default();
+        let x = solver.create_new_integer_variable(0, 10, None);
+        let y = solver.create_new_integer_variable(0, 10, None);
+
+        let result = solver.add_propagator(
+            LinearNotEqualPropagator::new([x.scaled(1), y.scaled(-1)].into(), 0),
+            None,
+        );
+        assert!(result.is_ok());
+
+        let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+        let _ =
+            solver.solve_under_assumptions(&[predicate!(x == 5)], &mut Indefinite, &mut brancher);
+
+        let statistics = solver.propagators.get_statistics(PropagatorId(1));
+        assert!(statistics.num_calls > 0);
+        assert!(statistics.num_prunings > 0);
+        assert_eq!(statistics.num_conflicts, 0);
+    }
+
+    #[test]
+    fn propagation_time_is_only_recorded_when_profiling() {
+        for profile_propagators in [false, true] {
+            let mut solver = ConstraintSatisfactionSolver::new(SatisfactionSolverOptions {
+                profile_propagators,
+                ..Default::default()
+            });
+            let x = solver.create_new_integer_variable(0, 10, None);
+            let y = solver.create_new_integer_variable(0, 10, None);
+
+            let result = solver.add_propagator(
+                LinearNotEqualPropagator::new([x.scaled(1), y.scaled(-1)].into(), 0),
+                None,
+            );
+            assert!(result.is_ok());
+
+            let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+            let _ = solver.solve_under_assumptions(
+                &[predicate!(x == 5)],
+                &mut Indefinite,
+                &mut brancher,
+            );
+
+            let statistics = solver.propagators.get_statistics(PropagatorId(1));
+            assert!(statistics.num_calls > 0);
+            assert_eq!(
+                statistics.time_spent_in_propagation > 0,
+                profile_propagators
+            );
+        }
+    }
+
     #[test]
     fn new_domain_with_negative_lower_bound() {
         let lb = -2;
@@ -1951,4 +3357,389 @@ mod tests {
         let result = solver.add_propagator(propagator, None);
         assert!(result.is_err());
     }
+
+    /// Creates the variables `x_1, ..., x_{num_pigeons} ∈ [1, num_holes]` which are pairwise not
+    /// equal.
+    fn create_pigeon_hole_instance(
+        options: SatisfactionSolverOptions,
+        num_pigeons: usize,
+        num_holes: i32,
+    ) -> (ConstraintSatisfactionSolver, Vec<DomainId>) {
+        let mut solver = ConstraintSatisfactionSolver::new(options);
+        let pigeons = (0..num_pigeons)
+            .map(|_| solver.create_new_integer_variable(1, num_holes, None))
+            .collect::<Vec<_>>();
+        for (index, &first) in pigeons.iter().enumerate() {
+            for &second in &pigeons[index + 1..] {
+                let result = solver.add_propagator(
+                    LinearNotEqualPropagator::new([first.scaled(1), second.scaled(-1)].into(), 0),
+                    None,
+                );
+                assert!(result.is_ok());
+            }
+        }
+
+        (solver, pigeons)
+    }
+
+    #[test]
+    fn symmetric_nogoods_are_derived_from_learned_nogoods() {
+        let (mut solver, pigeons) =
+            create_pigeon_hole_instance(SatisfactionSolverOptions::default(), 5, 4);
+        solver.add_symmetry(Box::new(VariablePermutation::new([
+            (pigeons[0], pigeons[1]),
+            (pigeons[1], pigeons[0]),
+        ])));
+
+        let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+        let flag = solver.solve_under_assumptions(&[], &mut Indefinite, &mut brancher);
+
+        assert!(matches!(flag, CSPSolverExecutionFlag::Infeasible));
+        assert!(
+            solver
+                .solver_statistics
+                .learned_clause_statistics
+                .num_symmetric_nogoods
+                > 0
+        );
+    }
+
+    fn chronological_backtracking_options() -> SatisfactionSolverOptions {
+        SatisfactionSolverOptions {
+            chronological_backtracking_threshold: Some(0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn chronological_backtracking_detects_infeasibility() {
+        run_test(
+            create_pigeon_hole_instance(chronological_backtracking_options(), 6, 5).0,
+            vec![],
+            CSPSolverExecutionFlag::Infeasible,
+            CoreExtractionResult::Core(vec![]),
+        );
+    }
+
+    #[test]
+    fn chronological_backtracking_finds_solution() {
+        run_test(
+            create_pigeon_hole_instance(chronological_backtracking_options(), 5, 5).0,
+            vec![],
+            CSPSolverExecutionFlag::Feasible,
+            CoreExtractionResult::Core(vec![]), // will be ignored in the test
+        );
+    }
+
+    /// Creates a solver with the literals `a`, `b`, `c`, `d` and `e` and the clause `d \/ e`, in
+    /// which `a`, `b` and `c` are decided at the decision levels 1, 2 and 3, after which the
+    /// learned nogood `a /\ d` asserts `~d` (and hence `e`) at decision level 3.
+    fn create_chronologically_asserted_nogood_instance() -> (
+        ConstraintSatisfactionSolver,
+        DefaultBrancher,
+        Predicate,
+        Predicate,
+    ) {
+        let mut solver = ConstraintSatisfactionSolver::new(chronological_backtracking_options());
+        let [a, b, c, d, e] =
+            std::array::from_fn(|_| solver.create_new_literal(None).get_true_predicate());
+        let _ = solver.add_clause([d, e]);
+        let brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+
+        for decision in [a, b, c] {
+            solver.declare_new_decision_level();
+            solver
+                .assignments
+                .post_predicate(decision, None)
+                .expect("Deciding an unassigned predicate cannot fail");
+        }
+        solver.add_learned_nogood(LearnedNogood {
+            predicates: vec![d, a],
+            backjump_level: 1,
+        });
+        solver.propagate();
+
+        (solver, brancher, d, e)
+    }
+
+    #[test]
+    fn chronologically_asserted_nogood_is_reimplied_after_backtracking() {
+        let (mut solver, mut brancher, d, _) = create_chronologically_asserted_nogood_instance();
+        assert_eq!(
+            solver.assignments.get_decision_level_for_predicate(&!d),
+            Some(3)
+        );
+
+        for decision_level in [2, 1] {
+            solver.restore_state_at_decision_level(decision_level, &mut brancher);
+            assert_eq!(
+                solver.assignments.get_decision_level_for_predicate(&!d),
+                Some(decision_level)
+            );
+        }
+
+        // The other predicate of the nogood is unassigned at the root.
+        solver.restore_state_at_root(&mut brancher);
+        assert!(!solver.assignments.is_predicate_falsified(d));
+        assert!(!solver.assignments.is_predicate_satisfied(d));
+    }
+
+    #[test]
+    fn reimplied_predicate_is_propagated_after_backtracking() {
+        let (mut solver, mut brancher, _, e) = create_chronologically_asserted_nogood_instance();
+        assert_eq!(
+            solver.assignments.get_decision_level_for_predicate(&e),
+            Some(3)
+        );
+
+        solver.restore_state_at_decision_level(2, &mut brancher);
+        solver.propagate();
+        assert!(solver.state.no_conflict());
+        assert_eq!(
+            solver.assignments.get_decision_level_for_predicate(&e),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn learned_nogoods_are_only_inprocessed_when_enabled() {
+        for inprocess_nogoods in [false, true] {
+            let options = SatisfactionSolverOptions {
+                // In stable mode, the solver restarts after every 5 conflicts.
+                restart_options: RestartOptions {
+                    search_mode_strategy: SearchModeStrategy::Stable,
+                    stable_sequence_generator_type: SequenceGeneratorType::Constant,
+                    stable_base_interval: 5,
+                    ..Default::default()
+                },
+                learning_options: LearningOptions {
+                    inprocess_nogoods,
+                    inprocessing_interval: 1,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let (mut solver, _) = create_pigeon_hole_instance(options, 6, 5);
+
+            let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+            let flag = solver.solve(&mut Indefinite, &mut brancher);
+
+            assert_eq!(flag, CSPSolverExecutionFlag::Infeasible);
+            assert_eq!(
+                solver.solver_statistics.inprocessing_statistics.num_rounds > 0,
+                inprocess_nogoods
+            );
+        }
+    }
+
+    #[test]
+    fn inprocessing_is_disabled_by_default() {
+        assert!(!LearningOptions::default().inprocess_nogoods);
+    }
+
+    fn create_presolving_solver() -> ConstraintSatisfactionSolver {
+        ConstraintSatisfactionSolver::new(SatisfactionSolverOptions {
+            presolve_options: PresolveOptions {
+                time_budget: Some(Duration::from_secs(10)),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn presolve_shaves_bounds() {
+        let mut solver = create_presolving_solver();
+        let x = solver.create_new_integer_variable(0, 10, None);
+        let a = solver.create_new_literal(None).get_true_predicate();
+
+        // If `x <= 2` or `x >= 8`, then both `a` and `!a` are implied.
+        for predicate in [predicate!(x >= 3), predicate!(x <= 7)] {
+            let _ = solver.add_clause([predicate, a]);
+            let _ = solver.add_clause([predicate, !a]);
+        }
+
+        let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+        solver.presolve(&mut Indefinite, &mut brancher);
+
+        assert!(solver.state.no_conflict());
+        assert_eq!(solver.assignments.get_lower_bound(x), 3);
+        assert_eq!(solver.assignments.get_upper_bound(x), 7);
+    }
+
+    #[test]
+    fn presolve_detects_implied_and_equivalent_literals() {
+        let mut solver = create_presolving_solver();
+        let a = solver.create_new_literal(None).get_true_predicate();
+        let b = solver.create_new_literal(None).get_true_predicate();
+        let c = solver.create_new_literal(None).get_true_predicate();
+        let d = solver.create_new_literal(None).get_true_predicate();
+
+        // `c` is implied by both polarities of `a`.
+        let _ = solver.add_clause([!a, c]);
+        let _ = solver.add_clause([a, c, d]);
+        let _ = solver.add_clause([a, c, !d]);
+        // `b` is equivalent to `a`.
+        let _ = solver.add_clause([!a, b]);
+        let _ = solver.add_clause([a, !b]);
+
+        let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+        solver.presolve(&mut Indefinite, &mut brancher);
+
+        assert!(solver.state.no_conflict());
+        assert!(solver.assignments.is_predicate_satisfied(c));
+        assert!(!solver.assignments.is_domain_assigned(&a.get_domain()));
+        assert_eq!(
+            solver
+                .solver_statistics
+                .presolve_statistics
+                .num_equivalent_literals,
+            1
+        );
+    }
+
+    #[test]
+    fn presolve_substitutes_equivalent_literals() {
+        let mut solver = create_presolving_solver();
+        let [a, b, c, d] =
+            std::array::from_fn(|_| solver.create_new_literal(None).get_true_predicate());
+
+        // `b` is equivalent to `!a`; these clauses become tautologies after the substitution.
+        let _ = solver.add_clause([a, b]);
+        let _ = solver.add_clause([!a, !b]);
+        // The clause in which `b` is substituted by `!a`.
+        let _ = solver.add_clause([b, c, d]);
+
+        let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+        solver.presolve(&mut Indefinite, &mut brancher);
+
+        assert!(solver.state.no_conflict());
+        let statistics = &solver.solver_statistics.presolve_statistics;
+        assert_eq!(statistics.num_equivalent_literals, 1);
+        assert_eq!(statistics.num_substituted_nogoods, 1);
+
+        // Only the nogoods which encode the equivalence contain `b`.
+        let nogoods_over_b =
+            ConstraintSatisfactionSolver::get_nogood_propagator(&mut solver.propagators)
+                .remove_nogoods_over_domain(b.get_domain());
+        assert_eq!(nogoods_over_b.len(), 2);
+        assert!(nogoods_over_b
+            .iter()
+            .all(|nogood| nogood.predicates.len() == 2 && !nogood.is_learned));
+    }
+
+    #[test]
+    fn substituted_nogoods_propagate() {
+        let mut solver = create_presolving_solver();
+        let [a, b, c, d] =
+            std::array::from_fn(|_| solver.create_new_literal(None).get_true_predicate());
+        let _ = solver.add_clause([a, b]);
+        let _ = solver.add_clause([!a, !b]);
+        let _ = solver.add_clause([b, c, d]);
+
+        let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+        solver.presolve(&mut Indefinite, &mut brancher);
+
+        // The substituted clause `!a \/ c \/ d` propagates `d`, and the equivalence propagates
+        // `!b`.
+        for decision in [a, !c] {
+            solver.declare_new_decision_level();
+            solver
+                .assignments
+                .post_predicate(decision, None)
+                .expect("Deciding an unassigned predicate cannot fail");
+        }
+        solver.propagate();
+
+        assert!(solver.state.no_conflict());
+        assert!(solver.assignments.is_predicate_satisfied(d));
+        assert!(solver.assignments.is_predicate_satisfied(!b));
+    }
+
+    #[test]
+    fn presolve_only_runs_before_the_first_solve() {
+        let mut solver = create_presolving_solver();
+        let a = solver.create_new_literal(None).get_true_predicate();
+        let b = solver.create_new_literal(None).get_true_predicate();
+        let _ = solver.add_clause([a, b]);
+
+        let mut brancher = DefaultBrancher::default_over_all_variables(&solver.assignments);
+        for _ in 0..2 {
+            let flag = solver.solve(&mut Indefinite, &mut brancher);
+            assert_eq!(flag, CSPSolverExecutionFlag::Feasible);
+            solver.restore_state_at_root(&mut brancher);
+
+            // Adding a clause does not lead to presolving again.
+            let _ = solver.add_clause([!a, !b]);
+        }
+
+        assert_eq!(solver.solver_statistics.presolve_statistics.num_rounds, 1);
+    }
+
+    #[test]
+    fn presolve_detects_infeasibility() {
+        let mut solver = create_presolving_solver();
+        let a = solver.create_new_literal(None).get_true_predicate();
+        let b = solver.create_new_literal(None).get_true_predicate();
+
+        let _ = solver.add_clause([a, b]);
+        let _ = solver.add_clause([a, !b]);
+        let _ = solver.add_clause([!a, b]);
+        let _ = solver.add_clause([!a, !b]);
+
+        run_test(
+            solver,
+            vec![],
+            CSPSolverExecutionFlag::Infeasible,
+            CoreExtractionResult::Core(vec![]),
+        );
+    }
+
+    /// A brancher which records the search modes it is informed of, and otherwise behaves as the
+    /// [`DefaultBrancher`].
+    struct SearchModeRecorder {
+        brancher: DefaultBrancher,
+        search_modes: Vec<SearchMode>,
+    }
+
+    impl Brancher for SearchModeRecorder {
+        fn next_decision(&mut self, context: &mut SelectionContext) -> Option<Predicate> {
+            self.brancher.next_decision(context)
+        }
+
+        fn on_search_mode_switch(&mut self, search_mode: SearchMode) {
+            self.search_modes.push(search_mode);
+        }
+
+        fn subscribe_to_events(&self) -> Vec<BrancherEvent> {
+            self.brancher.subscribe_to_events()
+        }
+    }
+
+    #[test]
+    fn brancher_is_informed_of_initial_search_mode() {
+        for (search_mode_strategy, expected) in [
+            (SearchModeStrategy::Focused, vec![]),
+            (SearchModeStrategy::Alternating, vec![SearchMode::Focused]),
+            (SearchModeStrategy::Stable, vec![SearchMode::Stable]),
+        ] {
+            let mut solver = ConstraintSatisfactionSolver::new(SatisfactionSolverOptions {
+                restart_options: RestartOptions {
+                    search_mode_strategy,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+            let _ = solver.create_new_integer_variable(0, 5, None);
+
+            let mut brancher = SearchModeRecorder {
+                brancher: DefaultBrancher::default_over_all_variables(&solver.assignments),
+                search_modes: vec![],
+            };
+            let flag = solver.solve(&mut Indefinite, &mut brancher);
+
+            assert_eq!(flag, CSPSolverExecutionFlag::Feasible);
+            assert_eq!(brancher.search_modes, expected);
+        }
+    }
 }
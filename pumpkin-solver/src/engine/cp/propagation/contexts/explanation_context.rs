@@ -4,7 +4,11 @@ use super::HasAssignments;
 use crate::basic_types::PredicateId;
 use crate::basic_types::PredicateIdGenerator;
 use crate::containers::KeyValueHeap;
+#[cfg(doc)]
+use crate::engine::propagation::Propagator;
+use crate::engine::variables::IntegerVariable;
 use crate::engine::Assignments;
+use crate::predicate;
 use crate::predicates::Predicate;
 
 /// The context that is available when lazily explaining propagations.
@@ -49,6 +53,70 @@ impl HasAssignments for ExplanationContext<'_> {
     }
 }
 
+/// The context that is available when a propagator is asked for a generalised reason for one of
+/// its propagations (see [`Propagator::explain_with_generalised_reason`]).
+///
+/// Next to the domains, it provides the predicates which are available to explain the propagation
+/// (e.g. the other predicates in a learned nogood). A generalised reason should only consist of
+/// predicates which are implied by the available predicates (see
+/// [`GeneralisationContext::is_implied`]).
+pub(crate) struct GeneralisationContext<'a> {
+    assignments: &'a Assignments,
+    available_predicates: &'a [Predicate],
+}
+
+impl<'a> GeneralisationContext<'a> {
+    pub(crate) fn new(assignments: &'a Assignments, available_predicates: &'a [Predicate]) -> Self {
+        GeneralisationContext {
+            assignments,
+            available_predicates,
+        }
+    }
+
+    /// Returns whether the provided predicate is implied by one of the available predicates, or
+    /// whether it holds at the root.
+    pub(crate) fn is_implied(&self, predicate: Predicate) -> bool {
+        self.assignments
+            .get_decision_level_for_predicate(&predicate)
+            == Some(0)
+            || self
+                .available_predicates
+                .iter()
+                .any(|available_predicate| available_predicate.implies(predicate))
+    }
+
+    /// Returns the largest value `v` in the range `[min, max]` such that `[var >= v]` is implied
+    /// (see [`GeneralisationContext::is_implied`]), or [`None`] if `[var >= min]` is not implied.
+    pub(crate) fn implied_lower_bound<Var: IntegerVariable>(
+        &self,
+        var: &Var,
+        min: i32,
+        max: i32,
+    ) -> Option<i32> {
+        if min > max || !self.is_implied(predicate!(var >= min)) {
+            return None;
+        }
+
+        // Invariant: `[var >= low]` is implied and `[var >= high + 1]` is not known to be implied
+        let (mut low, mut high) = (min, max);
+        while low < high {
+            let middle = low + (high - low + 1) / 2;
+            if self.is_implied(predicate!(var >= middle)) {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+        Some(low)
+    }
+}
+
+impl HasAssignments for GeneralisationContext<'_> {
+    fn assignments(&self) -> &Assignments {
+        self.assignments
+    }
+}
+
 static EMPTY_HEAP: KeyValueHeap<PredicateId, u32> = KeyValueHeap::new();
 
 static EMPTY_PREDICATE_IDS: LazyLock<PredicateIdGenerator> =
@@ -85,13 +85,16 @@ pub(crate) mod store;
 
 pub(crate) use contexts::explanation_context::CurrentNogood;
 pub(crate) use contexts::explanation_context::ExplanationContext;
+pub(crate) use contexts::explanation_context::GeneralisationContext;
 pub(crate) use contexts::propagation_context::PropagationContext;
 pub(crate) use contexts::propagation_context::PropagationContextMut;
 pub(crate) use contexts::propagation_context::ReadDomains;
 pub(crate) use contexts::propagator_initialisation_context::PropagatorInitialisationContext;
 pub(crate) use local_id::LocalId;
 pub(crate) use propagator::EnqueueDecision;
+pub use propagator::PropagationDelay;
 pub(crate) use propagator::Propagator;
+pub(crate) use propagator::PropagatorCostClass;
 pub(crate) use propagator_id::PropagatorId;
 pub(crate) use propagator_var_id::PropagatorVarId;
 
@@ -1,8 +1,13 @@
+use std::fmt::Display;
+use std::num::NonZero;
+
+use clap::ValueEnum;
 use downcast_rs::impl_downcast;
 use downcast_rs::Downcast;
 
 use super::contexts::PropagationContextWithTrailedValues;
 use super::ExplanationContext;
+use super::GeneralisationContext;
 use super::PropagationContext;
 use super::PropagationContextMut;
 use super::PropagatorInitialisationContext;
@@ -30,7 +35,7 @@ impl_downcast!(Propagator);
 /// All propagators implement the [`Propagator`] trait, which defines the main propagator logic with
 /// regards to propagation, detecting conflicts, and providing explanations.
 ///
-/// The only required functions are [`Propagator::name`],
+/// The only required functions are [`Propagator::name`], [`Propagator::inference_label`],
 /// [`Propagator::initialise_at_root`], and [`Propagator::debug_propagate_from_scratch`]; all other
 /// functions have default implementations. For initial development, the required functions are
 /// enough, but a more mature implementation considers all functions in most cases.
@@ -40,6 +45,14 @@ pub(crate) trait Propagator: Downcast {
     /// Return the name of the propagator, this is a convenience method that is used for printing.
     fn name(&self) -> &str;
 
+    /// Return the label of the filtering algorithm of the propagator.
+    ///
+    /// The label is logged with every inference of the propagator in the proof, such that a
+    /// proof checker knows which reasoning to use to verify the inference. Therefore, the label
+    /// should not change between versions of the solver, and propagators which implement the same
+    /// filtering algorithm (e.g. incremental variants) should share a label.
+    fn inference_label(&self) -> &'static str;
+
     /// A propagation method that is used to help debugging.
     ///
     /// This method propagates without relying on internal data structures, hence the immutable
@@ -135,6 +148,25 @@ pub(crate) trait Propagator: Downcast {
         3
     }
 
+    /// Returns the [`PropagatorCostClass`] of the propagator; a propagator is only propagated by
+    /// the [`ConstraintSatisfactionSolver`] once all of the enqueued propagators of cheaper cost
+    /// classes have reached a fixed point. Within a cost class, propagators are ordered based on
+    /// [`Propagator::priority`].
+    ///
+    /// By default the cost class is set to [`PropagatorCostClass::Moderate`]. It is expected that
+    /// propagator implementations would set this value to some appropriate value.
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Moderate
+    }
+
+    /// Returns until when the propagation of this propagator should be delayed once it is
+    /// enqueued (see [`PropagationDelay`]).
+    ///
+    /// By default, the propagation is not delayed.
+    fn propagation_delay(&self) -> PropagationDelay {
+        PropagationDelay::None
+    }
+
     /// Initialises the propagator without performing propagation. This method is called only once
     /// by the [`ConstraintSatisfactionSolver`] when the propagator is added using
     /// [`ConstraintSatisfactionSolver::add_propagator`].
@@ -176,12 +208,80 @@ pub(crate) trait Propagator: Downcast {
             )
         );
     }
+
+    /// Return the tag of the constraint for which the propagation with the lazy reason `code` was
+    /// made, if it differs from the tag which the propagator was added with.
+    ///
+    /// This is used by propagators which propagate several constraints at once (e.g. the nogood
+    /// propagator, which propagates the clauses of the model), such that the inferences in the
+    /// proof refer to the constraint which was actually propagated.
+    fn lazy_reason_tag(&self, _code: u64) -> Option<NonZero<u32>> {
+        None
+    }
+
+    /// Hook which is called during the minimisation of a learned nogood to find an alternative,
+    /// weaker reason for `predicate`, which was propagated by this propagator.
+    ///
+    /// The reason given at the time of propagation is based on the domains at that time, while
+    /// a weaker reason often suffices (e.g. a bound `[x >= 3]` rather than the equality `[x ==
+    /// 5]`). If a reason can be constructed which only consists of predicates that are implied by
+    /// the available predicates in the [`GeneralisationContext`] (see
+    /// [`GeneralisationContext::is_implied`]), then `predicate` is redundant in the nogood.
+    ///
+    /// By default, no generalised reason is provided.
+    fn explain_with_generalised_reason(
+        &mut self,
+        _predicate: Predicate,
+        _context: GeneralisationContext,
+    ) -> Option<PropositionalConjunction> {
+        None
+    }
+
     /// Logs statistics of the propagator using the provided [`StatisticLogger`].
     ///
     /// It is recommended to create a struct through the [`create_statistics_struct!`] macro!
     fn log_statistics(&self, _statistic_logger: StatisticLogger) {}
 }
 
+/// The cost class of a propagator (see [`Propagator::cost_class`]); the variants are ordered from
+/// cheapest to most expensive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum PropagatorCostClass {
+    /// Propagators which are cheap to run (e.g. the nogood propagator and the linear
+    /// propagators).
+    Cheap,
+    /// Propagators which are moderately expensive to run (e.g. the element propagator).
+    Moderate,
+    /// Propagators which are expensive to run (e.g. global constraints such as the cumulative
+    /// and global cardinality propagators).
+    Expensive,
+}
+
+/// Determines until when the propagation of an enqueued propagator is delayed.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PropagationDelay {
+    /// The propagator is propagated based on its cost class and priority.
+    #[default]
+    None,
+    /// The propagator is only propagated once all non-delayed propagators have reached a fixed
+    /// point.
+    UntilFixedPoint,
+    /// The propagator is only propagated once the brancher cannot make any further decisions
+    /// (i.e. at a leaf of the search tree); the propagator then only serves as a checker of
+    /// the candidate solution.
+    UntilLeaf,
+}
+
+impl Display for PropagationDelay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropagationDelay::None => write!(f, "none"),
+            PropagationDelay::UntilFixedPoint => write!(f, "until-fixed-point"),
+            PropagationDelay::UntilLeaf => write!(f, "until-leaf"),
+        }
+    }
+}
+
 /// Indicator of what to do when a propagator is notified.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum EnqueueDecision {
@@ -7,15 +7,17 @@ use super::Propagator;
 use super::PropagatorId;
 use crate::containers::KeyedVec;
 use crate::engine::DebugDyn;
+use crate::engine::PropagatorStatistics;
 
 /// A central store for propagators.
 ///
 /// The propagator store associates tags with propagators, whenever a tag is provided for a
-/// propagator.
+/// propagator. It also keeps track of the profiling statistics of every propagator.
 #[derive(Default)]
 pub(crate) struct PropagatorStore {
     propagators: KeyedVec<PropagatorId, Box<dyn Propagator>>,
     tags: KeyedVec<PropagatorId, Option<NonZero<u32>>>,
+    statistics: KeyedVec<PropagatorId, PropagatorStatistics>,
 }
 
 impl PropagatorStore {
@@ -26,6 +28,7 @@ impl PropagatorStore {
     ) -> PropagatorId {
         let id = self.propagators.push(propagator);
         let _ = self.tags.push(tag);
+        let _ = self.statistics.push(PropagatorStatistics::default());
 
         id
     }
@@ -34,6 +37,21 @@ impl PropagatorStore {
         self.tags[propagator_id]
     }
 
+    pub(crate) fn get_inference_label(&self, propagator_id: PropagatorId) -> &'static str {
+        self.propagators[propagator_id].inference_label()
+    }
+
+    pub(crate) fn get_statistics(&self, propagator_id: PropagatorId) -> &PropagatorStatistics {
+        &self.statistics[propagator_id]
+    }
+
+    pub(crate) fn get_statistics_mut(
+        &mut self,
+        propagator_id: PropagatorId,
+    ) -> &mut PropagatorStatistics {
+        &mut self.statistics[propagator_id]
+    }
+
     pub(crate) fn iter_propagators(&self) -> impl Iterator<Item = &dyn Propagator> + '_ {
         self.propagators.iter().map(|b| b.as_ref())
     }
@@ -3,35 +3,105 @@ use std::collections::BinaryHeap;
 use std::collections::VecDeque;
 
 use crate::basic_types::HashSet;
+use crate::engine::cp::propagation::PropagationDelay;
+use crate::engine::cp::propagation::Propagator;
+use crate::engine::cp::propagation::PropagatorCostClass;
 use crate::engine::cp::propagation::PropagatorId;
 use crate::pumpkin_assert_moderate;
 
+/// The number of tiers in the queue; one for every [`PropagatorCostClass`] and one for the
+/// propagators which are delayed until a fixed point is reached.
+const NUM_TIERS: u32 = 4;
+
+/// The queue of propagators which are waiting to be propagated.
+///
+/// The propagators are ordered by tier and then by priority (see [`Propagator::priority`]), where
+/// the tier is determined by the [`PropagatorCostClass`] of the propagator; the propagators of a
+/// tier are only popped once all propagators of the cheaper tiers have reached a fixed point.
+/// Propagators which are delayed until a fixed point (see [`PropagationDelay::UntilFixedPoint`])
+/// are placed in the final tier, while propagators which are delayed until a leaf (see
+/// [`PropagationDelay::UntilLeaf`]) are kept aside until they are released by
+/// [`PropagatorQueue::release_leaf_propagators`].
 #[derive(Debug)]
 pub(crate) struct PropagatorQueue {
     queues: Vec<VecDeque<PropagatorId>>,
     present_propagators: HashSet<PropagatorId>,
     present_priorities: BinaryHeap<Reverse<u32>>,
+    num_priority_levels: u32,
+    /// The delay which is applied to every propagator of the [`PropagatorCostClass::Expensive`]
+    /// cost class (unless the propagator itself requests a longer delay).
+    expensive_propagator_delay: PropagationDelay,
+    /// The propagators which are waiting for a leaf of the search tree.
+    leaf_propagators: Vec<PropagatorId>,
+    /// The propagators which were waiting for a leaf of the search tree and which have been
+    /// released into the queue, but which have not been popped yet. These are moved back to
+    /// [`PropagatorQueue::leaf_propagators`] by [`PropagatorQueue::clear`], since the domain
+    /// changes for which they were enqueued might still be present after backtracking.
+    released_leaf_propagators: Vec<PropagatorId>,
 }
 
 impl PropagatorQueue {
-    pub(crate) fn new(num_priority_levels: u32) -> PropagatorQueue {
+    pub(crate) fn new(
+        num_priority_levels: u32,
+        expensive_propagator_delay: PropagationDelay,
+    ) -> PropagatorQueue {
         PropagatorQueue {
-            queues: vec![VecDeque::new(); num_priority_levels as usize],
+            queues: vec![VecDeque::new(); (NUM_TIERS * num_priority_levels) as usize],
             present_propagators: HashSet::default(),
             present_priorities: BinaryHeap::new(),
+            num_priority_levels,
+            expensive_propagator_delay,
+            leaf_propagators: Vec::default(),
+            released_leaf_propagators: Vec::default(),
         }
     }
 
-    pub(crate) fn enqueue_propagator(&mut self, propagator_id: PropagatorId, priority: u32) {
-        pumpkin_assert_moderate!((priority as usize) < self.queues.len());
+    pub(crate) fn enqueue_propagator(
+        &mut self,
+        propagator_id: PropagatorId,
+        propagator: &dyn Propagator,
+    ) {
+        let priority = propagator.priority();
+        pumpkin_assert_moderate!(priority < self.num_priority_levels);
+
+        if self.is_propagator_enqueued(propagator_id) {
+            return;
+        }
+        let _ = self.present_propagators.insert(propagator_id);
+
+        let cost_class = propagator.cost_class();
+        let delay = if cost_class == PropagatorCostClass::Expensive {
+            propagator
+                .propagation_delay()
+                .max(self.expensive_propagator_delay)
+        } else {
+            propagator.propagation_delay()
+        };
 
-        if !self.is_propagator_enqueued(propagator_id) {
-            if self.queues[priority as usize].is_empty() {
-                self.present_priorities.push(Reverse(priority));
+        let tier = match delay {
+            PropagationDelay::None => cost_class as u32,
+            PropagationDelay::UntilFixedPoint => NUM_TIERS - 1,
+            PropagationDelay::UntilLeaf => {
+                self.leaf_propagators.push(propagator_id);
+                return;
             }
-            self.queues[priority as usize].push_back(propagator_id);
-            let _ = self.present_propagators.insert(propagator_id);
+        };
+        self.push(propagator_id, tier * self.num_priority_levels + priority);
+    }
+
+    /// Moves the propagators which are delayed until a leaf of the search tree into the final
+    /// tier of the queue. Returns whether any propagators were released.
+    pub(crate) fn release_leaf_propagators(&mut self) -> bool {
+        if self.leaf_propagators.is_empty() {
+            return false;
+        }
+
+        let index = (NUM_TIERS - 1) * self.num_priority_levels;
+        for propagator_id in std::mem::take(&mut self.leaf_propagators) {
+            self.push(propagator_id, index);
+            self.released_leaf_propagators.push(propagator_id);
         }
+        true
     }
 
     pub(crate) fn pop(&mut self) -> Option<PropagatorId> {
@@ -46,6 +116,13 @@ impl PropagatorQueue {
 
         next_propagator_id.iter().for_each(|next_propagator_id| {
             let _ = self.present_propagators.remove(next_propagator_id);
+            if let Some(position) = self
+                .released_leaf_propagators
+                .iter()
+                .position(|propagator_id| propagator_id == next_propagator_id)
+            {
+                let _ = self.released_leaf_propagators.swap_remove(position);
+            }
 
             if self.queues[top_priority].is_empty() {
                 let _ = self.present_priorities.pop();
@@ -63,9 +140,220 @@ impl PropagatorQueue {
         }
         self.present_propagators.clear();
         self.present_priorities.clear();
+
+        // The propagators which are waiting for a leaf remain enqueued
+        self.leaf_propagators
+            .append(&mut self.released_leaf_propagators);
+        self.present_propagators
+            .extend(self.leaf_propagators.iter().copied());
+    }
+
+    /// Returns whether there are propagators which are waiting for a leaf of the search tree.
+    pub(crate) fn has_leaf_propagators(&self) -> bool {
+        !self.leaf_propagators.is_empty() || !self.released_leaf_propagators.is_empty()
+    }
+
+    fn push(&mut self, propagator_id: PropagatorId, index: u32) {
+        if self.queues[index as usize].is_empty() {
+            self.present_priorities.push(Reverse(index));
+        }
+        self.queues[index as usize].push_back(propagator_id);
     }
 
     fn is_propagator_enqueued(&self, propagator_id: PropagatorId) -> bool {
         self.present_propagators.contains(&propagator_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::basic_types::PropagationStatusCP;
+    use crate::basic_types::PropositionalConjunction;
+    use crate::engine::propagation::PropagationContextMut;
+    use crate::engine::propagation::PropagatorInitialisationContext;
+
+    struct TestPropagator {
+        priority: u32,
+        cost_class: PropagatorCostClass,
+        propagation_delay: PropagationDelay,
+    }
+
+    impl Propagator for TestPropagator {
+        fn name(&self) -> &str {
+            "TestPropagator"
+        }
+
+        fn inference_label(&self) -> &'static str {
+            "test"
+        }
+
+        fn debug_propagate_from_scratch(&self, _: PropagationContextMut) -> PropagationStatusCP {
+            Ok(())
+        }
+
+        fn initialise_at_root(
+            &mut self,
+            _: &mut PropagatorInitialisationContext,
+        ) -> Result<(), PropositionalConjunction> {
+            Ok(())
+        }
+
+        fn priority(&self) -> u32 {
+            self.priority
+        }
+
+        fn cost_class(&self) -> PropagatorCostClass {
+            self.cost_class
+        }
+
+        fn propagation_delay(&self) -> PropagationDelay {
+            self.propagation_delay
+        }
+    }
+
+    fn propagator(
+        priority: u32,
+        cost_class: PropagatorCostClass,
+        propagation_delay: PropagationDelay,
+    ) -> TestPropagator {
+        TestPropagator {
+            priority,
+            cost_class,
+            propagation_delay,
+        }
+    }
+
+    #[test]
+    fn cheaper_cost_classes_are_popped_first() {
+        let mut queue = PropagatorQueue::new(5, PropagationDelay::None);
+
+        queue.enqueue_propagator(
+            PropagatorId(0),
+            &propagator(3, PropagatorCostClass::Expensive, PropagationDelay::None),
+        );
+        queue.enqueue_propagator(
+            PropagatorId(1),
+            &propagator(2, PropagatorCostClass::Moderate, PropagationDelay::None),
+        );
+        queue.enqueue_propagator(
+            PropagatorId(2),
+            &propagator(0, PropagatorCostClass::Cheap, PropagationDelay::None),
+        );
+
+        assert_eq!(queue.pop(), Some(PropagatorId(2)));
+        assert_eq!(queue.pop(), Some(PropagatorId(1)));
+        assert_eq!(queue.pop(), Some(PropagatorId(0)));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn cost_class_takes_precedence_over_priority() {
+        let mut queue = PropagatorQueue::new(5, PropagationDelay::None);
+
+        queue.enqueue_propagator(
+            PropagatorId(0),
+            &propagator(0, PropagatorCostClass::Moderate, PropagationDelay::None),
+        );
+        queue.enqueue_propagator(
+            PropagatorId(1),
+            &propagator(3, PropagatorCostClass::Cheap, PropagationDelay::None),
+        );
+
+        assert_eq!(queue.pop(), Some(PropagatorId(1)));
+        assert_eq!(queue.pop(), Some(PropagatorId(0)));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn only_expensive_propagators_are_delayed_based_on_option() {
+        let mut queue = PropagatorQueue::new(5, PropagationDelay::UntilLeaf);
+
+        queue.enqueue_propagator(
+            PropagatorId(0),
+            &propagator(3, PropagatorCostClass::Cheap, PropagationDelay::None),
+        );
+        queue.enqueue_propagator(
+            PropagatorId(1),
+            &propagator(3, PropagatorCostClass::Moderate, PropagationDelay::None),
+        );
+
+        assert_eq!(queue.pop(), Some(PropagatorId(0)));
+        assert_eq!(queue.pop(), Some(PropagatorId(1)));
+        assert_eq!(queue.pop(), None);
+        assert!(!queue.has_leaf_propagators());
+    }
+
+    #[test]
+    fn delayed_propagators_are_popped_after_expensive_propagators() {
+        let mut queue = PropagatorQueue::new(5, PropagationDelay::None);
+
+        queue.enqueue_propagator(
+            PropagatorId(0),
+            &propagator(
+                0,
+                PropagatorCostClass::Cheap,
+                PropagationDelay::UntilFixedPoint,
+            ),
+        );
+        queue.enqueue_propagator(
+            PropagatorId(1),
+            &propagator(3, PropagatorCostClass::Expensive, PropagationDelay::None),
+        );
+
+        assert_eq!(queue.pop(), Some(PropagatorId(1)));
+        assert_eq!(queue.pop(), Some(PropagatorId(0)));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn expensive_propagators_are_delayed_based_on_option() {
+        let mut queue = PropagatorQueue::new(5, PropagationDelay::UntilLeaf);
+
+        queue.enqueue_propagator(
+            PropagatorId(0),
+            &propagator(3, PropagatorCostClass::Expensive, PropagationDelay::None),
+        );
+        queue.enqueue_propagator(
+            PropagatorId(1),
+            &propagator(0, PropagatorCostClass::Cheap, PropagationDelay::None),
+        );
+
+        assert_eq!(queue.pop(), Some(PropagatorId(1)));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.has_leaf_propagators());
+
+        assert!(queue.release_leaf_propagators());
+        assert_eq!(queue.pop(), Some(PropagatorId(0)));
+        assert_eq!(queue.pop(), None);
+        assert!(!queue.has_leaf_propagators());
+        assert!(!queue.release_leaf_propagators());
+    }
+
+    #[test]
+    fn leaf_propagators_remain_enqueued_after_clear() {
+        let mut queue = PropagatorQueue::new(5, PropagationDelay::None);
+
+        queue.enqueue_propagator(
+            PropagatorId(0),
+            &propagator(0, PropagatorCostClass::Cheap, PropagationDelay::UntilLeaf),
+        );
+        queue.enqueue_propagator(
+            PropagatorId(1),
+            &propagator(0, PropagatorCostClass::Cheap, PropagationDelay::None),
+        );
+        assert!(queue.release_leaf_propagators());
+
+        queue.clear();
+        assert_eq!(queue.pop(), None);
+
+        // The propagator is still waiting for a leaf and is thus not enqueued twice
+        queue.enqueue_propagator(
+            PropagatorId(0),
+            &propagator(0, PropagatorCostClass::Cheap, PropagationDelay::UntilLeaf),
+        );
+        assert!(queue.release_leaf_propagators());
+        assert_eq!(queue.pop(), Some(PropagatorId(0)));
+        assert_eq!(queue.pop(), None);
+    }
+}
@@ -1,7 +1,10 @@
 use std::fmt::Debug;
+use std::num::NonZero;
 
 use super::propagation::store::PropagatorStore;
 use super::propagation::ExplanationContext;
+#[cfg(doc)]
+use super::propagation::Propagator;
 use super::propagation::PropagatorId;
 use crate::basic_types::PropositionalConjunction;
 use crate::basic_types::Trail;
@@ -78,6 +81,24 @@ impl ReasonStore {
     pub(crate) fn get_propagator(&self, reason_ref: ReasonRef) -> PropagatorId {
         self.trail.get(reason_ref.0 as usize).unwrap().0
     }
+
+    /// Get the tag of the constraint for which the change with the given reason was made. This is
+    /// the tag which the propagator was added with, unless the propagator reports a tag for its
+    /// lazy reasons (see [`Propagator::lazy_reason_tag`]).
+    pub(crate) fn get_constraint_tag(
+        &self,
+        reason_ref: ReasonRef,
+        propagators: &PropagatorStore,
+    ) -> Option<NonZero<u32>> {
+        let (propagator_id, reason) = self.trail.get(reason_ref.0 as usize).unwrap();
+
+        let lazy_reason_tag = match reason {
+            StoredReason::DynamicLazy(code) => propagators[*propagator_id].lazy_reason_tag(*code),
+            StoredReason::Eager(_) | StoredReason::ReifiedLazy(_, _) => None,
+        };
+
+        lazy_reason_tag.or_else(|| propagators.get_tag(*propagator_id))
+    }
 }
 
 /// A reference to a reason
@@ -85,7 +106,7 @@ impl ReasonStore {
 pub struct ReasonRef(pub(crate) u32);
 
 /// A reason for CP propagator to make a change
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum Reason {
     /// An eager reason contains the propositional conjunction with the reason, without the
     ///   propagated predicate.
@@ -225,6 +246,10 @@ mod tests {
                 todo!()
             }
 
+            fn inference_label(&self) -> &'static str {
+                todo!()
+            }
+
             fn debug_propagate_from_scratch(
                 &self,
                 _: crate::engine::propagation::PropagationContextMut,
@@ -4,12 +4,17 @@ pub(crate) mod cp;
 mod debug_helper;
 mod literal_block_distance;
 pub(crate) mod predicates;
+mod presolve;
+mod propagator_fuzzer;
 mod restart_strategy;
 mod solver_statistics;
+pub(crate) mod symmetry;
 pub(crate) mod termination;
 pub(crate) mod variable_names;
 pub(crate) mod variables;
 
+pub use conflict_analysis::ConflictTrace;
+pub use conflict_analysis::ExtendedResolutionOptions;
 pub(crate) use conflict_analysis::ResolutionResolver;
 pub use constraint_satisfaction_solver::ConflictResolver;
 pub(crate) use constraint_satisfaction_solver::ConstraintSatisfactionSolver;
@@ -19,7 +24,13 @@ pub(crate) use debug_helper::DebugDyn;
 pub(crate) use debug_helper::DebugHelper;
 pub(crate) use domain_events::DomainEvents;
 pub(crate) use literal_block_distance::Lbd;
+pub use presolve::PresolveOptions;
+pub use propagator_fuzzer::fuzz_propagators;
+pub use propagator_fuzzer::PropagatorFuzzerOptions;
 pub use restart_strategy::RestartOptions;
 pub(crate) use restart_strategy::RestartStrategy;
+pub use restart_strategy::SearchMode;
+pub use restart_strategy::SearchModeStrategy;
+pub(crate) use solver_statistics::PropagatorStatistics;
 pub(crate) use solver_statistics::SolverStatistics;
 pub(crate) use variable_names::VariableNames;
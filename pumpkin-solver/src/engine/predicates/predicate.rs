@@ -127,6 +127,32 @@ impl Predicate {
             ) => domain_id == domain_id_other && equality_constant != equality_constant_other,
         }
     }
+
+    /// Returns whether `self` being true implies that `other` is true, taking into account the
+    /// semantics of the domains; e.g. `[x >= 5]` implies `[x >= 3]` and `[x != 2]`.
+    ///
+    /// Note that this only considers predicates over the same [`DomainId`].
+    pub(crate) fn implies(self, other: Predicate) -> bool {
+        if self.get_domain() != other.get_domain() {
+            return false;
+        }
+
+        let lhs = self.get_right_hand_side();
+        let rhs = other.get_right_hand_side();
+        match (self, other) {
+            (Predicate::LowerBound { .. }, Predicate::LowerBound { .. })
+            | (Predicate::Equal { .. }, Predicate::LowerBound { .. }) => lhs >= rhs,
+            (Predicate::UpperBound { .. }, Predicate::UpperBound { .. })
+            | (Predicate::Equal { .. }, Predicate::UpperBound { .. }) => lhs <= rhs,
+            (Predicate::LowerBound { .. }, Predicate::NotEqual { .. }) => lhs > rhs,
+            (Predicate::UpperBound { .. }, Predicate::NotEqual { .. }) => lhs < rhs,
+            (Predicate::Equal { .. }, Predicate::NotEqual { .. }) => lhs != rhs,
+            (Predicate::Equal { .. }, Predicate::Equal { .. })
+            | (Predicate::NotEqual { .. }, Predicate::NotEqual { .. }) => lhs == rhs,
+            _ => false,
+        }
+    }
+
     pub fn is_equality_predicate(&self) -> bool {
         matches!(
             *self,
@@ -304,6 +330,8 @@ impl std::fmt::Debug for Predicate {
 #[cfg(test)]
 mod test {
     use super::Predicate;
+    use crate::engine::variables::DomainId;
+    use crate::predicate;
 
     #[test]
     fn negating_trivially_true_predicate() {
@@ -312,6 +340,22 @@ mod test {
         assert!(!trivially_true == trivially_false);
     }
 
+    #[test]
+    fn implication_takes_domain_semantics_into_account() {
+        let x = DomainId::new(1);
+        let y = DomainId::new(2);
+
+        assert!(predicate!(x >= 5).implies(predicate!(x >= 3)));
+        assert!(!predicate!(x >= 3).implies(predicate!(x >= 5)));
+        assert!(predicate!(x >= 5).implies(predicate!(x != 4)));
+        assert!(!predicate!(x >= 5).implies(predicate!(x != 5)));
+        assert!(predicate!(x <= 2).implies(predicate!(x <= 2)));
+        assert!(predicate!(x == 3).implies(predicate!(x <= 3)));
+        assert!(predicate!(x == 3).implies(predicate!(x != 4)));
+        assert!(!predicate!(x != 3).implies(predicate!(x <= 3)));
+        assert!(!predicate!(x >= 5).implies(predicate!(y >= 3)));
+    }
+
     #[test]
     fn negating_trivially_false_predicate() {
         let trivially_true = Predicate::trivially_true();
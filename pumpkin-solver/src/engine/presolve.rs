@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+#[cfg(doc)]
+use crate::engine::ConstraintSatisfactionSolver;
+
+/// Options which determine whether and for how long the [`ConstraintSatisfactionSolver`]
+/// presolves the model at the root level before searching.
+///
+/// Presolving consists of failed-literal probing on the bounds of every variable; the bounds
+/// which cannot be extended to a consistent root assignment are shaved off until a fixpoint is
+/// reached (i.e. singleton consistency on the bounds). For Boolean variables, the consequences of
+/// both polarities are compared to detect implied and equivalent literals; equivalent literals are
+/// substituted in the nogoods.
+#[derive(Debug, Clone, Copy)]
+pub struct PresolveOptions {
+    /// The time which is allowed to be spent on presolving; if [`None`], then no presolving takes
+    /// place.
+    pub time_budget: Option<Duration>,
+    /// Whether equivalent Boolean literals are detected during probing and substituted.
+    pub detect_equivalences: bool,
+}
+
+impl Default for PresolveOptions {
+    fn default() -> Self {
+        Self {
+            time_budget: None,
+            detect_equivalences: true,
+        }
+    }
+}
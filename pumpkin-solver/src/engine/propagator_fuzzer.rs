@@ -0,0 +1,632 @@
+//! A harness which tests the propagators of a [`Constraint`] on small random instances.
+//!
+//! For every instance, all solutions are enumerated by brute force, after which a random sequence
+//! of decisions and backtracks is performed. After every round of propagation, the harness checks
+//! that:
+//! - no value which is part of a solution has been removed from a domain (and, optionally, that
+//!   every remaining value is part of a solution);
+//! - every propagation and conflict is explained correctly, i.e. propagating the explanation from
+//!   scratch (using [`Propagator::debug_propagate_from_scratch`]) leads to the propagated predicate
+//!   or to a conflict;
+//! - propagation is idempotent, i.e. calling the propagators again at the fixed point (both
+//!   incrementally and from scratch) does not lead to new propagations;
+//! - propagation from scratch is monotonic, i.e. stronger domains never lead to weaker domains
+//!   after propagation.
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use itertools::Itertools;
+use log::debug;
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use super::conflict_analysis::SemanticMinimiser;
+use super::propagation::PropagationContextMut;
+use super::propagation::PropagatorId;
+use super::reason::ReasonStore;
+use super::Assignments;
+use super::ConstraintSatisfactionSolver;
+use super::TrailedValues;
+use crate::basic_types::StoredConflictInfo;
+use crate::constraints::Constraint;
+#[cfg(doc)]
+use crate::engine::propagation::Propagator;
+use crate::predicate;
+use crate::predicates::Predicate;
+use crate::variables::DomainId;
+use crate::DefaultBrancher;
+use crate::Solver;
+
+/// Options which determine the random instances on which [`fuzz_propagators`] tests the
+/// propagators of a constraint.
+#[derive(Debug, Clone, Copy)]
+pub struct PropagatorFuzzerOptions {
+    /// The number of random instances which are generated.
+    pub num_instances: usize,
+    /// The number of variables which are passed to the constraint in every instance.
+    pub num_variables: usize,
+    /// The smallest value which can occur in the initial domain of a variable.
+    pub min_value: i32,
+    /// The largest value which can occur in the initial domain of a variable.
+    pub max_value: i32,
+    /// The probability that a value is left out of the initial domain of a variable.
+    pub hole_probability: f64,
+    /// The number of random decisions (or backtracks) which are made in every instance.
+    pub num_decisions: usize,
+    /// The probability that the solver backtracks to a random decision level instead of making a
+    /// decision.
+    pub backtrack_probability: f64,
+    /// Whether every value which remains in a domain after propagation should be part of a
+    /// solution; this should only be enabled for propagators which enforce domain consistency.
+    pub check_domain_consistency: bool,
+    /// The seed which is used to generate the random instances.
+    pub seed: u64,
+}
+
+impl Default for PropagatorFuzzerOptions {
+    fn default() -> Self {
+        Self {
+            num_instances: 50,
+            num_variables: 4,
+            min_value: 0,
+            max_value: 4,
+            hole_probability: 0.2,
+            num_decisions: 10,
+            backtrack_probability: 0.2,
+            check_domain_consistency: false,
+            seed: 42,
+        }
+    }
+}
+
+/// Tests the propagators of the constraint created by `create_constraint` on random instances
+/// generated according to the provided [`PropagatorFuzzerOptions`].
+///
+/// The closure receives a fresh [`Solver`] (which can be used to create auxiliary variables) and
+/// the variables of the instance; note that only these variables are enumerated to determine the
+/// solutions, auxiliary variables are expected to be fixed (or at least consistent) by
+/// propagation once all of the variables of the instance are fixed.
+///
+/// # Panics
+/// If any of the checks fails; the message contains the instance and the decisions which led to
+/// the failure.
+pub fn fuzz_propagators<C: Constraint>(
+    options: PropagatorFuzzerOptions,
+    mut create_constraint: impl FnMut(&mut Solver, &[DomainId]) -> C,
+) {
+    assert!(
+        options.min_value <= options.max_value,
+        "The minimum value should be at most the maximum value"
+    );
+
+    let mut random = SmallRng::seed_from_u64(options.seed);
+    for instance_index in 0..options.num_instances {
+        let initial_domains = (0..options.num_variables)
+            .map(|_| create_random_domain(&mut random, &options))
+            .collect::<Vec<_>>();
+        debug!("Fuzzing instance {instance_index} with domains {initial_domains:?}");
+
+        FuzzedInstance::new(initial_domains, &mut create_constraint).fuzz(&mut random, &options);
+    }
+}
+
+fn create_random_domain(random: &mut SmallRng, options: &PropagatorFuzzerOptions) -> Vec<i32> {
+    let mut values = (options.min_value..=options.max_value)
+        .filter(|_| !random.gen_bool(options.hole_probability))
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        values.push(random.gen_range(options.min_value..=options.max_value));
+    }
+    values
+}
+
+/// A single random instance of the constraint under test, together with all of its solutions.
+struct FuzzedInstance {
+    solver: Solver,
+    brancher: DefaultBrancher,
+    description: InstanceDescription,
+    /// The assignments before any propagation took place; used to propagate from scratch.
+    initial_assignments: Assignments,
+    initial_trailed_values: TrailedValues,
+    /// Whether posting the constraint led to a conflict at the root.
+    is_infeasible_at_root: bool,
+    solutions: Vec<Vec<i32>>,
+}
+
+/// The variables, initial domains and decisions of a [`FuzzedInstance`]; it is reported when one
+/// of the checks fails.
+struct InstanceDescription {
+    variables: Vec<DomainId>,
+    initial_domains: Vec<Vec<i32>>,
+    decisions: Vec<Predicate>,
+}
+
+impl FuzzedInstance {
+    fn new<C: Constraint>(
+        initial_domains: Vec<Vec<i32>>,
+        create_constraint: &mut impl FnMut(&mut Solver, &[DomainId]) -> C,
+    ) -> Self {
+        let mut solver = Solver::default();
+        let variables = initial_domains
+            .iter()
+            .map(|values| solver.new_sparse_integer(values.clone()))
+            .collect::<Vec<_>>();
+
+        let num_trail_entries = solver.satisfaction_solver.assignments.num_trail_entries();
+        let is_infeasible_at_root = create_constraint(&mut solver, &variables)
+            .post(&mut solver, None)
+            .is_err();
+
+        // The empty clone only retains the initial bounds of the domains, so the holes in the
+        // initial domains are removed explicitly.
+        let mut initial_assignments = solver
+            .satisfaction_solver
+            .assignments
+            .debug_create_empty_clone();
+        for (&variable, values) in variables.iter().zip(initial_domains.iter()) {
+            for value in values[0]..=values[values.len() - 1] {
+                if !values.contains(&value) {
+                    initial_assignments
+                        .post_predicate(predicate!(variable != value), None)
+                        .expect("Removing a hole cannot lead to an empty domain");
+                }
+            }
+        }
+        let initial_trailed_values = solver
+            .satisfaction_solver
+            .trailed_values
+            .debug_create_empty_clone();
+        let brancher = solver.default_brancher();
+
+        let mut instance = Self {
+            solver,
+            brancher,
+            description: InstanceDescription {
+                variables,
+                initial_domains,
+                decisions: vec![],
+            },
+            initial_assignments,
+            initial_trailed_values,
+            is_infeasible_at_root,
+            solutions: vec![],
+        };
+        instance.solutions = instance.enumerate_solutions();
+        if !is_infeasible_at_root {
+            instance.check_propagations(num_trail_entries);
+        }
+        instance
+    }
+
+    fn fuzz(&mut self, random: &mut SmallRng, options: &PropagatorFuzzerOptions) {
+        if self.is_infeasible_at_root {
+            let description = &self.description;
+            assert!(
+                self.solutions.is_empty(),
+                "Posting the constraint led to infeasibility at the root while solution {:?} exists\n{description}",
+                self.solutions[0]
+            );
+            return;
+        }
+        self.check_state(options);
+
+        for _ in 0..options.num_decisions {
+            let decision_level = self.solver.satisfaction_solver.get_decision_level();
+            let decision = if decision_level > 0 && random.gen_bool(options.backtrack_probability) {
+                None
+            } else {
+                self.create_random_decision(random)
+            };
+
+            let Some(decision) = decision else {
+                if decision_level == 0 {
+                    // All variables are fixed at the root
+                    break;
+                }
+                self.backtrack(random.gen_range(0..decision_level));
+                self.check_state(options);
+                continue;
+            };
+
+            self.decide(decision);
+            if self.solver.satisfaction_solver.state.is_conflicting() {
+                self.check_conflict();
+                self.backtrack(decision_level);
+            } else {
+                self.check_state(options);
+            }
+        }
+    }
+
+    fn create_random_decision(&self, random: &mut SmallRng) -> Option<Predicate> {
+        let assignments = &self.solver.satisfaction_solver.assignments;
+        let unfixed_variables = self
+            .description
+            .variables
+            .iter()
+            .filter(|variable| !assignments.is_domain_assigned(*variable))
+            .collect::<Vec<_>>();
+        if unfixed_variables.is_empty() {
+            return None;
+        }
+
+        let variable = *unfixed_variables[random.gen_range(0..unfixed_variables.len())];
+        let values = assignments
+            .get_domain_iterator(variable)
+            .collect::<Vec<_>>();
+        let value = values[random.gen_range(0..values.len())];
+
+        let decision = match random.gen_range(0..4) {
+            0 => predicate!(variable == value),
+            1 if value > assignments.get_lower_bound(variable) => predicate!(variable >= value),
+            2 if value < assignments.get_upper_bound(variable) => predicate!(variable <= value),
+            _ => predicate!(variable != value),
+        };
+        Some(decision)
+    }
+
+    fn decide(&mut self, decision: Predicate) {
+        self.description.decisions.push(decision);
+
+        let satisfaction_solver = &mut self.solver.satisfaction_solver;
+        satisfaction_solver.declare_new_decision_level();
+        let num_trail_entries = satisfaction_solver.assignments.num_trail_entries();
+        satisfaction_solver
+            .assignments
+            .post_predicate(decision, None)
+            .expect("Deciding an unassigned predicate cannot fail");
+        satisfaction_solver.propagate();
+
+        self.check_propagations(num_trail_entries);
+        self.check_monotonicity();
+    }
+
+    fn backtrack(&mut self, decision_level: usize) {
+        self.solver
+            .satisfaction_solver
+            .restore_state_at_decision_level(decision_level, &mut self.brancher);
+        self.description.decisions.truncate(decision_level);
+    }
+
+    /// Enumerates all assignments to the variables of the instance for which propagating until
+    /// the fixed point from scratch does not lead to a conflict.
+    fn enumerate_solutions(&self) -> Vec<Vec<i32>> {
+        self.description
+            .initial_domains
+            .iter()
+            .cloned()
+            .multi_cartesian_product()
+            .filter(|values| {
+                let assignment = self
+                    .description
+                    .variables
+                    .iter()
+                    .zip(values)
+                    .map(|(&variable, &value)| predicate!(variable == value))
+                    .collect::<Vec<_>>();
+                self.propagate_from_scratch(&assignment, None, true)
+                    .is_some()
+            })
+            .collect()
+    }
+
+    /// Propagates the provided predicates from scratch starting from the initial domains; every
+    /// propagator (or only the `selected_propagator` if provided) is called once, or until the
+    /// fixed point if `until_fixed_point` is true.
+    ///
+    /// Returns [`None`] if a conflict was detected.
+    fn propagate_from_scratch(
+        &self,
+        predicates: &[Predicate],
+        selected_propagator: Option<PropagatorId>,
+        until_fixed_point: bool,
+    ) -> Option<Assignments> {
+        let mut assignments = self.initial_assignments.clone();
+        let mut trailed_values = self.initial_trailed_values.clone();
+        if predicates
+            .iter()
+            .any(|&predicate| assignments.post_predicate(predicate, None).is_err())
+        {
+            return None;
+        }
+
+        loop {
+            let num_trail_entries = assignments.num_trail_entries();
+            for (propagator_id, propagator) in self
+                .solver
+                .satisfaction_solver
+                .propagator_store()
+                .iter_propagators()
+                .enumerate()
+                .map(|(propagator_id, propagator)| (PropagatorId(propagator_id as u32), propagator))
+                .filter(|(propagator_id, _)| {
+                    selected_propagator.is_none_or(|id| id == *propagator_id)
+                })
+            {
+                let mut reason_store = ReasonStore::default();
+                let mut semantic_minimiser = SemanticMinimiser::default();
+                let context = PropagationContextMut::new(
+                    &mut trailed_values,
+                    &mut assignments,
+                    &mut reason_store,
+                    &mut semantic_minimiser,
+                    propagator_id,
+                );
+                if propagator.debug_propagate_from_scratch(context).is_err() {
+                    return None;
+                }
+            }
+
+            if !until_fixed_point || assignments.num_trail_entries() == num_trail_entries {
+                return Some(assignments);
+            }
+        }
+    }
+
+    /// Returns the solutions which satisfy the decisions which have been made.
+    fn compatible_solutions(&self) -> impl Iterator<Item = &Vec<i32>> + '_ {
+        self.solutions.iter().filter(|solution| {
+            self.description.decisions.iter().all(|decision| {
+                let index = self
+                    .description
+                    .variables
+                    .iter()
+                    .position(|&variable| variable == decision.get_domain())
+                    .expect("Decisions are only made over the variables of the instance");
+                is_satisfied_by(*decision, solution[index])
+            })
+        })
+    }
+
+    /// Checks the state after propagation has reached a fixed point without a conflict.
+    fn check_state(&mut self, options: &PropagatorFuzzerOptions) {
+        let description = &self.description;
+        let assignments = &self.solver.satisfaction_solver.assignments;
+        let compatible_solutions = self.compatible_solutions().collect::<Vec<_>>();
+
+        for solution in compatible_solutions.iter() {
+            for (&variable, &value) in self.description.variables.iter().zip(solution.iter()) {
+                assert!(
+                    assignments.is_value_in_domain(variable, value),
+                    "Propagation removed {value} from the domain of {variable} while it is part of solution {solution:?}\n{description}"
+                );
+            }
+        }
+
+        if options.check_domain_consistency {
+            for (index, &variable) in self.description.variables.iter().enumerate() {
+                for value in assignments.get_domain_iterator(variable) {
+                    assert!(
+                        compatible_solutions
+                            .iter()
+                            .any(|solution| solution[index] == value),
+                        "Value {value} remains in the domain of {variable} while it is not part of any solution\n{description}"
+                    );
+                }
+            }
+        }
+
+        self.check_idempotence();
+    }
+
+    /// Checks that calling the propagators again at the fixed point does not lead to any new
+    /// propagations or conflicts, both when propagating from scratch and incrementally.
+    fn check_idempotence(&mut self) {
+        let description = &self.description;
+        let satisfaction_solver = &mut self.solver.satisfaction_solver;
+
+        let mut assignments = satisfaction_solver.assignments.clone();
+        let mut trailed_values = satisfaction_solver.trailed_values.clone();
+        for (propagator_id, propagator) in satisfaction_solver
+            .propagator_store()
+            .iter_propagators()
+            .enumerate()
+        {
+            let mut reason_store = ReasonStore::default();
+            let mut semantic_minimiser = SemanticMinimiser::default();
+            let context = PropagationContextMut::new(
+                &mut trailed_values,
+                &mut assignments,
+                &mut reason_store,
+                &mut semantic_minimiser,
+                PropagatorId(propagator_id as u32),
+            );
+            let result = propagator.debug_propagate_from_scratch(context);
+            let num_missed_propagations = assignments.num_trail_entries()
+                - satisfaction_solver.assignments.num_trail_entries();
+
+            if result.is_err() || num_missed_propagations > 0 {
+                let missed_propagations = (satisfaction_solver.assignments.num_trail_entries()
+                    ..assignments.num_trail_entries())
+                    .map(|trail_index| assignments.get_trail_entry(trail_index).predicate)
+                    .collect::<Vec<_>>();
+                let name = propagator.name();
+                panic!(
+                    "Propagator '{name}' with id '{propagator_id}' is not at a fixed point when propagating from scratch; result {result:?} and missed propagations {missed_propagations:?}\n{description}"
+                );
+            }
+        }
+
+        let num_propagators = satisfaction_solver
+            .propagator_store()
+            .iter_propagators()
+            .count();
+        for propagator_id in (0..num_propagators as u32).map(PropagatorId) {
+            let num_trail_entries = satisfaction_solver.assignments.num_trail_entries();
+            let result = satisfaction_solver.propagate_single_propagator(propagator_id);
+            let num_new_propagations =
+                satisfaction_solver.assignments.num_trail_entries() - num_trail_entries;
+
+            if result.is_err() || num_new_propagations > 0 {
+                let name = satisfaction_solver.propagator_store()[propagator_id]
+                    .name()
+                    .to_owned();
+                panic!(
+                    "Propagator '{name}' with id '{propagator_id}' is not idempotent; propagating again at the fixed point led to {result:?} with {num_new_propagations} new propagations\n{description}"
+                );
+            }
+        }
+    }
+
+    /// Checks that propagating the decisions once from scratch leads to domains which are at
+    /// least as strong as propagating all but the last decision.
+    fn check_monotonicity(&self) {
+        let description = &self.description;
+        let Some((_, parent_decisions)) = self.description.decisions.split_last() else {
+            return;
+        };
+
+        let child = self.propagate_from_scratch(&self.description.decisions, None, false);
+        let parent = self.propagate_from_scratch(parent_decisions, None, false);
+
+        match (child, parent) {
+            (Some(_), None) => panic!(
+                "Propagation is not monotonic; propagating without the last decision led to a conflict while propagating with it did not\n{description}"
+            ),
+            (Some(child), Some(parent)) => {
+                for domain in child.get_domains() {
+                    for value in child.get_domain_iterator(domain) {
+                        assert!(
+                            parent.is_value_in_domain(domain, value),
+                            "Propagation is not monotonic; {value} is in the domain of {domain} after propagating with the last decision but not without it\n{description}"
+                        );
+                    }
+                }
+            }
+            (None, _) => {}
+        }
+    }
+
+    /// Checks the explanations of the propagations which took place since `num_trail_entries`.
+    fn check_propagations(&mut self, num_trail_entries: usize) {
+        let num_trail_entries_after_propagation = self
+            .solver
+            .satisfaction_solver
+            .assignments
+            .num_trail_entries();
+        for trail_index in num_trail_entries..num_trail_entries_after_propagation {
+            let satisfaction_solver = &mut self.solver.satisfaction_solver;
+            let trail_entry = satisfaction_solver.assignments.get_trail_entry(trail_index);
+            let Some(reason_ref) = trail_entry.reason else {
+                continue;
+            };
+            let propagator_id = satisfaction_solver.reason_store.get_propagator(reason_ref);
+            if propagator_id == ConstraintSatisfactionSolver::get_nogood_propagator_id() {
+                continue;
+            }
+
+            let mut reason = vec![];
+            satisfaction_solver.compute_reason(reason_ref, &mut reason);
+
+            let description = &self.description;
+            let satisfaction_solver = &self.solver.satisfaction_solver;
+            let propagated_predicate = trail_entry.predicate;
+            for predicate in reason.iter() {
+                let trail_position = satisfaction_solver
+                    .assignments
+                    .get_trail_position(predicate);
+                assert!(
+                    trail_position.is_some_and(|position| position < trail_index),
+                    "The reason {reason:?} for {propagated_predicate} contains {predicate} which did not hold at the time of propagation\n{description}"
+                );
+            }
+
+            // Note that the explanation is also correct if it leads to a conflict, since the
+            // explanation then (vacuously) implies the propagated predicate.
+            let name = satisfaction_solver.propagator_store()[propagator_id].name();
+            if let Some(assignments) =
+                self.propagate_from_scratch(&reason, Some(propagator_id), true)
+            {
+                assert!(
+                    assignments.is_predicate_satisfied(propagated_predicate),
+                    "Propagator '{name}' with id '{propagator_id}' propagated {propagated_predicate} with reason {reason:?}, but propagating the reason from scratch does not lead to the propagation\n{description}"
+                );
+            }
+        }
+    }
+
+    /// Checks the reported conflict, and that there is indeed no solution which satisfies the
+    /// decisions.
+    fn check_conflict(&self) {
+        let description = &self.description;
+        let satisfaction_solver = &self.solver.satisfaction_solver;
+        if let StoredConflictInfo::Propagator {
+            conflict_nogood,
+            propagator_id,
+        } = satisfaction_solver.state.get_conflict_info()
+        {
+            assert!(
+                conflict_nogood
+                    .iter()
+                    .all(|&predicate| satisfaction_solver
+                        .assignments
+                        .is_predicate_satisfied(predicate)),
+                "The conflict {conflict_nogood} reported by propagator with id '{propagator_id}' contains predicates which do not hold\n{description}"
+            );
+            if propagator_id != ConstraintSatisfactionSolver::get_nogood_propagator_id() {
+                let name = satisfaction_solver.propagator_store()[propagator_id].name();
+                assert!(
+                    self.propagate_from_scratch(conflict_nogood.as_slice(), Some(propagator_id), true)
+                        .is_none(),
+                    "Propagator '{name}' with id '{propagator_id}' reported the conflict {conflict_nogood}, but propagating it from scratch does not lead to a conflict\n{description}"
+                );
+            }
+        }
+
+        if let Some(solution) = self.compatible_solutions().next() {
+            panic!("Propagation reported a conflict while solution {solution:?} satisfies the decisions\n{description}");
+        }
+    }
+}
+
+impl Display for InstanceDescription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Initial domains:")?;
+        for (variable, values) in self.variables.iter().zip(self.initial_domains.iter()) {
+            writeln!(f, "  {variable}: {values:?}")?;
+        }
+        write!(f, "Decisions: {:?}", self.decisions)
+    }
+}
+
+fn is_satisfied_by(predicate: Predicate, value: i32) -> bool {
+    match predicate {
+        Predicate::LowerBound { lower_bound, .. } => value >= lower_bound,
+        Predicate::UpperBound { upper_bound, .. } => value <= upper_bound,
+        Predicate::NotEqual {
+            not_equal_constant, ..
+        } => value != not_equal_constant,
+        Predicate::Equal {
+            equality_constant, ..
+        } => value == equality_constant,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzz_propagators;
+    use super::PropagatorFuzzerOptions;
+    use crate::constraints;
+
+    #[test]
+    fn linear_less_than_or_equals() {
+        fuzz_propagators(PropagatorFuzzerOptions::default(), |_, variables| {
+            constraints::less_than_or_equals(variables.to_vec(), 7)
+        });
+    }
+
+    #[test]
+    fn all_different() {
+        fuzz_propagators(PropagatorFuzzerOptions::default(), |_, variables| {
+            constraints::all_different(variables.to_vec())
+        });
+    }
+
+    #[test]
+    fn element() {
+        fuzz_propagators(PropagatorFuzzerOptions::default(), |_, variables| {
+            constraints::element(variables[0], variables[1..3].to_vec(), variables[3])
+        });
+    }
+}
@@ -1,14 +1,21 @@
 use std::fmt::Debug;
+use std::fmt::Display;
+
+use clap::ValueEnum;
 
 use crate::basic_types::moving_averages::CumulativeMovingAverage;
 use crate::basic_types::moving_averages::MovingAverage;
 use crate::basic_types::moving_averages::WindowedMovingAverage;
 use crate::basic_types::sequence_generators::ConstantSequence;
 use crate::basic_types::sequence_generators::GeometricSequence;
+use crate::basic_types::sequence_generators::InnerOuterGeometricSequence;
 use crate::basic_types::sequence_generators::LubySequence;
 use crate::basic_types::sequence_generators::SequenceGenerator;
 use crate::basic_types::sequence_generators::SequenceGeneratorType;
+use crate::create_statistics_struct;
 use crate::pumpkin_assert_simple;
+use crate::statistics::Statistic;
+use crate::statistics::StatisticLogger;
 
 /// The options which are used by the solver to determine when a restart should occur.
 ///
@@ -28,6 +35,10 @@ use crate::pumpkin_assert_simple;
 /// (see [\[5\]](https://www.sciencedirect.com/science/article/pii/0020019093900299)) and
 /// constant restarts (see [Section 3 of \[1\]](https://fmv.jku.at/papers/BiereFroehlich-POS15.pdf)).
 ///
+/// Additionally, the solver can switch between a focused mode (using the aforementioned restart
+/// strategy) and a stable mode (using restarts based solely on a sequence, by default Luby
+/// restarts, also called "reluctant doubling") as done in \[6\]; see [`SearchModeStrategy`].
+///
 /// # Bibliography
 /// \[1\] A. Biere and A. Fröhlich, ‘Evaluating CDCL restart schemes’, Proceedings of Pragmatics of
 /// SAT, pp. 1–17, 2015.
@@ -44,6 +55,10 @@ use crate::pumpkin_assert_simple;
 ///
 /// \[5\] M. Luby, A. Sinclair, and D. Zuckerman, ‘Optimal speedup of Las Vegas algorithms’,
 /// Information Processing Letters, vol. 47, no. 4, pp. 173–180, 1993.
+///
+/// \[6\] A. Biere and M. Heule and M. Järvisalo and N. Manthey, ‘CaDiCaL, Kissat, Paracooba,
+/// Plingeling and Treengeling entering the SAT Competition 2020’, Proc. of SAT Competition, pp.
+/// 51–53, 2020.
 #[derive(Debug, Clone, Copy)]
 pub struct RestartOptions {
     /// Decides the sequence based on which the restarts are performed.
@@ -78,6 +93,66 @@ pub struct RestartOptions {
     pub geometric_coef: Option<f64>,
     /// Determines whether restarts should be able to occur
     pub no_restarts: bool,
+    /// Determines which search mode(s) are used by the solver.
+    pub search_mode_strategy: SearchModeStrategy,
+    /// Decides the sequence based on which the restarts are performed in
+    /// [`SearchMode::Stable`]; no restarts are forced or blocked based on the LBD in this mode.
+    pub stable_sequence_generator_type: SequenceGeneratorType,
+    /// The base interval of the sequence used in [`SearchMode::Stable`] (see
+    /// [`RestartOptions::base_interval`]).
+    pub stable_base_interval: u64,
+    /// The number of conflicts in the first phase when using
+    /// [`SearchModeStrategy::Alternating`]; every subsequent phase is twice as long as the
+    /// previous one.
+    pub mode_switch_interval: u64,
+}
+
+/// The search modes between which the solver can switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Frequent restarts which are forced (or blocked) based on the quality of the learned
+    /// clauses.
+    Focused,
+    /// Infrequent restarts which are based solely on a sequence.
+    Stable,
+}
+
+impl Display for SearchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchMode::Focused => write!(f, "focused"),
+            SearchMode::Stable => write!(f, "stable"),
+        }
+    }
+}
+
+/// Specifies which [`SearchMode`]s are used by the solver.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SearchModeStrategy {
+    /// Only use [`SearchMode::Focused`].
+    #[default]
+    Focused,
+    /// Only use [`SearchMode::Stable`].
+    Stable,
+    /// Alternate between [`SearchMode::Focused`] and [`SearchMode::Stable`] (starting in focused
+    /// mode), where the length of the phases (in number of conflicts) doubles after every switch.
+    ///
+    /// The [`Brancher`] is notified of every switch (see [`Brancher::on_search_mode_switch`]) so
+    /// that it can adjust how it determines the phase of its decisions.
+    ///
+    /// [`Brancher`]: crate::branching::Brancher
+    /// [`Brancher::on_search_mode_switch`]: crate::branching::Brancher::on_search_mode_switch
+    Alternating,
+}
+
+impl Display for SearchModeStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchModeStrategy::Focused => write!(f, "focused"),
+            SearchModeStrategy::Stable => write!(f, "stable"),
+            SearchModeStrategy::Alternating => write!(f, "alternating"),
+        }
+    }
 }
 
 impl Default for RestartOptions {
@@ -92,15 +167,37 @@ impl Default for RestartOptions {
             num_assigned_window: 5000,
             geometric_coef: None,
             no_restarts: false,
+            search_mode_strategy: SearchModeStrategy::Focused,
+            stable_sequence_generator_type: SequenceGeneratorType::Luby,
+            stable_base_interval: 1024,
+            mode_switch_interval: 1000,
         }
     }
 }
 
+create_statistics_struct!(
+    /// The statistics related to the restart strategy
+    RestartStatistics {
+        /// The number of restarts which have been blocked
+        num_blocked_restarts: u64,
+        /// The number of times the solver has switched between search modes
+        num_mode_switches: u64,
+        /// The number of conflicts encountered in focused mode
+        num_conflicts_in_focused_mode: u64,
+        /// The number of conflicts encountered in stable mode
+        num_conflicts_in_stable_mode: u64,
+});
+
 #[derive(Debug)]
 pub(crate) struct RestartStrategy {
     /// A generator for determining how many conflicts should be found before the next restart is
-    /// able to take place (one example of such a generator is [`LubySequence`]).
+    /// able to take place in [`SearchMode::Focused`] (one example of such a generator is
+    /// [`LubySequence`]).
     sequence_generator: Box<dyn SequenceGenerator>,
+    /// A generator for determining how many conflicts should be found before the next restart
+    /// takes place in [`SearchMode::Stable`]; it is only created if the solver can be in that
+    /// mode.
+    stable_sequence_generator: Option<Box<dyn SequenceGenerator>>,
     /// The number of conflicts encountered since the last restart took place
     number_of_conflicts_encountered_since_restart: u64,
     /// The minimum number of conflicts until the next restart is able to take place (note that if
@@ -128,10 +225,20 @@ pub(crate) struct RestartStrategy {
     number_of_assigned_variables_moving_average: Box<dyn MovingAverage<u64>>,
     /// The number of restarts which have been performed.
     number_of_restarts: u64,
-    /// The number of restarts which have been blocked.
-    number_of_blocked_restarts: u64,
     /// Determines whether restarts should be able to occur
     no_restarts: bool,
+    /// The current search mode of the solver.
+    search_mode: SearchMode,
+    /// Determines whether the solver switches between search modes.
+    search_mode_strategy: SearchModeStrategy,
+    /// The number of conflicts encountered since the last switch between search modes.
+    number_of_conflicts_encountered_since_mode_switch: u64,
+    /// The number of conflicts in the current phase before switching between search modes.
+    mode_switch_interval: u64,
+    /// The options which were used to create the restart strategy; used for reporting
+    /// statistics.
+    options: RestartOptions,
+    statistics: RestartStatistics,
 }
 
 impl Default for RestartStrategy {
@@ -142,26 +249,30 @@ impl Default for RestartStrategy {
 
 impl RestartStrategy {
     pub(crate) fn new(options: RestartOptions) -> Self {
-        let mut sequence_generator: Box<dyn SequenceGenerator> =
-            match options.sequence_generator_type {
-                SequenceGeneratorType::Constant => Box::new(ConstantSequence::new(
-                    options.base_interval as i64,
-                )),
-                SequenceGeneratorType::Geometric => Box::new(GeometricSequence::new(
-                    options.base_interval as i64,
-                    options.geometric_coef.expect(
-                        "Using the geometric sequence for restarts, but the parameter restarts-geometric-coef is not defined.",
-                    ),
-                )),
-                SequenceGeneratorType::Luby => Box::new(LubySequence::new(options.base_interval as i64)),
-            };
+        let sequence_generator = create_sequence_generator(
+            options.sequence_generator_type,
+            options.base_interval,
+            options.geometric_coef,
+        );
+        let stable_sequence_generator =
+            (options.search_mode_strategy != SearchModeStrategy::Focused).then(|| {
+                create_sequence_generator(
+                    options.stable_sequence_generator_type,
+                    options.stable_base_interval,
+                    options.geometric_coef,
+                )
+            });
 
-        let number_of_conflicts_until_restart = sequence_generator.next().try_into().expect("Expected restart generator to generate a positive value but it generated a negative one");
+        let search_mode = match options.search_mode_strategy {
+            SearchModeStrategy::Focused | SearchModeStrategy::Alternating => SearchMode::Focused,
+            SearchModeStrategy::Stable => SearchMode::Stable,
+        };
 
-        RestartStrategy {
+        let mut restart_strategy = RestartStrategy {
             sequence_generator,
+            stable_sequence_generator,
             number_of_conflicts_encountered_since_restart: 0,
-            number_of_conflicts_until_restart,
+            number_of_conflicts_until_restart: 0,
             minimum_number_of_conflicts_before_first_restart: options
                 .min_num_conflicts_before_first_restart,
             lbd_short_term_moving_average: Box::new(WindowedMovingAverage::new(
@@ -174,9 +285,23 @@ impl RestartStrategy {
                 options.num_assigned_window,
             )),
             number_of_restarts: 0,
-            number_of_blocked_restarts: 0,
             no_restarts: options.no_restarts,
-        }
+            search_mode,
+            search_mode_strategy: options.search_mode_strategy,
+            number_of_conflicts_encountered_since_mode_switch: 0,
+            mode_switch_interval: options.mode_switch_interval,
+            options,
+            statistics: RestartStatistics::default(),
+        };
+        restart_strategy.number_of_conflicts_until_restart = restart_strategy.next_restart_limit();
+
+        restart_strategy
+    }
+
+    /// Returns the [`SearchMode`] in which the search starts if the solver can be in
+    /// [`SearchMode::Stable`], and [`None`] if the solver is always in [`SearchMode::Focused`].
+    pub(crate) fn initial_search_mode(&self) -> Option<SearchMode> {
+        (self.search_mode_strategy != SearchModeStrategy::Focused).then_some(self.search_mode)
     }
 
     /// Determines whether the restart strategy indicates that a restart should take place; the
@@ -198,6 +323,16 @@ impl RestartStrategy {
             return false;
         }
 
+        // A restart is forced when the solver should switch between search modes
+        if self.should_switch_mode() {
+            return true;
+        }
+
+        if self.search_mode == SearchMode::Stable {
+            // In stable mode, the restarts are based solely on the restart sequence
+            return self.should_trigger_later_restart();
+        }
+
         // Do not restart until a certain number of conflicts take place before the first restart
         // this is done to collect some early runtime statistics for the restart strategy
         if self.should_restart_first_time() {
@@ -216,6 +351,11 @@ impl RestartStrategy {
             <= self.lbd_short_term_moving_average.value()
     }
 
+    fn should_switch_mode(&self) -> bool {
+        self.search_mode_strategy == SearchModeStrategy::Alternating
+            && self.number_of_conflicts_encountered_since_mode_switch >= self.mode_switch_interval
+    }
+
     fn should_restart_first_time(&self) -> bool {
         self.number_of_restarts == 0
             && self.number_of_conflicts_encountered_since_restart
@@ -237,10 +377,20 @@ impl RestartStrategy {
 
         // Increase the number of conflicts encountered since the last restart
         self.number_of_conflicts_encountered_since_restart += 1;
+        self.number_of_conflicts_encountered_since_mode_switch += 1;
+
+        match self.search_mode {
+            SearchMode::Focused => self.statistics.num_conflicts_in_focused_mode += 1,
+            SearchMode::Stable => {
+                // Restarts are not blocked in stable mode
+                self.statistics.num_conflicts_in_stable_mode += 1;
+                return;
+            }
+        }
 
         if self.should_block_restart(number_of_pruned_values) {
             // Restart has been blocked
-            self.number_of_blocked_restarts += 1;
+            self.statistics.num_blocked_restarts += 1;
             self.reset_values()
         }
     }
@@ -267,12 +417,32 @@ impl RestartStrategy {
     }
 
     /// Notifies the restart strategy that a restart has taken place so that it can adjust its
-    /// internal values
-    pub(crate) fn notify_restart(&mut self) {
+    /// internal values.
+    ///
+    /// Returns the new [`SearchMode`] if the solver has switched between search modes as part of
+    /// this restart.
+    pub(crate) fn notify_restart(&mut self) -> Option<SearchMode> {
         pumpkin_assert_simple!(!self.no_restarts);
 
         self.number_of_restarts += 1;
-        self.reset_values()
+
+        let switched_mode = if self.should_switch_mode() {
+            self.search_mode = match self.search_mode {
+                SearchMode::Focused => SearchMode::Stable,
+                SearchMode::Stable => SearchMode::Focused,
+            };
+            self.number_of_conflicts_encountered_since_mode_switch = 0;
+            self.mode_switch_interval *= 2;
+            self.statistics.num_mode_switches += 1;
+
+            Some(self.search_mode)
+        } else {
+            None
+        };
+
+        self.reset_values();
+
+        switched_mode
     }
 
     /// Resets the values related to determining whether a restart takes place; this method should
@@ -280,10 +450,151 @@ impl RestartStrategy {
     fn reset_values(&mut self) {
         pumpkin_assert_simple!(!self.no_restarts);
 
-        self.number_of_conflicts_until_restart =
-            self.sequence_generator.next().try_into().expect("Expected restart generator to generate a positive value but it generated a negative one");
+        self.number_of_conflicts_until_restart = self.next_restart_limit();
         self.number_of_conflicts_encountered_since_restart = 0;
-        self.lbd_short_term_moving_average
-            .adapt(self.number_of_conflicts_until_restart);
+        if self.search_mode == SearchMode::Focused {
+            self.lbd_short_term_moving_average
+                .adapt(self.number_of_conflicts_until_restart);
+        }
+    }
+
+    /// Returns the minimum number of conflicts until the next restart according to the sequence
+    /// of the current search mode.
+    fn next_restart_limit(&mut self) -> u64 {
+        let sequence_generator = match self.search_mode {
+            SearchMode::Focused => &mut self.sequence_generator,
+            SearchMode::Stable => self
+                .stable_sequence_generator
+                .as_mut()
+                .expect("the stable sequence is created when stable mode can be used"),
+        };
+
+        sequence_generator.next().try_into().expect("Expected restart generator to generate a positive value but it generated a negative one")
+    }
+
+    pub(crate) fn log_statistics(&self, statistic_logger: StatisticLogger) {
+        // The strategies are logged as strings since they are not numerical statistics
+        statistic_logger
+            .attach_to_prefix("sequence")
+            .log_statistic(format!("\"{}\"", self.options.sequence_generator_type));
+        statistic_logger
+            .attach_to_prefix("searchModeStrategy")
+            .log_statistic(format!("\"{}\"", self.options.search_mode_strategy));
+        if self.options.search_mode_strategy != SearchModeStrategy::Focused {
+            statistic_logger
+                .attach_to_prefix("stableSequence")
+                .log_statistic(format!(
+                    "\"{}\"",
+                    self.options.stable_sequence_generator_type
+                ));
+        }
+        self.statistics.log(statistic_logger);
+    }
+}
+
+fn create_sequence_generator(
+    sequence_generator_type: SequenceGeneratorType,
+    base_interval: u64,
+    geometric_coef: Option<f64>,
+) -> Box<dyn SequenceGenerator> {
+    match sequence_generator_type {
+        SequenceGeneratorType::Constant => Box::new(ConstantSequence::new(base_interval as i64)),
+        SequenceGeneratorType::Geometric => Box::new(GeometricSequence::new(
+            base_interval as i64,
+            geometric_coef.expect(
+                "Using the geometric sequence for restarts, but the parameter restarts-geometric-coef is not defined.",
+            ),
+        )),
+        SequenceGeneratorType::Luby => Box::new(LubySequence::new(base_interval as i64)),
+        SequenceGeneratorType::InnerOuterGeometric => Box::new(InnerOuterGeometricSequence::new(
+            base_interval as i64,
+            geometric_coef.expect(
+                "Using the inner-outer geometric sequence for restarts, but the parameter restarts-geometric-coef is not defined.",
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RestartOptions;
+    use super::RestartStrategy;
+    use super::SearchMode;
+    use super::SearchModeStrategy;
+    use super::SequenceGeneratorType;
+
+    #[test]
+    fn focused_strategy_does_not_create_stable_sequence() {
+        // The stable sequence would require the geometric coefficient, which is not provided.
+        let restart_strategy = RestartStrategy::new(RestartOptions {
+            search_mode_strategy: SearchModeStrategy::Focused,
+            stable_sequence_generator_type: SequenceGeneratorType::Geometric,
+            geometric_coef: None,
+            ..Default::default()
+        });
+
+        assert_eq!(restart_strategy.initial_search_mode(), None);
+    }
+
+    #[test]
+    fn alternating_strategy_starts_in_focused_mode() {
+        let restart_strategy = RestartStrategy::new(RestartOptions {
+            search_mode_strategy: SearchModeStrategy::Alternating,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            restart_strategy.initial_search_mode(),
+            Some(SearchMode::Focused)
+        );
+    }
+
+    #[test]
+    fn alternating_strategy_switches_modes_with_doubling_interval() {
+        let mut restart_strategy = RestartStrategy::new(RestartOptions {
+            search_mode_strategy: SearchModeStrategy::Alternating,
+            mode_switch_interval: 10,
+            ..Default::default()
+        });
+
+        let mut switches = vec![];
+        for conflict in 1..=70 {
+            restart_strategy.notify_conflict(1, 0);
+            if restart_strategy.should_restart() {
+                if let Some(search_mode) = restart_strategy.notify_restart() {
+                    switches.push((conflict, search_mode));
+                }
+            }
+        }
+
+        assert_eq!(
+            switches,
+            vec![
+                (10, SearchMode::Stable),
+                (30, SearchMode::Focused),
+                (70, SearchMode::Stable)
+            ]
+        );
+    }
+
+    #[test]
+    fn stable_strategy_follows_restart_sequence() {
+        let mut restart_strategy = RestartStrategy::new(RestartOptions {
+            search_mode_strategy: SearchModeStrategy::Stable,
+            stable_base_interval: 4,
+            ..Default::default()
+        });
+
+        // The Luby sequence multiplied by 4 is 4, 4, 8, ...
+        let mut restarts = vec![];
+        for conflict in 1..=16 {
+            restart_strategy.notify_conflict(1, 0);
+            if restart_strategy.should_restart() {
+                assert_eq!(restart_strategy.notify_restart(), None);
+                restarts.push(conflict);
+            }
+        }
+
+        assert_eq!(restarts, vec![4, 8, 16]);
     }
 }
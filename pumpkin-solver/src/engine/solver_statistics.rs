@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use crate::basic_types::moving_averages::CumulativeMovingAverage;
+use crate::basic_types::moving_averages::MovingAverage;
 use crate::create_statistics_struct;
+use crate::engine::propagation::PropagatorCostClass;
 
 create_statistics_struct!(
     /// Structure responsible for storing several statistics of the solving process of the
@@ -8,7 +12,13 @@ create_statistics_struct!(
         /// Core statistics of the solver engine (e.g. the number of decisions)
         engine_statistics: EngineStatistics,
         /// The statistics related to clause learning
-        learned_clause_statistics: LearnedClauseStatistics
+        learned_clause_statistics: LearnedClauseStatistics,
+        /// The statistics related to the inprocessing of learned nogoods
+        inprocessing_statistics: InprocessingStatistics,
+        /// The statistics related to presolving
+        presolve_statistics: PresolveStatistics,
+        /// The statistics related to the propagation of the different cost classes
+        propagation_statistics: PropagationStatistics
     }
 );
 
@@ -36,12 +46,146 @@ create_statistics_struct!(
         average_number_of_removed_literals_recursive: CumulativeMovingAverage<u64>,
         /// The average number of literals removed by semantic minimisation during conflict analysis
         average_number_of_removed_literals_semantic: CumulativeMovingAverage<u64>,
+        /// The average number of literals removed by the generalised reasons of propagators during conflict analysis
+        average_number_of_removed_literals_generalised: CumulativeMovingAverage<u64>,
         /// The number of learned clauses which have a size of 1
         num_unit_clauses_learned: u64,
         /// The average length of the learned clauses
         average_learned_clause_length: CumulativeMovingAverage<u64>,
         /// The average number of levels which have been backtracked by the solver (e.g. when a learned clause is created)
         average_backtrack_amount: CumulativeMovingAverage<u64>,
+        /// The number of conflicts after which the solver backtracked chronologically rather than to the asserting level
+        num_chronological_backtracks: u64,
         /// The average literal-block distance (LBD) metric for newly added learned nogoods
         average_lbd: CumulativeMovingAverage<u64>,
+        /// The number of extension literals which have been defined by extended resolution
+        num_extension_literals: u64,
+        /// The number of times a pair of predicates in a learned nogood has been replaced by an extension literal
+        num_extension_literal_substitutions: u64,
+        /// The number of nogoods which have been derived from learned nogoods using the symmetries of the problem
+        num_symmetric_nogoods: u64,
+});
+
+create_statistics_struct!(
+    /// The statistics related to the inprocessing of learned nogoods
+    InprocessingStatistics {
+        /// The number of rounds of inprocessing which have been performed
+        num_rounds: u64,
+        /// The number of learned nogoods which have been vivified
+        num_vivified_nogoods: u64,
+        /// The number of learned nogoods which have been shortened by vivification
+        num_shortened_nogoods: u64,
+        /// The number of predicates which have been removed from learned nogoods by vivification
+        num_removed_predicates: u64,
+        /// The number of learned nogoods which have been removed since they were subsumed by another nogood
+        num_subsumed_nogoods: u64,
+        /// The number of learned nogoods which have been strengthened by self-subsuming resolution
+        num_strengthened_nogoods: u64,
+        /// The number of learned nogoods which have been removed since they were satisfied at the root
+        num_satisfied_nogoods: u64,
+});
+
+create_statistics_struct!(
+    /// The statistics related to presolving
+    PresolveStatistics {
+        /// The number of rounds of presolving which have been performed
+        num_rounds: u64,
+        /// The number of predicates which have been probed
+        num_probes: u64,
+        /// The number of values which have been removed from the bounds of the variables by failed probes
+        num_shaved_values: u64,
+        /// The number of Boolean literals which have been found to be implied at the root by probing both polarities of a variable
+        num_implied_literals: u64,
+        /// The number of pairs of equivalent Boolean literals which have been detected
+        num_equivalent_literals: u64,
+        /// The number of nogoods in which a Boolean variable has been substituted by an equivalent literal
+        num_substituted_nogoods: u64,
+        /// The amount of time (in milliseconds) which is spent in presolving
+        time_spent_in_presolve: u64,
+});
+
+create_statistics_struct!(
+    /// The statistics related to the propagation of the different cost classes
+    PropagationStatistics {
+        /// The statistics of the propagators in the cheap cost class
+        cheap: CostClassStatistics,
+        /// The statistics of the propagators in the moderate cost class
+        moderate: CostClassStatistics,
+        /// The statistics of the propagators in the expensive cost class
+        expensive: CostClassStatistics,
+        /// The number of times that propagators which are delayed until a leaf of the search tree have been released
+        num_leaf_releases: u64,
+});
+
+create_statistics_struct!(
+    /// The statistics related to the propagation of the propagators in a single cost class
+    CostClassStatistics {
+        /// The number of times a propagator of the cost class has been propagated
+        num_calls: u64,
+        /// The amount of time (in nanoseconds) which is spent propagating the propagators of the cost class; this is only measured if the propagators are profiled
+        time_spent_in_propagation: u64,
 });
+
+impl PropagationStatistics {
+    /// Records a single call to a propagator of the provided cost class which took `duration`.
+    pub(crate) fn record_propagation(
+        &mut self,
+        cost_class: PropagatorCostClass,
+        duration: Duration,
+    ) {
+        let statistics = match cost_class {
+            PropagatorCostClass::Cheap => &mut self.cheap,
+            PropagatorCostClass::Moderate => &mut self.moderate,
+            PropagatorCostClass::Expensive => &mut self.expensive,
+        };
+
+        statistics.num_calls += 1;
+        statistics.time_spent_in_propagation += duration.as_nanos() as u64;
+    }
+}
+
+create_statistics_struct!(
+    /// The profiling statistics of a single propagator (or of all propagators with the same name)
+    PropagatorStatistics {
+        /// The number of times the propagator has been propagated
+        num_calls: u64,
+        /// The amount of time (in nanoseconds) which is spent propagating the propagator; this is only measured if the propagators are profiled
+        time_spent_in_propagation: u64,
+        /// The number of domain changes made by the propagator
+        num_prunings: u64,
+        /// The number of conflicts reported by the propagator
+        num_conflicts: u64,
+        /// The average number of predicates in the explanations (and conflicts) provided by the propagator during conflict analysis
+        average_explanation_size: CumulativeMovingAverage<u64>,
+});
+
+impl PropagatorStatistics {
+    /// Records a single call to the propagator which took `duration` and made `num_prunings`
+    /// domain changes.
+    pub(crate) fn record_propagation(
+        &mut self,
+        duration: Duration,
+        num_prunings: usize,
+        is_conflict: bool,
+    ) {
+        self.num_calls += 1;
+        self.time_spent_in_propagation += duration.as_nanos() as u64;
+        self.num_prunings += num_prunings as u64;
+        self.num_conflicts += is_conflict as u64;
+    }
+
+    /// Records an explanation (or a conflict) consisting of `size` predicates.
+    pub(crate) fn record_explanation(&mut self, size: usize) {
+        self.average_explanation_size.add_term(size as u64);
+    }
+
+    /// Adds the statistics of `other` to these statistics.
+    pub(crate) fn merge(&mut self, other: &PropagatorStatistics) {
+        self.num_calls += other.num_calls;
+        self.time_spent_in_propagation += other.time_spent_in_propagation;
+        self.num_prunings += other.num_prunings;
+        self.num_conflicts += other.num_conflicts;
+        self.average_explanation_size
+            .merge(&other.average_explanation_size);
+    }
+}
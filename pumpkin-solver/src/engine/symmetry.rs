@@ -0,0 +1,239 @@
+use std::fmt::Debug;
+
+use crate::basic_types::HashMap;
+use crate::basic_types::HashSet;
+use crate::engine::variables::DomainId;
+use crate::predicates::Predicate;
+#[cfg(doc)]
+use crate::Solver;
+
+/// A symmetry of a problem, which can be registered with [`Solver::add_symmetry`].
+///
+/// A symmetry maps (partial) assignments onto (partial) assignments such that solutions are mapped
+/// onto solutions. When a symmetry is registered, the solver maps every nogood which it learns
+/// onto its symmetric image, and adds this image as a learned nogood as well (also known as
+/// symmetric nogood learning).
+///
+/// The symmetry should be a symmetry of _all_ constraints which are added to the solver, including
+/// the constraints over auxiliary variables and the bounds on the objective when optimising;
+/// otherwise the symmetric nogoods can remove solutions.
+pub trait Symmetry: Debug {
+    /// Returns the image of the provided predicate under the symmetry, or [`None`] if the image
+    /// cannot be expressed as a single predicate; in the latter case, no symmetric nogood is
+    /// derived from a nogood containing the predicate.
+    fn map_predicate(&self, predicate: Predicate) -> Option<Predicate>;
+}
+
+/// A [`Symmetry`] which permutes the variables; the variables which are not part of the
+/// permutation are mapped onto themselves.
+///
+/// For example, swapping two rows of a matrix model corresponds to a permutation which maps every
+/// variable of the first row onto the variable in the same column of the second row, and vice
+/// versa.
+#[derive(Clone, Debug)]
+pub struct VariablePermutation {
+    mapping: HashMap<DomainId, DomainId>,
+}
+
+impl VariablePermutation {
+    /// Creates the permutation which maps the first variable of every pair onto the second
+    /// variable.
+    ///
+    /// Panics if the pairs do not describe a permutation; i.e. if a variable is mapped onto more
+    /// than once or if it is mapped onto multiple variables.
+    pub fn new(mapping: impl IntoIterator<Item = (DomainId, DomainId)>) -> Self {
+        let mapping: HashMap<DomainId, DomainId> = mapping.into_iter().collect();
+
+        let images: HashSet<DomainId> = mapping.values().copied().collect();
+        assert!(
+            images.len() == mapping.len() && images.iter().all(|image| mapping.contains_key(image)),
+            "The provided mapping should be a permutation of the variables"
+        );
+
+        VariablePermutation { mapping }
+    }
+}
+
+impl Symmetry for VariablePermutation {
+    fn map_predicate(&self, predicate: Predicate) -> Option<Predicate> {
+        let Some(&image) = self.mapping.get(&predicate.get_domain()) else {
+            return Some(predicate);
+        };
+
+        Some(match predicate {
+            Predicate::LowerBound { lower_bound, .. } => Predicate::LowerBound {
+                domain_id: image,
+                lower_bound,
+            },
+            Predicate::UpperBound { upper_bound, .. } => Predicate::UpperBound {
+                domain_id: image,
+                upper_bound,
+            },
+            Predicate::NotEqual {
+                not_equal_constant, ..
+            } => Predicate::NotEqual {
+                domain_id: image,
+                not_equal_constant,
+            },
+            Predicate::Equal {
+                equality_constant, ..
+            } => Predicate::Equal {
+                domain_id: image,
+                equality_constant,
+            },
+        })
+    }
+}
+
+/// A [`Symmetry`] which permutes the values of the provided variables; the values which are not
+/// part of the permutation are mapped onto themselves.
+///
+/// For example, in a graph colouring problem the colours of all nodes can be swapped.
+///
+/// Bound predicates (e.g. `[x >= v]`) can only be mapped if the permutation maps the values which
+/// satisfy the predicate onto each other.
+#[derive(Clone, Debug)]
+pub struct ValuePermutation {
+    variables: HashSet<DomainId>,
+    mapping: HashMap<i32, i32>,
+}
+
+impl ValuePermutation {
+    /// Creates the permutation which maps the first value of every pair onto the second value
+    /// for each of the provided `variables`.
+    ///
+    /// Panics if the pairs do not describe a permutation; i.e. if a value is mapped onto more than
+    /// once or if it is mapped onto multiple values.
+    pub fn new(
+        variables: impl IntoIterator<Item = DomainId>,
+        mapping: impl IntoIterator<Item = (i32, i32)>,
+    ) -> Self {
+        let mapping: HashMap<i32, i32> = mapping.into_iter().collect();
+
+        let images: HashSet<i32> = mapping.values().copied().collect();
+        assert!(
+            images.len() == mapping.len() && images.iter().all(|image| mapping.contains_key(image)),
+            "The provided mapping should be a permutation of the values"
+        );
+
+        ValuePermutation {
+            variables: variables.into_iter().collect(),
+            mapping,
+        }
+    }
+
+    fn map_value(&self, value: i32) -> i32 {
+        self.mapping.get(&value).copied().unwrap_or(value)
+    }
+
+    /// Returns whether the values which satisfy `condition` are mapped onto values which satisfy
+    /// `condition`.
+    fn preserves(&self, condition: impl Fn(i32) -> bool) -> bool {
+        self.mapping
+            .iter()
+            .all(|(&value, &image)| condition(value) == condition(image))
+    }
+}
+
+impl Symmetry for ValuePermutation {
+    fn map_predicate(&self, predicate: Predicate) -> Option<Predicate> {
+        if !self.variables.contains(&predicate.get_domain()) {
+            return Some(predicate);
+        }
+
+        match predicate {
+            Predicate::LowerBound { lower_bound, .. } => self
+                .preserves(|value| value >= lower_bound)
+                .then_some(predicate),
+            Predicate::UpperBound { upper_bound, .. } => self
+                .preserves(|value| value <= upper_bound)
+                .then_some(predicate),
+            Predicate::NotEqual {
+                domain_id,
+                not_equal_constant,
+            } => Some(Predicate::NotEqual {
+                domain_id,
+                not_equal_constant: self.map_value(not_equal_constant),
+            }),
+            Predicate::Equal {
+                domain_id,
+                equality_constant,
+            } => Some(Predicate::Equal {
+                domain_id,
+                equality_constant: self.map_value(equality_constant),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicate;
+
+    #[test]
+    fn variable_permutation_maps_the_variable_of_a_predicate() {
+        let x = DomainId::new(0);
+        let y = DomainId::new(1);
+        let z = DomainId::new(2);
+
+        let symmetry = VariablePermutation::new([(x, y), (y, x)]);
+
+        assert_eq!(
+            Some(predicate![y >= 3]),
+            symmetry.map_predicate(predicate![x >= 3])
+        );
+        assert_eq!(
+            Some(predicate![x != 2]),
+            symmetry.map_predicate(predicate![y != 2])
+        );
+        assert_eq!(
+            Some(predicate![z <= 1]),
+            symmetry.map_predicate(predicate![z <= 1])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn variable_mapping_which_is_not_a_permutation_is_rejected() {
+        let x = DomainId::new(0);
+        let y = DomainId::new(1);
+
+        let _ = VariablePermutation::new([(x, y)]);
+    }
+
+    #[test]
+    fn value_permutation_maps_the_value_of_a_predicate() {
+        let x = DomainId::new(0);
+        let y = DomainId::new(1);
+
+        let symmetry = ValuePermutation::new([x], [(1, 2), (2, 1)]);
+
+        assert_eq!(
+            Some(predicate![x == 2]),
+            symmetry.map_predicate(predicate![x == 1])
+        );
+        assert_eq!(
+            Some(predicate![x != 1]),
+            symmetry.map_predicate(predicate![x != 2])
+        );
+        assert_eq!(
+            Some(predicate![y == 1]),
+            symmetry.map_predicate(predicate![y == 1])
+        );
+    }
+
+    #[test]
+    fn value_permutation_only_maps_preserved_bounds() {
+        let x = DomainId::new(0);
+
+        let symmetry = ValuePermutation::new([x], [(1, 2), (2, 1)]);
+
+        assert_eq!(
+            Some(predicate![x >= 1]),
+            symmetry.map_predicate(predicate![x >= 1])
+        );
+        assert_eq!(None, symmetry.map_predicate(predicate![x >= 2]));
+        assert_eq!(None, symmetry.map_predicate(predicate![x <= 1]));
+    }
+}
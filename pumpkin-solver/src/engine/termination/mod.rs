@@ -15,6 +15,12 @@ pub trait TerminationCondition {
     fn should_stop(&mut self) -> bool;
 }
 
+impl<T: TerminationCondition + ?Sized> TerminationCondition for &mut T {
+    fn should_stop(&mut self) -> bool {
+        (**self).should_stop()
+    }
+}
+
 impl<T: TerminationCondition> TerminationCondition for Option<T> {
     fn should_stop(&mut self) -> bool {
         match self {
@@ -5,6 +5,9 @@ use crate::engine::variables::DomainId;
 
 pub(crate) struct VariableNames {
     integers: HashMap<DomainId, String>,
+    /// Additional names of the integer variables, e.g. for model variables which are represented
+    /// by the same domain.
+    aliases: HashMap<DomainId, Vec<String>>,
 }
 
 impl VariableNames {
@@ -13,9 +16,30 @@ impl VariableNames {
         self.integers.get(&domain_id).map(|s| s.as_str())
     }
 
+    /// The integer variables which have a name, ordered by their domain id.
+    pub(crate) fn named_integers(&self) -> Vec<DomainId> {
+        let mut integers: Vec<DomainId> = self.integers.keys().copied().collect();
+        integers.sort_by_key(|domain_id| domain_id.id);
+        integers
+    }
+
     /// Add a name to the integer variable. This will override existing the name if it
     /// exists.
     pub(crate) fn add_integer(&mut self, integer: DomainId, name: String) {
         let _ = self.integers.insert(integer, name);
     }
+
+    /// Add an additional name to the integer variable, which does not replace the name given by
+    /// [`VariableNames::add_integer`].
+    pub(crate) fn add_alias(&mut self, integer: DomainId, alias: String) {
+        self.aliases.entry(integer).or_default().push(alias);
+    }
+
+    /// Get the additional names of the integer variable, in the order in which they were added.
+    pub(crate) fn get_aliases(&self, domain_id: DomainId) -> &[String] {
+        self.aliases
+            .get(&domain_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
 }
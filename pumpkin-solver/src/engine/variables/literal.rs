@@ -47,6 +47,12 @@ impl Literal {
     }
 }
 
+impl From<Literal> for AffineView<DomainId> {
+    fn from(literal: Literal) -> Self {
+        literal.get_integer_variable()
+    }
+}
+
 impl Not for Literal {
     type Output = Literal;
 
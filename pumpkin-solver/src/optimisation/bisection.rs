@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use log::info;
+
+use super::solution_callback::SolutionCallback;
+use super::OptimisationProcedure;
+use crate::basic_types::CSPSolverExecutionFlag;
+use crate::branching::Brancher;
+use crate::optimisation::OptimisationDirection;
+use crate::predicate;
+use crate::results::OptimisationResult;
+use crate::results::Solution;
+use crate::results::SolutionReference;
+use crate::termination::Combinator;
+use crate::termination::TerminationCondition;
+use crate::termination::TimeBudget;
+use crate::variables::IntegerVariable;
+use crate::Solver;
+
+/// Implements the bisection (or binary search) optimisation procedure.
+///
+/// The procedure keeps track of a proven lower-bound and the value of the best solution found so
+/// far, and probes the midpoint between the two under an assumption. If the probe is satisfiable
+/// then the upper-bound is improved, otherwise the negated assumption is added as a constraint and
+/// the lower-bound is improved; in both cases, the nogoods learned during the probe are kept.
+///
+/// Every probe is given a time budget (see [`Bisection::with_probe_budget`]); if a probe exceeds
+/// its budget then the next midpoint is chosen closer to the upper-bound. After a number of probes
+/// have exceeded their budget (see [`Bisection::with_max_probe_timeouts`]), the procedure falls
+/// back to linear steps without a budget per probe, similar to [`LinearSatUnsat`].
+///
+/// [`LinearSatUnsat`]: super::linear_sat_unsat::LinearSatUnsat
+#[derive(Debug, Clone, Copy)]
+pub struct Bisection<Var, Callback> {
+    direction: OptimisationDirection,
+    objective: Var,
+    solution_callback: Callback,
+    probe_budget: Option<Duration>,
+    max_probe_timeouts: usize,
+}
+
+impl<Var, Callback> Bisection<Var, Callback> {
+    /// Create a new instance of [`Bisection`].
+    pub fn new(
+        direction: OptimisationDirection,
+        objective: Var,
+        solution_callback: Callback,
+    ) -> Self {
+        Self {
+            direction,
+            objective,
+            solution_callback,
+            probe_budget: Some(Duration::from_secs(10)),
+            max_probe_timeouts: 3,
+        }
+    }
+
+    /// Sets the time budget for a single probe; if `None` is provided, the probes are only
+    /// limited by the termination condition provided to [`Solver::optimise`].
+    pub fn with_probe_budget(mut self, probe_budget: Option<Duration>) -> Self {
+        self.probe_budget = probe_budget;
+        self
+    }
+
+    /// Sets the number of probes which can exceed their budget before falling back to linear
+    /// steps.
+    pub fn with_max_probe_timeouts(mut self, max_probe_timeouts: usize) -> Self {
+        self.max_probe_timeouts = max_probe_timeouts;
+        self
+    }
+}
+
+impl<Var, B, Callback> OptimisationProcedure<B, Callback> for Bisection<Var, Callback>
+where
+    Var: IntegerVariable,
+    B: Brancher,
+    Callback: SolutionCallback<B>,
+{
+    fn optimise(
+        &mut self,
+        brancher: &mut B,
+        termination: &mut impl TerminationCondition,
+        solver: &mut Solver,
+    ) -> OptimisationResult {
+        let is_maximising = matches!(self.direction, OptimisationDirection::Maximise);
+        let objective = match self.direction {
+            OptimisationDirection::Maximise => self.objective.scaled(-1),
+            OptimisationDirection::Minimise => self.objective.scaled(1),
+        };
+        // If we are maximising then when we simply scale the variable by -1, however, this will
+        // lead to the printed objective value in the statistics to be multiplied by -1; this
+        // objective_multiplier ensures that the objective is correctly logged.
+        let objective_multiplier = if is_maximising { -1 } else { 1 };
+
+        let initial_solve = solver.satisfaction_solver.solve(termination, brancher);
+        match initial_solve {
+            CSPSolverExecutionFlag::Feasible => {}
+            CSPSolverExecutionFlag::Infeasible => {
+                // Reset the state whenever we return a result
+                solver.satisfaction_solver.restore_state_at_root(brancher);
+                let _ = solver.satisfaction_solver.conclude_proof_unsat();
+                return OptimisationResult::Unsatisfiable;
+            }
+            CSPSolverExecutionFlag::Timeout => {
+                // Reset the state whenever we return a result
+                solver.satisfaction_solver.restore_state_at_root(brancher);
+                return OptimisationResult::Unknown;
+            }
+        }
+        let mut best_objective_value = Default::default();
+        let mut best_solution = Solution::default();
+
+        self.update_best_solution_and_process(
+            objective_multiplier,
+            &objective,
+            &mut best_objective_value,
+            &mut best_solution,
+            brancher,
+            solver,
+        );
+        solver.satisfaction_solver.restore_state_at_root(brancher);
+
+        // The value of the best solution in terms of the (minimised) objective.
+        let mut upper_bound = best_objective_value as i32 * objective_multiplier;
+        // The proven lower-bound. An infeasible probe adds `[objective > midpoint]` as a clause,
+        // but if a scope is open then that clause is conditional on the scope and the root bound of
+        // the objective is not tightened; hence, the lower-bound is also tracked explicitly.
+        let mut lower_bound = solver.lower_bound(&objective);
+        // The lowest value which will be probed; this can be larger than the proven lower-bound
+        // if probes have exceeded their budget.
+        let mut probe_lower_bound = lower_bound;
+
+        let mut num_probe_timeouts = 0;
+        let mut is_linear = false;
+
+        loop {
+            lower_bound = lower_bound.max(solver.lower_bound(&objective));
+
+            if lower_bound >= upper_bound {
+                let objective_bound_predicate = if is_maximising {
+                    predicate![self.objective >= best_objective_value as i32]
+                } else {
+                    predicate![self.objective <= best_objective_value as i32]
+                };
+                let _ = solver
+                    .satisfaction_solver
+                    .conclude_proof_optimal(objective_bound_predicate);
+
+                return OptimisationResult::Optimal(best_solution);
+            }
+
+            probe_lower_bound = probe_lower_bound.clamp(lower_bound, upper_bound - 1);
+            let midpoint = if is_linear {
+                upper_bound - 1
+            } else {
+                probe_lower_bound + (upper_bound - 1 - probe_lower_bound) / 2
+            };
+            let assumption = predicate!(objective <= midpoint);
+
+            info!("Bisection Search - Attempting to find solution with assumption {assumption}");
+
+            let probe_budget = if is_linear {
+                None
+            } else {
+                self.probe_budget.map(TimeBudget::starting_now)
+            };
+            let solve_result = solver.satisfaction_solver.solve_under_assumptions(
+                &[assumption],
+                &mut Combinator::new(&mut *termination, probe_budget),
+                brancher,
+            );
+
+            match solve_result {
+                CSPSolverExecutionFlag::Feasible => {
+                    self.update_best_solution_and_process(
+                        objective_multiplier,
+                        &objective,
+                        &mut best_objective_value,
+                        &mut best_solution,
+                        brancher,
+                        solver,
+                    );
+                    solver.satisfaction_solver.restore_state_at_root(brancher);
+
+                    upper_bound = best_objective_value as i32 * objective_multiplier;
+                }
+                CSPSolverExecutionFlag::Infeasible => {
+                    solver.satisfaction_solver.restore_state_at_root(brancher);
+                    // There is no solution with an objective value of at most the midpoint, so we
+                    // add the negated assumption as a (hard) constraint.
+                    let _ = solver.add_clause([!assumption]);
+                    lower_bound = midpoint + 1;
+                }
+                CSPSolverExecutionFlag::Timeout => {
+                    solver.satisfaction_solver.restore_state_at_root(brancher);
+
+                    if termination.should_stop() {
+                        return OptimisationResult::Satisfiable(best_solution);
+                    }
+
+                    // Only the probe exceeded its budget; the next probe is closer to the
+                    // upper-bound since it is expected to be easier to find a solution there.
+                    num_probe_timeouts += 1;
+                    probe_lower_bound = midpoint + 1;
+
+                    if num_probe_timeouts >= self.max_probe_timeouts {
+                        info!("Bisection Search - Falling back to linear search after {num_probe_timeouts} probes exceeded their budget");
+                        is_linear = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn on_solution_callback(&self, solver: &Solver, solution: SolutionReference, brancher: &B) {
+        self.solution_callback
+            .on_solution_callback(solver, solution, brancher)
+    }
+}
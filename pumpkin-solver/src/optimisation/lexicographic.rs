@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use super::bisection::Bisection;
+use super::linear_sat_unsat::LinearSatUnsat;
+use super::linear_unsat_sat::LinearUnsatSat;
+use super::solution_callback::SolutionCallback;
+use super::OptimisationProcedure;
+use super::OptimisationStrategy;
+use crate::branching::Brancher;
+use crate::optimisation::OptimisationDirection;
+use crate::predicate;
+use crate::results::OptimisationResult;
+use crate::results::ProblemSolution;
+use crate::results::SatisfactionResult;
+use crate::results::Solution;
+use crate::results::SolutionReference;
+use crate::termination::TerminationCondition;
+use crate::variables::IntegerVariable;
+use crate::Solver;
+
+/// Implements lexicographic optimisation over a list of objectives.
+///
+/// The objectives are optimised one at a time in the provided order (using the provided
+/// [`OptimisationStrategy`]); once an objective has been optimised, its optimal value is fixed
+/// while the next objective is optimised. The bounds on the objectives are added in scopes (see
+/// [`Solver::push_scope`]) which are closed when the procedure terminates, so the solver can be
+/// reused afterwards.
+///
+/// The solution callback is called for every improving solution on any of the objectives. When
+/// [`OptimisationStrategy::Bisection`] is used, the options of the individual [`Bisection`] runs
+/// can be set with [`LexicographicOptimisation::with_probe_budget`] and
+/// [`LexicographicOptimisation::with_max_probe_timeouts`].
+///
+/// Proof logging is not supported: the proof format has a single objective, so the bounds on the
+/// other objectives cannot be justified, and the proof cannot be concluded while the scopes are
+/// open.
+#[derive(Debug, Clone)]
+pub struct LexicographicOptimisation<Var, Callback> {
+    objectives: Vec<(OptimisationDirection, Var)>,
+    strategy: OptimisationStrategy,
+    solution_callback: Callback,
+    probe_budget: Option<Duration>,
+    max_probe_timeouts: usize,
+}
+
+impl<Var, Callback> LexicographicOptimisation<Var, Callback> {
+    /// Create a new instance of [`LexicographicOptimisation`]; the `objectives` are ordered from
+    /// most to least important.
+    pub fn new(
+        objectives: impl IntoIterator<Item = (OptimisationDirection, Var)>,
+        strategy: OptimisationStrategy,
+        solution_callback: Callback,
+    ) -> Self {
+        Self {
+            objectives: objectives.into_iter().collect(),
+            strategy,
+            solution_callback,
+            probe_budget: Some(Duration::from_secs(10)),
+            max_probe_timeouts: 3,
+        }
+    }
+
+    /// Sets the time budget for a single probe when the [`OptimisationStrategy::Bisection`]
+    /// strategy is used (see [`Bisection::with_probe_budget`]).
+    pub fn with_probe_budget(mut self, probe_budget: Option<Duration>) -> Self {
+        self.probe_budget = probe_budget;
+        self
+    }
+
+    /// Sets the number of probes which can exceed their budget before falling back to linear
+    /// steps when the [`OptimisationStrategy::Bisection`] strategy is used (see
+    /// [`Bisection::with_max_probe_timeouts`]).
+    pub fn with_max_probe_timeouts(mut self, max_probe_timeouts: usize) -> Self {
+        self.max_probe_timeouts = max_probe_timeouts;
+        self
+    }
+}
+
+impl<Var, Callback, B> OptimisationProcedure<B, Callback>
+    for LexicographicOptimisation<Var, Callback>
+where
+    Var: IntegerVariable + 'static,
+    B: Brancher,
+    Callback: SolutionCallback<B>,
+{
+    fn optimise(
+        &mut self,
+        brancher: &mut B,
+        termination: &mut impl TerminationCondition,
+        solver: &mut Solver,
+    ) -> OptimisationResult {
+        let solution_callback = &self.solution_callback;
+        let callback = |solver: &Solver, solution: SolutionReference<'_>, brancher: &B| {
+            solution_callback.on_solution_callback(solver, solution, brancher)
+        };
+
+        let _ = solver.push_scope();
+
+        let mut best_solution: Option<Solution> = None;
+
+        for (direction, objective) in self.objectives.iter() {
+            // The bounds which are added while optimising the current objective are retracted
+            // afterwards, only the optimal value of the objective is kept.
+            let _ = solver.push_scope();
+            let result = match self.strategy {
+                OptimisationStrategy::LinearSatUnsat => solver.optimise(
+                    brancher,
+                    termination,
+                    LinearSatUnsat::new(*direction, objective.clone(), callback),
+                ),
+                OptimisationStrategy::LinearUnsatSat => solver.optimise(
+                    brancher,
+                    termination,
+                    LinearUnsatSat::new(*direction, objective.clone(), callback),
+                ),
+                OptimisationStrategy::Bisection => solver.optimise(
+                    brancher,
+                    termination,
+                    Bisection::new(*direction, objective.clone(), callback)
+                        .with_probe_budget(self.probe_budget)
+                        .with_max_probe_timeouts(self.max_probe_timeouts),
+                ),
+            };
+            let _ = solver.pop_scope();
+
+            match result {
+                OptimisationResult::Optimal(solution) => {
+                    // Fix the optimal value of the current objective before continuing with the
+                    // next one.
+                    let optimal_value = solution.get_integer_value(objective.clone());
+                    let bound = match direction {
+                        OptimisationDirection::Maximise => predicate!(objective >= optimal_value),
+                        OptimisationDirection::Minimise => predicate!(objective <= optimal_value),
+                    };
+                    best_solution = Some(solution);
+
+                    if solver.add_clause([bound]).is_err() {
+                        break;
+                    }
+                }
+                OptimisationResult::Satisfiable(solution) => {
+                    let _ = solver.pop_scope();
+                    return OptimisationResult::Satisfiable(solution);
+                }
+                OptimisationResult::Unsatisfiable => {
+                    let _ = solver.pop_scope();
+                    return OptimisationResult::Unsatisfiable;
+                }
+                OptimisationResult::Unknown => {
+                    let _ = solver.pop_scope();
+                    return match best_solution {
+                        Some(solution) => OptimisationResult::Satisfiable(solution),
+                        None => OptimisationResult::Unknown,
+                    };
+                }
+            }
+        }
+
+        let _ = solver.pop_scope();
+
+        match best_solution {
+            Some(solution) => OptimisationResult::Optimal(solution),
+            // There are no objectives, so any solution is optimal.
+            None => match solver.satisfy(brancher, termination) {
+                SatisfactionResult::Satisfiable(solution) => OptimisationResult::Optimal(solution),
+                SatisfactionResult::Unsatisfiable => OptimisationResult::Unsatisfiable,
+                SatisfactionResult::Unknown => OptimisationResult::Unknown,
+            },
+        }
+    }
+
+    fn on_solution_callback(&self, solver: &Solver, solution: SolutionReference, brancher: &B) {
+        self.solution_callback
+            .on_solution_callback(solver, solution, brancher)
+    }
+}
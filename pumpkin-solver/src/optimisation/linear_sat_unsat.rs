@@ -40,15 +40,22 @@ impl<Var: IntegerVariable, Callback> LinearSatUnsat<Var, Callback> {
     /// Given the current objective value `best_objective_value`, it adds a constraint specifying
     /// that the objective value should be at most `best_objective_value - 1`. Note that it is
     /// assumed that we are always minimising the variable.
+    ///
+    /// The constraint is logged to the proof as a nogood which refers to the last solution.
     fn strengthen(
         &mut self,
         objective_variable: &impl IntegerVariable,
         best_objective_value: i64,
         solver: &mut Solver,
     ) -> Result<(), ConstraintOperationError> {
-        solver.satisfaction_solver.add_clause([predicate!(
-            objective_variable <= (best_objective_value - 1) as i32
-        )])
+        let bound = predicate!(objective_variable <= (best_objective_value - 1) as i32);
+
+        // The bound is justified in the proof by the last solution, after which it is propagated
+        // like any other clause.
+        solver
+            .satisfaction_solver
+            .log_objective_bound_to_proof(bound);
+        solver.add_clause([bound])
     }
 
     fn debug_bound_change(
@@ -129,9 +136,9 @@ where
             solver.satisfaction_solver.restore_state_at_root(brancher);
 
             let objective_bound_predicate = if is_maximising {
-                predicate![objective >= best_objective_value as i32 * objective_multiplier]
+                predicate![self.objective >= best_objective_value as i32]
             } else {
-                predicate![objective <= best_objective_value as i32 * objective_multiplier]
+                predicate![self.objective <= best_objective_value as i32]
             };
 
             if self
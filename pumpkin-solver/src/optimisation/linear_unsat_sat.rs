@@ -87,8 +87,13 @@ where
         );
         solver.satisfaction_solver.restore_state_at_root(brancher);
 
+        // The lower-bound is tracked explicitly since, if a scope is open, the lower-bounding
+        // clauses do not tighten the root bound of the objective.
+        let mut lower_bound = solver.lower_bound(&objective);
+
         loop {
-            let assumption = predicate!(objective <= solver.lower_bound(&objective));
+            lower_bound = lower_bound.max(solver.lower_bound(&objective));
+            let assumption = predicate!(objective <= lower_bound);
 
             info!(
                 "Lower-Bounding Search - Attempting to find solution with assumption {assumption}"
@@ -117,9 +122,9 @@ where
                     // We create a predicate specifying the best-found solution for the proof
                     // logging
                     let objective_bound_predicate = if is_maximising {
-                        predicate![objective >= best_objective_value as i32 * objective_multiplier]
+                        predicate![self.objective >= best_objective_value as i32]
                     } else {
-                        predicate![objective <= best_objective_value as i32 * objective_multiplier]
+                        predicate![self.objective <= best_objective_value as i32]
                     };
                     let _ = solver
                         .satisfaction_solver
@@ -132,6 +137,7 @@ where
                     // We add the (hard) constraint that the negated assumption should hold (i.e.,
                     // the solution should be at least as large as the found solution)
                     let _ = solver.add_clause([!assumption]);
+                    lower_bound += 1;
                 }
                 CSPSolverExecutionFlag::Timeout => {
                     // Reset the state whenever we return a result
@@ -12,8 +12,11 @@ use crate::termination::TerminationCondition;
 use crate::variables::IntegerVariable;
 use crate::Solver;
 
+pub mod bisection;
+pub mod lexicographic;
 pub mod linear_sat_unsat;
 pub mod linear_unsat_sat;
+pub mod pareto_front;
 pub mod solution_callback;
 
 pub trait OptimisationProcedure<B: Brancher, Callback: SolutionCallback<B>> {
@@ -30,6 +33,7 @@ pub trait OptimisationProcedure<B: Brancher, Callback: SolutionCallback<B>> {
     /// - Assigning `best_objective_value` the value assigned to `objective_variable` (multiplied by
     ///   `objective_multiplier`).
     /// - Storing the new best solution in `best_solution`.
+    /// - Logging the solution to the proof, such that it can justify the next objective bound.
     /// - Calling [`Brancher::on_solution`] on the provided `brancher`.
     /// - Logging the statistics using [`Solver::log_statistics_with_objective`].
     /// - Calling the solution callback.
@@ -40,7 +44,7 @@ pub trait OptimisationProcedure<B: Brancher, Callback: SolutionCallback<B>> {
         best_objective_value: &mut i64,
         best_solution: &mut Solution,
         brancher: &mut B,
-        solver: &Solver,
+        solver: &mut Solver,
     ) {
         *best_objective_value = (objective_multiplier
             * solver
@@ -48,6 +52,7 @@ pub trait OptimisationProcedure<B: Brancher, Callback: SolutionCallback<B>> {
                 .get_assigned_integer_value(objective_variable)
                 .expect("expected variable to be assigned")) as i64;
         *best_solution = solver.satisfaction_solver.get_solution_reference().into();
+        solver.satisfaction_solver.log_solution_to_proof();
 
         self.internal_process_solution(best_solution, brancher, solver)
     }
@@ -69,6 +74,10 @@ pub enum OptimisationStrategy {
     /// Linear UNSAT-SAT - Starts with an unsatisfiable solution and tightens the bound on the
     /// objective variable until a SAT result is reached. Can be seen as lower-bounding search.
     LinearUnsatSat,
+    /// Bisection - Maintains a lower-bound and an upper-bound on the objective variable and
+    /// repeatedly tests the midpoint between the two. Falls back to linear steps after several
+    /// probes have exceeded their time budget.
+    Bisection,
 }
 
 impl Display for OptimisationStrategy {
@@ -76,6 +85,7 @@ impl Display for OptimisationStrategy {
         match self {
             OptimisationStrategy::LinearSatUnsat => write!(f, "linear-sat-unsat"),
             OptimisationStrategy::LinearUnsatSat => write!(f, "linear-unsat-sat"),
+            OptimisationStrategy::Bisection => write!(f, "bisection"),
         }
     }
 }
@@ -0,0 +1,198 @@
+use log::info;
+
+use super::solution_callback::SolutionCallback;
+use super::OptimisationProcedure;
+use crate::basic_types::CSPSolverExecutionFlag;
+use crate::branching::Brancher;
+use crate::optimisation::OptimisationDirection;
+use crate::predicate;
+use crate::predicates::Predicate;
+use crate::results::OptimisationResult;
+use crate::results::Solution;
+use crate::results::SolutionReference;
+use crate::termination::TerminationCondition;
+use crate::variables::IntegerVariable;
+use crate::Solver;
+
+/// Enumerates the Pareto front of a multi-objective optimisation problem.
+///
+/// A solution is on the Pareto front if there is no other solution which is at least as good on
+/// every objective and strictly better on at least one. The procedure repeatedly finds a solution
+/// which is not dominated by the Pareto-optimal solutions found so far, and then improves it until
+/// no solution dominating it exists.
+///
+/// The solution callback is called once for every solution on the Pareto front; the returned
+/// [`OptimisationResult`] contains the last Pareto-optimal solution which was found. All
+/// constraints which are added by this procedure are added in a scope (see
+/// [`Solver::push_scope`]) which is closed when the procedure terminates.
+///
+/// Proof logging is not supported, since the nogoods which exclude the dominated solutions cannot
+/// be justified in a proof with a single objective.
+#[derive(Debug, Clone)]
+pub struct ParetoFront<Var, Callback> {
+    objectives: Vec<(OptimisationDirection, Var)>,
+    solution_callback: Callback,
+}
+
+impl<Var, Callback> ParetoFront<Var, Callback> {
+    /// Create a new instance of [`ParetoFront`].
+    pub fn new(
+        objectives: impl IntoIterator<Item = (OptimisationDirection, Var)>,
+        solution_callback: Callback,
+    ) -> Self {
+        Self {
+            objectives: objectives.into_iter().collect(),
+            solution_callback,
+        }
+    }
+}
+
+impl<Var: IntegerVariable, Callback> ParetoFront<Var, Callback> {
+    /// Returns the objectives scaled such that each of them is minimised.
+    fn minimised_objectives(&self) -> Vec<Var::AffineView> {
+        self.objectives
+            .iter()
+            .map(|(direction, objective)| match direction {
+                OptimisationDirection::Maximise => objective.scaled(-1),
+                OptimisationDirection::Minimise => objective.scaled(1),
+            })
+            .collect()
+    }
+
+    /// Returns the values of the (minimised) objectives in the current solution of the solver.
+    fn objective_values(objectives: &[Var::AffineView], solver: &Solver) -> Vec<i32> {
+        objectives
+            .iter()
+            .map(|objective| {
+                solver
+                    .satisfaction_solver
+                    .get_assigned_integer_value(objective)
+                    .expect("expected objective to be assigned")
+            })
+            .collect()
+    }
+
+    /// Returns the clause which excludes all solutions which are weakly dominated by a solution
+    /// with the provided objective values.
+    fn dominance_clause(objectives: &[Var::AffineView], values: &[i32]) -> Vec<Predicate> {
+        objectives
+            .iter()
+            .zip(values)
+            .map(|(objective, &value)| predicate!(objective <= value - 1))
+            .collect()
+    }
+}
+
+impl<Var, Callback, B> OptimisationProcedure<B, Callback> for ParetoFront<Var, Callback>
+where
+    Var: IntegerVariable,
+    B: Brancher,
+    Callback: SolutionCallback<B>,
+{
+    fn optimise(
+        &mut self,
+        brancher: &mut B,
+        termination: &mut impl TerminationCondition,
+        solver: &mut Solver,
+    ) -> OptimisationResult {
+        let objectives = self.minimised_objectives();
+
+        let _ = solver.push_scope();
+
+        let mut last_pareto_optimal: Option<Solution> = None;
+        let mut num_pareto_optimal = 0;
+
+        let result = 'front: loop {
+            // Find a solution which is not dominated by the solutions found so far.
+            match solver.satisfaction_solver.solve(termination, brancher) {
+                CSPSolverExecutionFlag::Feasible => {}
+                CSPSolverExecutionFlag::Infeasible => {
+                    solver.satisfaction_solver.restore_state_at_root(brancher);
+                    break match last_pareto_optimal.take() {
+                        Some(solution) => OptimisationResult::Optimal(solution),
+                        None => OptimisationResult::Unsatisfiable,
+                    };
+                }
+                CSPSolverExecutionFlag::Timeout => {
+                    solver.satisfaction_solver.restore_state_at_root(brancher);
+                    break match last_pareto_optimal.take() {
+                        Some(solution) => OptimisationResult::Satisfiable(solution),
+                        None => OptimisationResult::Unknown,
+                    };
+                }
+            }
+
+            let mut values = Self::objective_values(&objectives, solver);
+            let mut solution: Solution = solver.get_solution_reference().into();
+            brancher.on_solution(solution.as_reference());
+
+            // Improve the solution until it is not dominated by any other solution.
+            loop {
+                solver.satisfaction_solver.restore_state_at_root(brancher);
+
+                let _ = solver.push_scope();
+                let dominating_constraints = objectives
+                    .iter()
+                    .zip(&values)
+                    .try_for_each(|(objective, &value)| {
+                        solver.add_clause([predicate!(objective <= value)])
+                    })
+                    .and_then(|_| solver.add_clause(Self::dominance_clause(&objectives, &values)));
+
+                let improvement = if dominating_constraints.is_ok() {
+                    solver.satisfaction_solver.solve(termination, brancher)
+                } else {
+                    CSPSolverExecutionFlag::Infeasible
+                };
+
+                match improvement {
+                    CSPSolverExecutionFlag::Feasible => {
+                        values = Self::objective_values(&objectives, solver);
+                        solution = solver.get_solution_reference().into();
+                        brancher.on_solution(solution.as_reference());
+
+                        solver.satisfaction_solver.restore_state_at_root(brancher);
+                        let _ = solver.pop_scope();
+                    }
+                    CSPSolverExecutionFlag::Infeasible => {
+                        solver.satisfaction_solver.restore_state_at_root(brancher);
+                        let _ = solver.pop_scope();
+                        break;
+                    }
+                    CSPSolverExecutionFlag::Timeout => {
+                        solver.satisfaction_solver.restore_state_at_root(brancher);
+                        let _ = solver.pop_scope();
+                        break 'front OptimisationResult::Satisfiable(
+                            last_pareto_optimal.take().unwrap_or(solution),
+                        );
+                    }
+                }
+            }
+
+            num_pareto_optimal += 1;
+            info!("Found Pareto-optimal solution {num_pareto_optimal} with objectives {values:?}");
+            self.on_solution_callback(solver, solution.as_reference(), brancher);
+
+            // Exclude the part of the search space which is dominated by the new solution.
+            let blocking_result = solver.add_clause(Self::dominance_clause(&objectives, &values));
+            last_pareto_optimal = Some(solution);
+
+            if blocking_result.is_err() {
+                break OptimisationResult::Optimal(
+                    last_pareto_optimal
+                        .take()
+                        .expect("a Pareto-optimal solution was just found"),
+                );
+            }
+        };
+
+        let _ = solver.pop_scope();
+
+        result
+    }
+
+    fn on_solution_callback(&self, solver: &Solver, solution: SolutionReference, brancher: &B) {
+        self.solution_callback
+            .on_solution_callback(solver, solution, brancher)
+    }
+}
@@ -97,7 +97,9 @@ pub(crate) fn explain_root_assignment(
         &mut reason,
     );
 
-    assert!(!reason.is_empty());
+    // An empty reason means that the predicate was propagated by a unit nogood which implies it
+    // (e.g. `[x == v]` implies `[x >= v]`); that nogood step has then already been added as a
+    // propagation while retrieving the reason.
 
     for p in reason {
         explain_root_assignment(context, p);
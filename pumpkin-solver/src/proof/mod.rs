@@ -3,10 +3,17 @@
 //!
 //! Proof logging for CP is supported in the DRCP format. This format explicitly supports usage
 //! where the solver logs a proof scaffold which later processed into a full proof after search
-//! has completed.
+//! has completed. Every inference is labelled with the filtering algorithm which made it, and with
+//! the tag of the constraint which the propagator was posted for (if any). For FlatZinc models, the
+//! tag is the 1-based index of the constraint item in the model.
+//!
+//! For (weighted) DIMACS instances, a proof can also be logged in the VeriPB format. This
+//! pseudo-Boolean proof system can justify the auxiliary variables introduced by encodings as
+//! reifications, as well as the improving solutions found during optimisation.
 mod dimacs;
 mod finalizer;
 mod proof_literals;
+mod veripb;
 
 use std::fs::File;
 use std::num::NonZero;
@@ -14,12 +21,16 @@ use std::num::NonZeroU64;
 use std::path::Path;
 use std::path::PathBuf;
 
+use drcp_format::compression;
+use drcp_format::compression::CompressedWriter;
 use drcp_format::writer::ProofWriter;
 pub use drcp_format::Format;
+use drcp_format::VariableNameMap;
 pub(crate) use finalizer::*;
 
 use self::dimacs::DimacsProof;
 use self::proof_literals::ProofLiterals;
+use self::veripb::VeriPbProof;
 use crate::engine::variable_names::VariableNames;
 use crate::predicates::Predicate;
 use crate::variables::Literal;
@@ -30,6 +41,7 @@ use crate::Solver;
 /// allow the following types of proofs:
 /// - A CP proof log - This can be created using [`ProofLog::cp`].
 /// - A DIMACS proof log - This can be created using [`ProofLog::dimacs`].
+/// - A VeriPB proof log - This can be created using [`ProofLog::veripb`].
 ///
 /// When a proof log should not be generated, use the implementation of [`Default`].
 #[derive(Debug, Default)]
@@ -38,18 +50,24 @@ pub struct ProofLog {
 }
 
 /// A dummy proof step ID. Used when there is proof logging is not enabled.
-const DUMMY_STEP_ID: NonZeroU64 = NonZeroU64::new(1).unwrap();
+pub(crate) const DUMMY_STEP_ID: NonZeroU64 = NonZeroU64::new(1).unwrap();
 
 impl ProofLog {
     /// Create a CP proof logger.
+    ///
+    /// The literal definitions are written next to the proof, with the `lits` extension, and so
+    /// are the names of the variables in those definitions, with the `names` extension (see
+    /// [`drcp_format::VariableNameMap`]). If the extension of `file_path` is `gz` or `zst`, all
+    /// these files are compressed (see [`drcp_format::compression`]).
     pub fn cp(
         file_path: &Path,
         format: Format,
         log_inferences: bool,
         log_hints: bool,
     ) -> std::io::Result<ProofLog> {
-        let definitions_path = file_path.with_extension("lits");
-        let file = File::create(file_path)?;
+        let definitions_path = compression::literals_path(file_path);
+        let names_path = compression::names_path(file_path);
+        let file = compression::create(file_path)?;
 
         let writer = ProofWriter::new(format, file, ProofLiterals::default());
 
@@ -58,7 +76,9 @@ impl ProofLog {
                 writer,
                 log_inferences,
                 definitions_path,
+                names_path,
                 propagation_order_hint: if log_hints { Some(vec![]) } else { None },
+                last_solution: None,
             }),
         })
     }
@@ -71,10 +91,24 @@ impl ProofLog {
         })
     }
 
-    /// Log an inference to the proof.
+    /// Create a VeriPB proof logger.
+    ///
+    /// The proof is checked against the pseudo-Boolean translation of a (weighted) DIMACS
+    /// instance, in which the variables are named `x<id>` after the ids of their domains.
+    pub fn veripb(file_path: &Path) -> std::io::Result<ProofLog> {
+        let file = File::create(file_path)?;
+        Ok(ProofLog {
+            internal_proof: Some(ProofImpl::VeriPb(VeriPbProof::new(file)?)),
+        })
+    }
+
+    /// Log an inference to the proof. The `inference_label` identifies the filtering algorithm
+    /// which made the inference, see
+    /// [`Propagator::inference_label`](crate::engine::propagation::Propagator::inference_label).
     pub(crate) fn log_inference(
         &mut self,
         constraint_tag: Option<NonZero<u32>>,
+        inference_label: &str,
         premises: impl IntoIterator<Item = Predicate>,
         propagated: Option<Predicate>,
     ) -> std::io::Result<NonZeroU64> {
@@ -88,8 +122,8 @@ impl ProofLog {
             return Ok(DUMMY_STEP_ID);
         };
 
-        // TODO: Log the inference label.
-        let id = writer.log_inference(constraint_tag, None, premises, propagated)?;
+        let id =
+            writer.log_inference(constraint_tag, Some(inference_label), premises, propagated)?;
 
         if let Some(hints) = propagation_order_hint {
             hints.push(id);
@@ -144,24 +178,227 @@ impl ProofLog {
 
             Some(ProofImpl::DimacsProof(writer)) => writer.learned_clause(literals, variable_names),
 
+            Some(ProofImpl::VeriPb(writer)) => writer.implied_clause(literals, &[]),
+
+            None => Ok(DUMMY_STEP_ID),
+        }
+    }
+
+    /// Log a clause which is implied by the sum of the `antecedents` and unit propagation. This
+    /// is only supported by VeriPB proofs, the other proofs ignore the clause.
+    pub(crate) fn log_implied_clause(
+        &mut self,
+        literals: impl IntoIterator<Item = Predicate>,
+        antecedents: &[NonZeroU64],
+    ) -> std::io::Result<NonZeroU64> {
+        let Some(ProofImpl::VeriPb(writer)) = self.internal_proof.as_mut() else {
+            return Ok(DUMMY_STEP_ID);
+        };
+
+        writer.implied_clause(literals, antecedents)
+    }
+
+    /// Log the constraint `\sum w_i * l_i >= degree`, which is implied by unit propagation. This
+    /// is only supported by VeriPB proofs, the other proofs ignore the constraint.
+    pub(crate) fn log_implied_constraint(
+        &mut self,
+        terms: &[(u64, Literal)],
+        degree: u64,
+    ) -> std::io::Result<NonZeroU64> {
+        let Some(ProofImpl::VeriPb(writer)) = self.internal_proof.as_mut() else {
+            return Ok(DUMMY_STEP_ID);
+        };
+
+        let terms = terms
+            .iter()
+            .map(|&(weight, literal)| (weight, literal.get_true_predicate()))
+            .collect::<Vec<_>>();
+        writer.implied_constraint(&terms, degree)
+    }
+
+    /// Log the sum of the constraints derived by the given steps, each multiplied by its factor,
+    /// divided by the `divisor`. This is only supported by VeriPB proofs, the other proofs ignore
+    /// the linear combination.
+    pub(crate) fn log_linear_combination(
+        &mut self,
+        terms: &[(u64, NonZeroU64)],
+        divisor: u64,
+    ) -> std::io::Result<NonZeroU64> {
+        let Some(ProofImpl::VeriPb(writer)) = self.internal_proof.as_mut() else {
+            return Ok(DUMMY_STEP_ID);
+        };
+
+        writer.linear_combination(terms, divisor)
+    }
+
+    /// Log the definition of the fresh `literal` as false. This is only supported by VeriPB
+    /// proofs, the other proofs ignore the definition.
+    pub(crate) fn log_false_literal(&mut self, literal: Literal) -> std::io::Result<NonZeroU64> {
+        let Some(ProofImpl::VeriPb(writer)) = self.internal_proof.as_mut() else {
+            return Ok(DUMMY_STEP_ID);
+        };
+
+        writer.false_literal(literal.get_true_predicate())
+    }
+
+    /// Log the definition of the fresh `literal` as the reification of `\sum w_i * l_i >=
+    /// degree`.
+    ///
+    /// This is only supported by VeriPB proofs, the other proofs ignore the definition.
+    pub(crate) fn log_reification(
+        &mut self,
+        literal: Literal,
+        terms: &[(u64, Literal)],
+        degree: u64,
+    ) -> std::io::Result<Reification> {
+        let Some(ProofImpl::VeriPb(writer)) = self.internal_proof.as_mut() else {
+            return Ok(Reification::DUMMY);
+        };
+
+        let terms = terms
+            .iter()
+            .map(|&(weight, literal)| (weight, literal.get_true_predicate()))
+            .collect::<Vec<_>>();
+        let (literal_implies_constraint, constraint_implies_literal) =
+            writer.reification(literal.get_true_predicate(), &terms, degree)?;
+
+        Ok(Reification {
+            literal_implies_constraint,
+            constraint_implies_literal,
+        })
+    }
+
+    /// Log a solution with the given objective value, after which only better solutions are
+    /// sought. Returns the id of the constraint which states that the objective value is smaller
+    /// than `objective_value`.
+    ///
+    /// This is only supported by VeriPB proofs, the other proofs ignore the solution.
+    pub(crate) fn log_improving_solution(
+        &mut self,
+        assignment: impl IntoIterator<Item = Predicate>,
+        objective_value: u64,
+    ) -> std::io::Result<NonZeroU64> {
+        let Some(ProofImpl::VeriPb(writer)) = self.internal_proof.as_mut() else {
+            return Ok(DUMMY_STEP_ID);
+        };
+
+        writer.solution(assignment, objective_value)
+    }
+
+    /// Log a solution to the proof, given as the assignment of the variables. The solution
+    /// justifies the next objective bound which is logged with [`ProofLog::log_objective_bound`].
+    pub(crate) fn log_solution(
+        &mut self,
+        assignment: impl IntoIterator<Item = Predicate>,
+    ) -> std::io::Result<NonZeroU64> {
+        let Some(ProofImpl::CpProof {
+            writer,
+            last_solution,
+            ..
+        }) = self.internal_proof.as_mut()
+        else {
+            return Ok(DUMMY_STEP_ID);
+        };
+
+        let id = writer.log_solution(assignment)?;
+        *last_solution = Some(id);
+
+        Ok(id)
+    }
+
+    /// Log the nogood which tightens the bound on the objective to `bound`, e.g. `[objective <= v -
+    /// 1]` after a solution with objective value `v` is found when minimising. The nogood refers
+    /// to the last solution which was logged with [`ProofLog::log_solution`].
+    pub(crate) fn log_objective_bound(&mut self, bound: Predicate) -> std::io::Result<NonZeroU64> {
+        let Some(ProofImpl::CpProof {
+            writer,
+            last_solution,
+            ..
+        }) = self.internal_proof.as_mut()
+        else {
+            return Ok(DUMMY_STEP_ID);
+        };
+
+        writer.log_nogood_clause([bound], last_solution.map(|solution| [solution]))
+    }
+
+    /// Log the definition of the fresh `literal` as the conjunction of the predicates in
+    /// `conjunction` to the proof, i.e. `literal <-> /\ conjunction`.
+    ///
+    /// As the literal is introduced during search, it is given a name in `variable_names`. In a
+    /// DIMACS proof, the definition is logged as the clauses which encode it, and it is assumed
+    /// that the DIMACS variables correspond to the domain ids of the literals. In a VeriPB proof,
+    /// the literal is defined as the reification of the conjunction.
+    pub(crate) fn log_definition(
+        &mut self,
+        literal: Literal,
+        conjunction: &[Predicate],
+        variable_names: &mut VariableNames,
+    ) -> std::io::Result<NonZeroU64> {
+        let domain_id = literal.get_true_predicate().get_domain();
+
+        match &mut self.internal_proof {
+            Some(ProofImpl::CpProof { writer, .. }) => {
+                variable_names.add_integer(domain_id, format!("__extension_{}", domain_id.id));
+                writer.log_definition(literal.get_true_predicate(), conjunction.iter().copied())
+            }
+
+            Some(ProofImpl::DimacsProof(writer)) => {
+                variable_names.add_integer(domain_id, domain_id.id.to_string());
+
+                // The clause `literal \/ ~conjunction` is added first, such that the other clauses
+                // are resolution asymmetric tautologies on `~literal`.
+                let id = writer.learned_clause(
+                    std::iter::once(literal.get_true_predicate())
+                        .chain(conjunction.iter().map(|&predicate| !predicate)),
+                    variable_names,
+                )?;
+                for &predicate in conjunction {
+                    let _ = writer.learned_clause(
+                        [literal.get_false_predicate(), predicate],
+                        variable_names,
+                    )?;
+                }
+
+                Ok(id)
+            }
+
+            Some(ProofImpl::VeriPb(writer)) => {
+                let terms = conjunction
+                    .iter()
+                    .map(|&predicate| (1, predicate))
+                    .collect::<Vec<_>>();
+                let (id, _) = writer.reification(
+                    literal.get_true_predicate(),
+                    &terms,
+                    conjunction.len() as u64,
+                )?;
+
+                Ok(id)
+            }
+
             None => Ok(DUMMY_STEP_ID),
         }
     }
 
+    /// Conclude the proof with the unsatisfiable claim. In a VeriPB proof in which solutions are
+    /// logged, this means that the last logged solution is optimal.
     pub(crate) fn unsat(self, variable_names: &VariableNames) -> std::io::Result<()> {
         match self.internal_proof {
             Some(ProofImpl::CpProof {
                 writer,
                 definitions_path,
+                names_path,
                 ..
             }) => {
                 let literals = writer.unsat()?;
-                let file = File::create(definitions_path)?;
-                literals.write(file, variable_names)
+                write_definitions(literals, &definitions_path, variable_names)?;
+                write_names(&names_path, variable_names)
             }
             Some(ProofImpl::DimacsProof(mut writer)) => writer
                 .learned_clause(std::iter::empty(), variable_names)
                 .map(|_| ()),
+            Some(ProofImpl::VeriPb(writer)) => writer.unsat(),
             None => Ok(()),
         }
     }
@@ -175,21 +412,30 @@ impl ProofLog {
             Some(ProofImpl::CpProof {
                 writer,
                 definitions_path,
+                names_path,
                 ..
             }) => {
                 let literals = writer.optimal(objective_bound)?;
-                let file = File::create(definitions_path)?;
-                literals.write(file, variable_names)
+                write_definitions(literals, &definitions_path, variable_names)?;
+                write_names(&names_path, variable_names)
             }
 
             Some(ProofImpl::DimacsProof(_)) => {
                 panic!("Cannot conclude optimality in DIMACS proof")
             }
 
+            Some(ProofImpl::VeriPb(_)) => {
+                panic!("Cannot conclude optimality with an objective bound in VeriPB proof")
+            }
+
             None => Ok(()),
         }
     }
 
+    pub(crate) fn is_logging_proof(&self) -> bool {
+        self.internal_proof.is_some()
+    }
+
     pub(crate) fn is_logging_inferences(&self) -> bool {
         matches!(
             self.internal_proof,
@@ -209,15 +455,70 @@ impl ProofLog {
     }
 }
 
+/// The ids of the proof steps which define a literal as the reification of a pseudo-Boolean
+/// constraint, see [`Solver::log_reification_to_proof`].
+#[derive(Clone, Copy, Debug)]
+pub struct Reification {
+    /// The step which derives `literal -> constraint`.
+    pub literal_implies_constraint: NonZeroU64,
+    /// The step which derives `constraint -> literal`.
+    pub constraint_implies_literal: NonZeroU64,
+}
+
+impl Reification {
+    /// The reification which is returned when the proof does not log reifications.
+    pub(crate) const DUMMY: Reification = Reification {
+        literal_implies_constraint: DUMMY_STEP_ID,
+        constraint_implies_literal: DUMMY_STEP_ID,
+    };
+}
+
+/// Write the literal definitions to the (possibly compressed) file at `path`.
+fn write_definitions(
+    literals: ProofLiterals,
+    path: &Path,
+    variable_names: &VariableNames,
+) -> std::io::Result<()> {
+    let mut file = compression::create(path)?;
+    literals.write(&mut file, variable_names)?;
+    let _ = file.finish()?;
+    Ok(())
+}
+
+/// Write the names of the named variables, where every variable is identified by the name which
+/// is used in the literal definitions.
+fn write_names(path: &Path, variable_names: &VariableNames) -> std::io::Result<()> {
+    let mut names = VariableNameMap::default();
+    for domain_id in variable_names.named_integers() {
+        let identifier = variable_names
+            .get_int_name(domain_id)
+            .expect("only named integers are considered");
+
+        names.add(identifier, identifier);
+        for alias in variable_names.get_aliases(domain_id) {
+            names.add(identifier, alias.as_str());
+        }
+    }
+
+    let mut file = compression::create(path)?;
+    names.write(&mut file)?;
+    let _ = file.finish()?;
+    Ok(())
+}
+
 #[derive(Debug)]
 enum ProofImpl {
     CpProof {
-        writer: ProofWriter<File, ProofLiterals>,
+        writer: ProofWriter<CompressedWriter<File>, ProofLiterals>,
         log_inferences: bool,
         definitions_path: PathBuf,
+        names_path: PathBuf,
         // If propagation hints are enabled, this is a buffer used to record propagations in the
         // order they can be applied to derive the next nogood.
         propagation_order_hint: Option<Vec<NonZeroU64>>,
+        /// The step id of the last solution, which justifies the next objective bound.
+        last_solution: Option<NonZeroU64>,
     },
     DimacsProof(DimacsProof<File>),
+    VeriPb(VeriPbProof<File>),
 }
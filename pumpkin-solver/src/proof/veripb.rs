@@ -0,0 +1,366 @@
+use std::io::BufWriter;
+use std::io::Write;
+use std::num::NonZeroU64;
+
+use crate::predicates::Predicate;
+use crate::pumpkin_assert_simple;
+
+/// A proof in the VeriPB format (version 2.0), which is checked against the pseudo-Boolean
+/// translation of a (weighted) DIMACS instance.
+///
+/// The variables in the proof are named `x<id>`, where `id` is the id of the domain of the
+/// variable. For DIMACS instances, these coincide with the DIMACS codes of the variables.
+///
+/// The proof does not know how many constraints are in the instance. Therefore, derived
+/// constraints are referenced relative to the last derived constraint, e.g. `-1` refers to the
+/// last derived constraint. The ids handed out by the proof are the indices of the derived
+/// constraints.
+#[derive(Debug)]
+pub(crate) struct VeriPbProof<W: Write> {
+    writer: BufWriter<W>,
+    num_derived_constraints: u64,
+    /// The objective value of the last logged solution, which is the claimed optimal value when
+    /// the proof is concluded.
+    best_objective_value: Option<u64>,
+}
+
+impl<W: Write> VeriPbProof<W> {
+    pub(crate) fn new(writer: W) -> std::io::Result<VeriPbProof<W>> {
+        let mut writer = BufWriter::new(writer);
+        writeln!(writer, "pseudo-Boolean proof version 2.0")?;
+        writeln!(writer, "f ;")?;
+
+        Ok(VeriPbProof {
+            writer,
+            num_derived_constraints: 0,
+            best_objective_value: None,
+        })
+    }
+
+    /// Log a clause which is implied by unit propagation. If more than one antecedent is given,
+    /// the antecedents are first added up, and unit propagation is performed on the resulting
+    /// constraint as well.
+    pub(crate) fn implied_clause(
+        &mut self,
+        predicates: impl IntoIterator<Item = Predicate>,
+        antecedents: &[NonZeroU64],
+    ) -> std::io::Result<NonZeroU64> {
+        if antecedents.len() > 1 {
+            write!(self.writer, "pol")?;
+            for (index, &antecedent) in antecedents.iter().enumerate() {
+                write!(self.writer, " {}", self.relative_id(antecedent))?;
+                if index > 0 {
+                    write!(self.writer, " +")?;
+                }
+            }
+            writeln!(self.writer, " ;")?;
+            let _ = self.next_constraint_id();
+        }
+
+        write!(self.writer, "rup")?;
+        for predicate in predicates {
+            write!(self.writer, " 1 {}", veripb_literal(predicate))?;
+        }
+        writeln!(self.writer, " >= 1 ;")?;
+
+        Ok(self.next_constraint_id())
+    }
+
+    /// Log the constraint `\sum w_i * l_i >= degree`, which is implied by unit propagation.
+    pub(crate) fn implied_constraint(
+        &mut self,
+        terms: &[(u64, Predicate)],
+        degree: u64,
+    ) -> std::io::Result<NonZeroU64> {
+        write!(self.writer, "rup")?;
+        for &(weight, predicate) in terms {
+            write!(self.writer, " {weight} {}", veripb_literal(predicate))?;
+        }
+        writeln!(self.writer, " >= {degree} ;")?;
+
+        Ok(self.next_constraint_id())
+    }
+
+    /// Log the sum of the given constraints, each multiplied by its factor, divided by the
+    /// `divisor` (where the coefficients and the degree are rounded up).
+    pub(crate) fn linear_combination(
+        &mut self,
+        terms: &[(u64, NonZeroU64)],
+        divisor: u64,
+    ) -> std::io::Result<NonZeroU64> {
+        pumpkin_assert_simple!(!terms.is_empty() && divisor > 0);
+
+        write!(self.writer, "pol")?;
+        for (index, &(factor, constraint)) in terms.iter().enumerate() {
+            write!(self.writer, " {}", self.relative_id(constraint))?;
+            if factor > 1 {
+                write!(self.writer, " {factor} *")?;
+            }
+            if index > 0 {
+                write!(self.writer, " +")?;
+            }
+        }
+        if divisor > 1 {
+            write!(self.writer, " {divisor} d")?;
+        }
+        writeln!(self.writer, " ;")?;
+
+        Ok(self.next_constraint_id())
+    }
+
+    /// Define the fresh `literal` to be false, which is logged as a redundance-based
+    /// strengthening step.
+    pub(crate) fn false_literal(&mut self, literal: Predicate) -> std::io::Result<NonZeroU64> {
+        writeln!(
+            self.writer,
+            "red 1 {} >= 1 : {} ;",
+            veripb_literal(!literal),
+            witness(!literal)
+        )?;
+
+        Ok(self.next_constraint_id())
+    }
+
+    /// Define the fresh `literal` as the reification of `\sum w_i * l_i >= degree`. This is
+    /// logged as two redundance-based strengthening steps, and the ids of `literal -> constraint`
+    /// and `constraint -> literal` are returned in that order.
+    pub(crate) fn reification(
+        &mut self,
+        literal: Predicate,
+        terms: &[(u64, Predicate)],
+        degree: u64,
+    ) -> std::io::Result<(NonZeroU64, NonZeroU64)> {
+        let sum_of_weights = terms.iter().map(|&(weight, _)| weight).sum::<u64>();
+        pumpkin_assert_simple!(
+            degree > 0 && degree <= sum_of_weights,
+            "the reified constraint should not be trivial"
+        );
+
+        // literal -> \sum w_i * l_i >= degree
+        write!(self.writer, "red {degree} {}", veripb_literal(!literal))?;
+        for &(weight, predicate) in terms {
+            write!(self.writer, " {weight} {}", veripb_literal(predicate))?;
+        }
+        writeln!(self.writer, " >= {degree} : {} ;", witness(!literal))?;
+        let literal_implies_constraint = self.next_constraint_id();
+
+        // \sum w_i * l_i >= degree -> literal
+        let coefficient = sum_of_weights - degree + 1;
+        write!(self.writer, "red {coefficient} {}", veripb_literal(literal))?;
+        for &(weight, predicate) in terms {
+            write!(self.writer, " {weight} {}", veripb_literal(!predicate))?;
+        }
+        writeln!(self.writer, " >= {coefficient} : {} ;", witness(literal))?;
+        let constraint_implies_literal = self.next_constraint_id();
+
+        Ok((literal_implies_constraint, constraint_implies_literal))
+    }
+
+    /// Log a solution with the given objective value. This derives the constraint which states
+    /// that the objective value of every remaining solution is smaller than `objective_value`.
+    pub(crate) fn solution(
+        &mut self,
+        assignment: impl IntoIterator<Item = Predicate>,
+        objective_value: u64,
+    ) -> std::io::Result<NonZeroU64> {
+        write!(self.writer, "soli")?;
+        for predicate in assignment {
+            write!(self.writer, " {}", veripb_literal(predicate))?;
+        }
+        writeln!(self.writer, " ;")?;
+
+        self.best_objective_value = Some(objective_value);
+        Ok(self.next_constraint_id())
+    }
+
+    /// Derive the contradiction and conclude the proof. If a solution was logged, the conclusion
+    /// is that the last logged solution is optimal, otherwise that the instance is unsatisfiable.
+    pub(crate) fn unsat(mut self) -> std::io::Result<()> {
+        let _ = self.implied_clause(std::iter::empty(), &[])?;
+
+        writeln!(self.writer, "output NONE ;")?;
+        match self.best_objective_value {
+            Some(value) => writeln!(self.writer, "conclusion BOUNDS {value} {value} ;")?,
+            None => writeln!(self.writer, "conclusion UNSAT : -1 ;")?,
+        }
+        writeln!(self.writer, "end pseudo-Boolean proof ;")?;
+
+        self.writer.flush()
+    }
+
+    fn relative_id(&self, id: NonZeroU64) -> i64 {
+        id.get() as i64 - self.num_derived_constraints as i64 - 1
+    }
+
+    fn next_constraint_id(&mut self) -> NonZeroU64 {
+        self.num_derived_constraints += 1;
+        NonZeroU64::new(self.num_derived_constraints).expect("is incremented before")
+    }
+}
+
+/// Get the VeriPB literal which corresponds to the 0-1 predicate.
+fn veripb_literal(predicate: Predicate) -> String {
+    let variable = predicate.get_domain().id;
+
+    if is_positive(predicate) {
+        format!("x{variable}")
+    } else {
+        format!("~x{variable}")
+    }
+}
+
+/// Get the witness which assigns the variable of the predicate such that the predicate is true.
+fn witness(predicate: Predicate) -> String {
+    let variable = predicate.get_domain().id;
+    let value = if is_positive(predicate) { 1 } else { 0 };
+
+    format!("x{variable} -> {value}")
+}
+
+fn is_positive(predicate: Predicate) -> bool {
+    match predicate {
+        Predicate::LowerBound { lower_bound: 1, .. }
+        | Predicate::Equal {
+            equality_constant: 1,
+            ..
+        }
+        | Predicate::NotEqual {
+            not_equal_constant: 0,
+            ..
+        } => true,
+
+        Predicate::UpperBound { upper_bound: 0, .. }
+        | Predicate::Equal {
+            equality_constant: 0,
+            ..
+        }
+        | Predicate::NotEqual {
+            not_equal_constant: 1,
+            ..
+        } => false,
+
+        other => panic!("Unexpected predicate {other:?} in VeriPB proof, all variables are 0-1"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::variables::DomainId;
+    use crate::predicate;
+
+    fn proof_text(proof: VeriPbProof<Vec<u8>>) -> String {
+        let bytes = proof.writer.into_inner().expect("no io errors");
+        String::from_utf8(bytes).expect("valid utf8")
+    }
+
+    #[test]
+    fn clauses_are_justified_by_the_sum_of_the_antecedents() {
+        let x1 = DomainId::new(1);
+        let x2 = DomainId::new(2);
+
+        let mut proof = VeriPbProof::new(Vec::new()).unwrap();
+        let first = proof.implied_clause([predicate![x1 >= 1]], &[]).unwrap();
+        let second = proof.implied_clause([predicate![x2 <= 0]], &[]).unwrap();
+        let third = proof
+            .implied_clause([predicate![x1 <= 0], predicate![x2 >= 1]], &[first, second])
+            .unwrap();
+
+        assert_eq!(4, third.get());
+        assert_eq!(
+            "pseudo-Boolean proof version 2.0\nf ;\nrup 1 x1 >= 1 ;\nrup 1 ~x2 >= 1 ;\npol -2 -1 + ;\nrup 1 ~x1 1 x2 >= 1 ;\n",
+            proof_text(proof)
+        );
+    }
+
+    #[test]
+    fn reification_is_logged_in_both_directions() {
+        let x1 = DomainId::new(1);
+        let x2 = DomainId::new(2);
+        let x3 = DomainId::new(3);
+
+        let mut proof = VeriPbProof::new(Vec::new()).unwrap();
+        let ids = proof
+            .reification(
+                predicate![x3 >= 1],
+                &[(2, predicate![x1 >= 1]), (3, predicate![x2 <= 0])],
+                3,
+            )
+            .unwrap();
+
+        assert_eq!((1, 2), (ids.0.get(), ids.1.get()));
+        assert_eq!(
+            "pseudo-Boolean proof version 2.0\nf ;\nred 3 ~x3 2 x1 3 ~x2 >= 3 : x3 -> 0 ;\nred 3 x3 2 ~x1 3 x2 >= 3 : x3 -> 1 ;\n",
+            proof_text(proof)
+        );
+    }
+
+    #[test]
+    fn linear_combinations_are_multiplied_and_divided() {
+        let x1 = DomainId::new(1);
+        let x2 = DomainId::new(2);
+
+        let mut proof = VeriPbProof::new(Vec::new()).unwrap();
+        let first = proof
+            .implied_constraint(&[(2, predicate![x1 >= 1]), (1, predicate![x2 >= 1])], 2)
+            .unwrap();
+        let second = proof.implied_clause([predicate![x2 <= 0]], &[]).unwrap();
+        let third = proof
+            .linear_combination(&[(1, first), (3, second)], 2)
+            .unwrap();
+
+        assert_eq!(3, third.get());
+        assert_eq!(
+            "pseudo-Boolean proof version 2.0\nf ;\nrup 2 x1 1 x2 >= 2 ;\nrup 1 ~x2 >= 1 ;\npol -2 -1 3 * + 2 d ;\n",
+            proof_text(proof)
+        );
+    }
+
+    #[test]
+    fn false_literal_is_defined_by_redundance() {
+        let x1 = DomainId::new(1);
+
+        let mut proof = VeriPbProof::new(Vec::new()).unwrap();
+        let id = proof.false_literal(predicate![x1 >= 1]).unwrap();
+
+        assert_eq!(1, id.get());
+        assert_eq!(
+            "pseudo-Boolean proof version 2.0\nf ;\nred 1 ~x1 >= 1 : x1 -> 0 ;\n",
+            proof_text(proof)
+        );
+    }
+
+    #[test]
+    fn last_solution_is_concluded_to_be_optimal() {
+        let x1 = DomainId::new(1);
+        let x2 = DomainId::new(2);
+
+        let mut output = Vec::new();
+        let mut proof = VeriPbProof::new(&mut output).unwrap();
+        let first = proof
+            .solution([predicate![x1 >= 1], predicate![x2 <= 0]], 3)
+            .unwrap();
+        let second = proof
+            .solution([predicate![x1 <= 0], predicate![x2 >= 1]], 1)
+            .unwrap();
+        proof.unsat().unwrap();
+
+        assert_eq!((1, 2), (first.get(), second.get()));
+        assert_eq!(
+            "pseudo-Boolean proof version 2.0\nf ;\nsoli x1 ~x2 ;\nsoli ~x1 x2 ;\nrup >= 1 ;\noutput NONE ;\nconclusion BOUNDS 1 1 ;\nend pseudo-Boolean proof ;\n",
+            String::from_utf8(output).expect("valid utf8")
+        );
+    }
+
+    #[test]
+    fn proof_without_solutions_is_concluded_to_be_unsatisfiable() {
+        let mut output = Vec::new();
+        let proof = VeriPbProof::new(&mut output).unwrap();
+        proof.unsat().unwrap();
+
+        assert_eq!(
+            "pseudo-Boolean proof version 2.0\nf ;\nrup >= 1 ;\noutput NONE ;\nconclusion UNSAT : -1 ;\nend pseudo-Boolean proof ;\n",
+            String::from_utf8(output).expect("valid utf8")
+        );
+    }
+}
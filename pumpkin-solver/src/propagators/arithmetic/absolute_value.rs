@@ -5,6 +5,7 @@ use crate::engine::domain_events::DomainEvents;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::variables::IntegerVariable;
 
@@ -45,10 +46,18 @@ impl<VA: IntegerVariable + 'static, VB: IntegerVariable + 'static> Propagator
         0
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
     fn name(&self) -> &str {
         "IntAbs"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "absolute_value"
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
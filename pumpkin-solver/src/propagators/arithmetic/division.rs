@@ -3,6 +3,7 @@ use crate::conjunction;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::propagation::ReadDomains;
 use crate::engine::variables::IntegerVariable;
@@ -47,10 +48,18 @@ where
         0
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
     fn name(&self) -> &str {
         "Division"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "division"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut PropagatorInitialisationContext,
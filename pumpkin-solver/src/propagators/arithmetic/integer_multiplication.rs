@@ -5,6 +5,7 @@ use crate::engine::domain_events::DomainEvents;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::variables::IntegerVariable;
 use crate::pumpkin_assert_simple;
@@ -56,10 +57,18 @@ where
         0
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
     fn name(&self) -> &str {
         "IntTimes"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "integer_multiplication"
+    }
+
     fn debug_propagate_from_scratch(&self, context: PropagationContextMut) -> PropagationStatusCP {
         perform_propagation(context, &self.a, &self.b, &self.c)
     }
@@ -5,17 +5,21 @@ use crate::basic_types::PropositionalConjunction;
 use crate::engine::cp::propagation::ReadDomains;
 use crate::engine::domain_events::DomainEvents;
 use crate::engine::opaque_domain_event::OpaqueDomainEvent;
+use crate::engine::propagation::contexts::HasAssignments;
 use crate::engine::propagation::contexts::ManipulateTrailedValues;
 use crate::engine::propagation::contexts::PropagationContextWithTrailedValues;
 use crate::engine::propagation::EnqueueDecision;
+use crate::engine::propagation::GeneralisationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::variables::IntegerVariable;
 use crate::engine::TrailedInteger;
 use crate::predicate;
+use crate::predicates::Predicate;
 use crate::pumpkin_assert_simple;
 
 /// Propagator for the constraint `\sum x_i <= c`.
@@ -122,10 +126,18 @@ where
         0
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
     fn name(&self) -> &str {
         "LinearLeq"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "linear_bound"
+    }
+
     fn propagate(&mut self, mut context: PropagationContextMut) -> PropagationStatusCP {
         if let Some(conjunction) = self.detect_inconsistency(context.as_trailed_readonly()) {
             return Err(conjunction.into());
@@ -177,6 +189,56 @@ where
         Ok(())
     }
 
+    fn explain_with_generalised_reason(
+        &mut self,
+        predicate: Predicate,
+        context: GeneralisationContext,
+    ) -> Option<PropositionalConjunction> {
+        let trail_position = context.assignments().get_trail_position(&predicate)?;
+
+        // Find the variable of which the upper-bound was propagated
+        let (propagated_index, upper_bound) = self.x.iter().enumerate().find_map(|(i, x_i)| {
+            let upper_bound = context.upper_bound_at_trail_position(x_i, trail_position);
+            (predicate![x_i <= upper_bound] == predicate).then_some((i, upper_bound))
+        })?;
+
+        // The propagation holds as long as the lower-bounds of the other variables sum to at
+        // least `c - upper_bound`; we greedily select the largest implied lower-bound for every
+        // variable, which is at most its current lower-bound.
+        let required = self.c as i64 - upper_bound as i64;
+        let mut remaining = self
+            .x
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != propagated_index)
+            .map(|(_, x_j)| context.lower_bound(x_j) as i64)
+            .sum::<i64>();
+        let mut sum_of_bounds = 0_i64;
+
+        let mut reason = Vec::with_capacity(self.x.len() - 1);
+        for (j, x_j) in self.x.iter().enumerate() {
+            if j == propagated_index {
+                continue;
+            }
+
+            let current_lower_bound = context.lower_bound(x_j);
+            remaining -= current_lower_bound as i64;
+
+            // The smallest lower-bound for `x_j` which could still lead to the propagation
+            let min = required - sum_of_bounds - remaining;
+            if min > current_lower_bound as i64 {
+                return None;
+            }
+            let min = min.max(i32::MIN as i64) as i32;
+
+            let bound = context.implied_lower_bound(x_j, min, current_lower_bound)?;
+            sum_of_bounds += bound as i64;
+            reason.push(predicate![x_j >= bound]);
+        }
+
+        (sum_of_bounds >= required).then(|| PropositionalConjunction::new(reason))
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -273,6 +335,63 @@ mod tests {
         assert_eq!(conjunction!([x >= 1]), reason);
     }
 
+    #[test]
+    fn generalised_reason_uses_available_predicates() {
+        let mut solver = TestSolver::default();
+        let x = solver.new_variable(0, 10);
+        let y = solver.new_variable(0, 10);
+        let z = solver.new_variable(0, 10);
+
+        let propagator = solver
+            .new_propagator(LinearLessOrEqualPropagator::new([x, y, z].into(), 10))
+            .expect("no empty domains");
+
+        solver.assignments.increase_decision_level();
+        solver.trailed_values.increase_decision_level();
+
+        let _ = solver.increase_lower_bound_and_notify(propagator, 0, x, 6);
+        solver.propagate(propagator).expect("non-empty domain");
+        let _ = solver.increase_lower_bound_and_notify(propagator, 1, y, 3);
+        solver.propagate(propagator).expect("non-empty domain");
+        solver.assert_bounds(z, 0, 1);
+
+        // The original reason for `[z <= 4]` is `[x >= 6]`, but `[x >= 5] /\ [y >= 2]` is also
+        // sufficient.
+        let available_predicates = [predicate![x >= 5], predicate![y >= 2]];
+        let context = GeneralisationContext::new(&solver.assignments, &available_predicates);
+        let reason = solver.propagator_store[propagator]
+            .explain_with_generalised_reason(predicate![z <= 4], context);
+
+        assert_eq!(Some(conjunction!([x >= 5] & [y >= 2])), reason);
+    }
+
+    #[test]
+    fn generalised_reason_is_not_found_if_available_predicates_are_too_weak() {
+        let mut solver = TestSolver::default();
+        let x = solver.new_variable(0, 10);
+        let y = solver.new_variable(0, 10);
+        let z = solver.new_variable(0, 10);
+
+        let propagator = solver
+            .new_propagator(LinearLessOrEqualPropagator::new([x, y, z].into(), 10))
+            .expect("no empty domains");
+
+        solver.assignments.increase_decision_level();
+        solver.trailed_values.increase_decision_level();
+
+        let _ = solver.increase_lower_bound_and_notify(propagator, 0, x, 6);
+        let _ = solver.increase_lower_bound_and_notify(propagator, 1, y, 3);
+        solver.propagate(propagator).expect("non-empty domain");
+        solver.assert_bounds(z, 0, 1);
+
+        let available_predicates = [predicate![x >= 5], predicate![y >= 2]];
+        let context = GeneralisationContext::new(&solver.assignments, &available_predicates);
+        let reason = solver.propagator_store[propagator]
+            .explain_with_generalised_reason(predicate![z <= 1], context);
+
+        assert_eq!(None, reason);
+    }
+
     #[test]
     fn overflow_leads_to_conflict() {
         let mut solver = TestSolver::default();
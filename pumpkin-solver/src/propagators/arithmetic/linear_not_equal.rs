@@ -13,6 +13,7 @@ use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::variables::IntegerVariable;
 use crate::engine::IntDomainEvent;
@@ -67,10 +68,18 @@ where
         0
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
     fn name(&self) -> &str {
         "LinearNe"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "linear_not_equal"
+    }
+
     fn notify(
         &mut self,
         context: PropagationContextWithTrailedValues,
@@ -0,0 +1,100 @@
+use crate::engine::propagation::contexts::HasAssignments;
+use crate::engine::propagation::GeneralisationContext;
+use crate::engine::propagation::ReadDomains;
+use crate::predicate;
+use crate::predicates::Predicate;
+use crate::predicates::PropositionalConjunction;
+use crate::propagators::CumulativeParameters;
+use crate::propagators::Task;
+use crate::variables::IntegerVariable;
+
+/// Creates a generalised explanation for the provided bound `predicate` on the start variable of
+/// one of the tasks (see
+/// [`Propagator::explain_with_generalised_reason`](crate::engine::propagation::Propagator::explain_with_generalised_reason)).
+///
+/// The explanation is a pointwise explanation (see Section 4.5.2 of \[1\]) which only uses
+/// predicates which are implied according to the `context`; i.e. for a lower-bound propagation
+/// `[s_i >= v]`, it consists of `[s_i >= v - p_i]` together with the bounds of a set of tasks
+/// which have a mandatory part at time-point `v - 1` and which overload the resource together
+/// with the propagating task.
+///
+/// Returns [`None`] if no such explanation could be found.
+///
+/// # Bibliography
+/// \[1\] A. Schutt, Improving scheduling by learning. University of Melbourne, Department of
+/// Computer Science and Software Engineering, 2011.
+pub(crate) fn create_generalised_explanation<Var: IntegerVariable + 'static>(
+    predicate: Predicate,
+    context: &GeneralisationContext,
+    parameters: &CumulativeParameters<Var>,
+) -> Option<PropositionalConjunction> {
+    let trail_position = context.assignments().get_trail_position(&predicate)?;
+
+    parameters.tasks.iter().find_map(|task| {
+        let lower_bound =
+            context.lower_bound_at_trail_position(&task.start_variable, trail_position);
+        if predicate![task.start_variable >= lower_bound] == predicate {
+            // The task cannot overlap with time-point `lower_bound - 1`
+            return create_explanation_at_time_point(
+                context,
+                parameters,
+                task,
+                predicate![task.start_variable >= lower_bound - task.processing_time],
+                lower_bound - 1,
+            );
+        }
+
+        let upper_bound =
+            context.upper_bound_at_trail_position(&task.start_variable, trail_position);
+        if predicate![task.start_variable <= upper_bound] == predicate {
+            // The task cannot overlap with time-point `upper_bound + processing_time`
+            return create_explanation_at_time_point(
+                context,
+                parameters,
+                task,
+                predicate![task.start_variable <= upper_bound + task.processing_time],
+                upper_bound + task.processing_time,
+            );
+        }
+
+        None
+    })
+}
+
+/// Creates an explanation for the fact that `propagating_task` cannot overlap with `time_point`
+/// given `propagating_task_predicate` (which restricts the task to either overlap with
+/// `time_point` or to satisfy the propagated bound).
+fn create_explanation_at_time_point<Var: IntegerVariable + 'static>(
+    context: &GeneralisationContext,
+    parameters: &CumulativeParameters<Var>,
+    propagating_task: &Task<Var>,
+    propagating_task_predicate: Predicate,
+    time_point: i32,
+) -> Option<PropositionalConjunction> {
+    if !context.is_implied(propagating_task_predicate) {
+        return None;
+    }
+
+    let mut explanation = vec![propagating_task_predicate];
+    let mut resource_usage = propagating_task.resource_usage;
+
+    for task in parameters.tasks.iter() {
+        if resource_usage > parameters.capacity {
+            break;
+        }
+        if task.id == propagating_task.id || task.resource_usage == 0 {
+            continue;
+        }
+
+        // The task has a mandatory part at `time_point` if both of these predicates hold
+        let starts_before = predicate![task.start_variable <= time_point];
+        let ends_after = predicate![task.start_variable >= time_point + 1 - task.processing_time];
+        if context.is_implied(starts_before) && context.is_implied(ends_after) {
+            resource_usage += task.resource_usage;
+            explanation.push(ends_after);
+            explanation.push(starts_before);
+        }
+    }
+
+    (resource_usage > parameters.capacity).then(|| PropositionalConjunction::new(explanation))
+}
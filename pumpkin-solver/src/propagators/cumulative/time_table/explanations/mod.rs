@@ -1,4 +1,5 @@
 pub(crate) mod big_step;
+pub(crate) mod generalised;
 pub(crate) mod naive;
 pub(crate) mod pointwise;
 use std::fmt::Display;
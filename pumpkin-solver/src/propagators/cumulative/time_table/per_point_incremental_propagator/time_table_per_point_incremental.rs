@@ -7,15 +7,19 @@ use crate::basic_types::PropagationStatusCP;
 use crate::engine::opaque_domain_event::OpaqueDomainEvent;
 use crate::engine::propagation::contexts::PropagationContextWithTrailedValues;
 use crate::engine::propagation::EnqueueDecision;
+use crate::engine::propagation::GeneralisationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::variables::IntegerVariable;
 use crate::engine::IntDomainEvent;
+use crate::predicates::Predicate;
 use crate::predicates::PropositionalConjunction;
 use crate::propagators::create_time_table_per_point_from_scratch;
+use crate::propagators::cumulative::time_table::explanations::generalised::create_generalised_explanation;
 use crate::propagators::cumulative::time_table::per_point_incremental_propagator::synchronisation::check_synchronisation_conflict_explanation_per_point;
 use crate::propagators::cumulative::time_table::per_point_incremental_propagator::synchronisation::create_synchronised_conflict_explanation;
 use crate::propagators::cumulative::time_table::per_point_incremental_propagator::synchronisation::find_synchronised_conflict;
@@ -462,10 +466,18 @@ impl<Var: IntegerVariable + 'static + Debug, const SYNCHRONISE: bool> Propagator
         3
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Expensive
+    }
+
     fn name(&self) -> &str {
         "CumulativeTimeTablePerPointIncremental"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "time_table"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut PropagatorInitialisationContext,
@@ -480,6 +492,14 @@ impl<Var: IntegerVariable + 'static + Debug, const SYNCHRONISE: bool> Propagator
         Ok(())
     }
 
+    fn explain_with_generalised_reason(
+        &mut self,
+        predicate: Predicate,
+        context: GeneralisationContext,
+    ) -> Option<PropositionalConjunction> {
+        create_generalised_explanation(predicate, &context, &self.parameters)
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
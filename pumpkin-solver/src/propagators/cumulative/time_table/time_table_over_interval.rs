@@ -6,15 +6,19 @@ use crate::basic_types::PropagationStatusCP;
 use crate::engine::opaque_domain_event::OpaqueDomainEvent;
 use crate::engine::propagation::contexts::PropagationContextWithTrailedValues;
 use crate::engine::propagation::EnqueueDecision;
+use crate::engine::propagation::GeneralisationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::propagation::ReadDomains;
 use crate::engine::variables::IntegerVariable;
 use crate::engine::IntDomainEvent;
+use crate::predicates::Predicate;
 use crate::predicates::PropositionalConjunction;
+use crate::propagators::cumulative::time_table::explanations::generalised::create_generalised_explanation;
 use crate::propagators::cumulative::time_table::propagation_handler::create_conflict_explanation;
 use crate::propagators::util::create_tasks;
 use crate::propagators::util::register_tasks;
@@ -148,10 +152,18 @@ impl<Var: IntegerVariable + 'static> Propagator for TimeTableOverIntervalPropaga
         3
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Expensive
+    }
+
     fn name(&self) -> &str {
         "CumulativeTimeTableOverInterval"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "time_table"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut PropagatorInitialisationContext,
@@ -163,6 +175,14 @@ impl<Var: IntegerVariable + 'static> Propagator for TimeTableOverIntervalPropaga
         Ok(())
     }
 
+    fn explain_with_generalised_reason(
+        &mut self,
+        predicate: Predicate,
+        context: GeneralisationContext,
+    ) -> Option<PropositionalConjunction> {
+        create_generalised_explanation(predicate, &context, &self.parameters)
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -12,14 +12,18 @@ use crate::engine::cp::propagation::ReadDomains;
 use crate::engine::opaque_domain_event::OpaqueDomainEvent;
 use crate::engine::propagation::contexts::PropagationContextWithTrailedValues;
 use crate::engine::propagation::EnqueueDecision;
+use crate::engine::propagation::GeneralisationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::variables::IntegerVariable;
 use crate::engine::IntDomainEvent;
+use crate::predicates::Predicate;
 use crate::predicates::PropositionalConjunction;
+use crate::propagators::cumulative::time_table::explanations::generalised::create_generalised_explanation;
 use crate::propagators::cumulative::time_table::propagation_handler::create_conflict_explanation;
 use crate::propagators::util::create_tasks;
 use crate::propagators::util::register_tasks;
@@ -143,10 +147,18 @@ impl<Var: IntegerVariable + 'static> Propagator for TimeTablePerPointPropagator<
         3
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Expensive
+    }
+
     fn name(&self) -> &str {
         "CumulativeTimeTablePerPoint"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "time_table"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut PropagatorInitialisationContext,
@@ -158,6 +170,14 @@ impl<Var: IntegerVariable + 'static> Propagator for TimeTablePerPointPropagator<
         Ok(())
     }
 
+    fn explain_with_generalised_reason(
+        &mut self,
+        predicate: Predicate,
+        context: GeneralisationContext,
+    ) -> Option<PropositionalConjunction> {
+        create_generalised_explanation(predicate, &context, &self.parameters)
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -247,6 +267,7 @@ mod tests {
     use crate::conjunction;
     use crate::engine::predicates::predicate::Predicate;
     use crate::engine::propagation::EnqueueDecision;
+    use crate::engine::propagation::GeneralisationContext;
     use crate::engine::test_solver::TestSolver;
     use crate::options::CumulativeExplanationType;
     use crate::predicate;
@@ -795,4 +816,51 @@ mod tests {
             assert_eq!(conjunction!([s1 <= 4] & [s1 >= 4]), reason);
         }
     }
+
+    #[test]
+    fn propagator_provides_generalised_reason() {
+        let mut solver = TestSolver::default();
+        let s1 = solver.new_variable(0, 3);
+        let s2 = solver.new_variable(4, 10);
+
+        let propagator = solver
+            .new_propagator(TimeTablePerPointPropagator::new(
+                &[
+                    ArgTask {
+                        start_time: s1,
+                        processing_time: 4,
+                        resource_usage: 1,
+                    },
+                    ArgTask {
+                        start_time: s2,
+                        processing_time: 3,
+                        resource_usage: 1,
+                    },
+                ]
+                .into_iter()
+                .collect::<Vec<_>>(),
+                1,
+                CumulativePropagatorOptions::default(),
+            ))
+            .expect("No conflict");
+
+        solver.assignments.increase_decision_level();
+        solver.trailed_values.increase_decision_level();
+
+        let _ = solver.increase_lower_bound_and_notify(propagator, 0, s1, 2);
+        solver.propagate(propagator).expect("No conflict");
+        assert_eq!(solver.lower_bound(s2), 6);
+
+        // The task of `s1` has a mandatory part at time-point 5 as long as `[s1 >= 2] /\ [s1 <=
+        // 5]` holds, where the latter holds at the root.
+        let available_predicates = [predicate!(s1 >= 2)];
+        let context = GeneralisationContext::new(&solver.assignments, &available_predicates);
+        let reason = solver.propagator_store[propagator]
+            .explain_with_generalised_reason(predicate!(s2 >= 6), context);
+
+        assert_eq!(
+            Some(conjunction!([s2 >= 3] & [s1 >= 2] & [s1 <= 5])),
+            reason
+        );
+    }
 }
@@ -3,10 +3,12 @@ use bitfield_struct::bitfield;
 use crate::basic_types::PropagationStatusCP;
 use crate::conjunction;
 use crate::engine::domain_events::DomainEvents;
+use crate::engine::propagation::contexts::HasAssignments;
 use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::propagation::ReadDomains;
 use crate::engine::reason::Reason;
@@ -55,10 +57,18 @@ where
         2
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Moderate
+    }
+
     fn name(&self) -> &str {
         "Element"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "element"
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -96,10 +106,18 @@ where
     fn lazy_explanation(&mut self, code: u64, context: ExplanationContext) -> &[Predicate] {
         let payload = RightHandSideReason::from_bits(code);
 
+        // The explanation is based on the domain of the index at the time of propagation, since
+        // values can have been removed from it afterwards.
+        let trail_position = payload.trail_position() as usize;
+
         self.rhs_reason_buffer.clear();
         self.rhs_reason_buffer
             .extend(self.array.iter().enumerate().map(|(idx, variable)| {
-                if context.contains(&self.index, idx as i32) {
+                if self.index.contains_at_trail_position(
+                    context.assignments(),
+                    idx as i32,
+                    trail_position,
+                ) {
                     match payload.bound() {
                         Bound::Lower => predicate![variable >= payload.value()],
                         Bound::Upper => predicate![variable <= payload.value()],
@@ -154,6 +172,7 @@ where
                 RightHandSideReason::new()
                     .with_bound(Bound::Lower)
                     .with_value(rhs_lb)
+                    .with_trail_position(context.assignments.num_trail_entries() as u32)
                     .into_bits(),
             ),
         )?;
@@ -164,6 +183,7 @@ where
                 RightHandSideReason::new()
                     .with_bound(Bound::Upper)
                     .with_value(rhs_ub)
+                    .with_trail_position(context.assignments.num_trail_entries() as u32)
                     .into_bits(),
             ),
         )?;
@@ -251,9 +271,12 @@ impl Bound {
 
 #[bitfield(u64)]
 struct RightHandSideReason {
-    #[bits(32, from = Bound::from_bits)]
+    #[bits(1, from = Bound::from_bits)]
     bound: Bound,
     value: i32,
+    /// The trail position at which the bound of the right-hand side was propagated.
+    #[bits(31)]
+    trail_position: u32,
 }
 
 #[cfg(test)]
@@ -329,6 +352,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn explanation_of_rhs_bounds_uses_index_domain_at_time_of_propagation() {
+        let mut solver = TestSolver::default();
+
+        let x_0 = solver.new_variable(3, 10);
+        let x_1 = solver.new_variable(2, 3);
+        let x_2 = solver.new_variable(7, 9);
+        let x_3 = solver.new_variable(14, 15);
+
+        let index = solver.new_variable(0, 3);
+        let rhs = solver.new_variable(0, 20);
+
+        let _ = solver
+            .new_propagator(ElementPropagator::new(
+                vec![x_0, x_1, x_2, x_3].into(),
+                index,
+                rhs,
+            ))
+            .expect("no empty domains");
+
+        solver.assert_bounds(rhs, 2, 15);
+
+        // Removing a value from the index after the propagation should not affect the explanation,
+        // since `[index != 1]` did not hold when the bound was propagated.
+        solver.remove(index, 1).expect("Value can be removed");
+
+        assert_eq!(
+            solver.get_reason_int(predicate![rhs >= 2]),
+            conjunction!([x_0 >= 2] & [x_1 >= 2] & [x_2 >= 2] & [x_3 >= 2])
+        );
+    }
+
+    #[test]
+    fn right_hand_side_reason_preserves_negative_values() {
+        let reason = RightHandSideReason::from_bits(
+            RightHandSideReason::new()
+                .with_bound(Bound::Upper)
+                .with_value(-5)
+                .with_trail_position(42)
+                .into_bits(),
+        );
+
+        assert_eq!(reason.bound(), Bound::Upper);
+        assert_eq!(reason.value(), -5);
+        assert_eq!(reason.trail_position(), 42);
+    }
+
     #[test]
     fn fixed_index_propagates_bounds_on_element() {
         let mut solver = TestSolver::default();
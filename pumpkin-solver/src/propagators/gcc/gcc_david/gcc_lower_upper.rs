@@ -4,10 +4,10 @@ use fnv::FnvBuildHasher;
 use fnv::FnvHashMap;
 // use log::debug;
 use log::warn;
-use petgraph::algo::has_path_connecting;
 use petgraph::graph::DiGraph;
 use petgraph::graph::NodeIndex;
 use petgraph::prelude::EdgeIndex;
+use petgraph::visit::Dfs;
 use petgraph::visit::EdgeRef;
 use petgraph::Graph;
 
@@ -19,6 +19,7 @@ use crate::create_statistics_struct;
 use crate::engine::cp::propagation::contexts::PropagationContextWithTrailedValues;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::ReadDomains;
 use crate::engine::DomainEvents;
 use crate::predicate;
@@ -35,8 +36,17 @@ struct GraphData {
     sink: NodeIndex,
     variables_nodes: Vec<NodeIndex>,
     values_nodes: Vec<NodeIndex>,
+    /// The node which represents all values which are not part of the cover; these values can
+    /// occur any number of times.
+    other_values_node: NodeIndex,
     intermediate_edges: HashSet<EdgeIndex>,
     initial_intermediate_edges: Vec<(NodeIndex, NodeIndex)>,
+    /// The edges from the [`GraphData::other_values_node`] to the variables which contain a value
+    /// which is not part of the cover.
+    other_values_edges: HashSet<EdgeIndex>,
+    /// The variable nodes which initially had an edge from the
+    /// [`GraphData::other_values_node`].
+    initial_other_values_variables: HashSet<NodeIndex>,
     node_index_to_variable_index: FnvHashMap<NodeIndex, usize>,
     node_index_to_value_index: FnvHashMap<NodeIndex, usize>,
 }
@@ -49,25 +59,32 @@ pub(crate) struct GCCLowerUpper<Variable> {
 }
 
 impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
-    fn edge_joins_sccs(
-        variable_index: NodeIndex,
-        value_index: NodeIndex,
+    /// Returns the nodes which are reachable from `variable_node` in the residual graph.
+    ///
+    /// An edge from the value to the variable which is not part of the residual graph can only
+    /// become consistent if a path from the variable to the value appears; this can only happen if
+    /// a removed edge from a value inside this set to a variable outside of it is restored.
+    fn reachable_nodes(
+        variable_node: NodeIndex,
         residual_graph: &Graph<String, u32>,
-        edge_source: NodeIndex,
-        edge_target: NodeIndex,
-    ) -> bool {
-        // Clone the original graph
-        let mut cloned_graph = residual_graph.clone();
-
-        // Add the new edge to the cloned graph
-        let _ = cloned_graph.add_edge(edge_source, edge_target, 1);
-
-        // Check if there is a path from `source` to `target` in the cloned graph
-        has_path_connecting(&cloned_graph, variable_index, value_index, None)
+    ) -> HashSet<NodeIndex> {
+        let mut reachable = HashSet::default();
+        let mut dfs = Dfs::new(residual_graph, variable_node);
+        while let Some(node) = dfs.next(residual_graph) {
+            let _ = reachable.insert(node);
+        }
+        reachable
     }
 }
 
 impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
+    /// Returns whether the domain of the variable contains a value which is not part of the cover.
+    fn contains_other_value(&self, variable: &Variable, context: &impl ReadDomains) -> bool {
+        context
+            .iterate_domain(variable)
+            .any(|value| self.values.iter().all(|v| v.value != value))
+    }
+
     fn construct_graph(
         &self,
         context: &crate::engine::propagation::PropagatorInitialisationContext,
@@ -95,6 +112,8 @@ impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
             .map(|v| graph.borrow_mut().add_node(v.value.to_string()))
             .collect();
 
+        let other_values_node = graph.borrow_mut().add_node("other".to_owned());
+
         // Add from vals to vars if the var has that val in its domain
         let intermediate_edges: HashSet<_> = values_nodes
             .iter()
@@ -112,6 +131,18 @@ impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
             })
             .collect();
 
+        // Add from the other values to vars if the var has a value outside of the cover
+        let other_values_edges: HashSet<_> = variables_nodes
+            .iter()
+            .zip(self.variables.iter())
+            .filter(|(_, var)| self.contains_other_value(var, context))
+            .map(|(ivar, _)| {
+                graph
+                    .borrow_mut()
+                    .add_edge(other_values_node, *ivar, (0, 1).into())
+            })
+            .collect();
+
         // Add from vars to t
         variables_nodes.iter().for_each(|i| {
             let _ = graph.borrow_mut().add_edge(*i, sink, (0, 1).into());
@@ -143,8 +174,11 @@ impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
             sink,
             variables_nodes,
             values_nodes,
+            other_values_node,
             intermediate_edges,
             initial_intermediate_edges: vec![],
+            other_values_edges,
+            initial_other_values_variables: HashSet::default(),
             node_index_to_value_index,
             node_index_to_variable_index,
         }
@@ -175,6 +209,13 @@ impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
                     );
                 }
             });
+
+        // The other values have no lower bound on their occurrences
+        let _ = self.graph_data.graph.update_edge(
+            self.graph_data.source,
+            self.graph_data.other_values_node,
+            (0, 0).into(),
+        );
     }
 
     fn update_value_edges_max_flow(&mut self) {
@@ -190,15 +231,25 @@ impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
                     (v.omin, v.omax).into(),
                 );
             });
+
+        // The other values can occur any number of times
+        let _ = self.graph_data.graph.update_edge(
+            self.graph_data.source,
+            self.graph_data.other_values_node,
+            (0, self.variables.len() as u32).into(),
+        );
     }
 
     fn update_graph(&mut self, context: &crate::engine::propagation::PropagationContextMut) {
         let intermediate_edges = std::mem::take(&mut self.graph_data.intermediate_edges);
+        let other_values_edges = std::mem::take(&mut self.graph_data.other_values_edges);
 
         // Remove the specified edges using retain_edges
         // Using remove_edge shifts the indices, causing potential bugs.
-        let edge_set: std::collections::HashSet<EdgeIndex> =
-            intermediate_edges.into_iter().collect();
+        let edge_set: std::collections::HashSet<EdgeIndex> = intermediate_edges
+            .into_iter()
+            .chain(other_values_edges)
+            .collect();
         self.graph_data
             .graph
             .retain_edges(|_, edge_index| !edge_set.contains(&edge_index));
@@ -217,6 +268,18 @@ impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
         }
 
         self.graph_data.intermediate_edges = intermediate_edges.into_iter().collect();
+
+        let mut other_values_edges = HashSet::default();
+        for (ivar, var) in self.graph_data.variables_nodes.iter().zip(&self.variables) {
+            if self.contains_other_value(var, context) {
+                let _ = other_values_edges.insert(self.graph_data.graph.add_edge(
+                    self.graph_data.other_values_node,
+                    *ivar,
+                    (0, 1).into(),
+                ));
+            }
+        }
+        self.graph_data.other_values_edges = other_values_edges;
     }
 }
 
@@ -231,8 +294,11 @@ impl<Variable: IntegerVariable> GCCLowerUpper<Variable> {
                 sink: NodeIndex::default(),
                 variables_nodes: vec![],
                 values_nodes: vec![],
+                other_values_node: NodeIndex::default(),
                 intermediate_edges: HashSet::with_hasher(FnvBuildHasher::default()),
                 initial_intermediate_edges: vec![],
+                other_values_edges: HashSet::default(),
+                initial_other_values_variables: HashSet::default(),
                 node_index_to_variable_index: FnvHashMap::default(),
                 node_index_to_value_index: FnvHashMap::default(),
             },
@@ -245,6 +311,10 @@ impl<Variable: IntegerVariable + 'static> Propagator for GCCLowerUpper<Variable>
         "Global Cardinality Low Up"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "gcc_regin"
+    }
+
     fn propagate(
         &mut self,
         mut context: crate::engine::propagation::PropagationContextMut,
@@ -357,6 +427,20 @@ impl<Variable: IntegerVariable + 'static> Propagator for GCCLowerUpper<Variable>
                 edge.flow_display = flow.capacity;
             });
 
+        // If the maximum flow does not assign a value to every variable, then no solution exists
+        let num_assigned_variables = self
+            .graph_data
+            .graph
+            .edges_directed(self.graph_data.sink, petgraph::Direction::Incoming)
+            .filter(|edge| edge.weight().flow_display > 0)
+            .count();
+        if num_assigned_variables < self.variables.len() {
+            return Err(Inconsistency::Conflict(conjunction_all_vars(
+                &context,
+                &self.variables,
+            )));
+        }
+
         #[cfg(debug_assertions)]
         {
             // let dot = graph_to_dot(
@@ -509,7 +593,17 @@ impl<Variable: IntegerVariable + 'static> Propagator for GCCLowerUpper<Variable>
 
                 // Avoid lenghty explanation computation if the naive explanation only contains one
                 // predicate
-                if naive_expl.len() > 1 {
+                let reachable = Self::reachable_nodes(ivar, &residual_graph);
+                // Restoring a value outside of the cover cannot be described by a single
+                // predicate, so in that case the naive explanation is used
+                let other_values_leave_reachable = reachable
+                    .contains(&self.graph_data.other_values_node)
+                    && self
+                        .graph_data
+                        .initial_other_values_variables
+                        .iter()
+                        .any(|ivar| !reachable.contains(ivar));
+                if naive_expl.len() > 1 && !other_values_leave_reachable {
                     for (i, j) in self.graph_data.initial_intermediate_edges.iter() {
                         let var_index = self.graph_data.node_index_to_variable_index[j];
 
@@ -519,7 +613,8 @@ impl<Variable: IntegerVariable + 'static> Propagator for GCCLowerUpper<Variable>
                         let val = self.values[val_index].value;
 
                         if !context.contains(var, val)
-                            && Self::edge_joins_sccs(ivar, ival, &residual_graph, *i, *j)
+                            && reachable.contains(i)
+                            && !reachable.contains(j)
                         {
                             expl2.push(predicate!(var != val));
                         }
@@ -572,9 +667,11 @@ impl<Variable: IntegerVariable + 'static> Propagator for GCCLowerUpper<Variable>
 
     fn debug_propagate_from_scratch(
         &self,
-        _context: crate::engine::propagation::PropagationContextMut,
+        context: crate::engine::propagation::PropagationContextMut,
     ) -> crate::basic_types::PropagationStatusCP {
-        Ok(())
+        // The flow graph is rebuilt from the current domains upon every call, so propagating a
+        // copy of the propagator is equivalent to propagating from scratch.
+        self.clone().propagate(context)
     }
 
     fn initialise_at_root(
@@ -610,6 +707,19 @@ impl<Variable: IntegerVariable + 'static> Propagator for GCCLowerUpper<Variable>
             })
             .collect();
 
+        self.graph_data.initial_other_values_variables = self
+            .graph_data
+            .other_values_edges
+            .iter()
+            .map(|e| {
+                self.graph_data
+                    .graph
+                    .edge_endpoints(*e)
+                    .expect("Edge exists in the graph")
+                    .1
+            })
+            .collect();
+
         // Register for backtrack events if needed with:
         // context.register_for_backtrack_events(var, domain_events, local_id);
         Ok(())
@@ -645,14 +755,32 @@ impl<Variable: IntegerVariable + 'static> Propagator for GCCLowerUpper<Variable>
     fn priority(&self) -> u32 {
         3
     }
+
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Expensive
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::GCCLowerUpper;
+    use crate::engine::propagation::PropagationContextMut;
+    use crate::engine::propagation::PropagatorId;
     use crate::engine::test_solver::TestSolver;
+    use crate::predicate;
     use crate::propagators::gcc_david::Values;
 
+    fn values_with_at_most_one_occurrence(values: impl IntoIterator<Item = i32>) -> Box<[Values]> {
+        values
+            .into_iter()
+            .map(|value| Values {
+                value,
+                omin: 0,
+                omax: 1,
+            })
+            .collect()
+    }
+
     #[test]
     fn test_propagation() {
         let _ = env_logger::builder().is_test(true).try_init();
@@ -755,4 +883,96 @@ mod tests {
 
         assert!(solver.propagate_until_fixed_point(propagator).is_ok());
     }
+
+    #[test]
+    fn values_outside_of_the_cover_can_be_assigned() {
+        let mut solver = TestSolver::default();
+
+        let x_a = solver.new_variable(1, 2);
+        let x_b = solver.new_variable(1, 2);
+        let x_c = solver.new_variable(1, 3);
+
+        let _ = solver
+            .new_propagator(GCCLowerUpper::new(
+                vec![x_a, x_b, x_c].into(),
+                values_with_at_most_one_occurrence([1, 2]),
+            ))
+            .expect("No empty domains");
+
+        solver.assert_bounds(x_c, 3, 3);
+    }
+
+    #[test]
+    fn conflict_if_not_every_variable_can_be_assigned() {
+        let mut solver = TestSolver::default();
+
+        let x_a = solver.new_variable(1, 2);
+        let x_b = solver.new_variable(1, 2);
+        let x_c = solver.new_variable(1, 2);
+
+        let result = solver.new_propagator(GCCLowerUpper::new(
+            vec![x_a, x_b, x_c].into(),
+            values_with_at_most_one_occurrence([1, 2]),
+        ));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn propagation_from_scratch_removes_unsupported_values() {
+        let mut solver = TestSolver::default();
+
+        let x_a = solver.new_variable(1, 3);
+        let x_b = solver.new_variable(1, 3);
+        let x_c = solver.new_variable(1, 4);
+
+        let propagator = solver
+            .new_propagator(GCCLowerUpper::new(
+                vec![x_a, x_b, x_c].into(),
+                values_with_at_most_one_occurrence([1, 2, 3, 4]),
+            ))
+            .expect("No empty domains");
+
+        solver.remove(x_a, 3).expect("Value can be removed");
+        solver.remove(x_b, 3).expect("Value can be removed");
+
+        let context = PropagationContextMut::new(
+            &mut solver.trailed_values,
+            &mut solver.assignments,
+            &mut solver.reason_store,
+            &mut solver.semantic_minimiser,
+            PropagatorId(0),
+        );
+        let result = solver.propagator_store[propagator].debug_propagate_from_scratch(context);
+
+        assert!(result.is_ok());
+        solver.assert_bounds(x_c, 3, 4);
+    }
+
+    #[test]
+    fn explanation_contains_removed_values_outside_of_the_cover() {
+        let mut solver = TestSolver::default();
+
+        let x_a = solver.new_variable(1, 3);
+        let x_b = solver.new_variable(1, 3);
+        let x_c = solver.new_variable(1, 3);
+
+        let propagator = solver
+            .new_propagator(GCCLowerUpper::new(
+                vec![x_a, x_b, x_c].into(),
+                values_with_at_most_one_occurrence([1, 2]),
+            ))
+            .expect("No empty domains");
+
+        solver.assignments.increase_decision_level();
+        solver.remove(x_a, 3).expect("Value can be removed");
+        solver.remove(x_b, 3).expect("Value can be removed");
+
+        assert!(solver.propagate_until_fixed_point(propagator).is_ok());
+        solver.assert_bounds(x_c, 3, 3);
+
+        let reason = solver.get_reason_int(predicate![x_c != 1]);
+        assert!(reason.contains(predicate![x_a <= 2]));
+        assert!(reason.contains(predicate![x_b <= 2]));
+    }
 }
@@ -6,6 +6,7 @@ use crate::create_statistics_struct;
 use crate::engine::cp::propagation::contexts::PropagationContextWithTrailedValues;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::ReadDomains;
 use crate::engine::DomainEvents;
 use crate::predicate;
@@ -33,6 +34,14 @@ impl<Variable: IntegerVariable + 'static> Propagator for GCCLowerUpper2<Variable
         "Global Cardinality Low Up 2"
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Expensive
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_basic_filter"
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: crate::engine::propagation::PropagationContextMut,
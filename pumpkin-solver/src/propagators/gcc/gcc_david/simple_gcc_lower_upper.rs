@@ -5,6 +5,7 @@ use crate::basic_types::Inconsistency;
 use crate::engine::cp::propagation::contexts::PropagationContextWithTrailedValues;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::ReadDomains;
 use crate::engine::DomainEvents;
 use crate::predicates::Predicate;
@@ -33,6 +34,10 @@ impl<Variable: IntegerVariable + 'static> Propagator for SimpleGCCLowerUpper<Var
         "Simple Global Cardinality Low Up"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "gcc_bruteforce"
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         context: crate::engine::propagation::PropagationContextMut,
@@ -124,6 +129,10 @@ impl<Variable: IntegerVariable + 'static> Propagator for SimpleGCCLowerUpper<Var
         3
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Moderate
+    }
+
     fn detect_inconsistency(
         &self,
         _context: PropagationContextWithTrailedValues,
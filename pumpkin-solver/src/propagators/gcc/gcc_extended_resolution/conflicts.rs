@@ -2,6 +2,7 @@ use crate::basic_types::HashMap;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::DomainEvents;
 use crate::predicate;
 use crate::predicates::PropositionalConjunction;
@@ -40,6 +41,14 @@ impl<Var: IntegerVariable> Propagator for GccLowerboundConflicts<Var> {
         "GCC conflicts with extended resolution"
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Expensive
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_lower_bound_conflicts"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut crate::engine::propagation::PropagatorInitialisationContext,
@@ -1,9 +1,13 @@
-use crate::conjunction;
+use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::ReadDomains;
+use crate::engine::reason::Reason;
 use crate::engine::DomainEvents;
+use crate::predicate;
+use crate::predicates::Predicate;
 use crate::variables::IntegerVariable;
 use crate::variables::Literal;
 
@@ -11,6 +15,7 @@ pub(crate) struct GccEquality<Var: IntegerVariable + 'static> {
     left: Var,
     right: Var,
     literal: Literal,
+    explanation_buffer: Vec<Predicate>,
 }
 
 impl<Var: IntegerVariable> GccEquality<Var> {
@@ -19,6 +24,7 @@ impl<Var: IntegerVariable> GccEquality<Var> {
             left,
             right,
             literal,
+            explanation_buffer: vec![],
         }
     }
 }
@@ -28,6 +34,14 @@ impl<Var: IntegerVariable> Propagator for GccEquality<Var> {
         "x = v /\\ y = v => E_{x,y} = 1"
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_equality"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut crate::engine::propagation::PropagatorInitialisationContext,
@@ -38,6 +52,18 @@ impl<Var: IntegerVariable> Propagator for GccEquality<Var> {
         Ok(())
     }
 
+    fn lazy_explanation(&mut self, code: u64, _context: ExplanationContext) -> &[Predicate] {
+        // The code contains the value which is assigned to both variables.
+        let value = code as u32 as i32;
+
+        self.explanation_buffer.clear();
+        self.explanation_buffer.extend([
+            predicate!(self.left == value),
+            predicate!(self.right == value),
+        ]);
+        &self.explanation_buffer
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -47,8 +73,12 @@ impl<Var: IntegerVariable> Propagator for GccEquality<Var> {
             && context.lower_bound(&self.left) == context.lower_bound(&self.right)
         {
             let value = context.lower_bound(&self.left);
-            let reason = conjunction!([self.left == value] & [self.right == value]);
-            PropagationContextMut::assign_literal(&mut context, &self.literal, true, reason)?;
+            PropagationContextMut::assign_literal(
+                &mut context,
+                &self.literal,
+                true,
+                Reason::DynamicLazy(value as u32 as u64),
+            )?;
         }
 
         Ok(())
@@ -1,9 +1,13 @@
-use crate::conjunction;
+use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::ReadDomains;
+use crate::engine::reason::Reason;
 use crate::engine::DomainEvents;
+use crate::predicate;
+use crate::predicates::Predicate;
 use crate::variables::IntegerVariable;
 use crate::variables::Literal;
 
@@ -11,6 +15,7 @@ pub(crate) struct GccExclusion<Var: IntegerVariable + 'static> {
     literal: Literal,
     left: Var,
     right: Var,
+    explanation_buffer: Vec<Predicate>,
 }
 
 impl<Var: IntegerVariable> GccExclusion<Var> {
@@ -19,6 +24,7 @@ impl<Var: IntegerVariable> GccExclusion<Var> {
             literal,
             left,
             right,
+            explanation_buffer: vec![],
         }
     }
 }
@@ -28,6 +34,14 @@ impl<Var: IntegerVariable> Propagator for GccExclusion<Var> {
         "E_{x,y} = 0 /\\ x = v => y != v"
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_exclusion"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut crate::engine::propagation::PropagatorInitialisationContext,
@@ -38,15 +52,31 @@ impl<Var: IntegerVariable> Propagator for GccExclusion<Var> {
         Ok(())
     }
 
+    fn lazy_explanation(&mut self, code: u64, _context: ExplanationContext) -> &[Predicate] {
+        // The code contains the value which is assigned to the left variable.
+        let value = code as u32 as i32;
+
+        self.explanation_buffer.clear();
+        self.explanation_buffer.extend([
+            predicate!(self.literal == 0),
+            predicate!(self.left == value),
+        ]);
+        &self.explanation_buffer
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
     ) -> crate::basic_types::PropagationStatusCP {
         if context.is_literal_false(&self.literal) && context.is_fixed(&self.left) {
             let value = context.lower_bound(&self.left);
-            let reason = conjunction!([self.literal == 0] & [self.left == value]);
 
-            PropagationContextMut::remove(&mut context, &self.right, value, reason)?;
+            PropagationContextMut::remove(
+                &mut context,
+                &self.right,
+                value,
+                Reason::DynamicLazy(value as u32 as u64),
+            )?;
         }
 
         Ok(())
@@ -1,10 +1,15 @@
 use crate::basic_types::HashSet;
+use crate::engine::propagation::contexts::HasAssignments;
+use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
+use crate::engine::reason::Reason;
 use crate::engine::DomainEvents;
-use crate::predicate;
+use crate::predicates::Predicate;
 use crate::predicates::PropositionalConjunction;
+use crate::propagators::gcc_extended_resolution::describe_domain_at_trail_position;
 use crate::variables::IntegerVariable;
 use crate::variables::Literal;
 
@@ -12,6 +17,7 @@ pub(crate) struct GccInequality<Var: IntegerVariable + 'static> {
     left: Var,
     right: Var,
     literal: Literal,
+    explanation_buffer: Vec<Predicate>,
 }
 
 impl<Var: IntegerVariable> GccInequality<Var> {
@@ -20,6 +26,7 @@ impl<Var: IntegerVariable> GccInequality<Var> {
             left,
             right,
             literal,
+            explanation_buffer: vec![],
         }
     }
 }
@@ -29,6 +36,14 @@ impl<Var: IntegerVariable> Propagator for GccInequality<Var> {
         "If D(x) ∩ D(y) = {}, then E_{x,y} = 0"
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_inequality"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut crate::engine::propagation::PropagatorInitialisationContext,
@@ -39,6 +54,28 @@ impl<Var: IntegerVariable> Propagator for GccInequality<Var> {
         Ok(())
     }
 
+    fn lazy_explanation(&mut self, code: u64, context: ExplanationContext) -> &[Predicate] {
+        // The code contains the trail position of the propagation; the explanation consists of
+        // the descriptions of both domains at that point, since the domains could have been
+        // reduced further afterwards.
+        let trail_position = code as usize;
+
+        self.explanation_buffer.clear();
+        describe_domain_at_trail_position(
+            &mut self.explanation_buffer,
+            &self.left,
+            context.assignments(),
+            trail_position,
+        );
+        describe_domain_at_trail_position(
+            &mut self.explanation_buffer,
+            &self.right,
+            context.assignments(),
+            trail_position,
+        );
+        &self.explanation_buffer
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -47,35 +84,18 @@ impl<Var: IntegerVariable> Propagator for GccInequality<Var> {
         let right: HashSet<_> = self.right.iterate_domain(context.assignments).collect();
 
         if left.intersection(&right).next().is_none() {
-            let mut reason = Vec::new();
-            domain_description(&mut reason, &self.left, context.assignments);
-            domain_description(&mut reason, &self.right, context.assignments);
+            // Note that the propagation itself is the entry at this trail position, which does
+            // not affect the domains in the explanation.
+            let trail_position = context.assignments.num_trail_entries();
 
             PropagationContextMut::assign_literal(
                 &mut context,
                 &self.literal,
                 false,
-                PropositionalConjunction::new(reason),
+                Reason::DynamicLazy(trail_position as u64),
             )?;
         }
 
         Ok(())
     }
 }
-
-fn domain_description<Var: IntegerVariable>(
-    description: &mut Vec<crate::predicates::Predicate>,
-    var: &Var,
-    assignment: &crate::engine::Assignments,
-) {
-    let lower_bound = var.lower_bound(assignment);
-    let upper_bound = var.upper_bound(assignment);
-    description.push(predicate!(var >= lower_bound));
-    description.push(predicate!(var <= upper_bound));
-
-    for value in lower_bound..=upper_bound {
-        if !var.contains(assignment, value) {
-            description.push(predicate!(var != value));
-        }
-    }
-}
@@ -1,20 +1,44 @@
 use crate::basic_types::HashMap;
 use crate::basic_types::HashSet;
 use crate::basic_types::Inconsistency;
+use crate::basic_types::PropagationStatusCP;
+use crate::engine::propagation::contexts::HasAssignments;
+use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
+use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::ReadDomains;
+use crate::engine::reason::Reason;
+use crate::engine::Assignments;
 use crate::engine::DomainEvents;
 use crate::predicate;
 use crate::predicates::Predicate;
 use crate::predicates::PropositionalConjunction;
+use crate::propagators::gcc_extended_resolution::describe_domain_at_trail_position;
 use crate::variables::IntegerVariable;
 use crate::variables::Literal;
 
 pub(crate) struct GccInequalitySets<Var> {
     variables: Box<[Var]>,
     equalities: HashMap<(usize, usize), Literal>,
+    /// The reasons for the removals which are currently on the trail; the code of a lazy
+    /// explanation is the index of its reason.
+    reasons: Vec<InequalitySetsReason>,
+    explanation_buffer: Vec<Predicate>,
+}
+
+/// The information which is required to lazily explain the removal of a value from a variable in
+/// a clique of pairwise unequal variables.
+#[derive(Clone, Debug)]
+struct InequalitySetsReason {
+    /// The variables in the clique.
+    clique: Box<[usize]>,
+    /// The variables in the clique of which the domains are part of the explanation.
+    connected: Box<[usize]>,
+    /// The trail position at which the removal took place.
+    trail_position: usize,
 }
 
 impl<Var> GccInequalitySets<Var> {
@@ -25,6 +49,8 @@ impl<Var> GccInequalitySets<Var> {
         Self {
             variables: variables.into_iter().collect(),
             equalities,
+            reasons: vec![],
+            explanation_buffer: vec![],
         }
     }
 
@@ -51,11 +77,41 @@ impl<Var> GccInequalitySets<Var> {
     }
 }
 
+impl<Var: IntegerVariable + 'static> GccInequalitySets<Var> {
+    /// Adds the explanation for a removal to `explanation`; it consists of the inequalities
+    /// between the variables in the clique and the domains of the connected variables at the time
+    /// of the removal.
+    fn explain(
+        &self,
+        explanation: &mut Vec<Predicate>,
+        reason: &InequalitySetsReason,
+        assignments: &Assignments,
+    ) {
+        explanation.extend(self.get_inequality_explanation(&reason.clique));
+        for &variable in reason.connected.iter() {
+            describe_domain_at_trail_position(
+                explanation,
+                &self.variables[variable],
+                assignments,
+                reason.trail_position,
+            );
+        }
+    }
+}
+
 impl<Var: IntegerVariable + 'static> Propagator for GccInequalitySets<Var> {
     fn name(&self) -> &str {
         "GCC extended resolution inequality sets"
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Expensive
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_inequality_sets"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut crate::engine::propagation::PropagatorInitialisationContext,
@@ -77,10 +133,58 @@ impl<Var: IntegerVariable + 'static> Propagator for GccInequalitySets<Var> {
         Ok(())
     }
 
+    fn synchronise(&mut self, context: PropagationContext) {
+        // The reasons of the removals which have been undone are not needed anymore.
+        let num_trail_entries = context.assignments.num_trail_entries();
+        while self
+            .reasons
+            .last()
+            .is_some_and(|reason| reason.trail_position >= num_trail_entries)
+        {
+            let _ = self.reasons.pop();
+        }
+    }
+
+    fn lazy_explanation(&mut self, code: u64, context: ExplanationContext) -> &[Predicate] {
+        let mut explanation = std::mem::take(&mut self.explanation_buffer);
+        explanation.clear();
+        self.explain(
+            &mut explanation,
+            &self.reasons[code as usize],
+            context.assignments(),
+        );
+        self.explanation_buffer = explanation;
+
+        &self.explanation_buffer
+    }
+
+    fn propagate(&mut self, mut context: PropagationContextMut) -> PropagationStatusCP {
+        let mut reasons = std::mem::take(&mut self.reasons);
+        let result = self.propagate_inequality_sets(&mut context, Some(&mut reasons));
+        self.reasons = reasons;
+
+        result
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
-    ) -> crate::basic_types::PropagationStatusCP {
+    ) -> PropagationStatusCP {
+        self.propagate_inequality_sets(&mut context, None)
+    }
+}
+
+impl<Var: IntegerVariable + 'static> GccInequalitySets<Var> {
+    /// Finds a clique of pairwise unequal variables, and propagates it as an all-different
+    /// constraint.
+    ///
+    /// If `reasons` is provided, then the removals are explained lazily and their reasons are
+    /// stored; otherwise, the removals are explained eagerly.
+    fn propagate_inequality_sets(
+        &self,
+        context: &mut PropagationContextMut,
+        mut reasons: Option<&mut Vec<InequalitySetsReason>>,
+    ) -> PropagationStatusCP {
         // Order variables using heuristic
         // - descendingly on the number of inequalities the variable is involved in
         // - break ties by ordering ascendingly on domain size
@@ -269,27 +373,38 @@ impl<Var: IntegerVariable + 'static> Propagator for GccInequalitySets<Var> {
                             continue;
                         }
 
-                        let mut reason = self.get_inequality_explanation(&chosen_variables);
                         let mut connected: HashSet<usize> = HashSet::default();
 
                         // Find all nodes connected to the value
                         let _ = graph.dfs(u, sink + 1, &mut connected, &mut Vec::new());
 
                         // Filter out the variable nodes and use them in explanation
-                        for node in connected {
-                            if node > 0 && node <= vars_len {
-                                reason.extend(
-                                    self.variables[chosen_variables[node - 1]]
-                                        .describe_domain(context.assignments),
-                                );
+                        let reason = InequalitySetsReason {
+                            clique: chosen_variables.clone().into(),
+                            connected: connected
+                                .into_iter()
+                                .filter(|&node| node > 0 && node <= vars_len)
+                                .map(|node| chosen_variables[node - 1])
+                                .collect(),
+                            trail_position: context.assignments.num_trail_entries(),
+                        };
+                        let reason = match reasons.as_mut() {
+                            Some(reasons) => {
+                                reasons.push(reason);
+                                Reason::DynamicLazy((reasons.len() - 1) as u64)
                             }
-                        }
+                            None => {
+                                let mut explanation = vec![];
+                                self.explain(&mut explanation, &reason, context.assignments);
+                                Reason::Eager(PropositionalConjunction::new(explanation))
+                            }
+                        };
 
                         PropagationContextMut::remove(
-                            &mut context,
+                            context,
                             &self.variables[chosen_variables[v - 1]],
                             ids_to_values[&u],
-                            PropositionalConjunction::new(reason.clone()),
+                            reason,
                         )?;
 
                         graph.remove_edge(v, u);
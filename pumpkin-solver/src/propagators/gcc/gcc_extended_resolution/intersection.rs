@@ -1,10 +1,14 @@
 use crate::basic_types::HashSet;
-use crate::conjunction;
+use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::ReadDomains;
+use crate::engine::reason::Reason;
 use crate::engine::DomainEvents;
+use crate::predicate;
+use crate::predicates::Predicate;
 use crate::variables::IntegerVariable;
 use crate::variables::Literal;
 
@@ -12,6 +16,7 @@ pub(crate) struct GccIntersection<Var: IntegerVariable + 'static> {
     literal: Literal,
     left: Var,
     right: Var,
+    explanation_buffer: Vec<Predicate>,
 }
 
 impl<Var: IntegerVariable> GccIntersection<Var> {
@@ -20,6 +25,7 @@ impl<Var: IntegerVariable> GccIntersection<Var> {
             literal,
             left,
             right,
+            explanation_buffer: vec![],
         }
     }
 }
@@ -29,6 +35,14 @@ impl<Var: IntegerVariable> Propagator for GccIntersection<Var> {
         "if E_{x,y} = 1, then D'(x) = D'(y) = D(x) ∩ D(y)"
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_intersection"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut crate::engine::propagation::PropagatorInitialisationContext,
@@ -40,6 +54,18 @@ impl<Var: IntegerVariable> Propagator for GccIntersection<Var> {
         Ok(())
     }
 
+    fn lazy_explanation(&mut self, code: u64, _context: ExplanationContext) -> &[Predicate] {
+        // The code contains the value which is removed from the left variable.
+        let value = code as u32 as i32;
+
+        self.explanation_buffer.clear();
+        self.explanation_buffer.extend([
+            predicate!(self.literal == 1),
+            predicate!(self.right != value),
+        ]);
+        &self.explanation_buffer
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -54,8 +80,12 @@ impl<Var: IntegerVariable> Propagator for GccIntersection<Var> {
 
         for value in left {
             if !right.contains(&value) {
-                let reason = conjunction!([self.literal == 1] & [self.right != value]);
-                PropagationContextMut::remove(&mut context, &self.left, value, reason)?;
+                PropagationContextMut::remove(
+                    &mut context,
+                    &self.left,
+                    value,
+                    Reason::DynamicLazy(value as u32 as u64),
+                )?;
             }
         }
 
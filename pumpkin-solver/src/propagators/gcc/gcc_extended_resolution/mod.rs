@@ -7,6 +7,34 @@ pub(crate) mod intersection;
 pub(crate) mod transitive;
 pub(crate) mod upper_bound;
 
+use crate::engine::Assignments;
+use crate::predicate;
+use crate::predicates::Predicate;
+use crate::variables::IntegerVariable;
+
+/// Adds the description of the domain of the provided variable at the provided trail position
+/// (i.e. including the trail entry at that position) to `description`.
+///
+/// The propagators use this to lazily explain their propagations; at the time of explanation, the
+/// domains could have been reduced further than at the time of propagation.
+fn describe_domain_at_trail_position<Var: IntegerVariable>(
+    description: &mut Vec<Predicate>,
+    var: &Var,
+    assignments: &Assignments,
+    trail_position: usize,
+) {
+    let lower_bound = var.lower_bound_at_trail_position(assignments, trail_position);
+    let upper_bound = var.upper_bound_at_trail_position(assignments, trail_position);
+    description.push(predicate!(var >= lower_bound));
+    description.push(predicate!(var <= upper_bound));
+
+    for value in lower_bound..=upper_bound {
+        if !var.contains_at_trail_position(assignments, value, trail_position) {
+            description.push(predicate!(var != value));
+        }
+    }
+}
+
 #[cfg(test)]
 fn generate_equalities(
     solver: &mut crate::engine::test_solver::TestSolver,
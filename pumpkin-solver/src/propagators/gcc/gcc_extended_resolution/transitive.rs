@@ -1,8 +1,12 @@
-use crate::conjunction;
+use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
+use crate::engine::reason::Reason;
 use crate::engine::DomainEvents;
+use crate::predicate;
+use crate::predicates::Predicate;
 use crate::variables::IntegerVariable;
 use crate::variables::Literal;
 
@@ -10,11 +14,17 @@ pub(crate) struct GccTransitive {
     xy: Literal,
     yz: Literal,
     xz: Literal,
+    explanation_buffer: Vec<Predicate>,
 }
 
 impl GccTransitive {
     pub(crate) fn new(xy: Literal, yz: Literal, xz: Literal) -> Self {
-        Self { xy, yz, xz }
+        Self {
+            xy,
+            yz,
+            xz,
+            explanation_buffer: vec![],
+        }
     }
 }
 
@@ -23,6 +33,14 @@ impl Propagator for GccTransitive {
         "E_{x,y} = 1 /\\ E_{y,z} = 1 => E_{x,z} = 1"
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_transitive"
+    }
+
     fn initialise_at_root(
         &mut self,
         context: &mut crate::engine::propagation::PropagatorInitialisationContext,
@@ -34,6 +52,13 @@ impl Propagator for GccTransitive {
         Ok(())
     }
 
+    fn lazy_explanation(&mut self, _code: u64, _context: ExplanationContext) -> &[Predicate] {
+        self.explanation_buffer.clear();
+        self.explanation_buffer
+            .extend([predicate!(self.xy == 1), predicate!(self.yz == 1)]);
+        &self.explanation_buffer
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -41,8 +66,12 @@ impl Propagator for GccTransitive {
         if self.xy.lower_bound(context.assignments) == 1
             && self.yz.lower_bound(context.assignments) == 1
         {
-            let reason = conjunction!([self.xy == 1] & [self.yz == 1]);
-            PropagationContextMut::assign_literal(&mut context, &self.xz, true, reason)?;
+            PropagationContextMut::assign_literal(
+                &mut context,
+                &self.xz,
+                true,
+                Reason::DynamicLazy(0),
+            )?;
         }
 
         Ok(())
@@ -2,12 +2,20 @@ use reunion::UnionFind;
 use reunion::UnionFindTrait;
 
 use crate::basic_types::HashMap;
+use crate::basic_types::PropagationStatusCP;
+use crate::engine::propagation::contexts::HasAssignments;
+use crate::engine::propagation::ExplanationContext;
 use crate::engine::propagation::LocalId;
+use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::ReadDomains;
+use crate::engine::reason::Reason;
+use crate::engine::Assignments;
 use crate::engine::DomainEvents;
 use crate::predicate;
+use crate::predicates::Predicate;
 use crate::predicates::PropositionalConjunction;
 use crate::propagators::gcc_david::Values;
 use crate::variables::IntegerVariable;
@@ -17,6 +25,23 @@ pub(crate) struct GccUpperBound<Var: IntegerVariable + 'static> {
     variables: Box<[Var]>,
     values: HashMap<i32, (usize, usize)>,
     equalities: HashMap<(usize, usize), Literal>,
+    /// The reasons for the removals which are currently on the trail; the code of a lazy
+    /// explanation is the index of its reason. All removals of a value from the variables in a
+    /// set share the same reason.
+    reasons: Vec<UpperBoundReason>,
+    explanation_buffer: Vec<Predicate>,
+}
+
+/// The information which is required to lazily explain the removal of a value from the variables
+/// in a set of variables which are equal to each other.
+#[derive(Clone, Copy, Debug)]
+struct UpperBoundReason {
+    /// The index of one of the variables in the set.
+    variable: usize,
+    /// The value which is removed from the variables in the set.
+    value: i32,
+    /// The trail position at which the first removal took place.
+    trail_position: usize,
 }
 
 impl<Var: IntegerVariable> GccUpperBound<Var> {
@@ -36,6 +61,8 @@ impl<Var: IntegerVariable> GccUpperBound<Var> {
             variables: variables.into_iter().collect(),
             values: Self::values_to_bounds(values),
             equalities,
+            reasons: vec![],
+            explanation_buffer: vec![],
         }
     }
 
@@ -49,54 +76,76 @@ impl<Var: IntegerVariable> GccUpperBound<Var> {
             .or(self.equalities.get(&(y, x)))
             .expect("E_{x,y} or E_{y,x} must be defined")
     }
-}
-
-impl<Var: IntegerVariable> Propagator for GccUpperBound<Var> {
-    fn name(&self) -> &str {
-        "GCC upper-bound with extended resolution"
-    }
 
-    fn initialise_at_root(
-        &mut self,
-        context: &mut crate::engine::propagation::PropagatorInitialisationContext,
-    ) -> Result<(), PropositionalConjunction> {
-        for (i, var) in self.variables.iter().enumerate() {
-            let _ = context.register(var.clone(), DomainEvents::ASSIGN, LocalId::from(i as u32));
-        }
+    /// Returns the sets of variables which are equal to each other, based on the equality
+    /// literals which are true at the provided trail position.
+    fn equal_sets(&self, assignments: &Assignments, trail_position: usize) -> UnionFind<usize> {
+        let mut uf: UnionFind<usize> = UnionFind::with_capacity(self.variables.len());
 
-        for ((i, j), literal) in self.equalities.iter() {
-            let id = (self.variables.len()) * (i + 1) + j;
-            let _ = context.register(
-                *literal,
-                DomainEvents::LOWER_BOUND,
-                LocalId::from(id as u32),
-            );
+        for ((i, j), literal) in &self.equalities {
+            if literal.lower_bound_at_trail_position(assignments, trail_position) == 1 {
+                uf.union(*i, *j);
+            }
         }
 
-        Ok(())
+        uf
     }
 
-    fn debug_propagate_from_scratch(
+    /// Adds the explanation for removing the value from the variables in the set containing the
+    /// provided variable, based on the state at the provided trail position, to `explanation`.
+    ///
+    /// The explanation consists of the true equality literals between the variables in the set,
+    /// and the variables outside of the set which are assigned to the value.
+    fn explain(
         &self,
-        mut context: PropagationContextMut,
-    ) -> crate::basic_types::PropagationStatusCP {
-        let mut uf: UnionFind<usize> = UnionFind::with_capacity(self.variables.len());
+        explanation: &mut Vec<Predicate>,
+        reason: UpperBoundReason,
+        assignments: &Assignments,
+    ) {
+        let UpperBoundReason {
+            variable,
+            value,
+            trail_position,
+        } = reason;
+
+        let mut uf = self.equal_sets(assignments, trail_position);
+        let representative = uf.find(variable);
+        let set = (0..self.variables.len())
+            .filter(|&i| uf.find(i) == representative)
+            .collect::<Vec<_>>();
+
+        for (i, &elem_1) in set.iter().enumerate() {
+            for &elem_2 in set.iter().skip(i + 1) {
+                let lit = self.get_equality(elem_1, elem_2);
+                if lit.lower_bound_at_trail_position(assignments, trail_position) == 1 {
+                    explanation.push(predicate!(lit == 1));
+                }
+            }
+        }
 
-        for ((i, j), literal) in &self.equalities {
-            if context.is_literal_true(literal) {
-                uf.union(*i, *j);
+        for (i, var) in self.variables.iter().enumerate() {
+            if set.contains(&i) {
+                continue;
+            }
+            if var.lower_bound_at_trail_position(assignments, trail_position) == value
+                && var.upper_bound_at_trail_position(assignments, trail_position) == value
+            {
+                explanation.push(predicate!(var == value));
             }
         }
+    }
 
-        // Now recalculated per set, to include only variables that aren't in the set
-        // See `assigned_not_in_set`
-        // let mut assigned: HashMap<i32, Vec<&Var>> = HashMap::default();
-        // for var in &self.variables {
-        //     if context.is_fixed(var) {
-        //         let value = context.lower_bound(var);
-        //         assigned.entry(value).or_default().push(var);
-        //     }
-        // }
+    /// Removes the values from the sets of equal variables which would otherwise exceed their
+    /// upper bound.
+    ///
+    /// If `reasons` is provided, then the removals are explained lazily and their reasons are
+    /// stored; otherwise, the removals are explained eagerly.
+    fn propagate_upper_bounds(
+        &self,
+        context: &mut PropagationContextMut,
+        mut reasons: Option<&mut Vec<UpperBoundReason>>,
+    ) -> PropagationStatusCP {
+        let mut uf = self.equal_sets(context.assignments, context.assignments.num_trail_entries());
 
         for set in uf.subsets() {
             if set.len() < 2 {
@@ -104,33 +153,21 @@ impl<Var: IntegerVariable> Propagator for GccUpperBound<Var> {
                 continue;
             }
 
-            // Iterating through HashSet isn't stable, so copy into a Vec
-            let set_vec: Vec<usize> = set.iter().cloned().collect();
-            let mut set_reason = Vec::new();
-            for (i, &elem_1) in set_vec.iter().enumerate() {
-                for &elem_2 in set_vec.iter().skip(i + 1) {
-                    let lit = self.get_equality(elem_1, elem_2);
-                    if context.is_literal_true(&lit) {
-                        set_reason.push(predicate!(lit == 1));
-                    }
-                }
-            }
-
-            let mut assigned_not_in_set: HashMap<i32, Vec<&Var>> = HashMap::default();
+            let mut assigned_not_in_set: HashMap<i32, usize> = HashMap::default();
             for (i, var) in self.variables.iter().enumerate() {
                 if set.contains(&i) {
                     continue;
                 }
                 if context.is_fixed(var) {
                     let value = context.lower_bound(var);
-                    assigned_not_in_set.entry(value).or_default().push(var);
+                    *assigned_not_in_set.entry(value).or_default() += 1;
                 }
             }
 
-            let domain: Vec<_> = self.variables
-                [*set.iter().next().expect("set has size of at least 2")]
-            .iterate_domain(context.assignments)
-            .collect();
+            let variable = *set.iter().next().expect("set has size of at least 2");
+            let domain: Vec<_> = self.variables[variable]
+                .iterate_domain(context.assignments)
+                .collect();
 
             let k = set.len();
 
@@ -141,52 +178,39 @@ impl<Var: IntegerVariable> Propagator for GccUpperBound<Var> {
                     continue;
                 };
 
-                let assigned_vars = assigned_not_in_set.entry(value).or_default();
+                let num_assigned = assigned_not_in_set.get(&value).copied().unwrap_or(0);
 
-                if k + assigned_vars.len() <= upper_bound {
+                if k + num_assigned <= upper_bound {
                     // the upperbound is not exceeded even if all k variables from set
                     // are assigned the value
                     continue;
                 }
 
                 // we exceed the upper bound
-                // let mut reason = Vec::new();
-                // Arbitrary chain/tree for now
-                // todo: improve somehow
-
-                // The following might not work properly:
-                // For example, in uf (without path compression) we have
-                // x -> y -> z because E_xy and E_yz
-                // If the transitive propagator wasn't called then E_xz is still unassigned
-                // But because of path compression parent[x] = z
-                // So we try to add [E_xz == 1] to the reason, which isn't true yet
-                // For now this gets solved using `set_reason` above
-                // TODO: Fix/improve
-
-                // for var_index in set.iter() {
-                //     let parent_index = uf.find(*var_index);
-                //     if *var_index == parent_index {
-                //         continue;
-                //     }
-                //     let literal = self.get_equality(*var_index, parent_index);
-                //     reason.push(predicate!(literal == 1));
-                // }
-
-                let mut reason = set_reason.clone();
-
-                for assigned_var in assigned_vars {
-                    reason.push(predicate!(assigned_var == value))
-                }
+                //
+                // Note that the removals themselves do not affect the explanation, so the
+                // explanation is based on the state before the first removal.
+                let reason = UpperBoundReason {
+                    variable,
+                    value,
+                    trail_position: context.assignments.num_trail_entries(),
+                };
+                let reason = match reasons.as_mut() {
+                    Some(reasons) => {
+                        reasons.push(reason);
+                        Reason::DynamicLazy((reasons.len() - 1) as u64)
+                    }
+                    None => {
+                        let mut explanation = vec![];
+                        self.explain(&mut explanation, reason, context.assignments);
+                        Reason::Eager(PropositionalConjunction::new(explanation))
+                    }
+                };
 
                 for var_index in set.iter() {
                     let var = &self.variables[*var_index];
 
-                    PropagationContextMut::remove(
-                        &mut context,
-                        var,
-                        value,
-                        PropositionalConjunction::new(reason.clone()),
-                    )?;
+                    PropagationContextMut::remove(context, var, value, reason.clone())?;
                 }
             }
         }
@@ -195,12 +219,86 @@ impl<Var: IntegerVariable> Propagator for GccUpperBound<Var> {
     }
 }
 
+impl<Var: IntegerVariable> Propagator for GccUpperBound<Var> {
+    fn name(&self) -> &str {
+        "GCC upper-bound with extended resolution"
+    }
+
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Expensive
+    }
+
+    fn inference_label(&self) -> &'static str {
+        "gcc_upper_bound"
+    }
+
+    fn initialise_at_root(
+        &mut self,
+        context: &mut crate::engine::propagation::PropagatorInitialisationContext,
+    ) -> Result<(), PropositionalConjunction> {
+        for (i, var) in self.variables.iter().enumerate() {
+            let _ = context.register(var.clone(), DomainEvents::ASSIGN, LocalId::from(i as u32));
+        }
+
+        for ((i, j), literal) in self.equalities.iter() {
+            let id = (self.variables.len()) * (i + 1) + j;
+            let _ = context.register(
+                *literal,
+                DomainEvents::LOWER_BOUND,
+                LocalId::from(id as u32),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn synchronise(&mut self, context: PropagationContext) {
+        // The reasons of the removals which have been undone are not needed anymore.
+        let num_trail_entries = context.assignments.num_trail_entries();
+        while self
+            .reasons
+            .last()
+            .is_some_and(|reason| reason.trail_position >= num_trail_entries)
+        {
+            let _ = self.reasons.pop();
+        }
+    }
+
+    fn lazy_explanation(&mut self, code: u64, context: ExplanationContext) -> &[Predicate] {
+        let reason = self.reasons[code as usize];
+
+        let mut explanation = std::mem::take(&mut self.explanation_buffer);
+        explanation.clear();
+        self.explain(&mut explanation, reason, context.assignments());
+        self.explanation_buffer = explanation;
+
+        &self.explanation_buffer
+    }
+
+    fn propagate(&mut self, mut context: PropagationContextMut) -> PropagationStatusCP {
+        let mut reasons = std::mem::take(&mut self.reasons);
+        let result = self.propagate_upper_bounds(&mut context, Some(&mut reasons));
+        self.reasons = reasons;
+
+        result
+    }
+
+    fn debug_propagate_from_scratch(
+        &self,
+        mut context: PropagationContextMut,
+    ) -> PropagationStatusCP {
+        self.propagate_upper_bounds(&mut context, None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::GccUpperBound;
     use crate::basic_types::HashMap;
+    use crate::conjunction;
     use crate::engine::test_solver::TestSolver;
+    use crate::predicate;
     use crate::propagators::gcc_extended_resolution::generate_equalities;
 
     #[test]
@@ -220,6 +318,8 @@ mod tests {
             variables: Box::new([x1, x2, x3]),
             values,
             equalities: equalities.clone(),
+            reasons: vec![],
+            explanation_buffer: vec![],
         };
 
         let propagator = solver.new_propagator(propagator).expect("no empty domains");
@@ -255,6 +355,8 @@ mod tests {
             variables: Box::new([x1, x2, x3]),
             values,
             equalities: equalities.clone(),
+            reasons: vec![],
+            explanation_buffer: vec![],
         };
 
         let propagator = solver.new_propagator(propagator).expect("no empty domains");
@@ -269,4 +371,45 @@ mod tests {
             .propagate_until_fixed_point(propagator)
             .expect_err("no assignment is possible");
     }
+
+    #[test]
+    fn explanation_is_based_on_state_at_propagation() {
+        let mut solver = TestSolver::default();
+
+        let x1 = solver.new_variable(1, 3);
+        let x2 = solver.new_variable(1, 3);
+        let x3 = solver.new_variable(1, 3);
+
+        let values: HashMap<i32, (usize, usize)> =
+            HashMap::from_iter([(1, (0, 1)), (2, (0, 3)), (3, (0, 3))]);
+
+        let equalities = generate_equalities(&mut solver, &[x1, x2, x3]);
+
+        let propagator = GccUpperBound {
+            variables: Box::new([x1, x2, x3]),
+            values,
+            equalities: equalities.clone(),
+            reasons: vec![],
+            explanation_buffer: vec![],
+        };
+
+        let propagator = solver.new_propagator(propagator).expect("no empty domains");
+        solver.set_literal(equalities[&(0, 1)], true).unwrap(); // x1 = x2
+        solver
+            .propagate_until_fixed_point(propagator)
+            .expect("should not conflict");
+
+        solver.assert_bounds(x1, 2, 3);
+        solver.assert_bounds(x2, 2, 3);
+
+        // Assigning x3 to 1 afterwards would also explain the removal, but it should not be part
+        // of the explanation since it happened after the propagation.
+        let _ = solver.set_bounds(x3, 1, 1);
+
+        let literal = equalities[&(0, 1)];
+        assert_eq!(
+            solver.get_reason_int(predicate![x1 != 1]),
+            conjunction!([literal == 1])
+        );
+    }
 }
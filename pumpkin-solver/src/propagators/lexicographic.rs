@@ -0,0 +1,306 @@
+use crate::basic_types::PropagationStatusCP;
+use crate::basic_types::PropositionalConjunction;
+use crate::engine::cp::propagation::ReadDomains;
+use crate::engine::domain_events::DomainEvents;
+use crate::engine::propagation::LocalId;
+use crate::engine::propagation::PropagationContextMut;
+use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
+use crate::engine::propagation::PropagatorInitialisationContext;
+use crate::engine::variables::IntegerVariable;
+use crate::predicate;
+use crate::predicates::Predicate;
+
+/// Bounds-consistent propagator which enforces that the array `x` is lexicographically smaller than
+/// or equal to the array `y` (i.e. `x <=_lex y`); if `is_strict` is true, then `x <_lex y` is
+/// enforced instead. Both arrays should have the same length.
+///
+/// The propagator finds the first position `alpha` at which `x` and `y` are not forced to be equal.
+/// The positions before `alpha` are equal, hence `x[alpha] <= y[alpha]` should hold. If the
+/// remainder of the arrays after `alpha` is forced to be lexicographically larger (or equal, in
+/// the strict case), then `x[alpha] < y[alpha]` should hold.
+#[derive(Clone, Debug)]
+pub(crate) struct LexLessOrEqualPropagator<Var> {
+    x: Box<[Var]>,
+    y: Box<[Var]>,
+    is_strict: bool,
+}
+
+impl<Var> LexLessOrEqualPropagator<Var> {
+    pub(crate) fn new(x: Box<[Var]>, y: Box<[Var]>, is_strict: bool) -> Self {
+        assert_eq!(
+            x.len(),
+            y.len(),
+            "The lexicographic propagator expects arrays of the same length"
+        );
+
+        LexLessOrEqualPropagator { x, y, is_strict }
+    }
+}
+
+impl<Var: IntegerVariable + 'static> LexLessOrEqualPropagator<Var> {
+    /// Returns the predicates which explain that `x[index] >= y[index]`.
+    fn explain_greater_or_equal(
+        &self,
+        context: &PropagationContextMut,
+        index: usize,
+    ) -> [Predicate; 2] {
+        let x_lower_bound = context.lower_bound(&self.x[index]);
+        let y_upper_bound = context.upper_bound(&self.y[index]);
+
+        [
+            predicate![self.x[index] >= x_lower_bound],
+            predicate![self.y[index] <= y_upper_bound],
+        ]
+    }
+}
+
+impl<Var: IntegerVariable + 'static> Propagator for LexLessOrEqualPropagator<Var> {
+    fn initialise_at_root(
+        &mut self,
+        context: &mut PropagatorInitialisationContext,
+    ) -> Result<(), PropositionalConjunction> {
+        let num_elements = self.x.len();
+
+        self.x.iter().enumerate().for_each(|(index, variable)| {
+            let _ = context.register(
+                variable.clone(),
+                DomainEvents::BOUNDS,
+                LocalId::from(index as u32),
+            );
+        });
+        self.y.iter().enumerate().for_each(|(index, variable)| {
+            let _ = context.register(
+                variable.clone(),
+                DomainEvents::BOUNDS,
+                LocalId::from((num_elements + index) as u32),
+            );
+        });
+
+        Ok(())
+    }
+
+    fn priority(&self) -> u32 {
+        1
+    }
+
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Moderate
+    }
+
+    fn name(&self) -> &str {
+        if self.is_strict {
+            "LexLess"
+        } else {
+            "LexLessOrEqual"
+        }
+    }
+
+    fn inference_label(&self) -> &'static str {
+        if self.is_strict {
+            "lex_less"
+        } else {
+            "lex_less_or_equal"
+        }
+    }
+
+    fn debug_propagate_from_scratch(
+        &self,
+        mut context: PropagationContextMut,
+    ) -> PropagationStatusCP {
+        let num_elements = self.x.len();
+
+        // The predicates which explain that all positions before `alpha` are forced to be equal.
+        let mut prefix_reason = vec![];
+
+        let mut alpha = 0;
+        while alpha < num_elements {
+            // All positions before `alpha` are equal, hence `x[alpha] <= y[alpha]`.
+            let y_upper_bound = context.upper_bound(&self.y[alpha]);
+            if context.upper_bound(&self.x[alpha]) > y_upper_bound {
+                let reason: PropositionalConjunction = prefix_reason
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(predicate![self.y[alpha] <= y_upper_bound]))
+                    .collect();
+                context.set_upper_bound(&self.x[alpha], y_upper_bound, reason)?;
+            }
+
+            let x_lower_bound = context.lower_bound(&self.x[alpha]);
+            if context.lower_bound(&self.y[alpha]) < x_lower_bound {
+                let reason: PropositionalConjunction = prefix_reason
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(predicate![self.x[alpha] >= x_lower_bound]))
+                    .collect();
+                context.set_lower_bound(&self.y[alpha], x_lower_bound, reason)?;
+            }
+
+            // If `x[alpha] >= y[alpha]` holds as well, then both are equal and the next position
+            // is considered.
+            if context.lower_bound(&self.x[alpha]) < context.upper_bound(&self.y[alpha]) {
+                break;
+            }
+
+            prefix_reason.extend(self.explain_greater_or_equal(&context, alpha));
+            alpha += 1;
+        }
+
+        if alpha == num_elements {
+            // The arrays are forced to be equal, which is only allowed if the constraint is not
+            // strict.
+            return if self.is_strict {
+                Err(PropositionalConjunction::from(prefix_reason).into())
+            } else {
+                Ok(())
+            };
+        }
+
+        // Determine whether the remainder of `x` after `alpha` is forced to be lexicographically
+        // larger than the remainder of `y` (or equal if the constraint is strict); in that case
+        // `x[alpha] < y[alpha]` should hold.
+        let mut suffix_reason = vec![];
+        let mut requires_strict_inequality = self.is_strict;
+        for index in alpha + 1..num_elements {
+            let x_lower_bound = context.lower_bound(&self.x[index]);
+            let y_upper_bound = context.upper_bound(&self.y[index]);
+
+            if x_lower_bound < y_upper_bound {
+                requires_strict_inequality = false;
+                break;
+            }
+
+            suffix_reason.extend(self.explain_greater_or_equal(&context, index));
+
+            if x_lower_bound > y_upper_bound {
+                requires_strict_inequality = true;
+                break;
+            }
+        }
+
+        if requires_strict_inequality {
+            let y_upper_bound = context.upper_bound(&self.y[alpha]);
+            let reason: PropositionalConjunction = prefix_reason
+                .iter()
+                .chain(suffix_reason.iter())
+                .copied()
+                .chain(std::iter::once(predicate![self.y[alpha] <= y_upper_bound]))
+                .collect();
+            context.set_upper_bound(&self.x[alpha], y_upper_bound - 1, reason)?;
+
+            let x_lower_bound = context.lower_bound(&self.x[alpha]);
+            let reason: PropositionalConjunction = prefix_reason
+                .iter()
+                .chain(suffix_reason.iter())
+                .copied()
+                .chain(std::iter::once(predicate![self.x[alpha] >= x_lower_bound]))
+                .collect();
+            context.set_lower_bound(&self.y[alpha], x_lower_bound + 1, reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conjunction;
+    use crate::engine::test_solver::TestSolver;
+
+    #[test]
+    fn first_position_is_bounded_by_the_other_array() {
+        let mut solver = TestSolver::default();
+
+        let x = [solver.new_variable(2, 8), solver.new_variable(0, 5)];
+        let y = [solver.new_variable(1, 6), solver.new_variable(0, 5)];
+
+        let _ = solver
+            .new_propagator(LexLessOrEqualPropagator::new(x.into(), y.into(), false))
+            .expect("no empty domain");
+
+        solver.assert_bounds(x[0], 2, 6);
+        solver.assert_bounds(y[0], 2, 6);
+
+        let reason = solver.get_reason_int(predicate![x[0] <= 6]);
+        assert_eq!(conjunction!([y[0] <= 6]), reason);
+    }
+
+    #[test]
+    fn equal_prefix_moves_the_propagation_to_the_next_position() {
+        let mut solver = TestSolver::default();
+
+        let x = [solver.new_variable(3, 3), solver.new_variable(4, 9)];
+        let y = [solver.new_variable(0, 3), solver.new_variable(0, 7)];
+
+        let _ = solver
+            .new_propagator(LexLessOrEqualPropagator::new(x.into(), y.into(), false))
+            .expect("no empty domain");
+
+        solver.assert_bounds(y[0], 3, 3);
+        solver.assert_bounds(x[1], 4, 7);
+        solver.assert_bounds(y[1], 4, 7);
+
+        let reason = solver.get_reason_int(predicate![x[1] <= 7]);
+        assert_eq!(
+            conjunction!([x[0] >= 3] & [y[0] <= 3] & [y[1] <= 7]),
+            reason
+        );
+    }
+
+    #[test]
+    fn larger_suffix_requires_strict_inequality() {
+        let mut solver = TestSolver::default();
+
+        let x = [solver.new_variable(0, 5), solver.new_variable(4, 6)];
+        let y = [solver.new_variable(0, 5), solver.new_variable(1, 3)];
+
+        let _ = solver
+            .new_propagator(LexLessOrEqualPropagator::new(x.into(), y.into(), false))
+            .expect("no empty domain");
+
+        solver.assert_bounds(x[0], 0, 4);
+        solver.assert_bounds(y[0], 1, 5);
+
+        let reason = solver.get_reason_int(predicate![y[0] >= 1]);
+        assert_eq!(
+            conjunction!([x[1] >= 4] & [y[1] <= 3] & [x[0] >= 0]),
+            reason
+        );
+    }
+
+    #[test]
+    fn equal_arrays_are_not_allowed_by_strict_constraint() {
+        let mut solver = TestSolver::default();
+
+        let x = [solver.new_variable(1, 1), solver.new_variable(2, 2)];
+        let y = [solver.new_variable(1, 1), solver.new_variable(2, 2)];
+
+        let result = solver.new_propagator(LexLessOrEqualPropagator::new(x.into(), y.into(), true));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn equal_arrays_are_allowed_by_non_strict_constraint() {
+        let mut solver = TestSolver::default();
+
+        let x = [solver.new_variable(1, 1), solver.new_variable(2, 2)];
+        let y = [solver.new_variable(1, 1), solver.new_variable(2, 2)];
+
+        let result =
+            solver.new_propagator(LexLessOrEqualPropagator::new(x.into(), y.into(), false));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn lexicographically_larger_assignment_is_a_conflict() {
+        let mut solver = TestSolver::default();
+
+        let x = [solver.new_variable(1, 1), solver.new_variable(3, 3)];
+        let y = [solver.new_variable(1, 1), solver.new_variable(2, 2)];
+
+        let result =
+            solver.new_propagator(LexLessOrEqualPropagator::new(x.into(), y.into(), false));
+        assert!(result.is_err());
+    }
+}
@@ -6,6 +6,7 @@ pub(crate) mod arithmetic;
 mod cumulative;
 pub(crate) mod element;
 pub(crate) mod gcc;
+pub(crate) mod lexicographic;
 pub use gcc::GccOptions;
 pub use gcc::GccPropagatorMethod;
 pub(crate) use gcc::*;
@@ -20,6 +20,14 @@ pub struct LearningOptions {
     pub nogood_sorting_strategy: LearnedNogoodSortingStrategy,
     /// Specifies by how much the activity is increased when a nogood is bumped.
     pub activity_bump_increment: f32,
+    /// Whether the learned nogoods are inprocessed at the root level during restarts; this
+    /// consists of vivification and the removal of subsumed nogoods. Disabled by default.
+    pub inprocess_nogoods: bool,
+    /// The number of restarts between two rounds of inprocessing.
+    pub inprocessing_interval: u64,
+    /// The maximum number of learned nogoods which are vivified during a single round of
+    /// inprocessing.
+    pub max_num_vivified_nogoods: usize,
 }
 impl Default for LearningOptions {
     fn default() -> Self {
@@ -30,6 +38,9 @@ impl Default for LearningOptions {
             nogood_sorting_strategy: LearnedNogoodSortingStrategy::Lbd,
             lbd_threshold: 5,
             activity_bump_increment: 1.0,
+            inprocess_nogoods: false,
+            inprocessing_interval: 5,
+            max_num_vivified_nogoods: 500,
         }
     }
 }
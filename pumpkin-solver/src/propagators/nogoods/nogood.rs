@@ -1,3 +1,5 @@
+use std::num::NonZero;
+
 use crate::predicates::PropositionalConjunction;
 
 /// A struct which represents a nogood (i.e. a list of [`Predicate`]s which cannot all be true at
@@ -21,6 +23,11 @@ pub(crate) struct Nogood {
     pub(crate) block_bumps: bool,
     /// The activity score of the nogood.
     pub(crate) activity: f32,
+    /// Whether the nogood has already been vivified during inprocessing.
+    pub(crate) is_vivified: bool,
+    /// The tag of the constraint which the nogood was added for; only permanent nogoods can be
+    /// tagged.
+    pub(crate) tag: Option<NonZero<u32>>,
 }
 
 impl Nogood {
@@ -33,9 +40,13 @@ impl Nogood {
         }
     }
 
-    pub(crate) fn new_permanent_nogood(predicates: PropositionalConjunction) -> Self {
+    pub(crate) fn new_permanent_nogood(
+        predicates: PropositionalConjunction,
+        tag: Option<NonZero<u32>>,
+    ) -> Self {
         Nogood {
             predicates,
+            tag,
             ..Default::default()
         }
     }
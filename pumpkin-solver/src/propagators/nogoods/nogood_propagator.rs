@@ -1,5 +1,7 @@
+use std::num::NonZero;
 use std::ops::Not;
 
+use itertools::Itertools;
 use log::warn;
 
 use super::LearnedNogoodSortingStrategy;
@@ -7,6 +9,7 @@ use super::LearningOptions;
 use super::NogoodId;
 use super::NogoodWatchList;
 use crate::basic_types::moving_averages::MovingAverage;
+use crate::basic_types::HashMap;
 use crate::basic_types::Inconsistency;
 use crate::basic_types::PropagationStatusCP;
 use crate::basic_types::PropositionalConjunction;
@@ -22,6 +25,7 @@ use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::propagation::ReadDomains;
 use crate::engine::reason::Reason;
@@ -71,6 +75,10 @@ pub(crate) struct NogoodPropagator {
     parameters: LearningOptions,
     /// The nogoods which have been bumped.
     bumped_nogoods: Vec<NogoodId>,
+    /// The learned nogoods which have asserted their predicate at a higher decision level than
+    /// the decision levels of the other predicates in the nogood (due to chronological
+    /// backtracking); see [`NogoodPropagator::reimply_chronologically_asserted_nogoods`].
+    chronologically_asserted_nogoods: Vec<NogoodId>,
 }
 
 /// A struct which keeps track of which nogoods are considered "high" LBD and which nogoods are
@@ -131,10 +139,22 @@ impl Propagator for NogoodPropagator {
         "NogoodPropagator"
     }
 
+    fn inference_label(&self) -> &'static str {
+        "nogood"
+    }
+
+    fn lazy_reason_tag(&self, code: u64) -> Option<NonZero<u32>> {
+        self.nogoods[NogoodId { id: code as u32 }].tag
+    }
+
     fn priority(&self) -> u32 {
         0
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        PropagatorCostClass::Cheap
+    }
+
     fn propagate(&mut self, mut context: PropagationContextMut) -> Result<(), Inconsistency> {
         pumpkin_assert_advanced!(self.debug_is_properly_watched());
 
@@ -901,7 +921,7 @@ impl NogoodPropagator {
                 context.get_decision_level() == 0,
                 "A unit nogood should have backtracked to the root-level"
             );
-            self.add_permanent_nogood(nogood, context)
+            self.add_permanent_nogood(nogood, None, context)
                 .expect("Unit learned nogoods cannot fail.");
             return;
         }
@@ -917,8 +937,62 @@ impl NogoodPropagator {
             .average_lbd
             .add_term(lbd as u64);
 
-        // Add the nogood to the database.
-        //
+        let new_id = self.add_learned_nogood_to_database(nogood, lbd);
+
+        // Then we propagate the asserting predicate and as reason we give the index to the
+        // asserting nogood such that we can re-create the reason when asked for it
+        let reason = Reason::DynamicLazy(new_id.id as u64);
+        context
+            .post_predicate(!self.nogoods[new_id].predicates[0], reason)
+            .expect("Cannot fail to add the asserting predicate.");
+
+        // If the solver did not backjump to the level of the second predicate, then the nogood
+        // should propagate again if the solver backtracks to a level in between.
+        if context
+            .assignments()
+            .get_decision_level_for_predicate(&self.nogoods[new_id].predicates[1])
+            .is_some_and(|decision_level| decision_level < context.get_decision_level())
+        {
+            self.chronologically_asserted_nogoods.push(new_id);
+        }
+    }
+
+    /// Adds a learned nogood which is neither propagating nor conflicting in the current state,
+    /// e.g. the symmetric image of a learned nogood (see
+    /// [`Symmetry`](crate::engine::symmetry::Symmetry)).
+    ///
+    /// Since no propagation takes place, the nogood is only added if at least two of its
+    /// predicates are not satisfied; these predicates are then watched. Returns whether the
+    /// nogood has been added.
+    pub(crate) fn add_non_propagating_learned_nogood(
+        &mut self,
+        mut nogood: Vec<Predicate>,
+        context: &PropagationContextMut,
+    ) -> bool {
+        let mut unsatisfied_positions = nogood
+            .iter()
+            .positions(|&predicate| !context.is_predicate_satisfied(predicate));
+        let (Some(first), Some(second)) =
+            (unsatisfied_positions.next(), unsatisfied_positions.next())
+        else {
+            return false;
+        };
+
+        // The watched predicates are placed at the first two positions.
+        nogood.swap(0, first);
+        nogood.swap(1, second);
+
+        // Since not all predicates are assigned, the LBD cannot be computed; the number of
+        // predicates is used as an upper bound.
+        let lbd = nogood.len() as u32;
+        let _ = self.add_learned_nogood_to_database(nogood, lbd);
+
+        true
+    }
+
+    /// Adds the learned nogood to the database and watches its first two predicates; the nogood is
+    /// placed in the tier corresponding to its LBD.
+    fn add_learned_nogood_to_database(&mut self, nogood: Vec<Predicate>, lbd: u32) -> NogoodId {
         // If there is an available nogood id, use it, otherwise allocate a fresh id.
         let new_id = if let Some(reused_id) = self.delete_ids.pop() {
             self.nogoods[reused_id] = Nogood::new_learned_nogood(nogood.into(), lbd);
@@ -945,41 +1019,126 @@ impl NogoodPropagator {
             new_id,
         );
 
-        // Then we propagate the asserting predicate and as reason we give the index to the
-        // asserting nogood such that we can re-create the reason when asked for it
-        let reason = Reason::DynamicLazy(new_id.id as u64);
-        context
-            .post_predicate(!self.nogoods[new_id].predicates[0], reason)
-            .expect("Cannot fail to add the asserting predicate.");
-
         // We then divide the new nogood based on the LBD level
         if lbd <= self.parameters.lbd_threshold {
             self.learned_nogood_ids.low_lbd.push(new_id);
         } else {
             self.learned_nogood_ids.high_lbd.push(new_id);
         }
+
+        new_id
+    }
+
+    /// Propagates the learned nogoods which have been asserted at a higher decision level than the
+    /// decision levels of their other predicates, and which are not propagating anymore after
+    /// backtracking; this should be called after backtracking.
+    ///
+    /// Since the decision level of a propagation is the decision level at which it is posted, the
+    /// asserting predicate of such a nogood is removed when backtracking to a level in between,
+    /// while the nogood is still unit. As no predicate of the nogood is updated, the watchers do
+    /// not detect this. Rather than allowing propagations at out-of-order decision levels on the
+    /// trail, the asserting predicate is propagated again at the current decision level.
+    pub(crate) fn reimply_chronologically_asserted_nogoods(
+        &mut self,
+        context: &mut PropagationContextMut,
+    ) {
+        let mut index = 0;
+        while index < self.chronologically_asserted_nogoods.len() {
+            let id = self.chronologically_asserted_nogoods[index];
+            let nogood = &self.nogoods[id];
+
+            // The nogood is only unit if all the predicates other than the asserting predicate
+            // are still assigned; the ids of deleted nogoods are discarded as well.
+            //
+            // If the asserting predicate has been assigned by another propagation in the
+            // meantime, then the nogood is violated; this is detected by its watchers.
+            let is_unit = !nogood.is_deleted
+                && !context.is_predicate_satisfied(nogood.predicates[0])
+                && nogood
+                    .predicates
+                    .iter()
+                    .skip(1)
+                    .all(|&predicate| context.is_predicate_satisfied(predicate));
+            if !is_unit {
+                let _ = self.chronologically_asserted_nogoods.swap_remove(index);
+                continue;
+            }
+
+            if !context.is_predicate_falsified(nogood.predicates[0]) {
+                context
+                    .post_predicate(!nogood.predicates[0], Reason::DynamicLazy(id.id as u64))
+                    .expect("Cannot fail to add the asserting predicate.");
+            }
+
+            // Once the asserting predicate is propagated at the level of the second predicate, it
+            // is not removed anymore while the nogood is unit.
+            let is_in_order = context
+                .assignments()
+                .get_decision_level_for_predicate(&nogood.predicates[1])
+                .is_some_and(|decision_level| decision_level == context.get_decision_level());
+            if is_in_order {
+                let _ = self.chronologically_asserted_nogoods.swap_remove(index);
+            } else {
+                index += 1;
+            }
+        }
     }
 
     /// Adds a nogood to the propagator as a permanent nogood and sets the internal state to be
     /// infeasible if the nogood led to a conflict.
+    ///
+    /// The `tag` is the tag of the constraint which the nogood was added for; it is logged with
+    /// the inferences of the nogood in the proof.
     pub(crate) fn add_nogood(
         &mut self,
         nogood: Vec<Predicate>,
+        tag: Option<NonZero<u32>>,
         context: &mut PropagationContextMut,
     ) -> PropagationStatusCP {
-        self.add_permanent_nogood(nogood, context)
+        self.add_permanent_nogood(nogood, tag, context)
     }
 
     /// Adds a nogood which cannot be deleted by clause management.
     fn add_permanent_nogood(
+        &mut self,
+        nogood: Vec<Predicate>,
+        tag: Option<NonZero<u32>>,
+        context: &mut PropagationContextMut,
+    ) -> PropagationStatusCP {
+        self.add_nogood_at_root(nogood, None, tag, context)
+    }
+
+    /// Adds a learned nogood at the root level; in contrast to
+    /// [`NogoodPropagator::add_asserting_nogood`], the nogood is not required to be asserting.
+    ///
+    /// Nogoods which are added at the root are the result of inprocessing, and are therefore not
+    /// vivified again.
+    pub(crate) fn add_learned_nogood_at_root(
+        &mut self,
+        nogood: Vec<Predicate>,
+        lbd: u32,
+        context: &mut PropagationContextMut,
+    ) -> PropagationStatusCP {
+        self.add_nogood_at_root(nogood, Some(lbd), None, context)
+    }
+
+    /// Adds a nogood at the root level; if an LBD is provided then the nogood is added as a
+    /// learned nogood, otherwise it is added as a permanent nogood with the given `tag`.
+    fn add_nogood_at_root(
         &mut self,
         mut nogood: Vec<Predicate>,
+        lbd: Option<u32>,
+        tag: Option<NonZero<u32>>,
         context: &mut PropagationContextMut,
     ) -> PropagationStatusCP {
         pumpkin_assert_simple!(
             context.get_decision_level() == 0,
             "Only allowed to add nogoods permanently at the root for now."
         );
+        pumpkin_assert_simple!(
+            lbd.is_none() || tag.is_none(),
+            "Learned nogoods cannot be tagged."
+        );
 
         // If the nogood is empty then it is automatically satisfied (though it is unusual!)
         if nogood.is_empty() {
@@ -987,6 +1146,15 @@ impl NogoodPropagator {
             return Ok(());
         }
 
+        // Preprocessing removes the predicates which are true at the root, after which the nogood
+        // is only implied by its constraint together with the root-level facts. Hence, its
+        // inferences can no longer refer to the constraint alone.
+        let tag = tag.filter(|_| {
+            !nogood
+                .iter()
+                .any(|&predicate| context.is_predicate_satisfied(predicate))
+        });
+
         // After preprocessing the nogood may propagate. If that happens, there is no reason for
         // the propagation which breaks the proof logging. Therefore, we keep the original nogood
         // here so we can construct a reason for the propagation later.
@@ -1009,17 +1177,30 @@ impl NogoodPropagator {
         //
         // The preprocessing ensures that all predicates are unassigned.
         else {
+            let new_nogood = match lbd {
+                Some(lbd) => Nogood {
+                    is_vivified: true,
+                    ..Nogood::new_learned_nogood(nogood.into(), lbd)
+                },
+                None => Nogood::new_permanent_nogood(nogood.into(), tag),
+            };
+
             // Add the nogood to the database.
             // If there is an available nogood id, use it, otherwise allocate a fresh id.
             let new_id = if let Some(reused_id) = self.delete_ids.pop() {
-                self.nogoods[reused_id] = Nogood::new_permanent_nogood(nogood.into());
+                self.nogoods[reused_id] = new_nogood;
                 reused_id
             } else {
-                self.nogoods
-                    .push(Nogood::new_permanent_nogood(nogood.into()))
+                self.nogoods.push(new_nogood)
             };
 
-            self.permanent_nogoods.push(new_id);
+            match lbd {
+                Some(lbd) if lbd <= self.parameters.lbd_threshold => {
+                    self.learned_nogood_ids.low_lbd.push(new_id)
+                }
+                Some(_) => self.learned_nogood_ids.high_lbd.push(new_id),
+                None => self.permanent_nogoods.push(new_id),
+            }
 
             NogoodPropagator::add_watcher(
                 &mut self.watch_lists,
@@ -1245,6 +1426,352 @@ impl NogoodPropagator {
     }
 }
 
+/// Inprocessing of the learned nogoods
+impl NogoodPropagator {
+    /// Returns the ids of the learned nogoods which should be vivified; these are the learned
+    /// nogoods which have not been vivified yet and which are not currently propagating. The
+    /// nogoods with a "low" LBD are considered first.
+    pub(crate) fn get_vivification_candidates(
+        &self,
+        context: PropagationContext,
+        reason_store: &ReasonStore,
+    ) -> Vec<NogoodId> {
+        self.learned_nogood_ids
+            .low_lbd
+            .iter()
+            .chain(self.learned_nogood_ids.high_lbd.iter())
+            .copied()
+            .filter(|&id| {
+                !self.nogoods[id].is_vivified
+                    && !self.is_nogood_propagating(context, reason_store, id)
+            })
+            .take(self.parameters.max_num_vivified_nogoods)
+            .collect()
+    }
+
+    /// Returns the predicates of the nogood with the provided id.
+    pub(crate) fn get_nogood_predicates(&self, id: NogoodId) -> &[Predicate] {
+        self.nogoods[id].predicates.as_slice()
+    }
+
+    /// Removes the learned nogood with the provided id from the database, and returns it.
+    ///
+    /// The caller should ensure that the nogood is not the reason for a propagation.
+    pub(crate) fn remove_learned_nogood(&mut self, id: NogoodId) -> Nogood {
+        pumpkin_assert_simple!(self.nogoods[id].is_learned && !self.nogoods[id].is_deleted);
+
+        Self::remove_nogood_from_watch_list(
+            &mut self.watch_lists,
+            self.nogoods[id].predicates[0],
+            id,
+        );
+        Self::remove_nogood_from_watch_list(
+            &mut self.watch_lists,
+            self.nogoods[id].predicates[1],
+            id,
+        );
+
+        // The id is immediately removed from the learned nogoods since it could be reused before
+        // the next clean up.
+        self.learned_nogood_ids
+            .low_lbd
+            .retain(|&other_id| other_id != id);
+        self.learned_nogood_ids
+            .high_lbd
+            .retain(|&other_id| other_id != id);
+
+        let nogood = self.nogoods[id].clone();
+        self.nogoods[id].is_deleted = true;
+        self.delete_ids.push(id);
+
+        nogood
+    }
+
+    /// Removes the nogoods (both permanent and learned) which contain a predicate over the
+    /// provided domain from the database, and returns them; it is up to the caller to add them
+    /// back (e.g. after substituting the domain by an equivalent literal).
+    ///
+    /// The caller should ensure that none of the nogoods is the reason for a propagation, which is
+    /// the case if the domain is unassigned at the root.
+    pub(crate) fn remove_nogoods_over_domain(&mut self, domain: DomainId) -> Vec<Nogood> {
+        let ids = self
+            .permanent_nogoods
+            .iter()
+            .chain(self.learned_nogood_ids.low_lbd.iter())
+            .chain(self.learned_nogood_ids.high_lbd.iter())
+            .copied()
+            .filter(|&id| {
+                self.nogoods[id]
+                    .predicates
+                    .iter()
+                    .any(|predicate| predicate.get_domain() == domain)
+            })
+            .collect::<Vec<_>>();
+
+        let removed_nogoods = ids
+            .into_iter()
+            .map(|id| {
+                Self::remove_nogood_from_watch_list(
+                    &mut self.watch_lists,
+                    self.nogoods[id].predicates[0],
+                    id,
+                );
+                Self::remove_nogood_from_watch_list(
+                    &mut self.watch_lists,
+                    self.nogoods[id].predicates[1],
+                    id,
+                );
+
+                let nogood = self.nogoods[id].clone();
+                self.nogoods[id].is_deleted = true;
+                self.delete_ids.push(id);
+
+                nogood
+            })
+            .collect::<Vec<_>>();
+
+        // The ids are removed from the lists at once, rather than for every nogood separately.
+        let nogoods = &self.nogoods;
+        self.permanent_nogoods.retain(|&id| !nogoods[id].is_deleted);
+        self.learned_nogood_ids
+            .low_lbd
+            .retain(|&id| !nogoods[id].is_deleted);
+        self.learned_nogood_ids
+            .high_lbd
+            .retain(|&id| !nogoods[id].is_deleted);
+
+        removed_nogoods
+    }
+
+    /// Removes the learned nogoods which contain a predicate which is falsified at the root; these
+    /// nogoods can never be violated again.
+    ///
+    /// Returns the number of removed nogoods.
+    pub(crate) fn remove_satisfied_learned_nogoods(
+        &mut self,
+        context: PropagationContext,
+        reason_store: &ReasonStore,
+    ) -> usize {
+        pumpkin_assert_simple!(context.assignments().get_decision_level() == 0);
+
+        let satisfied_nogoods = self
+            .learned_nogood_ids
+            .low_lbd
+            .iter()
+            .chain(self.learned_nogood_ids.high_lbd.iter())
+            .copied()
+            .filter(|&id| {
+                self.nogoods[id]
+                    .predicates
+                    .iter()
+                    .any(|&predicate| context.is_predicate_falsified(predicate))
+                    && !self.is_nogood_propagating(context, reason_store, id)
+            })
+            .collect::<Vec<_>>();
+
+        for &id in satisfied_nogoods.iter() {
+            let _ = self.remove_learned_nogood(id);
+        }
+
+        satisfied_nogoods.len()
+    }
+
+    /// Removes the learned nogoods which are subsumed by another nogood, taking into account the
+    /// semantics of the predicates (e.g. the nogood `[x >= 5] /\ [y >= 1]` is subsumed by the
+    /// nogood `[x >= 3]`).
+    ///
+    /// Additionally, learned nogoods are strengthened using self-subsuming resolution (e.g. the
+    /// nogood `[x >= 5] /\ [y >= 1]` is strengthened to `[x >= 5]` using the nogood `[x >= 3] /\
+    /// [y <= 0]`). The strengthened nogoods are removed from the database and returned; it is up
+    /// to the caller to log them and add them back using
+    /// [`NogoodPropagator::add_learned_nogood_at_root`].
+    ///
+    /// Returns the number of removed nogoods together with the strengthened nogoods.
+    pub(crate) fn remove_subsumed_learned_nogoods(
+        &mut self,
+        context: PropagationContext,
+        reason_store: &ReasonStore,
+    ) -> (usize, Vec<StrengthenedNogood>) {
+        pumpkin_assert_simple!(context.assignments().get_decision_level() == 0);
+
+        // For every domain, we keep track of the learned nogoods which contain it; only these
+        // nogoods are considered for removal.
+        let mut occurrences: HashMap<DomainId, Vec<NogoodId>> = HashMap::default();
+        for &id in self
+            .learned_nogood_ids
+            .low_lbd
+            .iter()
+            .chain(self.learned_nogood_ids.high_lbd.iter())
+        {
+            if self.is_nogood_propagating(context, reason_store, id) {
+                continue;
+            }
+
+            for predicate in self.nogoods[id].predicates.iter() {
+                let occurrence_list = occurrences.entry(predicate.get_domain()).or_default();
+                if occurrence_list.last() != Some(&id) {
+                    occurrence_list.push(id);
+                }
+            }
+        }
+
+        // Shorter nogoods are more likely to subsume other nogoods, so these are considered first.
+        let mut subsuming_ids = self
+            .permanent_nogoods
+            .iter()
+            .chain(self.learned_nogood_ids.low_lbd.iter())
+            .chain(self.learned_nogood_ids.high_lbd.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        subsuming_ids.sort_by_key(|&id| self.nogoods[id].predicates.len());
+
+        let mut num_subsumed = 0;
+        let mut strengthened_nogoods = vec![];
+        let mut num_checks = 0;
+
+        'subsuming: for subsuming_id in subsuming_ids {
+            if self.nogoods[subsuming_id].is_deleted {
+                continue;
+            }
+
+            // A (self-)subsumed nogood contains a predicate over every domain in the subsuming
+            // nogood, so we only need to consider the shortest occurrence list.
+            let Some(occurrence_list) = self.nogoods[subsuming_id]
+                .predicates
+                .iter()
+                .map(|predicate| occurrences.get(&predicate.get_domain()))
+                .min_by_key(|occurrence_list| occurrence_list.map_or(0, Vec::len))
+                .flatten()
+            else {
+                continue;
+            };
+
+            for &id in occurrence_list {
+                if id == subsuming_id
+                    || self.nogoods[id].is_deleted
+                    || self.nogoods[id].predicates.len()
+                        < self.nogoods[subsuming_id].predicates.len()
+                {
+                    continue;
+                }
+
+                num_checks += 1;
+                if num_checks > MAX_NUM_SUBSUMPTION_CHECKS {
+                    break 'subsuming;
+                }
+
+                match check_subsumption(
+                    self.nogoods[subsuming_id].predicates.as_slice(),
+                    self.nogoods[id].predicates.as_slice(),
+                ) {
+                    Subsumption::None => {}
+                    Subsumption::Subsumed => {
+                        let subsumed_nogood = self.remove_learned_nogood(id);
+                        num_subsumed += 1;
+
+                        // The subsuming nogood inherits the LBD of the removed nogood if it is
+                        // better.
+                        if self.nogoods[subsuming_id].is_learned {
+                            self.nogoods[subsuming_id].lbd =
+                                self.nogoods[subsuming_id].lbd.min(subsumed_nogood.lbd);
+                        }
+                    }
+                    Subsumption::Strengthened {
+                        removed_index,
+                        resolved_predicate,
+                    } => {
+                        let original_nogood = self.remove_learned_nogood(id);
+                        let mut strengthened_nogood: Vec<Predicate> =
+                            original_nogood.predicates.clone().into();
+                        let _ = strengthened_nogood.remove(removed_index);
+
+                        strengthened_nogoods.push(StrengthenedNogood {
+                            subsuming_nogood: self.nogoods[subsuming_id].predicates.clone().into(),
+                            resolved_predicate,
+                            original_nogood: original_nogood.predicates.into(),
+                            strengthened_nogood,
+                            lbd: original_nogood.lbd,
+                        });
+                    }
+                }
+            }
+        }
+
+        (num_subsumed, strengthened_nogoods)
+    }
+}
+
+/// The maximum number of pairs of nogoods which are checked for subsumption during a single call
+/// to [`NogoodPropagator::remove_subsumed_learned_nogoods`].
+const MAX_NUM_SUBSUMPTION_CHECKS: usize = 100_000;
+
+/// A learned nogood which has been strengthened using self-subsuming resolution.
+///
+/// The strengthened nogood is derived by resolving the original nogood with the subsuming nogood on
+/// the resolved predicate; the subsuming nogood propagates the negation of the resolved predicate,
+/// which implies the predicate which was removed from the original nogood.
+#[derive(Debug, Clone)]
+pub(crate) struct StrengthenedNogood {
+    pub(crate) subsuming_nogood: Vec<Predicate>,
+    pub(crate) resolved_predicate: Predicate,
+    pub(crate) original_nogood: Vec<Predicate>,
+    pub(crate) strengthened_nogood: Vec<Predicate>,
+    pub(crate) lbd: u32,
+}
+
+/// The result of [`check_subsumption`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Subsumption {
+    /// The nogood is not (self-)subsumed.
+    None,
+    /// The nogood is implied by the subsuming nogood.
+    Subsumed,
+    /// The predicate at `removed_index` can be removed from the nogood by resolving it with the
+    /// subsuming nogood on `resolved_predicate`.
+    Strengthened {
+        removed_index: usize,
+        resolved_predicate: Predicate,
+    },
+}
+
+/// Determines whether `nogood` is subsumed by `subsuming_nogood`, i.e. whether every predicate in
+/// `subsuming_nogood` is implied by a predicate in `nogood`.
+///
+/// If all but a single predicate `p` of `subsuming_nogood` are implied, and `nogood` contains a
+/// predicate `q` which is implied by `!p` (while the other predicates are implied without `q`),
+/// then `q` can be removed from `nogood` (self-subsuming resolution).
+fn check_subsumption(subsuming_nogood: &[Predicate], nogood: &[Predicate]) -> Subsumption {
+    let is_implied = |predicate: Predicate, skipped_index: Option<usize>| {
+        nogood
+            .iter()
+            .enumerate()
+            .any(|(index, other)| Some(index) != skipped_index && other.implies(predicate))
+    };
+
+    let mut not_implied = subsuming_nogood
+        .iter()
+        .filter(|&&predicate| !is_implied(predicate, None));
+
+    match (not_implied.next(), not_implied.next()) {
+        (None, _) => Subsumption::Subsumed,
+        (Some(&resolved_predicate), None) => nogood
+            .iter()
+            .position(|&predicate| (!resolved_predicate).implies(predicate))
+            .filter(|&removed_index| {
+                subsuming_nogood.iter().all(|&predicate| {
+                    predicate == resolved_predicate || is_implied(predicate, Some(removed_index))
+                })
+            })
+            .map_or(Subsumption::None, |removed_index| {
+                Subsumption::Strengthened {
+                    removed_index,
+                    resolved_predicate,
+                }
+            }),
+        _ => Subsumption::None,
+    }
+}
+
 impl NogoodPropagator {
     /// Does simple preprocessing, modifying the input nogood by:
     ///     1. Removing duplicate predicates.
@@ -1253,7 +1780,7 @@ impl NogoodPropagator {
     ///        to the empty nogood.
     ///     4. Conflicting predicates?
     fn preprocess_nogood(nogood: &mut Vec<Predicate>, context: &mut PropagationContextMut) {
-        pumpkin_assert_simple!(context.get_decision_level() == 0);
+        pumpkin_assert_simple!(context.assignments().get_decision_level() == 0);
         // The code below is broken down into several parts
 
         // We opt for semantic minimisation upfront. This way we avoid the possibility of having
@@ -1444,13 +1971,17 @@ impl NogoodPropagator {
 
 #[cfg(test)]
 mod tests {
+    use super::check_subsumption;
     use super::NogoodPropagator;
+    use super::Subsumption;
     use crate::conjunction;
     use crate::engine::propagation::store::PropagatorStore;
+    use crate::engine::propagation::PropagationContext;
     use crate::engine::propagation::PropagationContextMut;
     use crate::engine::propagation::PropagatorId;
     use crate::engine::test_solver::TestSolver;
     use crate::predicate;
+    use crate::variables::DomainId;
 
     fn downcast_to_nogood_propagator(
         nogood_propagator: PropagatorId,
@@ -1487,7 +2018,7 @@ mod tests {
             );
 
             downcast_to_nogood_propagator(propagator, &mut solver.propagator_store)
-                .add_nogood(nogood.into(), &mut context)
+                .add_nogood(nogood.into(), None, &mut context)
                 .expect("");
         }
 
@@ -1528,7 +2059,7 @@ mod tests {
             );
 
             downcast_to_nogood_propagator(propagator, &mut solver.propagator_store)
-                .add_nogood(nogood.into(), &mut context)
+                .add_nogood(nogood.into(), None, &mut context)
                 .expect("");
         }
 
@@ -1539,4 +2070,88 @@ mod tests {
         let result = solver.propagate_until_fixed_point(propagator);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn subsumption_takes_domain_semantics_into_account() {
+        let x = DomainId::new(1);
+        let y = DomainId::new(2);
+
+        assert_eq!(
+            check_subsumption(
+                &[predicate!(x >= 3)],
+                &[predicate!(x >= 5), predicate!(y >= 1)]
+            ),
+            Subsumption::Subsumed
+        );
+        assert_eq!(
+            check_subsumption(
+                &[predicate!(x >= 5)],
+                &[predicate!(x >= 3), predicate!(y >= 1)]
+            ),
+            Subsumption::None
+        );
+        assert_eq!(
+            check_subsumption(
+                &[predicate!(x >= 3), predicate!(y <= 0)],
+                &[predicate!(x >= 5), predicate!(y >= 1)]
+            ),
+            Subsumption::Strengthened {
+                removed_index: 1,
+                resolved_predicate: predicate!(y <= 0)
+            }
+        );
+        assert_eq!(
+            check_subsumption(
+                &[predicate!(x >= 3), predicate!(y <= 0)],
+                &[predicate!(x >= 5), predicate!(y >= 2)]
+            ),
+            Subsumption::None
+        );
+    }
+
+    #[test]
+    fn subsumed_learned_nogoods_are_removed() {
+        let mut solver = TestSolver::default();
+        let a = solver.new_variable(0, 10);
+        let b = solver.new_variable(0, 10);
+        let c = solver.new_variable(0, 10);
+
+        let propagator = solver
+            .new_propagator(NogoodPropagator::default())
+            .expect("no empty domains");
+
+        {
+            let mut context = PropagationContextMut::new(
+                &mut solver.trailed_values,
+                &mut solver.assignments,
+                &mut solver.reason_store,
+                &mut solver.semantic_minimiser,
+                propagator,
+            );
+            let nogood_propagator =
+                downcast_to_nogood_propagator(propagator, &mut solver.propagator_store);
+
+            for nogood in [
+                vec![predicate!(a >= 3), predicate!(b >= 2)],
+                vec![predicate!(a >= 5), predicate!(b >= 4), predicate!(c >= 1)],
+                vec![predicate!(a >= 3), predicate!(b <= 1), predicate!(c >= 1)],
+            ] {
+                nogood_propagator
+                    .add_learned_nogood_at_root(nogood, 2, &mut context)
+                    .expect("no conflict");
+            }
+        }
+
+        let context = PropagationContext::new(&solver.assignments);
+        let (num_subsumed, strengthened_nogoods) =
+            downcast_to_nogood_propagator(propagator, &mut solver.propagator_store)
+                .remove_subsumed_learned_nogoods(context, &solver.reason_store);
+
+        assert_eq!(num_subsumed, 1);
+        assert_eq!(strengthened_nogoods.len(), 1);
+        assert_eq!(
+            strengthened_nogoods[0].strengthened_nogood,
+            vec![predicate!(a >= 3), predicate!(c >= 1)]
+        );
+    }
 }
@@ -6,7 +6,9 @@ use crate::engine::propagation::EnqueueDecision;
 use crate::engine::propagation::LocalId;
 use crate::engine::propagation::PropagationContext;
 use crate::engine::propagation::PropagationContextMut;
+use crate::engine::propagation::PropagationDelay;
 use crate::engine::propagation::Propagator;
+use crate::engine::propagation::PropagatorCostClass;
 use crate::engine::propagation::PropagatorInitialisationContext;
 use crate::engine::propagation::ReadDomains;
 use crate::engine::DomainEvents;
@@ -110,6 +112,14 @@ impl<WrappedPropagator: Propagator> Propagator for ReifiedPropagator<WrappedProp
         self.propagator.priority()
     }
 
+    fn cost_class(&self) -> PropagatorCostClass {
+        self.propagator.cost_class()
+    }
+
+    fn propagation_delay(&self) -> PropagationDelay {
+        self.propagator.propagation_delay()
+    }
+
     fn synchronise(&mut self, context: PropagationContext) {
         // We remove the inconsistency upon backtracking since it might be invalid now
         self.inconsistency = None;
@@ -139,6 +149,10 @@ impl<WrappedPropagator: Propagator> Propagator for ReifiedPropagator<WrappedProp
         &self.name
     }
 
+    fn inference_label(&self) -> &'static str {
+        self.propagator.inference_label()
+    }
+
     fn debug_propagate_from_scratch(
         &self,
         mut context: PropagationContextMut,
@@ -394,6 +408,10 @@ mod tests {
             "Generic Propagator"
         }
 
+        fn inference_label(&self) -> &'static str {
+            "generic"
+        }
+
         fn debug_propagate_from_scratch(
             &self,
             context: PropagationContextMut,
@@ -0,0 +1,86 @@
+#![cfg(test)] // workaround for https://github.com/rust-lang/rust-clippy/issues/11024
+
+mod helpers;
+
+use std::time::Duration;
+
+use helpers::models::create_sum_at_least_instance;
+use pumpkin_solver::constraints;
+use pumpkin_solver::optimisation::bisection::Bisection;
+use pumpkin_solver::optimisation::OptimisationDirection;
+use pumpkin_solver::results::OptimisationResult;
+use pumpkin_solver::results::ProblemSolution;
+use pumpkin_solver::results::SolutionReference;
+use pumpkin_solver::termination::Indefinite;
+use pumpkin_solver::variables::DomainId;
+use pumpkin_solver::DefaultBrancher;
+use pumpkin_solver::Solver;
+
+/// Creates the variables `x ∈ [0, 1000]` and `y ∈ [0, 500]` with the constraint `x + y >= 731`.
+fn create_instance() -> (Solver, DomainId) {
+    let (solver, x, _) = create_sum_at_least_instance(1000, 500, 731);
+    (solver, x)
+}
+
+#[test]
+fn bisection_finds_optimal_solution() {
+    let callback: fn(&Solver, SolutionReference, &DefaultBrancher) = |_, _, _| {};
+
+    for (direction, expected) in [
+        (OptimisationDirection::Minimise, 231),
+        (OptimisationDirection::Maximise, 1000),
+    ] {
+        let (mut solver, x) = create_instance();
+        let mut brancher = solver.default_brancher();
+
+        let result = solver.optimise(
+            &mut brancher,
+            &mut Indefinite,
+            Bisection::new(direction, x, callback),
+        );
+
+        let OptimisationResult::Optimal(solution) = result else {
+            panic!("Expected an optimal solution");
+        };
+        assert_eq!(solution.get_integer_value(x), expected);
+    }
+}
+
+#[test]
+fn bisection_falls_back_to_linear_search_when_probes_time_out() {
+    let (mut solver, x) = create_instance();
+    let mut brancher = solver.default_brancher();
+    let callback: fn(&Solver, SolutionReference, &DefaultBrancher) = |_, _, _| {};
+
+    // Every probe exceeds its budget immediately, so the optimal solution is found by the linear
+    // steps.
+    let result = solver.optimise(
+        &mut brancher,
+        &mut Indefinite,
+        Bisection::new(OptimisationDirection::Minimise, x, callback)
+            .with_probe_budget(Some(Duration::ZERO))
+            .with_max_probe_timeouts(2),
+    );
+
+    let OptimisationResult::Optimal(solution) = result else {
+        panic!("Expected an optimal solution");
+    };
+    assert_eq!(solution.get_integer_value(x), 231);
+}
+
+#[test]
+fn bisection_detects_unsatisfiability() {
+    let (mut solver, x) = create_instance();
+    let _ = solver
+        .add_constraint(constraints::less_than_or_equals(vec![x], 100))
+        .post();
+    let mut brancher = solver.default_brancher();
+    let callback: fn(&Solver, SolutionReference, &DefaultBrancher) = |_, _, _| {};
+
+    let result = solver.optimise(
+        &mut brancher,
+        &mut Indefinite,
+        Bisection::new(OptimisationDirection::Minimise, x, callback),
+    );
+    assert!(matches!(result, OptimisationResult::Unsatisfiable));
+}
@@ -0,0 +1,51 @@
+//! Small models which are shared between the integration tests that use the solver API directly.
+
+use std::num::NonZero;
+
+use pumpkin_solver::constraints;
+use pumpkin_solver::variables::DomainId;
+use pumpkin_solver::variables::TransformableVariable;
+use pumpkin_solver::Solver;
+
+/// Creates the variables `x ∈ [0, x_upper_bound]` and `y ∈ [0, y_upper_bound]` with the
+/// constraint `x + y >= sum`.
+pub(crate) fn create_sum_at_least_instance(
+    x_upper_bound: i32,
+    y_upper_bound: i32,
+    sum: i32,
+) -> (Solver, DomainId, DomainId) {
+    let mut solver = Solver::default();
+    let x = solver.new_bounded_integer(0, x_upper_bound);
+    let y = solver.new_bounded_integer(0, y_upper_bound);
+    let _ = solver
+        .add_constraint(constraints::less_than_or_equals(
+            vec![x.scaled(-1), y.scaled(-1)],
+            -sum,
+        ))
+        .post();
+
+    (solver, x, y)
+}
+
+/// Creates the variables `x0, ..., x{num_pigeons - 1} ∈ [1, num_holes]` which are pairwise not
+/// equal; every constraint is tagged with `1`.
+pub(crate) fn create_pigeon_hole_instance(
+    solver: &mut Solver,
+    num_pigeons: i32,
+    num_holes: i32,
+) -> Vec<DomainId> {
+    let pigeons = (0..num_pigeons)
+        .map(|index| solver.new_named_bounded_integer(1, num_holes, format!("x{index}")))
+        .collect::<Vec<_>>();
+    for (index, &first) in pigeons.iter().enumerate() {
+        for &second in &pigeons[index + 1..] {
+            solver
+                .add_constraint(constraints::binary_not_equals(first, second))
+                .with_tag(NonZero::new(1).unwrap())
+                .post()
+                .expect("no conflict");
+        }
+    }
+
+    pigeons
+}
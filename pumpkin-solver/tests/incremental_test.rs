@@ -0,0 +1,162 @@
+#![cfg(test)] // workaround for https://github.com/rust-lang/rust-clippy/issues/11024
+
+use drcp_format::Format;
+use pumpkin_solver::constraints;
+use pumpkin_solver::optimisation::linear_sat_unsat::LinearSatUnsat;
+use pumpkin_solver::optimisation::linear_unsat_sat::LinearUnsatSat;
+use pumpkin_solver::optimisation::OptimisationDirection;
+use pumpkin_solver::options::SolverOptions;
+use pumpkin_solver::predicate;
+use pumpkin_solver::proof::ProofLog;
+use pumpkin_solver::results::OptimisationResult;
+use pumpkin_solver::results::ProblemSolution;
+use pumpkin_solver::results::SatisfactionResult;
+use pumpkin_solver::results::SolutionReference;
+use pumpkin_solver::termination::Indefinite;
+use pumpkin_solver::variables::TransformableVariable;
+use pumpkin_solver::DefaultBrancher;
+use pumpkin_solver::Solver;
+
+#[test]
+fn constraints_can_be_added_between_solve_calls() {
+    let mut solver = Solver::default();
+    let x = solver.new_bounded_integer(0, 0);
+
+    let mut brancher = solver.default_brancher();
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Satisfiable(_)));
+
+    let y = solver.new_bounded_integer(0, 3);
+    let _ = solver
+        .add_constraint(constraints::not_equals(vec![x, y], 0))
+        .post();
+    let _ = solver.add_clause([predicate!(y <= 1)]);
+
+    let mut brancher = solver.default_brancher();
+    match solver.satisfy(&mut brancher, &mut Indefinite) {
+        SatisfactionResult::Satisfiable(solution) => {
+            assert_eq!(solution.get_integer_value(y), 1);
+        }
+        _ => panic!("Problem should be satisfiable"),
+    }
+}
+
+#[test]
+fn scoped_constraints_are_retracted_when_popping() {
+    let mut solver = Solver::default();
+    let x = solver.new_bounded_integer(0, 5);
+    let y = solver.new_bounded_integer(0, 5);
+    let _ = solver
+        .add_constraint(constraints::less_than_or_equals(vec![x, y], 5))
+        .post();
+
+    let mut brancher = solver.default_brancher();
+
+    let _ = solver.push_scope();
+    let _ = solver.add_clause([predicate!(x >= 3)]);
+    let _ = solver.push_scope();
+    let _ = solver
+        .add_constraint(constraints::equals(vec![y], 4))
+        .post();
+    assert_eq!(solver.num_open_scopes(), 2);
+
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+
+    solver.pop_scope().expect("no root-level conflict");
+    match solver.satisfy(&mut brancher, &mut Indefinite) {
+        SatisfactionResult::Satisfiable(solution) => {
+            assert!(solution.get_integer_value(x) >= 3);
+        }
+        _ => panic!("Problem should be satisfiable in the outer scope"),
+    }
+
+    solver.pop_scope().expect("no root-level conflict");
+    assert_eq!(solver.num_open_scopes(), 0);
+    let _ = solver.add_clause([predicate!(x <= 1)]);
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Satisfiable(_)));
+}
+
+#[test]
+fn optimisation_respects_open_scopes() {
+    let mut solver = Solver::default();
+    let x = solver.new_bounded_integer(0, 10);
+    let y = solver.new_bounded_integer(0, 10);
+    let _ = solver
+        .add_constraint(constraints::less_than_or_equals(
+            vec![x.scaled(-1), y.scaled(1)],
+            -2,
+        ))
+        .post();
+
+    let mut brancher = solver.default_brancher();
+    let callback: fn(&Solver, SolutionReference, &DefaultBrancher) = |_, _, _| {};
+
+    let _ = solver.push_scope();
+    let _ = solver.add_clause([predicate!(y >= 4)]);
+    let result = solver.optimise(
+        &mut brancher,
+        &mut Indefinite,
+        LinearUnsatSat::new(OptimisationDirection::Minimise, x, callback),
+    );
+    let OptimisationResult::Optimal(solution) = result else {
+        panic!("Expected an optimal solution in the scope");
+    };
+    assert_eq!(solution.get_integer_value(x), 6);
+
+    let result = solver.optimise(
+        &mut brancher,
+        &mut Indefinite,
+        LinearSatUnsat::new(OptimisationDirection::Minimise, x, callback),
+    );
+    let OptimisationResult::Optimal(solution) = result else {
+        panic!("Expected an optimal solution in the scope");
+    };
+    assert_eq!(solution.get_integer_value(x), 6);
+
+    solver.pop_scope().expect("no root-level conflict");
+    let result = solver.optimise(
+        &mut brancher,
+        &mut Indefinite,
+        LinearSatUnsat::new(OptimisationDirection::Minimise, x, callback),
+    );
+    let OptimisationResult::Optimal(solution) = result else {
+        panic!("Expected an optimal solution after popping the scope");
+    };
+    assert_eq!(solution.get_integer_value(x), 2);
+}
+
+#[test]
+fn proof_is_only_concluded_when_no_scope_is_open() {
+    let proof_path = std::env::temp_dir().join("incremental_scoped_proof.drcp");
+    let mut solver = Solver::with_options(SolverOptions {
+        proof_log: ProofLog::cp(&proof_path, Format::Text, true, true).expect("created proof"),
+        ..Default::default()
+    });
+    let x = solver.new_named_bounded_integer(0, 5, "x");
+    let y = solver.new_named_bounded_integer(0, 5, "y");
+    let _ = solver
+        .add_constraint(constraints::less_than_or_equals(vec![x, y], 5))
+        .post();
+
+    let mut brancher = solver.default_brancher();
+
+    let _ = solver.push_scope();
+    let _ = solver.add_clause([predicate!(x >= 3)]);
+    let _ = solver.add_clause([predicate!(y >= 3)]);
+    // The unsatisfiability only holds in the scope, so the proof is not concluded.
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+    solver.pop_scope().expect("no root-level conflict");
+
+    let _ = solver.add_clause([predicate!(x >= 4)]);
+    let _ = solver.add_clause([predicate!(y >= 4)]);
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+    drop(solver);
+
+    let proof = std::fs::read_to_string(&proof_path).expect("proof exists");
+    let conclusions = proof.lines().filter(|line| line.starts_with("c ")).count();
+    assert_eq!(conclusions, 1);
+}
@@ -3,7 +3,9 @@
 use pumpkin_solver::constraints;
 use pumpkin_solver::results::solution_iterator::IteratedSolution;
 use pumpkin_solver::results::ProblemSolution;
+use pumpkin_solver::results::SatisfactionResult;
 use pumpkin_solver::termination::Indefinite;
+use pumpkin_solver::variables::DomainId;
 use pumpkin_solver::Solver;
 
 #[test]
@@ -64,3 +66,129 @@ fn iterator_finds_all_solutions() {
     // There are six possible solutions to this problem
     assert_eq!(number_of_solutions, 6)
 }
+
+#[test]
+fn iterator_with_projection_finds_distinct_projected_solutions() {
+    let mut solver = Solver::default();
+
+    let x = solver.new_bounded_integer(0, 2);
+    let y = solver.new_bounded_integer(0, 2);
+    let z = solver.new_bounded_integer(0, 2);
+
+    let _ = solver
+        .add_constraint(constraints::all_different(vec![x, y, z]))
+        .post();
+
+    let mut termination = Indefinite;
+    let mut brancher = solver.default_brancher();
+
+    // Only the values of `x` and `y` are of interest
+    let mut solution_iterator = solver
+        .get_solution_iterator(&mut brancher, &mut termination)
+        .with_projection([x, y]);
+
+    let mut known_solutions = Vec::new();
+
+    loop {
+        match solution_iterator.next_solution() {
+            IteratedSolution::Solution(solution, _, _) => {
+                let projected_solution =
+                    (solution.get_integer_value(x), solution.get_integer_value(y));
+
+                assert!(!known_solutions.contains(&projected_solution));
+                known_solutions.push(projected_solution);
+            }
+            IteratedSolution::Finished => break,
+            IteratedSolution::Unknown => panic!("Termination condition should not trigger"),
+            IteratedSolution::Unsatisfiable => panic!("Problem should be satisfiable"),
+        }
+    }
+
+    known_solutions.sort();
+    assert_eq!(
+        known_solutions,
+        vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)]
+    );
+}
+
+#[test]
+fn iterator_with_projection_blocks_on_literals() {
+    let mut solver = Solver::default();
+
+    let a = solver.new_literal();
+    let _x = solver.new_bounded_integer(0, 5);
+
+    let mut termination = Indefinite;
+    let mut brancher = solver.default_brancher();
+
+    let mut solution_iterator = solver
+        .get_solution_iterator(&mut brancher, &mut termination)
+        .with_projection([a]);
+
+    let mut number_of_solutions = 0;
+    while let IteratedSolution::Solution(_, _, _) = solution_iterator.next_solution() {
+        number_of_solutions += 1;
+    }
+
+    // Only the two values of `a` are enumerated, regardless of the value of `_x`
+    assert_eq!(number_of_solutions, 2);
+}
+
+#[test]
+fn iterator_with_empty_projection_stops_after_one_solution() {
+    let mut solver = Solver::default();
+
+    let _x = solver.new_bounded_integer(0, 2);
+
+    let mut termination = Indefinite;
+    let mut brancher = solver.default_brancher();
+
+    let mut solution_iterator = solver
+        .get_solution_iterator(&mut brancher, &mut termination)
+        .with_projection(Vec::<DomainId>::new());
+
+    assert!(matches!(
+        solution_iterator.next_solution(),
+        IteratedSolution::Solution(_, _, _)
+    ));
+    assert!(matches!(
+        solution_iterator.next_solution(),
+        IteratedSolution::Finished
+    ));
+    assert!(matches!(
+        solution_iterator.next_solution(),
+        IteratedSolution::Finished
+    ));
+
+    // No (empty) blocking clause was added, so the solver is still satisfiable
+    let mut termination = Indefinite;
+    let mut brancher = solver.default_brancher();
+    assert!(matches!(
+        solver.satisfy(&mut brancher, &mut termination),
+        SatisfactionResult::Satisfiable(_)
+    ));
+}
+
+#[test]
+fn iterator_without_deduplication_finds_all_solutions() {
+    let mut solver = Solver::default();
+
+    let x = solver.new_bounded_integer(0, 2);
+    let _y = solver.new_bounded_integer(0, 2);
+
+    let mut termination = Indefinite;
+    let mut brancher = solver.default_brancher();
+
+    let mut solution_iterator = solver
+        .get_solution_iterator(&mut brancher, &mut termination)
+        .with_projection([x])
+        .with_deduplication(false);
+
+    let mut number_of_solutions = 0;
+    while let IteratedSolution::Solution(_, _, _) = solution_iterator.next_solution() {
+        number_of_solutions += 1;
+    }
+
+    // Every value of `x` is reported once for every value of `_y`
+    assert_eq!(number_of_solutions, 9);
+}
@@ -0,0 +1,85 @@
+#![cfg(test)] // workaround for https://github.com/rust-lang/rust-clippy/issues/11024
+
+mod helpers;
+
+use std::cell::RefCell;
+
+use helpers::models::create_sum_at_least_instance;
+use pumpkin_solver::optimisation::lexicographic::LexicographicOptimisation;
+use pumpkin_solver::optimisation::pareto_front::ParetoFront;
+use pumpkin_solver::optimisation::OptimisationDirection;
+use pumpkin_solver::optimisation::OptimisationStrategy;
+use pumpkin_solver::results::OptimisationResult;
+use pumpkin_solver::results::ProblemSolution;
+use pumpkin_solver::results::SolutionReference;
+use pumpkin_solver::termination::Indefinite;
+use pumpkin_solver::DefaultBrancher;
+use pumpkin_solver::Solver;
+
+#[test]
+fn lexicographic_optimisation_respects_objective_order() {
+    for strategy in [
+        OptimisationStrategy::LinearSatUnsat,
+        OptimisationStrategy::LinearUnsatSat,
+        OptimisationStrategy::Bisection,
+    ] {
+        let (mut solver, x, y) = create_sum_at_least_instance(5, 5, 5);
+        let mut brancher = solver.default_brancher();
+        let callback: fn(&Solver, SolutionReference, &DefaultBrancher) = |_, _, _| {};
+
+        let result = solver.optimise(
+            &mut brancher,
+            &mut Indefinite,
+            LexicographicOptimisation::new(
+                [
+                    (OptimisationDirection::Maximise, x),
+                    (OptimisationDirection::Minimise, y),
+                ],
+                strategy,
+                callback,
+            )
+            .with_probe_budget(None)
+            .with_max_probe_timeouts(1),
+        );
+
+        let OptimisationResult::Optimal(solution) = result else {
+            panic!("Expected an optimal solution");
+        };
+        assert_eq!(solution.get_integer_value(x), 5);
+        assert_eq!(solution.get_integer_value(y), 0);
+
+        // The bounds on the objectives are retracted afterwards.
+        assert_eq!(solver.num_open_scopes(), 0);
+        assert_eq!(solver.upper_bound(&y), 5);
+    }
+}
+
+#[test]
+fn pareto_front_is_enumerated() {
+    let (mut solver, x, y) = create_sum_at_least_instance(5, 5, 5);
+    let mut brancher = solver.default_brancher();
+
+    let front = RefCell::new(vec![]);
+    let callback = |_: &Solver, solution: SolutionReference<'_>, _: &DefaultBrancher| {
+        front
+            .borrow_mut()
+            .push((solution.get_integer_value(x), solution.get_integer_value(y)));
+    };
+
+    let result = solver.optimise(
+        &mut brancher,
+        &mut Indefinite,
+        ParetoFront::new(
+            [
+                (OptimisationDirection::Minimise, x),
+                (OptimisationDirection::Minimise, y),
+            ],
+            callback,
+        ),
+    );
+    assert!(matches!(result, OptimisationResult::Optimal(_)));
+
+    let mut front = front.into_inner();
+    front.sort();
+    assert_eq!(front, vec![(0, 5), (1, 4), (2, 3), (3, 2), (4, 1), (5, 0)]);
+}
@@ -66,6 +66,11 @@ mzn_test!(bool_lin_eq);
 mzn_test!(bool_lin_le);
 mzn_test!(bool_clause);
 
+mzn_test!(lex_lesseq);
+mzn_test!(lex_less);
+mzn_test!(lex_lesseq_bool);
+mzn_test!(lex_less_bool);
+
 cumulative!(time_table_per_point);
 cumulative!(time_table_per_point_incremental);
 cumulative!(time_table_per_point_incremental_synchronised);
@@ -3,6 +3,7 @@
 mod helpers;
 
 use helpers::run_mzn_test;
+use helpers::run_solver_with_options;
 use helpers::TestType;
 
 macro_rules! mzn_infeasible_test {
@@ -14,7 +15,61 @@ macro_rules! mzn_infeasible_test {
     };
 }
 mzn_infeasible_test!(prop_stress);
+mzn_infeasible_test!(clauses);
 
 pub fn run_mzn_infeasible_test(instance_name: &str, folder_name: &str) {
     let _ = run_mzn_test::<false>(instance_name, folder_name, TestType::Unsatisfiable);
 }
+
+#[test]
+fn inferences_refer_to_the_constraint_items_of_the_model() {
+    // The instances contain linear inequalities which are propagated by their own propagators, and
+    // clauses which are propagated together by the nogood propagator.
+    for instance_name in ["prop_stress", "clauses"] {
+        let instance_path = format!(
+            "{}/tests/mzn_infeasible/{instance_name}.fzn",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let files = run_solver_with_options(
+            &instance_path,
+            true,
+            ["--proof-type".to_owned(), "full".to_owned()],
+            Some("tagged"),
+        );
+
+        let model = std::fs::read_to_string(&instance_path).expect("model exists");
+        let constraint_names = model
+            .lines()
+            .filter_map(|line| line.strip_prefix("constraint "))
+            .map(|constraint| constraint.split('(').next().unwrap())
+            .collect::<Vec<_>>();
+
+        let proof = std::fs::read_to_string(&files.proof_file).expect("proof was written");
+        let inferences = proof
+            .lines()
+            .filter(|line| line.starts_with("i "))
+            .collect::<Vec<_>>();
+        assert!(!inferences.is_empty());
+
+        for inference in inferences {
+            let tag = inference
+                .split_whitespace()
+                .find_map(|hint| hint.strip_prefix("c:"))
+                .and_then(|tag| tag.parse::<usize>().ok())
+                .unwrap_or_else(|| panic!("inference without a constraint tag: {inference}"));
+            assert!((1..=constraint_names.len()).contains(&tag));
+
+            let expected_label = match constraint_names[tag - 1] {
+                "int_lin_le" => "linear_bound",
+                "bool_clause" => "nogood",
+                name => panic!("unexpected constraint {name}"),
+            };
+            assert!(
+                inference.ends_with(&format!(" l:{expected_label}")),
+                "{inference}"
+            );
+        }
+
+        files.cleanup().expect("files can be removed");
+    }
+}
@@ -2,15 +2,20 @@
 
 mod helpers;
 
+use std::process::Command;
+
 use helpers::run_mzn_test;
 use helpers::TestType;
 
 macro_rules! mzn_optimization_test {
     ($name:ident) => {
+        mzn_optimization_test!($name, TestType::Optimality);
+    };
+
+    ($name:ident, $test_type:expr) => {
         #[test]
         fn $name() {
-            let output =
-                run_mzn_test::<false>(stringify!($name), "mzn_optimization", TestType::Optimality);
+            let output = run_mzn_test::<false>(stringify!($name), "mzn_optimization", $test_type);
             assert!(output.ends_with("==========\n"));
         }
     };
@@ -18,3 +23,25 @@ macro_rules! mzn_optimization_test {
 
 mzn_optimization_test!(constant_objective);
 mzn_optimization_test!(unfixed_objective);
+mzn_optimization_test!(lexicographic_objectives, TestType::MultiObjectiveOptimality);
+mzn_optimization_test!(pareto_objectives, TestType::MultiObjectiveOptimality);
+
+#[test]
+fn proof_is_not_supported_with_multiple_objectives() {
+    let instance_path = format!(
+        "{}/tests/mzn_optimization/lexicographic_objectives.fzn",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let proof_path = std::env::temp_dir().join("lexicographic_objectives.proof");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pumpkin-solver"))
+        .arg("--proof-path")
+        .arg(&proof_path)
+        .arg(instance_path)
+        .output()
+        .expect("solver can be run");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("proof logging is not supported for multi-objective optimisation"));
+}
@@ -0,0 +1,176 @@
+#![cfg(test)] // workaround for https://github.com/rust-lang/rust-clippy/issues/11024
+
+use clap::ValueEnum;
+use fnv::FnvBuildHasher;
+use pumpkin_solver::constraints;
+use pumpkin_solver::constraints::Constraint;
+use pumpkin_solver::constraints::GccMethod;
+use pumpkin_solver::constraints::Values;
+use pumpkin_solver::options::CumulativeExplanationType;
+use pumpkin_solver::options::CumulativeOptions;
+use pumpkin_solver::options::CumulativePropagationMethod;
+use pumpkin_solver::options::GccPropagatorMethod;
+use pumpkin_solver::testing::fuzz_propagators;
+use pumpkin_solver::testing::PropagatorFuzzerOptions;
+use pumpkin_solver::variables::DomainId;
+use pumpkin_solver::variables::Literal;
+use pumpkin_solver::Solver;
+
+fn gcc_values() -> Vec<Values> {
+    vec![
+        Values {
+            value: 1,
+            omin: 0,
+            omax: 2,
+        },
+        Values {
+            value: 2,
+            omin: 1,
+            omax: 1,
+        },
+        Values {
+            value: 3,
+            omin: 0,
+            omax: 1,
+        },
+    ]
+}
+
+fn create_equality_literals(
+    solver: &mut Solver,
+    variables: &[DomainId],
+) -> std::collections::HashMap<(usize, usize), Literal, FnvBuildHasher> {
+    let mut equalities = std::collections::HashMap::default();
+    for i in 0..variables.len() {
+        for j in i + 1..variables.len() {
+            let _ = equalities.insert((i, j), solver.new_literal());
+        }
+    }
+    equalities
+}
+
+fn fuzz_gcc(method: GccPropagatorMethod) {
+    let options = PropagatorFuzzerOptions {
+        num_instances: 20,
+        ..Default::default()
+    };
+
+    match method {
+        GccPropagatorMethod::Bruteforce => fuzz_propagators(options, |_, variables| {
+            constraints::global_cardinality_lower_upper(
+                variables.to_vec(),
+                gcc_values(),
+                GccMethod::Bruteforce,
+            )
+        }),
+        GccPropagatorMethod::BasicFilter => fuzz_propagators(options, |_, variables| {
+            constraints::global_cardinality_lower_upper(
+                variables.to_vec(),
+                gcc_values(),
+                GccMethod::BasicFilter,
+            )
+        }),
+        GccPropagatorMethod::ReginArcConsistent => fuzz_propagators(options, |_, variables| {
+            constraints::global_cardinality_lower_upper(
+                variables.to_vec(),
+                gcc_values(),
+                GccMethod::ReginArcConsistent,
+            )
+        }),
+        GccPropagatorMethod::ExtendedResolution => {
+            fuzz_propagators(options, |solver, variables| {
+                let equalities = create_equality_literals(solver, variables);
+                constraints::gcc_extended_resolution(variables.to_vec(), gcc_values(), equalities)
+            })
+        }
+        GccPropagatorMethod::ExtendedResolutionWithRegin => {
+            fuzz_propagators(options, |solver, variables| {
+                let equalities = create_equality_literals(solver, variables);
+                // The outcome is reported by posting the returned constraint, which fails as well
+                // if this constraint already led to a root-level conflict.
+                let _ = constraints::gcc_extended_resolution(
+                    variables.to_vec(),
+                    gcc_values(),
+                    equalities,
+                )
+                .post(solver, None);
+                constraints::global_cardinality_lower_upper(
+                    variables.to_vec(),
+                    gcc_values(),
+                    GccMethod::ReginArcConsistent,
+                )
+            })
+        }
+    }
+}
+
+#[test]
+fn gcc_propagator_methods() {
+    for method in GccPropagatorMethod::value_variants() {
+        fuzz_gcc(*method);
+    }
+}
+
+fn fuzz_cumulative(cumulative_options: CumulativeOptions) {
+    let options = PropagatorFuzzerOptions {
+        num_instances: 10,
+        ..Default::default()
+    };
+    fuzz_propagators(options, |_, variables| {
+        constraints::cumulative_with_options(
+            variables.to_vec(),
+            [2, 1, 3, 2],
+            [1, 2, 1, 2],
+            2,
+            cumulative_options,
+        )
+    });
+}
+
+#[test]
+fn cumulative_propagation_methods() {
+    for propagation_method in CumulativePropagationMethod::value_variants() {
+        for explanation_type in CumulativeExplanationType::value_variants() {
+            fuzz_cumulative(CumulativeOptions::new(
+                false,
+                *explanation_type,
+                false,
+                *propagation_method,
+                false,
+            ));
+        }
+    }
+}
+
+#[test]
+fn cumulative_propagator_options() {
+    for propagation_method in CumulativePropagationMethod::value_variants() {
+        for (allow_holes_in_domain, generate_sequence, incremental_backtracking) in [
+            (true, false, false),
+            (false, true, false),
+            (false, false, true),
+        ] {
+            fuzz_cumulative(CumulativeOptions::new(
+                allow_holes_in_domain,
+                CumulativeExplanationType::default(),
+                generate_sequence,
+                *propagation_method,
+                incremental_backtracking,
+            ));
+        }
+    }
+}
+
+#[test]
+fn lexicographic_propagators() {
+    let options = PropagatorFuzzerOptions {
+        num_variables: 6,
+        ..Default::default()
+    };
+    fuzz_propagators(options, |_, variables| {
+        constraints::lex_less_or_equal(variables[..3].to_vec(), variables[3..].to_vec())
+    });
+    fuzz_propagators(options, |_, variables| {
+        constraints::lex_less(variables[..3].to_vec(), variables[3..].to_vec())
+    });
+}
@@ -1,13 +1,27 @@
 #![cfg(test)] // workaround for https://github.com/rust-lang/rust-clippy/issues/11024
 
+mod helpers;
+
 use std::num::NonZero;
+use std::path::Path;
 use std::path::PathBuf;
 
+use drcp_format::compression;
+use drcp_format::reader::ProofReader;
+use drcp_format::steps::Step;
+use drcp_format::LiteralDefinitions;
+use drcp_format::VariableNameMap;
+use helpers::models::create_pigeon_hole_instance;
 use pumpkin_solver::constraints;
+use pumpkin_solver::options::ConflictTrace;
 use pumpkin_solver::options::SolverOptions;
 use pumpkin_solver::predicate;
 use pumpkin_solver::proof::ProofLog;
+use pumpkin_solver::results::solution_iterator::IteratedSolution;
 use pumpkin_solver::results::SatisfactionResult;
+use pumpkin_solver::results::SatisfactionResultUnderAssumptions;
+use pumpkin_solver::symmetry::ValuePermutation;
+use pumpkin_solver::symmetry::VariablePermutation;
 use pumpkin_solver::termination::Indefinite;
 use pumpkin_solver::Solver;
 
@@ -73,3 +87,303 @@ fn proof_with_equality_unit_nogood_step() {
     let result = solver.satisfy(&mut brancher, &mut Indefinite);
     assert!(matches!(result, SatisfactionResult::Unsatisfiable));
 }
+
+/// Solves the pigeon hole problem with 6 pigeons and 5 holes while logging a proof in the given
+/// format to `proof_path`.
+fn solve_pigeon_hole_with_proof(proof_path: &Path, format: drcp_format::Format) {
+    let mut solver = Solver::with_options(SolverOptions {
+        proof_log: ProofLog::cp(proof_path, format, true, true).expect("created proof"),
+        ..Default::default()
+    });
+
+    let _ = create_pigeon_hole_instance(&mut solver, 6, 5);
+
+    let mut brancher = solver.default_brancher();
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+    solver.conclude_proof_unsat();
+}
+
+#[test]
+fn inferences_are_labelled_with_their_filtering_algorithm() {
+    let proof_path = PathBuf::from("/tmp/solver_proof_labels.drcp");
+    solve_pigeon_hole_with_proof(&proof_path, drcp_format::Format::Text);
+
+    let proof = std::fs::read(&proof_path).expect("proof was written");
+    let mut reader = ProofReader::with_format(
+        drcp_format::Format::Text,
+        proof.as_slice(),
+        std::convert::identity,
+    );
+
+    let mut num_inferences = 0;
+    while let Some(step) = reader.next_step().expect("valid step") {
+        let Step::Inference(inference) = step else {
+            continue;
+        };
+
+        num_inferences += 1;
+        match inference.hint_label {
+            Some("linear_not_equal") => assert_eq!(NonZero::new(1), inference.hint_constraint_id),
+            Some("nogood") => {}
+            other => panic!("unexpected inference label {other:?}"),
+        }
+    }
+
+    assert!(num_inferences > 0);
+}
+
+#[test]
+fn names_of_variables_are_written_next_to_proof() {
+    let proof_path = PathBuf::from("/tmp/solver_proof_names.drcp");
+    let mut solver = Solver::with_options(SolverOptions {
+        proof_log: ProofLog::cp(&proof_path, drcp_format::Format::Text, true, true)
+            .expect("created proof"),
+        ..Default::default()
+    });
+
+    let x = solver.new_named_bounded_integer(1, 2, "x");
+    let y = solver.new_named_bounded_integer(1, 2, "y");
+    let z = solver.new_named_bounded_integer(1, 2, "z");
+    solver.add_variable_alias(x, "start of task 1");
+    for (first, second) in [(x, y), (y, z), (x, z)] {
+        solver
+            .add_constraint(constraints::binary_not_equals(first, second))
+            .post()
+            .expect("no conflict");
+    }
+
+    let mut brancher = solver.default_brancher();
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+    solver.conclude_proof_unsat();
+    drop(solver);
+
+    let names_file =
+        compression::open(compression::names_path(&proof_path)).expect("names were written");
+    let names = VariableNameMap::parse(names_file).expect("valid names map");
+    assert_eq!(["x", "start of task 1"].as_slice(), names.names("x"));
+    assert_eq!(["y"].as_slice(), names.names("y"));
+}
+
+#[test]
+fn every_learned_nogood_is_written_to_conflict_trace() {
+    let trace_path = PathBuf::from("/tmp/solver_conflict_trace.jsonl");
+    let mut solver = Solver::with_options(SolverOptions {
+        conflict_trace: ConflictTrace::json(&trace_path).expect("created trace"),
+        ..Default::default()
+    });
+
+    let _ = create_pigeon_hole_instance(&mut solver, 5, 4);
+
+    let mut brancher = solver.default_brancher();
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+    drop(solver);
+
+    let trace = std::fs::read_to_string(&trace_path).expect("trace was written");
+    let lines = trace.lines().collect::<Vec<_>>();
+    assert!(!lines.is_empty());
+    for (index, line) in lines.iter().enumerate() {
+        assert!(line.starts_with(&format!("{{\"conflict\":{},", index + 1)));
+        assert!(line.contains("\"label\":\"linear_not_equal\""));
+    }
+}
+
+#[test]
+fn extracted_core_is_logged_as_nogood() {
+    let proof_path = PathBuf::from("/tmp/solver_proof_core.drcp");
+    let mut solver = Solver::with_options(SolverOptions {
+        proof_log: ProofLog::cp(&proof_path, drcp_format::Format::Text, true, true)
+            .expect("created proof"),
+        ..Default::default()
+    });
+
+    let x = solver.new_named_bounded_integer(0, 2, "x");
+    let y = solver.new_named_bounded_integer(0, 2, "y");
+    let z = solver.new_named_bounded_integer(0, 2, "z");
+    solver
+        .add_constraint(constraints::all_different(vec![x, y, z]))
+        .post()
+        .expect("no conflict");
+
+    let assumptions = [predicate!(x == 1), predicate!(y <= 1), predicate!(y != 0)];
+    let mut brancher = solver.default_brancher();
+    let core = match solver.satisfy_under_assumptions(&mut brancher, &mut Indefinite, &assumptions)
+    {
+        SatisfactionResultUnderAssumptions::UnsatisfiableUnderAssumptions(mut unsatisfiable) => {
+            unsatisfiable.extract_core()
+        }
+        _ => panic!("the assumptions should be unsatisfiable"),
+    };
+    drop(solver);
+
+    let proof = std::fs::read(&proof_path).expect("proof was written");
+    let mut reader = ProofReader::with_format(
+        drcp_format::Format::Text,
+        proof.as_slice(),
+        std::convert::identity,
+    );
+
+    let mut inferences = vec![];
+    let mut last_nogood = None;
+    while let Some(step) = reader.next_step().expect("valid step") {
+        match step {
+            Step::Inference(inference) => inferences.push(inference.id),
+            Step::Nogood(nogood) => last_nogood = Some(nogood),
+            _ => {}
+        }
+    }
+
+    let nogood = last_nogood.expect("the core is logged as a nogood");
+    assert_eq!(core.len(), nogood.literals.len());
+    let hints = nogood.hints.expect("hints are logged");
+    assert!(!hints.is_empty());
+    assert!(hints.iter().all(|hint| inferences.contains(hint)));
+}
+
+#[test]
+fn binary_proof_contains_the_same_steps_as_textual_proof() {
+    let text_path = PathBuf::from("/tmp/solver_proof_text.drcp");
+    let binary_path = PathBuf::from("/tmp/solver_proof_binary.drcp");
+    solve_pigeon_hole_with_proof(&text_path, drcp_format::Format::Text);
+    solve_pigeon_hole_with_proof(&binary_path, drcp_format::Format::Binary);
+
+    let text_proof = std::fs::read(&text_path).expect("proof was written");
+    let binary_proof = std::fs::read(&binary_path).expect("proof was written");
+    assert!(binary_proof.len() < text_proof.len());
+
+    let mut text_reader = ProofReader::with_format(
+        drcp_format::Format::Text,
+        text_proof.as_slice(),
+        std::convert::identity,
+    );
+    let mut binary_reader = ProofReader::with_format(
+        drcp_format::Format::Binary,
+        binary_proof.as_slice(),
+        std::convert::identity,
+    );
+
+    loop {
+        let text_step = text_reader.next_step().expect("valid textual step");
+        let binary_step = binary_reader.next_step().expect("valid binary step");
+        assert_eq!(text_step, binary_step);
+
+        if text_step.is_none() {
+            break;
+        }
+    }
+
+    // The literal definitions are written in an arbitrary order.
+    let read_literals = |proof_path: &PathBuf| {
+        let literals = std::fs::read_to_string(proof_path.with_extension("lits"))
+            .expect("literals were written");
+        let mut lines = literals.lines().map(str::to_owned).collect::<Vec<_>>();
+        lines.sort();
+        lines
+    };
+    let text_literals = read_literals(&text_path);
+    let binary_literals = read_literals(&binary_path);
+    assert_eq!(text_literals, binary_literals);
+}
+
+#[test]
+fn compressed_proof_contains_the_same_steps_as_uncompressed_proof() {
+    let plain_path = PathBuf::from("/tmp/solver_proof_plain.drcp");
+    solve_pigeon_hole_with_proof(&plain_path, drcp_format::Format::Text);
+    let plain_proof = std::fs::read(&plain_path).expect("proof was written");
+
+    for compressed_path in [
+        PathBuf::from("/tmp/solver_proof_compressed.drcp.gz"),
+        PathBuf::from("/tmp/solver_proof_compressed.drcp.zst"),
+    ] {
+        solve_pigeon_hole_with_proof(&compressed_path, drcp_format::Format::Text);
+        assert!(
+            std::fs::metadata(&compressed_path)
+                .expect("proof was written")
+                .len()
+                < plain_proof.len() as u64
+        );
+
+        let mut plain_reader = ProofReader::new(plain_proof.as_slice(), std::convert::identity);
+        let mut compressed_reader = ProofReader::open(
+            &compressed_path,
+            drcp_format::Format::Text,
+            std::convert::identity,
+        )
+        .expect("proof was written");
+
+        loop {
+            let plain_step = plain_reader.next_step().expect("valid step");
+            let compressed_step = compressed_reader
+                .next_step()
+                .expect("valid compressed step");
+            assert_eq!(plain_step, compressed_step);
+
+            if plain_step.is_none() {
+                break;
+            }
+        }
+
+        let literals = compression::open(compression::literals_path(&compressed_path))
+            .expect("literals were written");
+        let _ = LiteralDefinitions::<String>::parse(literals).expect("valid literals");
+    }
+}
+
+/// Creates a pigeon hole instance in which the pigeons and the holes can be swapped, and registers
+/// the swaps of consecutive pigeons and holes as symmetries.
+fn create_symmetric_pigeon_hole_instance(num_pigeons: i32, num_holes: i32) -> Solver {
+    let mut solver = Solver::default();
+    let pigeons = create_pigeon_hole_instance(&mut solver, num_pigeons, num_holes);
+
+    for window in pigeons.windows(2) {
+        solver.add_symmetry(VariablePermutation::new([
+            (window[0], window[1]),
+            (window[1], window[0]),
+        ]));
+    }
+    for hole in 1..num_holes {
+        solver.add_symmetry(ValuePermutation::new(
+            pigeons.clone(),
+            [(hole, hole + 1), (hole + 1, hole)],
+        ));
+    }
+
+    solver
+}
+
+#[test]
+fn symmetric_nogoods_preserve_infeasibility() {
+    let mut solver = create_symmetric_pigeon_hole_instance(7, 6);
+
+    let mut brancher = solver.default_brancher();
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Unsatisfiable));
+}
+
+#[test]
+fn symmetric_nogoods_preserve_feasibility() {
+    let mut solver = create_symmetric_pigeon_hole_instance(6, 6);
+
+    let mut brancher = solver.default_brancher();
+    let result = solver.satisfy(&mut brancher, &mut Indefinite);
+    assert!(matches!(result, SatisfactionResult::Satisfiable(_)));
+}
+
+#[test]
+fn symmetric_nogoods_do_not_exclude_solutions_when_iterating() {
+    let mut solver = create_symmetric_pigeon_hole_instance(5, 5);
+
+    let mut brancher = solver.default_brancher();
+    let mut termination = Indefinite;
+    let mut solution_iterator = solver.get_solution_iterator(&mut brancher, &mut termination);
+
+    let mut number_of_solutions = 0;
+    while let IteratedSolution::Solution(_, _, _) = solution_iterator.next_solution() {
+        number_of_solutions += 1;
+    }
+
+    // Every permutation of the pigeons over the holes is a solution.
+    assert_eq!(number_of_solutions, 120);
+}
@@ -6,6 +6,7 @@ mod helpers;
 
 use helpers::run_solution_checker;
 use helpers::run_solver;
+use helpers::run_solver_with_options;
 use helpers::Checker;
 use helpers::CheckerOutput;
 use helpers::Files;
@@ -29,6 +30,88 @@ test_wcnf_instance!(normalized_g9x3, 7);
 // test_wcnf_instance!(normalized_g9x9, 20);
 test_wcnf_instance!(ram_k3_n9, 1);
 
+#[test]
+fn veripb_proof_concludes_the_optimal_objective() {
+    let instance_path = format!("{}/tests/wcnf/karate.wcnf", env!("CARGO_MANIFEST_DIR"));
+    let files = run_solver_with_options(instance_path, true, std::iter::empty(), Some("veripb"));
+
+    let proof = std::fs::read_to_string(&files.proof_file).expect("proof is written");
+    assert!(proof.starts_with("pseudo-Boolean proof version 2.0\n"));
+    assert!(proof.contains("\nsoli "));
+    assert!(proof.ends_with("conclusion BOUNDS 4 4 ;\nend pseudo-Boolean proof ;\n"));
+
+    let instance_translation = files.proof_file.with_extension("opb");
+    let opb = std::fs::read_to_string(&instance_translation).expect("instance is written");
+    assert!(opb.starts_with("* #variable= "));
+    assert!(opb.contains("\nmin: "));
+
+    std::fs::remove_file(instance_translation).unwrap();
+    files.cleanup().unwrap();
+}
+
+/// Checks the proofs with VeriPB; this test is run by the `veripb` job in CI, which installs it.
+#[test]
+#[ignore = "requires veripb (https://gitlab.com/MIAOresearch/software/VeriPB) to be installed"]
+fn veripb_proof_is_verified() {
+    let instances = [
+        ("simple", "generalized-totalizer"),
+        ("karate", "generalized-totalizer"),
+        ("riskmap", "generalized-totalizer"),
+        ("normalized_g2x2", "generalized-totalizer"),
+        ("normalized_g2x2", "cardinality-network"),
+        ("johnson8_2_4", "cardinality-network"),
+    ];
+
+    for (instance, encoding) in instances {
+        let instance_path = format!("{}/tests/wcnf/{instance}.wcnf", env!("CARGO_MANIFEST_DIR"));
+        let files = run_solver_with_options(
+            instance_path,
+            true,
+            ["--upper-bound-encoding".to_owned(), encoding.to_owned()],
+            Some("veripb_verified"),
+        );
+        let instance_translation = files.proof_file.with_extension("opb");
+
+        let output = Command::new("veripb")
+            .arg(&instance_translation)
+            .arg(&files.proof_file)
+            .output()
+            .expect("veripb can be run");
+        assert!(
+            output.status.success(),
+            "{instance} ({encoding}): {}",
+            String::from_utf8_lossy(&output.stdout)
+        );
+
+        std::fs::remove_file(instance_translation).unwrap();
+        files.cleanup().unwrap();
+    }
+}
+
+#[test]
+fn veripb_proof_with_cardinality_network_encoding_concludes_the_optimal_objective() {
+    let instance_path = format!(
+        "{}/tests/wcnf/johnson8_2_4.wcnf",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let files = run_solver_with_options(
+        instance_path,
+        true,
+        [
+            "--upper-bound-encoding".to_owned(),
+            "cardinality-network".to_owned(),
+        ],
+        Some("veripb_cardinality_network"),
+    );
+
+    let proof = std::fs::read_to_string(&files.proof_file).expect("proof is written");
+    assert!(proof.contains("\nsoli "));
+    assert!(proof.ends_with("conclusion BOUNDS 24 24 ;\nend pseudo-Boolean proof ;\n"));
+
+    std::fs::remove_file(files.proof_file.with_extension("opb")).unwrap();
+    files.cleanup().unwrap();
+}
+
 struct MaxSATChecker {
     expected_objective: u64,
 }